@@ -0,0 +1,170 @@
+//! Self-benchmark for `--bench`.
+//!
+//! Runs the regular analysis pipeline over the input several times and reports
+//! per-run latency percentiles, statement throughput, and the process's peak
+//! resident memory, to help size CI runners.
+
+use anyhow::Result;
+use flowscope_core::{analyze, AnalyzeRequest, AnalyzeResult, Issue};
+use serde::Serialize;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::input::collect_sql_files;
+
+/// Timing and memory figures collected over the benchmark runs.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchReport {
+    /// Number of analysis runs
+    pub iterations: usize,
+    /// Input files analyzed per run
+    pub files: usize,
+    /// Statements analyzed per run
+    pub statements: usize,
+    /// Median run latency in milliseconds
+    pub p50_ms: f64,
+    /// 95th percentile run latency in milliseconds
+    pub p95_ms: f64,
+    /// Statements analyzed per second across all runs
+    pub statements_per_sec: f64,
+    /// Peak resident memory of the process in bytes, where the platform reports it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_memory_bytes: Option<u64>,
+    /// Issues reported by the last run, so a benchmark that fails says why
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<Issue>,
+}
+
+/// Expands directories in `paths` into the `.sql` files they contain, recursively
+/// and in sorted order. Other paths are kept as given.
+pub fn collect_bench_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            files.extend(collect_sql_files(path)?);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+/// Analyzes `request` `iterations` times and summarizes the timings, returning the
+/// result of the last run alongside so its issues are reported as in a regular run.
+pub fn run_bench(request: &AnalyzeRequest, iterations: usize) -> (BenchReport, AnalyzeResult) {
+    let iterations = iterations.max(1);
+    let mut durations = Vec::with_capacity(iterations);
+    let mut result = None;
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let run = analyze(request);
+        durations.push(start.elapsed());
+        result = Some(run);
+    }
+    let result = result.expect("at least one benchmark run");
+    let statements = result.summary.statement_count;
+
+    let total: Duration = durations.iter().sum();
+    durations.sort();
+    let statements_per_sec = if total.is_zero() {
+        0.0
+    } else {
+        (statements * iterations) as f64 / total.as_secs_f64()
+    };
+
+    let report = BenchReport {
+        iterations,
+        // A single input is sent as inline SQL rather than a one-element file list
+        files: request
+            .files
+            .as_ref()
+            .map_or(usize::from(!request.sql.is_empty()), Vec::len),
+        statements,
+        p50_ms: percentile_ms(&durations, 50),
+        p95_ms: percentile_ms(&durations, 95),
+        statements_per_sec,
+        peak_memory_bytes: peak_memory_bytes(),
+        issues: result.issues.clone(),
+    };
+    (report, result)
+}
+
+/// Nearest-rank percentile of sorted, non-empty `durations`.
+fn percentile_ms(durations: &[Duration], percentile: usize) -> f64 {
+    let rank = (percentile * durations.len()).div_ceil(100).max(1);
+    durations[rank - 1].as_secs_f64() * 1000.0
+}
+
+/// Peak resident set size (`VmHWM`) from `/proc/self/status`.
+#[cfg(target_os = "linux")]
+fn peak_memory_bytes() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_memory_bytes() -> Option<u64> {
+    None
+}
+
+pub fn format_bench_report(report: &BenchReport) -> String {
+    let mut out = String::new();
+    writeln!(out, "Iterations:      {}", report.iterations).unwrap();
+    writeln!(out, "Files:           {}", report.files).unwrap();
+    writeln!(out, "Statements:      {}", report.statements).unwrap();
+    writeln!(out, "Latency p50:     {:.2} ms", report.p50_ms).unwrap();
+    writeln!(out, "Latency p95:     {:.2} ms", report.p95_ms).unwrap();
+    writeln!(
+        out,
+        "Throughput:      {:.0} statements/sec",
+        report.statements_per_sec
+    )
+    .unwrap();
+    match report.peak_memory_bytes {
+        Some(bytes) => writeln!(
+            out,
+            "Peak memory:     {:.1} MiB",
+            bytes as f64 / (1024.0 * 1024.0)
+        ),
+        None => writeln!(out, "Peak memory:     n/a"),
+    }
+    .unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_uses_nearest_rank() {
+        let durations: Vec<Duration> = (1..=20).map(Duration::from_millis).collect();
+        assert_eq!(percentile_ms(&durations, 50), 10.0);
+        assert_eq!(percentile_ms(&durations, 95), 19.0);
+        assert_eq!(percentile_ms(&durations[..1], 95), 1.0);
+    }
+
+    #[test]
+    fn test_collect_bench_files_expands_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("nested")).unwrap();
+        fs::write(dir.path().join("b.sql"), "SELECT 1").unwrap();
+        fs::write(dir.path().join("nested").join("a.sql"), "SELECT 1").unwrap();
+        fs::write(dir.path().join("notes.md"), "# notes").unwrap();
+
+        let files = collect_bench_files(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(
+            files,
+            [
+                dir.path().join("b.sql"),
+                dir.path().join("nested").join("a.sql")
+            ]
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Dependency-ordered build plan for `--build-plan`.
+//!
+//! Treats each input file as a model (statements without a file are models of
+//! their own), links a model to every model that writes a table it reads, and
+//! topologically sorts the result into waves of models that can run in parallel.
+
+use flowscope_core::{AnalyzeResult, EdgeType, StatementLineage};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+/// Models grouped into runnable waves, plus any dependency cycles.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildPlan {
+    /// Models per wave; every model depends only on models in earlier waves
+    pub waves: Vec<Vec<String>>,
+    /// Groups of models that depend on each other and cannot be ordered
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cycles: Vec<Vec<String>>,
+    /// Models that are not in a cycle but depend on one
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blocked: Vec<String>,
+}
+
+impl BuildPlan {
+    pub fn has_cycles(&self) -> bool {
+        !self.cycles.is_empty()
+    }
+}
+
+/// Tables each model writes and reads.
+#[derive(Default)]
+struct ModelTables {
+    writes: BTreeSet<String>,
+    reads: BTreeSet<String>,
+}
+
+/// Builds the plan from the statements of an analysis result.
+pub fn build_plan(result: &AnalyzeResult) -> BuildPlan {
+    let models = collect_models(&result.statements);
+
+    let mut writers: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (model, tables) in &models {
+        for table in &tables.writes {
+            writers.entry(table).or_default().push(model);
+        }
+    }
+
+    // model -> models it depends on
+    let mut upstream: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for (model, tables) in &models {
+        let deps = upstream.entry(model).or_default();
+        for table in &tables.reads {
+            for writer in writers.get(table.as_str()).into_iter().flatten() {
+                if writer != model {
+                    deps.insert(writer);
+                }
+            }
+        }
+    }
+
+    let mut plan = BuildPlan::default();
+    let mut remaining = upstream.clone();
+    loop {
+        let wave: Vec<&str> = remaining
+            .iter()
+            .filter(|(_, deps)| deps.is_empty())
+            .map(|(model, _)| *model)
+            .collect();
+        if wave.is_empty() {
+            break;
+        }
+        for model in &wave {
+            remaining.remove(model);
+        }
+        for deps in remaining.values_mut() {
+            for model in &wave {
+                deps.remove(model);
+            }
+        }
+        plan.waves
+            .push(wave.into_iter().map(str::to_string).collect());
+    }
+
+    if !remaining.is_empty() {
+        plan.cycles = find_cycles(&remaining);
+        let in_cycle: BTreeSet<&String> = plan.cycles.iter().flatten().collect();
+        plan.blocked = remaining
+            .keys()
+            .map(|model| model.to_string())
+            .filter(|model| !in_cycle.contains(model))
+            .collect();
+    }
+    plan
+}
+
+/// Groups statements into models keyed by file name.
+///
+/// A statement writes a table when data flows into it; every other table it
+/// mentions is read.
+fn collect_models(statements: &[StatementLineage]) -> BTreeMap<String, ModelTables> {
+    let mut models: BTreeMap<String, ModelTables> = BTreeMap::new();
+    for stmt in statements {
+        let model = stmt
+            .source_name
+            .clone()
+            .unwrap_or_else(|| format!("statement {}", stmt.statement_index + 1));
+        let tables = models.entry(model).or_default();
+
+        for node in stmt.nodes.iter().filter(|n| n.node_type.is_table_or_view()) {
+            let name = node
+                .qualified_name
+                .as_deref()
+                .unwrap_or(&node.label)
+                .to_string();
+            let is_written = stmt
+                .edges
+                .iter()
+                .any(|e| e.to == node.id && e.edge_type == EdgeType::DataFlow);
+            if is_written {
+                tables.writes.insert(name);
+            } else {
+                tables.reads.insert(name);
+            }
+        }
+    }
+    models
+}
+
+/// Tarjan's algorithm bookkeeping for [`find_cycles`].
+#[derive(Default)]
+struct Tarjan<'a> {
+    index: BTreeMap<&'a str, usize>,
+    low: BTreeMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: BTreeSet<&'a str>,
+    components: Vec<Vec<String>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn visit(&mut self, node: &'a str, graph: &BTreeMap<&'a str, BTreeSet<&'a str>>) {
+        let index = self.index.len();
+        self.index.insert(node, index);
+        self.low.insert(node, index);
+        self.stack.push(node);
+        self.on_stack.insert(node);
+
+        for &next in graph.get(node).into_iter().flatten() {
+            if !graph.contains_key(next) {
+                continue;
+            }
+            if !self.index.contains_key(next) {
+                self.visit(next, graph);
+                let low = self.low[node].min(self.low[next]);
+                self.low.insert(node, low);
+            } else if self.on_stack.contains(next) {
+                let low = self.low[node].min(self.index[next]);
+                self.low.insert(node, low);
+            }
+        }
+
+        if self.low[node] == self.index[node] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                component.push(member.to_string());
+                if member == node {
+                    break;
+                }
+            }
+            if component.len() > 1 {
+                component.sort();
+                self.components.push(component);
+            }
+        }
+    }
+}
+
+/// Finds strongly connected components with more than one model.
+fn find_cycles(graph: &BTreeMap<&str, BTreeSet<&str>>) -> Vec<Vec<String>> {
+    let mut tarjan = Tarjan::default();
+    for &node in graph.keys() {
+        if !tarjan.index.contains_key(node) {
+            tarjan.visit(node, graph);
+        }
+    }
+    tarjan.components.sort();
+    tarjan.components
+}
+
+/// Formats the plan as numbered waves followed by any cycles.
+pub fn format_build_plan(plan: &BuildPlan) -> String {
+    let mut out = String::new();
+    for (i, wave) in plan.waves.iter().enumerate() {
+        writeln!(out, "Wave {}:", i + 1).unwrap();
+        for model in wave {
+            writeln!(out, "  {model}").unwrap();
+        }
+    }
+    for cycle in &plan.cycles {
+        writeln!(out, "Cycle between: {}", cycle.join(", ")).unwrap();
+    }
+    if !plan.blocked.is_empty() {
+        writeln!(out, "Blocked by cycles: {}", plan.blocked.join(", ")).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowscope_core::{analyze, AnalyzeRequest};
+
+    fn analyze_files(files: &[(&str, &str)]) -> AnalyzeResult {
+        let mut builder = AnalyzeRequest::builder();
+        for (name, sql) in files {
+            builder = builder.file(*name, *sql);
+        }
+        analyze(&builder.build())
+    }
+
+    #[test]
+    fn test_build_plan_orders_chain() {
+        let result = analyze_files(&[
+            ("mart.sql", "CREATE TABLE mart AS SELECT id FROM staging"),
+            ("raw.sql", "CREATE TABLE raw AS SELECT id FROM source"),
+            ("staging.sql", "CREATE TABLE staging AS SELECT id FROM raw"),
+        ]);
+
+        let plan = build_plan(&result);
+        assert_eq!(
+            plan.waves,
+            vec![vec!["raw.sql"], vec!["staging.sql"], vec!["mart.sql"]]
+        );
+        assert!(!plan.has_cycles());
+    }
+
+    #[test]
+    fn test_build_plan_groups_independent_models() {
+        let result = analyze_files(&[
+            ("a.sql", "CREATE TABLE a AS SELECT id FROM source"),
+            ("b.sql", "CREATE TABLE b AS SELECT id FROM source"),
+            (
+                "c.sql",
+                "CREATE TABLE c AS SELECT a.id FROM a JOIN b ON a.id = b.id",
+            ),
+        ]);
+
+        let plan = build_plan(&result);
+        assert_eq!(plan.waves, vec![vec!["a.sql", "b.sql"], vec!["c.sql"]]);
+    }
+
+    #[test]
+    fn test_build_plan_reports_cycle() {
+        let result = analyze_files(&[
+            ("a.sql", "INSERT INTO a SELECT id FROM b"),
+            ("b.sql", "INSERT INTO b SELECT id FROM a"),
+            ("c.sql", "CREATE TABLE c AS SELECT id FROM a"),
+            ("d.sql", "CREATE TABLE d AS SELECT id FROM source"),
+        ]);
+
+        let plan = build_plan(&result);
+        assert_eq!(plan.waves, vec![vec!["d.sql"]]);
+        assert_eq!(plan.cycles, vec![vec!["a.sql", "b.sql"]]);
+        assert_eq!(plan.blocked, vec!["c.sql"]);
+        assert!(format_build_plan(&plan).contains("Cycle between: a.sql, b.sql"));
+    }
+}
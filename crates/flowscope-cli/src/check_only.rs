@@ -0,0 +1,165 @@
+//! Pre-flight validation for `--check-only`.
+//!
+//! The run is set up as usual (arguments, dialect, schema, template settings), then the
+//! inputs are confirmed to be readable and the outcome is reported instead of
+//! analyzing anything. Any failure surfaces as the same error the full run would hit,
+//! only before the expensive part starts.
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use flowscope_core::{Dialect, SchemaMetadata};
+use serde::Serialize;
+
+use crate::input::collect_sql_files;
+
+/// What `--check-only` validated.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckReport {
+    pub dialect: Dialect,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<SchemaCheck>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<TemplateCheck>,
+    /// `--template-file`, read and compiled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template_file: Option<String>,
+    pub inputs: InputCheck,
+}
+
+/// The loaded schema.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaCheck {
+    /// Where it was loaded from (DDL files, metadata URL, or schema diff snapshot)
+    pub source: String,
+    pub tables: usize,
+}
+
+impl SchemaCheck {
+    pub fn new(source: impl Into<String>, schema: &SchemaMetadata) -> Self {
+        Self {
+            source: source.into(),
+            tables: schema.tables.len(),
+        }
+    }
+}
+
+/// The SQL templating settings.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateCheck {
+    pub mode: String,
+    pub variables: usize,
+}
+
+/// The SQL inputs, each read in full.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputCheck {
+    pub files: Vec<String>,
+    pub bytes: u64,
+    /// No files were given, so the run would read SQL from stdin (not read here)
+    pub stdin: bool,
+}
+
+/// Reads every input file, and the `.sql` files under input directories, failing on
+/// the first one that is missing or unreadable.
+pub fn check_inputs(paths: &[PathBuf]) -> Result<InputCheck> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            files.extend(collect_sql_files(path)?);
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    let mut bytes = 0;
+    for file in &files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read file: {}", file.display()))?;
+        bytes += content.len() as u64;
+    }
+
+    Ok(InputCheck {
+        files: files
+            .iter()
+            .map(|file| file.display().to_string())
+            .collect(),
+        bytes,
+        stdin: paths.is_empty(),
+    })
+}
+
+/// Counts `--template-var` entries, rejecting those the full run would silently skip
+/// because they are not `KEY=VALUE` with a non-empty key.
+pub fn check_template_vars(vars: &[String]) -> Result<usize> {
+    for var in vars {
+        match var.split_once('=') {
+            Some((key, _)) if !key.trim().is_empty() => {}
+            _ => bail!("Invalid --template-var `{var}`: expected KEY=VALUE"),
+        }
+    }
+    Ok(vars.len())
+}
+
+/// Human-readable summary of `report`.
+pub fn format_check_report(report: &CheckReport) -> String {
+    let mut out = String::new();
+    writeln!(out, "Configuration OK").unwrap();
+    writeln!(
+        out,
+        "  dialect:       {}",
+        format!("{:?}", report.dialect).to_lowercase()
+    )
+    .unwrap();
+    match &report.schema {
+        Some(schema) => writeln!(
+            out,
+            "  schema:        {} table(s) from {}",
+            schema.tables, schema.source
+        ),
+        None => writeln!(out, "  schema:        none"),
+    }
+    .unwrap();
+    match &report.template {
+        Some(template) => writeln!(
+            out,
+            "  template:      {} with {} variable(s)",
+            template.mode, template.variables
+        ),
+        None => writeln!(out, "  template:      none"),
+    }
+    .unwrap();
+    if let Some(path) = &report.template_file {
+        writeln!(out, "  template file: {path}").unwrap();
+    }
+    if report.inputs.stdin {
+        writeln!(out, "  inputs:        stdin").unwrap();
+    } else {
+        writeln!(
+            out,
+            "  inputs:        {} file(s), {} bytes readable",
+            report.inputs.files.len(),
+            report.inputs.bytes
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_vars_need_a_key() {
+        let vars = ["schema=analytics".to_string(), "flag=".to_string()];
+        assert_eq!(check_template_vars(&vars).unwrap(), 2);
+        assert!(check_template_vars(&["schema".to_string()]).is_err());
+        assert!(check_template_vars(&[" =x".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,476 @@
+//! Dialect-specific construct histogram for `--dialect-report`.
+//!
+//! Scans the input for syntax tied to particular dialects, such as QUALIFY, `::`
+//! casts, backtick quoting or `(+)` joins, and counts each construct with its file
+//! locations. Quoting, casts and `(+)` are found in the token stream; clauses and
+//! operators whose keywords could also be plain identifiers come from the parsed
+//! statements. With a target dialect, constructs the target lacks are marked so a
+//! migration can be sized up front.
+
+use flowscope_core::{parse_sql_with_dialect, Dialect, FileSource};
+use serde::Serialize;
+use sqlparser::ast::{BinaryOperator, Expr, Query, SetExpr, Spanned, TableFactor, Visit, Visitor};
+use sqlparser::tokenizer::{Location, Token, TokenWithSpan, Tokenizer};
+use std::fmt::Write;
+use std::ops::ControlFlow;
+
+/// A dialect-specific construct the report looks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Feature {
+    Qualify,
+    DoubleColonCast,
+    BacktickQuoting,
+    BracketQuoting,
+    OuterJoinOperator,
+    Lateral,
+    LateralView,
+    SubscriptAccess,
+    ColonPathAccess,
+    JsonArrowOperator,
+    Ilike,
+    Top,
+}
+
+impl Feature {
+    /// Stable identifier used in JSON output.
+    fn id(self) -> &'static str {
+        match self {
+            Feature::Qualify => "qualify",
+            Feature::DoubleColonCast => "double_colon_cast",
+            Feature::BacktickQuoting => "backtick_quoting",
+            Feature::BracketQuoting => "bracket_quoting",
+            Feature::OuterJoinOperator => "outer_join_operator",
+            Feature::Lateral => "lateral",
+            Feature::LateralView => "lateral_view",
+            Feature::SubscriptAccess => "subscript_access",
+            Feature::ColonPathAccess => "colon_path_access",
+            Feature::JsonArrowOperator => "json_arrow_operator",
+            Feature::Ilike => "ilike",
+            Feature::Top => "top",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Feature::Qualify => "QUALIFY",
+            Feature::DoubleColonCast => ":: casts",
+            Feature::BacktickQuoting => "`backtick` identifiers",
+            Feature::BracketQuoting => "[bracket] identifiers",
+            Feature::OuterJoinOperator => "(+) outer joins",
+            Feature::Lateral => "LATERAL",
+            Feature::LateralView => "LATERAL VIEW",
+            Feature::SubscriptAccess => "array/struct access",
+            Feature::ColonPathAccess => "col:path access",
+            Feature::JsonArrowOperator => "-> / ->> operators",
+            Feature::Ilike => "ILIKE",
+            Feature::Top => "TOP",
+        }
+    }
+
+    /// Dialects that accept the construct. The generic dialect accepts everything.
+    fn supported_by(self) -> &'static [Dialect] {
+        use Dialect::*;
+        match self {
+            Feature::Qualify => &[
+                Bigquery, Clickhouse, Databricks, Duckdb, Redshift, Snowflake,
+            ],
+            Feature::DoubleColonCast => &[
+                Clickhouse, Databricks, Duckdb, Postgres, Redshift, Snowflake,
+            ],
+            Feature::BacktickQuoting => &[Bigquery, Clickhouse, Databricks, Hive, Mysql, Sqlite],
+            Feature::BracketQuoting => &[Mssql, Sqlite],
+            Feature::OuterJoinOperator => &[Redshift, Snowflake],
+            Feature::Lateral => &[Databricks, Duckdb, Mysql, Postgres, Snowflake],
+            Feature::LateralView => &[Databricks, Hive],
+            Feature::SubscriptAccess => &[
+                Bigquery, Clickhouse, Databricks, Duckdb, Hive, Postgres, Redshift, Snowflake,
+            ],
+            Feature::ColonPathAccess => &[Databricks, Snowflake],
+            Feature::JsonArrowOperator => &[Duckdb, Mysql, Postgres, Sqlite],
+            Feature::Ilike => &[
+                Clickhouse, Databricks, Duckdb, Postgres, Redshift, Snowflake,
+            ],
+            Feature::Top => &[Mssql, Redshift, Snowflake],
+        }
+    }
+
+    fn is_supported_by(self, dialect: Dialect) -> bool {
+        dialect == Dialect::Generic || self.supported_by().contains(&dialect)
+    }
+}
+
+/// Where a construct was found; line and column are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct FeatureLocation {
+    pub file: String,
+    pub line: u64,
+    pub column: u64,
+}
+
+/// Uses of one construct across the input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureUsage {
+    /// Stable identifier, e.g. `qualify` or `double_colon_cast`
+    pub feature: &'static str,
+    /// Human-readable name
+    pub label: &'static str,
+    pub count: usize,
+    pub locations: Vec<FeatureLocation>,
+    /// Whether the target dialect accepts the construct, when a target was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supported_by_target: Option<bool>,
+}
+
+/// Histogram of the dialect-specific constructs in the input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DialectReport {
+    pub dialect: Dialect,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_dialect: Option<Dialect>,
+    /// Constructs found, most used first
+    pub features: Vec<FeatureUsage>,
+    /// Files that failed to parse; only token-level constructs are counted for them
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unparsed: Vec<String>,
+}
+
+impl DialectReport {
+    /// Whether any construct found is unsupported by the target dialect.
+    pub fn has_portability_issues(&self) -> bool {
+        self.features
+            .iter()
+            .any(|usage| usage.supported_by_target == Some(false))
+    }
+}
+
+/// Scans `sources`, written in `dialect`, for dialect-specific constructs, checking
+/// each against `target` when given.
+pub fn dialect_report(
+    sources: &[FileSource],
+    dialect: Dialect,
+    target: Option<Dialect>,
+) -> DialectReport {
+    let mut found: Vec<(Feature, FeatureLocation)> = Vec::new();
+    let mut unparsed = Vec::new();
+
+    for source in sources {
+        let mut record = |feature: Feature, location: Location| {
+            found.push((
+                feature,
+                FeatureLocation {
+                    file: source.name.clone(),
+                    line: location.line,
+                    column: location.column,
+                },
+            ));
+        };
+
+        let sqlparser_dialect = dialect.to_sqlparser_dialect();
+        if let Ok(tokens) =
+            Tokenizer::new(sqlparser_dialect.as_ref(), &source.content).tokenize_with_location()
+        {
+            scan_tokens(&tokens, &mut record);
+        }
+
+        match parse_sql_with_dialect(&source.content, dialect) {
+            Ok(statements) => {
+                let mut visitor = FeatureVisitor::default();
+                let _ = statements.visit(&mut visitor);
+                for (feature, location) in visitor.found {
+                    record(feature, location);
+                }
+            }
+            Err(_) => unparsed.push(source.name.clone()),
+        }
+    }
+
+    found.sort();
+    let mut features: Vec<FeatureUsage> = Vec::new();
+    for (feature, location) in found {
+        match features.last_mut() {
+            Some(usage) if usage.feature == feature.id() => {
+                usage.count += 1;
+                usage.locations.push(location);
+            }
+            _ => features.push(FeatureUsage {
+                feature: feature.id(),
+                label: feature.label(),
+                count: 1,
+                locations: vec![location],
+                supported_by_target: target.map(|target| feature.is_supported_by(target)),
+            }),
+        }
+    }
+    // Stable sort keeps the declaration order among equal counts
+    features.sort_by_key(|usage| std::cmp::Reverse(usage.count));
+
+    DialectReport {
+        dialect,
+        target_dialect: target,
+        features,
+        unparsed,
+    }
+}
+
+/// Records the constructs recognizable from tokens alone.
+fn scan_tokens(tokens: &[TokenWithSpan], record: &mut impl FnMut(Feature, Location)) {
+    let tokens: Vec<&TokenWithSpan> = tokens
+        .iter()
+        .filter(|token| !matches!(token.token, Token::Whitespace(_)))
+        .collect();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let start = token.span.start;
+        match &token.token {
+            Token::DoubleColon => record(Feature::DoubleColonCast, start),
+            Token::Word(word) if word.quote_style == Some('`') => {
+                record(Feature::BacktickQuoting, start)
+            }
+            Token::Word(word) if word.quote_style == Some('[') => {
+                record(Feature::BracketQuoting, start)
+            }
+            Token::LParen
+                if matches!(
+                    (tokens.get(i + 1), tokens.get(i + 2)),
+                    (Some(plus), Some(close))
+                        if plus.token == Token::Plus && close.token == Token::RParen
+                ) =>
+            {
+                record(Feature::OuterJoinOperator, start)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Collects the constructs that need the parsed statement to tell apart.
+#[derive(Default)]
+struct FeatureVisitor {
+    found: Vec<(Feature, Location)>,
+}
+
+impl FeatureVisitor {
+    fn record(&mut self, feature: Feature, node: &impl Spanned) {
+        self.found.push((feature, node.span().start));
+    }
+
+    /// Checks the SELECTs directly in `body`; nested queries are visited on their own.
+    fn visit_set_expr(&mut self, body: &SetExpr) {
+        match body {
+            SetExpr::SetOperation { left, right, .. } => {
+                self.visit_set_expr(left);
+                self.visit_set_expr(right);
+            }
+            SetExpr::Select(select) => {
+                if select.top.is_some() {
+                    self.found
+                        .push((Feature::Top, select.select_token.0.span.start));
+                }
+                for lateral_view in &select.lateral_views {
+                    self.record(Feature::LateralView, &lateral_view.lateral_view);
+                }
+                if let Some(qualify) = &select.qualify {
+                    self.record(Feature::Qualify, qualify);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visitor for FeatureVisitor {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        self.visit_set_expr(&query.body);
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_table_factor(&mut self, table_factor: &TableFactor) -> ControlFlow<Self::Break> {
+        if matches!(
+            table_factor,
+            TableFactor::Derived { lateral: true, .. }
+                | TableFactor::Function { lateral: true, .. }
+        ) {
+            self.record(Feature::Lateral, table_factor);
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+        let feature = match expr {
+            Expr::CompoundFieldAccess { .. } => Some(Feature::SubscriptAccess),
+            Expr::JsonAccess { .. } => Some(Feature::ColonPathAccess),
+            Expr::BinaryOp {
+                op: BinaryOperator::Arrow | BinaryOperator::LongArrow,
+                ..
+            } => Some(Feature::JsonArrowOperator),
+            Expr::ILike { .. } => Some(Feature::Ilike),
+            _ => None,
+        };
+        if let Some(feature) = feature {
+            self.record(feature, expr);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Formats the report as one line per construct with its locations below.
+pub fn format_dialect_report(report: &DialectReport) -> String {
+    let dialect = dialect_name(report.dialect);
+    let mut out = String::new();
+    if report.features.is_empty() {
+        writeln!(out, "No {dialect}-specific constructs found").unwrap();
+    } else {
+        writeln!(out, "Dialect-specific constructs ({dialect}):").unwrap();
+    }
+    for usage in &report.features {
+        let concern = match (usage.supported_by_target, report.target_dialect) {
+            (Some(false), Some(target)) => {
+                format!("  [not supported by {}]", dialect_name(target))
+            }
+            _ => String::new(),
+        };
+        writeln!(out, "  {:<24} {:>5}{concern}", usage.label, usage.count).unwrap();
+        for location in &usage.locations {
+            writeln!(
+                out,
+                "    {}:{}:{}",
+                location.file, location.line, location.column
+            )
+            .unwrap();
+        }
+    }
+
+    if let Some(target) = report.target_dialect {
+        let unsupported: Vec<&str> = report
+            .features
+            .iter()
+            .filter(|usage| usage.supported_by_target == Some(false))
+            .map(|usage| usage.label)
+            .collect();
+        if unsupported.is_empty() {
+            writeln!(
+                out,
+                "\nAll constructs found are supported by {}",
+                dialect_name(target)
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                out,
+                "\n{} construct(s) not supported by {}: {}",
+                unsupported.len(),
+                dialect_name(target),
+                unsupported.join(", ")
+            )
+            .unwrap();
+        }
+    }
+    if !report.unparsed.is_empty() {
+        writeln!(
+            out,
+            "\nNot parsed, so only quoting, casts and (+) joins were counted: {}",
+            report.unparsed.join(", ")
+        )
+        .unwrap();
+    }
+    out
+}
+
+fn dialect_name(dialect: Dialect) -> String {
+    format!("{dialect:?}").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(name: &str, content: &str) -> FileSource {
+        FileSource {
+            name: name.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    fn usage<'r>(report: &'r DialectReport, feature: &str) -> &'r FeatureUsage {
+        report
+            .features
+            .iter()
+            .find(|usage| usage.feature == feature)
+            .unwrap_or_else(|| panic!("{feature} not reported: {report:?}"))
+    }
+
+    #[test]
+    fn test_counts_qualify_and_double_colon_casts() {
+        let sources = [
+            source(
+                "latest.sql",
+                "SELECT id, ts::date AS day FROM events\n\
+                 QUALIFY ROW_NUMBER() OVER (PARTITION BY id ORDER BY ts DESC) = 1",
+            ),
+            source(
+                "totals.sql",
+                "SELECT amount::numeric, note::text FROM orders",
+            ),
+        ];
+        let report = dialect_report(&sources, Dialect::Snowflake, None);
+
+        let casts = usage(&report, "double_colon_cast");
+        assert_eq!(casts.count, 3);
+        assert_eq!(
+            casts.locations[0],
+            FeatureLocation {
+                file: "latest.sql".to_string(),
+                line: 1,
+                column: 14
+            }
+        );
+        assert_eq!(casts.supported_by_target, None);
+
+        let qualify = usage(&report, "qualify");
+        assert_eq!(qualify.count, 1);
+        assert_eq!(qualify.locations[0].file, "latest.sql");
+        assert_eq!(qualify.locations[0].line, 2);
+        assert_eq!(report.features[0].feature, "double_colon_cast");
+        assert!(report.unparsed.is_empty());
+    }
+
+    #[test]
+    fn test_flags_constructs_the_target_lacks() {
+        let sources = [source(
+            "q.sql",
+            "SELECT a.id FROM a, b WHERE a.id = b.id(+) AND a.name ILIKE 'x%' \
+             QUALIFY ROW_NUMBER() OVER (ORDER BY a.id) = 1",
+        )];
+        let report = dialect_report(&sources, Dialect::Snowflake, Some(Dialect::Postgres));
+
+        assert_eq!(usage(&report, "qualify").supported_by_target, Some(false));
+        assert_eq!(
+            usage(&report, "outer_join_operator").supported_by_target,
+            Some(false)
+        );
+        assert_eq!(usage(&report, "ilike").supported_by_target, Some(true));
+        assert!(report.has_portability_issues());
+
+        let text = format_dialect_report(&report);
+        assert!(
+            text.contains("2 construct(s) not supported by postgres"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn test_quoting_is_counted_even_when_parsing_fails() {
+        let sources = [source(
+            "broken.sql",
+            "SELECT `id`, `name` FROM `users` WHERE",
+        )];
+        let report = dialect_report(&sources, Dialect::Mysql, Some(Dialect::Postgres));
+
+        assert_eq!(usage(&report, "backtick_quoting").count, 3);
+        assert_eq!(report.unparsed, vec!["broken.sql".to_string()]);
+    }
+}
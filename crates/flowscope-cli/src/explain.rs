@@ -0,0 +1,384 @@
+//! Column provenance tracing for `--explain-lineage`.
+//!
+//! Walks the global lineage graph backward from a target column and produces
+//! a tree of upstream columns, annotated with the transformation at each hop.
+//! [`downstream_columns`] walks it forward instead, for impact analysis.
+
+use anyhow::{bail, Result};
+use flowscope_core::{AnalyzeResult, EdgeType, GlobalNode, NodeType};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+/// A column in a provenance chain together with its upstream sources.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineageTrace {
+    /// Qualified column name (`relation.column`)
+    pub column: String,
+    /// Global node ID of the column
+    pub node_id: String,
+    /// Expression of the downstream column computed from this one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expression: Option<String>,
+    /// Operation label of the hop (e.g. `JOIN`, `UNION`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation: Option<String>,
+    /// True when this column was already visited on the current path
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub cycle: bool,
+    /// True when this column's sources were already listed earlier in the trace
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub repeated: bool,
+    /// Upstream columns feeding this one (empty for base columns)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<LineageTrace>,
+}
+
+/// Indexed view over the global lineage graph used for backward traversal.
+struct LineageIndex<'a> {
+    nodes: HashMap<&'a str, &'a GlobalNode>,
+    owners: HashMap<&'a str, &'a GlobalNode>,
+    incoming: HashMap<&'a str, Vec<Hop<'a>>>,
+    outgoing: HashMap<&'a str, Vec<&'a str>>,
+}
+
+struct Hop<'a> {
+    from: &'a str,
+    expression: Option<&'a str>,
+    operation: Option<&'a str>,
+}
+
+impl<'a> LineageIndex<'a> {
+    fn new(result: &'a AnalyzeResult) -> Self {
+        let lineage = &result.global_lineage;
+        let nodes: HashMap<&str, &GlobalNode> =
+            lineage.nodes.iter().map(|n| (n.id.as_ref(), n)).collect();
+
+        // Column expressions live on the per-statement nodes, not the global ones.
+        let expressions: HashMap<&str, &str> = result
+            .statements
+            .iter()
+            .flat_map(|s| s.nodes.iter())
+            .filter_map(|n| n.expression.as_deref().map(|e| (n.id.as_ref(), e)))
+            .collect();
+
+        let mut owners = HashMap::new();
+        let mut incoming: HashMap<&str, Vec<Hop>> = HashMap::new();
+        let mut outgoing: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut seen_edges: HashSet<(&str, &str)> = HashSet::new();
+
+        for edge in &lineage.edges {
+            match edge.edge_type {
+                EdgeType::Ownership => {
+                    if let Some(owner) = nodes.get(edge.from.as_ref()) {
+                        owners.insert(edge.to.as_ref(), *owner);
+                    }
+                }
+                EdgeType::DataFlow | EdgeType::Derivation => {
+                    let is_column = |id: &str| {
+                        nodes
+                            .get(id)
+                            .is_some_and(|n| n.node_type == NodeType::Column)
+                    };
+                    if !is_column(&edge.from) || !is_column(&edge.to) {
+                        continue;
+                    }
+                    if !seen_edges.insert((edge.from.as_ref(), edge.to.as_ref())) {
+                        continue;
+                    }
+                    outgoing
+                        .entry(edge.from.as_ref())
+                        .or_default()
+                        .push(edge.to.as_ref());
+                    incoming.entry(edge.to.as_ref()).or_default().push(Hop {
+                        from: edge.from.as_ref(),
+                        expression: expressions.get(edge.to.as_ref()).copied(),
+                        operation: None,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        // Attach operation labels from the statement-level edges.
+        for edge in result.statements.iter().flat_map(|s| s.edges.iter()) {
+            if let (Some(op), Some(hops)) = (
+                edge.operation.as_deref(),
+                incoming.get_mut(edge.to.as_ref()),
+            ) {
+                if let Some(hop) = hops.iter_mut().find(|h| h.from == edge.from.as_ref()) {
+                    hop.operation.get_or_insert(op);
+                }
+            }
+        }
+
+        Self {
+            nodes,
+            owners,
+            incoming,
+            outgoing,
+        }
+    }
+
+    fn display_name(&self, column_id: &str) -> String {
+        let Some(column) = self.nodes.get(column_id) else {
+            return column_id.to_string();
+        };
+        match self.owners.get(column_id) {
+            Some(owner) => format!("{}.{}", relation_name(owner), column.label),
+            None => column.label.to_string(),
+        }
+    }
+
+    /// Finds column nodes matching `relation.column`, compared case-insensitively.
+    fn find_columns(&self, target: &str) -> Vec<&'a str> {
+        let Some((relation, column)) = target.rsplit_once('.') else {
+            return Vec::new();
+        };
+
+        let mut matches: Vec<&str> = self
+            .owners
+            .iter()
+            .filter(|(id, owner)| {
+                owner.node_type.is_relation()
+                    && relation_matches(owner, relation)
+                    && self
+                        .nodes
+                        .get(*id)
+                        .is_some_and(|n| n.label.eq_ignore_ascii_case(column))
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        matches.sort_unstable();
+        matches
+    }
+
+    fn trace(
+        &self,
+        column_id: &'a str,
+        hop: Option<&Hop<'a>>,
+        path: &mut Vec<&'a str>,
+        expanded: &mut HashSet<&'a str>,
+    ) -> LineageTrace {
+        let mut trace = LineageTrace {
+            column: self.display_name(column_id),
+            node_id: column_id.to_string(),
+            expression: hop.and_then(|h| h.expression).map(String::from),
+            operation: hop.and_then(|h| h.operation).map(String::from),
+            cycle: false,
+            repeated: false,
+            sources: Vec::new(),
+        };
+
+        if path.contains(&column_id) {
+            trace.cycle = true;
+            return trace;
+        }
+        // Diamond-shaped graphs reach the same column along many paths; expand
+        // it once so the walk stays linear in the size of the graph.
+        if let Some(hops) = self.incoming.get(column_id) {
+            if !expanded.insert(column_id) {
+                trace.repeated = true;
+                return trace;
+            }
+            let mut hops: Vec<_> = hops
+                .iter()
+                .map(|hop| (self.display_name(hop.from), hop))
+                .collect();
+            hops.sort_by(|a, b| a.0.cmp(&b.0));
+
+            path.push(column_id);
+            trace.sources = hops
+                .into_iter()
+                .map(|(_, hop)| self.trace(hop.from, Some(hop), path, expanded))
+                .collect();
+            path.pop();
+        }
+
+        trace
+    }
+}
+
+/// Columns computed, directly or transitively, from `target` (`relation.column`), as
+/// sorted `relation.column` names.
+///
+/// Returns an empty list when the column does not appear in the lineage graph.
+pub fn downstream_columns(result: &AnalyzeResult, target: &str) -> Vec<String> {
+    let index = LineageIndex::new(result);
+    let mut stack = index.find_columns(target);
+    let mut seen: HashSet<&str> = stack.iter().copied().collect();
+    let mut names = BTreeSet::new();
+    while let Some(column_id) = stack.pop() {
+        for next in index.outgoing.get(column_id).into_iter().flatten() {
+            if seen.insert(next) {
+                names.insert(index.display_name(next));
+                stack.push(next);
+            }
+        }
+    }
+    names.into_iter().collect()
+}
+
+fn relation_name(node: &GlobalNode) -> String {
+    if node.node_type == NodeType::Output {
+        return node.label.to_string();
+    }
+    node.canonical_name.to_qualified_string()
+}
+
+fn relation_matches(node: &GlobalNode, name: &str) -> bool {
+    relation_name(node).eq_ignore_ascii_case(name) || node.label.eq_ignore_ascii_case(name)
+}
+
+/// Builds the provenance tree for `target` (`relation.column`).
+///
+/// Returns one trace per matching column node; multiple matches occur when
+/// the same qualified name appears under different relation nodes.
+pub fn explain_lineage(result: &AnalyzeResult, target: &str) -> Result<Vec<LineageTrace>> {
+    if !target.contains('.') {
+        bail!(
+            "--explain-lineage expects a qualified column such as 'table.column', got '{target}'"
+        );
+    }
+
+    let index = LineageIndex::new(result);
+    let columns = index.find_columns(target);
+    if columns.is_empty() {
+        bail!("Column '{target}' not found in lineage graph");
+    }
+
+    Ok(columns
+        .into_iter()
+        .map(|id| index.trace(id, None, &mut Vec::new(), &mut HashSet::new()))
+        .collect())
+}
+
+/// Formats provenance traces as an indented tree.
+pub fn format_lineage_trace(traces: &[LineageTrace]) -> String {
+    let mut out = String::new();
+    for trace in traces {
+        writeln!(out, "{}", trace.column).unwrap();
+        write_sources(&mut out, &trace.sources, 1);
+    }
+    out
+}
+
+fn write_sources(out: &mut String, sources: &[LineageTrace], depth: usize) {
+    for source in sources {
+        let indent = "  ".repeat(depth);
+        let mut line = format!("{indent}<- {}", source.column);
+        if let Some(op) = &source.operation {
+            write!(line, " [{op}]").unwrap();
+        }
+        if let Some(expr) = &source.expression {
+            write!(line, " via {expr}").unwrap();
+        }
+        if source.cycle {
+            line.push_str(" (cycle)");
+        }
+        if source.repeated {
+            line.push_str(" (see above)");
+        }
+        writeln!(out, "{line}").unwrap();
+        write_sources(out, &source.sources, depth + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowscope_core::{analyze, AnalyzeRequest, Dialect};
+
+    fn analyze_sql(sql: &str) -> AnalyzeResult {
+        analyze(&AnalyzeRequest {
+            sql: sql.to_string(),
+            files: None,
+            dialect: Dialect::Generic,
+            source_name: None,
+            options: None,
+            schema: None,
+            template_config: None,
+        })
+    }
+
+    #[test]
+    fn test_explain_two_hop_derivation() {
+        let result = analyze_sql(
+            "CREATE TABLE staging.orders AS SELECT amount * 2 AS doubled FROM raw.orders;
+             CREATE TABLE mart.revenue AS SELECT doubled + 1 AS amount FROM staging.orders;",
+        );
+
+        let traces = explain_lineage(&result, "mart.revenue.amount").unwrap();
+        assert_eq!(
+            format_lineage_trace(&traces),
+            "mart.revenue.amount\n  \
+             <- staging.orders.doubled via doubled + 1\n    \
+             <- raw.orders.amount via amount * 2\n"
+        );
+    }
+
+    #[test]
+    fn test_explain_branching_sources() {
+        let result = analyze_sql(
+            "CREATE TABLE mart.totals AS SELECT o.price * o.qty AS total FROM raw.orders o",
+        );
+
+        let traces = explain_lineage(&result, "mart.totals.total").unwrap();
+        assert_eq!(traces.len(), 1);
+        let sources: Vec<_> = traces[0]
+            .sources
+            .iter()
+            .map(|s| s.column.as_str())
+            .collect();
+        assert_eq!(sources, vec!["raw.orders.price", "raw.orders.qty"]);
+    }
+
+    #[test]
+    fn test_explain_expands_shared_columns_once() {
+        let mut sql = String::from("CREATE TABLE t0 AS SELECT x, y FROM raw.base;\n");
+        for i in 1..=30 {
+            writeln!(
+                sql,
+                "CREATE TABLE t{i} AS SELECT p.x + p.y AS x, p.x - p.y AS y FROM t{} p;",
+                i - 1
+            )
+            .unwrap();
+        }
+        let result = analyze_sql(&sql);
+
+        let traces = explain_lineage(&result, "t30.x").unwrap();
+        let sources = &traces[0].sources;
+        assert_eq!(sources[0].column, "t29.x");
+        assert!(!sources[0].repeated);
+        assert_eq!(sources[1].column, "t29.y");
+        assert!(!sources[1].repeated);
+        // t29.y shares both of its sources with t29.x, listed first.
+        assert!(sources[1].sources.iter().all(|s| s.repeated));
+
+        let text = format_lineage_trace(&traces);
+        assert!(text.contains("<- t28.x via p.x - p.y (see above)"));
+        assert!(text.lines().count() < 200);
+    }
+
+    #[test]
+    fn test_downstream_columns_follow_derivations() {
+        let result = analyze_sql(
+            "CREATE TABLE staging.orders AS SELECT amount * 2 AS doubled, id FROM raw.orders;
+             CREATE TABLE mart.revenue AS SELECT doubled + 1 AS amount FROM staging.orders;",
+        );
+
+        assert_eq!(
+            downstream_columns(&result, "raw.orders.amount"),
+            vec!["mart.revenue.amount", "staging.orders.doubled"]
+        );
+        assert!(downstream_columns(&result, "mart.revenue.amount").is_empty());
+    }
+
+    #[test]
+    fn test_explain_unknown_column_errors() {
+        let result = analyze_sql("SELECT id FROM users");
+        assert!(explain_lineage(&result, "users.missing").is_err());
+        assert!(explain_lineage(&result, "unqualified").is_err());
+    }
+}
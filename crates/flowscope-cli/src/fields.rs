@@ -0,0 +1,122 @@
+//! Projection of JSON output to selected fields for `--fields`.
+//!
+//! Paths are dotted JSON keys as they appear in the output (`summary`,
+//! `statements.nodes`, `globalLineage.edges`). A key applied to an array applies to
+//! each of its elements, so `statements.statementType` keeps the type of every
+//! statement.
+
+use serde_json::{Map, Value};
+
+/// Keeps only the parts of `value` named by `fields`.
+///
+/// Returns the projected value and the paths that matched nothing in `value`.
+pub fn project_fields(value: &Value, fields: &[String]) -> (Value, Vec<String>) {
+    let paths: Vec<Vec<&str>> = fields
+        .iter()
+        .map(|field| field.split('.').map(str::trim).collect())
+        .collect();
+    let mut matched: Vec<&[&str]> = Vec::new();
+    let mut unmatched = Vec::new();
+    for (field, path) in fields.iter().zip(&paths) {
+        if path.iter().all(|key| !key.is_empty()) && path_exists(value, path) {
+            matched.push(path);
+        } else {
+            unmatched.push(field.clone());
+        }
+    }
+    (project(value, &matched), unmatched)
+}
+
+fn path_exists(value: &Value, path: &[&str]) -> bool {
+    let Some((key, rest)) = path.split_first() else {
+        return true;
+    };
+    match value {
+        // Optional keys may be missing from some elements, and an empty array cannot
+        // tell whether its elements would have the key
+        Value::Array(items) => items.is_empty() || items.iter().any(|item| path_exists(item, path)),
+        Value::Object(map) => map.get(*key).is_some_and(|child| path_exists(child, rest)),
+        _ => false,
+    }
+}
+
+fn project(value: &Value, paths: &[&[&str]]) -> Value {
+    if paths.iter().any(|path| path.is_empty()) {
+        return value.clone();
+    }
+    match value {
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|item| project(item, paths)).collect())
+        }
+        Value::Object(map) => {
+            // Keys keep their order in the output rather than the order requested
+            let mut projected = Map::new();
+            for (key, child) in map {
+                let rest: Vec<&[&str]> = paths
+                    .iter()
+                    .filter(|path| path[0] == key)
+                    .map(|path| &path[1..])
+                    .collect();
+                if !rest.is_empty() {
+                    projected.insert(key.clone(), project(child, &rest));
+                }
+            }
+            Value::Object(projected)
+        }
+        _ => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(list: &[&str]) -> Vec<String> {
+        list.iter().map(|field| field.to_string()).collect()
+    }
+
+    #[test]
+    fn projects_nested_paths_through_arrays() {
+        let value = json!({
+            "statements": [
+                {"statementIndex": 0, "statementType": "SELECT", "nodes": [1, 2]},
+                {"statementIndex": 1, "statementType": "INSERT", "nodes": []}
+            ],
+            "summary": {"statementCount": 2, "hasErrors": false},
+            "issues": []
+        });
+
+        let (projected, unmatched) =
+            project_fields(&value, &fields(&["summary", "statements.statementType"]));
+        assert!(unmatched.is_empty());
+        assert_eq!(
+            projected,
+            json!({
+                "statements": [{"statementType": "SELECT"}, {"statementType": "INSERT"}],
+                "summary": {"statementCount": 2, "hasErrors": false}
+            })
+        );
+    }
+
+    #[test]
+    fn reports_paths_that_match_nothing() {
+        let value = json!({"summary": {"statementCount": 1}, "issues": []});
+
+        let (projected, unmatched) = project_fields(
+            &value,
+            &fields(&[
+                "summary.statementCount",
+                "summary.bogus",
+                "nope",
+                "summary.",
+                "issues.code",
+            ]),
+        );
+        assert_eq!(unmatched, fields(&["summary.bogus", "nope", "summary."]));
+        assert_eq!(
+            projected,
+            json!({"summary": {"statementCount": 1}, "issues": []})
+        );
+    }
+}
@@ -0,0 +1,322 @@
+//! Applying issue fixes to the input files for `--fix`.
+
+use flowscope_core::{
+    apply_fixes, parse_sql_with_dialect, AnalyzeResult, Dialect, FileSource, Issue,
+};
+use serde::Serialize;
+use sqlparser::tokenizer::{Token, Tokenizer, Whitespace};
+
+/// What applying its fixes did to an input that had any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedSource {
+    /// Name of the input (its path, or `<stdin>.sql`)
+    pub name: String,
+    /// The fixed SQL, or `None` if the input is left unchanged
+    pub sql: Option<String>,
+    /// Number of fixes applied; overlapping fixes are skipped and not counted
+    pub fixes: usize,
+    /// Number of fixes skipped because they overlap one that was applied
+    pub skipped: usize,
+    /// Number of fixes skipped because they would delete comments
+    pub skipped_due_to_comments: usize,
+    /// The parse error of the fixed SQL, which is therefore not written
+    pub skipped_due_to_regression: Option<String>,
+}
+
+/// What `--fix` did to one input, for `--fix -f json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixReport {
+    /// Name of the input (its path, or `<stdin>.sql`)
+    pub file: String,
+    /// Whether the input was rewritten
+    pub changed: bool,
+    /// Number of fixes applied
+    pub applied: usize,
+    /// Why some or all of the input's fixes were not applied
+    pub skipped_reason: Option<String>,
+}
+
+/// Applies the fixes reported in `result` to each of `sources`, returning an outcome
+/// for every input that has fixes.
+///
+/// A fix whose replacement drops a comment of the text it replaces is skipped, and an
+/// input whose fixed SQL no longer parses in `dialect` is left unchanged, so neither
+/// user text nor a bad fix is ever written.
+pub fn fix_sources(
+    sources: &[FileSource],
+    result: &AnalyzeResult,
+    dialect: Dialect,
+) -> Vec<FixedSource> {
+    let mut fixed = Vec::new();
+    for source in sources {
+        let (issues, dropping_comments): (Vec<_>, Vec<_>) = result
+            .issues
+            .iter()
+            .filter(|issue| issue.fix.is_some())
+            .filter(|issue| issue_source(result, issue) == Some(source.name.as_str()))
+            .cloned()
+            .partition(|issue| !drops_comments(&source.content, issue, dialect));
+        if issues.is_empty() && dropping_comments.is_empty() {
+            continue;
+        }
+        let (sql, applied) = apply_fixes(&source.content, &issues);
+        let mut outcome = FixedSource {
+            name: source.name.clone(),
+            sql: None,
+            fixes: applied,
+            skipped: issues.len() - applied,
+            skipped_due_to_comments: dropping_comments.len(),
+            skipped_due_to_regression: None,
+        };
+        if sql != source.content {
+            match parse_sql_with_dialect(&sql, dialect) {
+                Ok(_) => outcome.sql = Some(sql.into_owned()),
+                Err(err) => {
+                    outcome.fixes = 0;
+                    outcome.skipped = issues.len();
+                    outcome.skipped_due_to_regression = Some(err.to_string());
+                }
+            }
+        }
+        fixed.push(outcome);
+    }
+    fixed
+}
+
+/// Reports the outcome of `--fix` for every one of `sources`, given the outcomes
+/// [`fix_sources`] returned.
+pub fn fix_report(sources: &[FileSource], fixed: &[FixedSource]) -> Vec<FixReport> {
+    sources
+        .iter()
+        .map(
+            |source| match fixed.iter().find(|f| f.name == source.name) {
+                Some(fixed) => FixReport {
+                    file: source.name.clone(),
+                    changed: fixed.sql.is_some(),
+                    applied: fixed.fixes,
+                    skipped_reason: skipped_reason(fixed),
+                },
+                None => FixReport {
+                    file: source.name.clone(),
+                    changed: false,
+                    applied: 0,
+                    skipped_reason: None,
+                },
+            },
+        )
+        .collect()
+}
+
+/// Why some of an input's fixes were not applied, most severe reason first.
+pub fn skipped_reason(fixed: &FixedSource) -> Option<String> {
+    let mut reasons = Vec::new();
+    if fixed.skipped_due_to_regression.is_some() {
+        reasons.push("fix would introduce a parse error".to_string());
+    } else if fixed.skipped > 0 {
+        reasons.push(format!("{} overlapping fix(es) skipped", fixed.skipped));
+    }
+    if fixed.skipped_due_to_comments > 0 {
+        reasons.push(format!(
+            "{} fix(es) skipped because they would delete comments",
+            fixed.skipped_due_to_comments
+        ));
+    }
+    (!reasons.is_empty()).then(|| reasons.join("; "))
+}
+
+/// Whether the fix of `issue` replaces a comment of `sql` that its replacement
+/// does not keep.
+fn drops_comments(sql: &str, issue: &Issue, dialect: Dialect) -> bool {
+    let Some(fix) = &issue.fix else {
+        return false;
+    };
+    let Some(replaced) = sql.get(fix.span.start..fix.span.end) else {
+        return false;
+    };
+    let sqlparser_dialect = dialect.to_sqlparser_dialect();
+    let Ok(tokens) = Tokenizer::new(sqlparser_dialect.as_ref(), replaced).tokenize() else {
+        return false;
+    };
+    tokens.iter().any(|token| match token {
+        Token::Whitespace(Whitespace::SingleLineComment { comment, .. }) => {
+            !fix.replacement.contains(comment.trim_end())
+        }
+        Token::Whitespace(Whitespace::MultiLineComment(comment)) => {
+            !fix.replacement.contains(comment.as_str())
+        }
+        _ => false,
+    })
+}
+
+/// The input an issue was reported against: its own source name, or that of its
+/// statement.
+pub(crate) fn issue_source<'r>(result: &'r AnalyzeResult, issue: &'r Issue) -> Option<&'r str> {
+    issue.source_name.as_deref().or_else(|| {
+        let index = issue.statement_index?;
+        result
+            .statements
+            .iter()
+            .find(|statement| statement.statement_index == index)?
+            .source_name
+            .as_deref()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowscope_core::{analyze, AnalyzeRequest};
+
+    #[test]
+    fn test_fixes_each_file_with_its_own_issues() {
+        let sources = vec![
+            FileSource {
+                name: "latest.sql".to_string(),
+                content: "SELECT id, ts FROM events QUALIFY ROW_NUMBER() OVER (PARTITION BY id ORDER BY ts DESC) = 1;\n".to_string(),
+            },
+            FileSource {
+                name: "clean.sql".to_string(),
+                content: "SELECT id FROM events;\n".to_string(),
+            },
+        ];
+        let result = analyze(&AnalyzeRequest {
+            sql: String::new(),
+            files: Some(sources.clone()),
+            dialect: Dialect::Postgres,
+            source_name: None,
+            options: None,
+            schema: None,
+            template_config: None,
+        });
+
+        let fixed = fix_sources(&sources, &result, Dialect::Postgres);
+        assert_eq!(
+            fixed,
+            vec![FixedSource {
+                name: "latest.sql".to_string(),
+                sql: Some("SELECT id, ts FROM (SELECT id, ts, ROW_NUMBER() OVER (PARTITION BY id ORDER BY ts DESC) AS _qualify_1 FROM events) AS qualified_rows WHERE _qualify_1 = 1;\n".to_string()),
+                fixes: 1,
+                skipped: 0,
+                skipped_due_to_comments: 0,
+                skipped_due_to_regression: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_counts_only_applied_fixes() {
+        let sources = vec![FileSource {
+            name: "latest.sql".to_string(),
+            content: "SELECT id AS order, ts FROM events QUALIFY ROW_NUMBER() OVER (PARTITION BY id ORDER BY ts DESC) = 1;\n".to_string(),
+        }];
+        let result = analyze(&AnalyzeRequest {
+            sql: String::new(),
+            files: Some(sources.clone()),
+            dialect: Dialect::Postgres,
+            source_name: None,
+            options: Some(flowscope_core::AnalysisOptions {
+                check_reserved_keywords: Some(true),
+                ..Default::default()
+            }),
+            schema: None,
+            template_config: None,
+        });
+        let reported = result.issues.iter().filter(|i| i.fix.is_some()).count();
+
+        let fixed = fix_sources(&sources, &result, Dialect::Postgres);
+        // The alias quoting fix overlaps the QUALIFY rewrite and is skipped
+        assert_eq!(reported, 2);
+        assert_eq!(fixed[0].fixes, 1);
+        assert_eq!(fixed[0].skipped, 1);
+
+        let report = fix_report(&sources, &fixed);
+        assert_eq!(report.len(), 1);
+        assert!(report[0].changed);
+        assert_eq!(report[0].applied, 1);
+        assert_eq!(
+            report[0].skipped_reason.as_deref(),
+            Some("1 overlapping fix(es) skipped")
+        );
+    }
+
+    /// Analyzes `source` in Postgres and adds an issue fixing `span` with `replacement`.
+    fn result_with_fix(
+        source: &FileSource,
+        span: (usize, usize),
+        replacement: &str,
+    ) -> AnalyzeResult {
+        let mut result = analyze(&AnalyzeRequest {
+            sql: String::new(),
+            files: Some(vec![source.clone()]),
+            dialect: Dialect::Postgres,
+            source_name: None,
+            options: None,
+            schema: None,
+            template_config: None,
+        });
+        result.issues.push(
+            Issue::warning("TEST_FIX", "test fix")
+                .with_source_name(&source.name)
+                .with_fix(flowscope_core::Span::new(span.0, span.1), replacement),
+        );
+        result
+    }
+
+    #[test]
+    fn test_skips_files_whose_fix_would_not_parse() {
+        let broken = FileSource {
+            name: "broken.sql".to_string(),
+            content: "SELECT id FROM t;\n".to_string(),
+        };
+        let result = result_with_fix(&broken, (0, 6), "SELEC");
+
+        let fixed = fix_sources(std::slice::from_ref(&broken), &result, Dialect::Postgres);
+        assert_eq!(fixed.len(), 1);
+        assert_eq!(fixed[0].sql, None);
+        assert!(fixed[0].skipped_due_to_regression.is_some());
+
+        let report = fix_report(&[broken], &fixed);
+        assert_eq!(
+            report[0],
+            FixReport {
+                file: "broken.sql".to_string(),
+                changed: false,
+                applied: 0,
+                skipped_reason: Some("fix would introduce a parse error".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_skips_fixes_that_would_delete_comments() {
+        let commented = FileSource {
+            name: "commented.sql".to_string(),
+            content: "SELECT id /* keep me */ FROM t;\n".to_string(),
+        };
+        let result = result_with_fix(&commented, (7, 23), "\"id\"");
+
+        let fixed = fix_sources(std::slice::from_ref(&commented), &result, Dialect::Postgres);
+        assert_eq!(fixed[0].sql, None);
+        assert_eq!(fixed[0].skipped_due_to_comments, 1);
+
+        let report = fix_report(&[commented], &fixed);
+        assert!(!report[0].changed);
+        assert_eq!(
+            report[0].skipped_reason.as_deref(),
+            Some("1 fix(es) skipped because they would delete comments")
+        );
+
+        // A replacement that keeps the comment is applied
+        let kept = FileSource {
+            name: "kept.sql".to_string(),
+            content: "SELECT id /* keep me */ FROM t;\n".to_string(),
+        };
+        let result = result_with_fix(&kept, (7, 23), "\"id\" /* keep me */");
+        let fixed = fix_sources(std::slice::from_ref(&kept), &result, Dialect::Postgres);
+        assert_eq!(
+            fixed[0].sql.as_deref(),
+            Some("SELECT \"id\" /* keep me */ FROM t;\n")
+        );
+    }
+}
@@ -1,9 +1,14 @@
 //! Input handling for file reading and stdin support.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use flowscope_core::FileSource;
+use std::collections::HashSet;
 use std::io::{self, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Source name given to SQL read from stdin; the `.sql` extension keeps it in
+/// frontend file filters.
+pub const STDIN_SOURCE_NAME: &str = "<stdin>.sql";
 
 /// Read SQL input from files or stdin.
 ///
@@ -25,8 +30,7 @@ fn read_from_stdin() -> Result<Vec<FileSource>> {
         .context("Failed to read from stdin")?;
 
     Ok(vec![FileSource {
-        // Use .sql extension so frontend filters include stdin content
-        name: "<stdin>.sql".to_string(),
+        name: STDIN_SOURCE_NAME.to_string(),
         content,
     }])
 }
@@ -47,6 +51,84 @@ fn read_from_files(files: &[PathBuf]) -> Result<Vec<FileSource>> {
         .collect()
 }
 
+/// The `.sql` files under `dir`, recursively and in sorted order.
+pub fn collect_sql_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    collect_sql_files_into(dir, &mut files)?;
+    Ok(files)
+}
+
+fn collect_sql_files_into(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory {}", dir.display()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()
+        .with_context(|| format!("Failed to read directory {}", dir.display()))?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_sql_files_into(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "sql") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Positional paths with glob patterns expanded by [`expand_globs`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlobExpansion {
+    /// Input paths in argument order, each pattern replaced by its sorted `.sql`
+    /// matches, without duplicates
+    pub paths: Vec<PathBuf>,
+    /// Matches left out because they are not `.sql` files
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Expands glob patterns such as `models/**/*.sql` among `paths`, for shells that
+/// do not expand them (Windows cmd) and argument lists too long to pass expanded.
+///
+/// A path is treated as a pattern only if it contains `*`, `?` or `[` and does not
+/// exist as written. Paths that appear more than once are kept at their first
+/// position. Fails when a pattern is invalid or matches no `.sql` file.
+pub fn expand_globs(paths: &[PathBuf]) -> Result<GlobExpansion> {
+    let mut expansion = GlobExpansion::default();
+    let mut seen = HashSet::new();
+
+    for path in paths {
+        let pattern = path.to_string_lossy();
+        if path.exists() || !pattern.contains(['*', '?', '[']) {
+            if seen.insert(path.clone()) {
+                expansion.paths.push(path.clone());
+            }
+            continue;
+        }
+
+        let mut matches = glob::glob(&pattern)
+            .with_context(|| format!("Invalid glob pattern: {pattern}"))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .with_context(|| format!("Failed to expand glob pattern: {pattern}"))?;
+        matches.sort();
+
+        let mut matched_sql = false;
+        for file in matches {
+            if file.is_file() && file.extension().is_some_and(|ext| ext == "sql") {
+                matched_sql = true;
+                if seen.insert(file.clone()) {
+                    expansion.paths.push(file);
+                }
+            } else if file.is_file() {
+                expansion.skipped.push(file);
+            }
+        }
+        if !matched_sql {
+            bail!("Glob pattern matched no .sql files: {pattern}");
+        }
+    }
+    Ok(expansion)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,4 +162,46 @@ mod tests {
         let result = read_from_files(&[PathBuf::from("/nonexistent/file.sql")]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_expand_globs_dedups_and_skips_non_sql() {
+        let dir = tempfile::tempdir().unwrap();
+        let models = dir.path().join("models");
+        std::fs::create_dir_all(models.join("staging")).unwrap();
+        for name in ["b.sql", "a.sql", "notes.md", "staging/c.sql"] {
+            std::fs::write(models.join(name), "SELECT 1").unwrap();
+        }
+
+        let explicit = models.join("b.sql");
+        let expansion = expand_globs(&[
+            explicit.clone(),
+            models.join("**").join("*"),
+            models.join("*.sql"),
+        ])
+        .unwrap();
+        assert_eq!(
+            expansion.paths,
+            vec![explicit, models.join("a.sql"), models.join("staging/c.sql")]
+        );
+        assert_eq!(expansion.skipped, vec![models.join("notes.md")]);
+
+        let plain = PathBuf::from("/nonexistent/file.sql");
+        assert_eq!(
+            expand_globs(std::slice::from_ref(&plain)).unwrap().paths,
+            vec![plain]
+        );
+    }
+
+    #[test]
+    fn test_expand_globs_without_matches_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.md"), "").unwrap();
+
+        let error = expand_globs(&[dir.path().join("*.sql")]).unwrap_err();
+        assert!(
+            error.to_string().contains("matched no .sql files"),
+            "{error}"
+        );
+        assert!(expand_globs(&[dir.path().join("*")]).is_err());
+    }
 }
@@ -0,0 +1,77 @@
+//! `--list-issue-codes`: print the issue codes the analyzer can report.
+
+use std::fmt::Write;
+
+use flowscope_core::{IssueCodeInfo, Severity};
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    }
+}
+
+/// Formats the catalog as a table with one code per line.
+pub fn format_issue_catalog(catalog: &[IssueCodeInfo]) -> String {
+    let code_width = catalog
+        .iter()
+        .map(|info| info.code.len())
+        .max()
+        .unwrap_or(0)
+        .max("CODE".len());
+    let option_width = catalog
+        .iter()
+        .map(|info| info.option.map_or(1, str::len))
+        .max()
+        .unwrap_or(0)
+        .max("OPTION".len());
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{:<code_width$}  {:<8}  {:<option_width$}  DESCRIPTION",
+        "CODE", "SEVERITY", "OPTION"
+    )
+    .unwrap();
+    for info in catalog {
+        let mut description = info.description.to_string();
+        if let Some(rule) = info.sqlfluff_rule {
+            write!(description, " (SQLFluff {rule})").unwrap();
+        }
+        if info.deprecated {
+            description.push_str(" [deprecated]");
+        }
+        writeln!(
+            out,
+            "{:<code_width$}  {:<8}  {:<option_width$}  {description}",
+            info.code,
+            severity_name(info.default_severity),
+            info.option.unwrap_or("-"),
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowscope_core::issue_catalog;
+
+    #[test]
+    fn test_format_issue_catalog_lists_every_code() {
+        let output = format_issue_catalog(issue_catalog());
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines[0].starts_with("CODE "));
+        assert_eq!(lines.len(), issue_catalog().len() + 1);
+        let long_line = lines
+            .iter()
+            .find(|line| line.starts_with("LONG_LINE "))
+            .expect("LONG_LINE row");
+        assert!(long_line.contains(" info "));
+        assert!(long_line.contains(" maxLineLength "));
+        assert!(long_line.ends_with("(SQLFluff layout.long_lines)"));
+    }
+}
@@ -3,12 +3,26 @@
 //! This module exposes internal types for testing purposes.
 //! The main entry point is the `flowscope` binary.
 
+pub mod bench;
+pub mod build_plan;
+pub mod check_only;
 pub mod cli;
+pub mod dialect_report;
+pub mod explain;
+pub mod fields;
+pub mod fix;
 pub mod input;
+pub mod issue_catalog;
 #[cfg(feature = "metadata-provider")]
 pub mod metadata;
+pub mod ndjson;
 pub mod output;
+pub mod per_file;
+#[cfg(feature = "templating")]
+pub mod report_template;
+pub mod sample;
 pub mod schema;
+pub mod schema_diff;
 #[cfg(feature = "serve")]
 pub mod server;
 
@@ -1,27 +1,42 @@
 //! FlowScope CLI - SQL lineage analyzer
 
+use flowscope_cli::bench;
+use flowscope_cli::build_plan;
+use flowscope_cli::check_only;
 use flowscope_cli::cli;
+use flowscope_cli::dialect_report;
+use flowscope_cli::explain;
+use flowscope_cli::fields;
+use flowscope_cli::fix;
 use flowscope_cli::input;
+use flowscope_cli::issue_catalog;
 #[cfg(feature = "metadata-provider")]
 use flowscope_cli::metadata;
+use flowscope_cli::ndjson;
 use flowscope_cli::output;
+use flowscope_cli::per_file;
+#[cfg(feature = "templating")]
+use flowscope_cli::report_template;
+use flowscope_cli::sample;
 use flowscope_cli::schema;
+use flowscope_cli::schema_diff;
 #[cfg(feature = "serve")]
 use flowscope_cli::server;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::Parser;
-use flowscope_core::{analyze, AnalyzeRequest, FileSource};
+use flowscope_core::{analyze, AnalyzeRequest};
 use flowscope_export::{
-    export_csv_bundle, export_duckdb, export_html, export_json, export_mermaid, export_sql,
-    export_xlsx, ExportFormat, ExportNaming, MermaidView,
+    export_csv_bundle, export_duckdb, export_html, export_html_colored, export_json,
+    export_markdown, export_mermaid, export_mermaid_colored, export_sql, export_turtle,
+    export_xlsx, qualify_table_names, ExportFormat, ExportNaming, MermaidView,
 };
 use std::fs;
 use std::io::{self, Write};
 use std::process::ExitCode;
 
-use cli::{Args, OutputFormat, ViewMode};
-use output::format_table;
+use cli::{Args, DialectArg, GranularityArg, OutputFormat, ViewMode};
+use output::{format_sarif, format_table};
 
 fn main() -> ExitCode {
     // Check for serve mode first (requires tokio runtime)
@@ -48,9 +63,25 @@ fn main() -> ExitCode {
     }
 }
 
+/// Replaces glob patterns among the positional files with the `.sql` files they
+/// match, warning about other matches.
+fn expand_file_globs(args: &mut Args) -> Result<()> {
+    let expansion = input::expand_globs(&args.files)?;
+    if !args.quiet {
+        for path in &expansion.skipped {
+            eprintln!(
+                "flowscope: warning: skipping non-SQL file matched by a glob: {}",
+                path.display()
+            );
+        }
+    }
+    args.files = expansion.paths;
+    Ok(())
+}
+
 /// Run the CLI in serve mode with embedded web UI.
 #[cfg(feature = "serve")]
-fn run_serve_mode(args: Args) -> ExitCode {
+fn run_serve_mode(mut args: Args) -> ExitCode {
     use server::ServerConfig;
 
     #[cfg(feature = "templating")]
@@ -62,6 +93,16 @@ fn run_serve_mode(args: Args) -> ExitCode {
         }
     });
 
+    if let Err(e) = expand_file_globs(&mut args) {
+        eprintln!("flowscope: error: {e:#}");
+        return ExitCode::from(1);
+    }
+    if args.schema_json.is_some() {
+        eprintln!(
+            "flowscope: warning: ignoring --schema-json in serve mode; use --schema DDL files"
+        );
+    }
+
     // Determine input source: watch directories or static files
     let (watch_dirs, static_files) = if !args.watch.is_empty() {
         // Watch mode takes precedence
@@ -96,11 +137,13 @@ fn run_serve_mode(args: Args) -> ExitCode {
         metadata_schema: args.metadata_schema.clone(),
         #[cfg(not(feature = "metadata-provider"))]
         metadata_schema: None,
-        schema_path: args.schema.clone(),
+        schema_paths: args.schema.clone(),
+        strict_schema: args.strict_schema,
         port: args.port,
         open_browser: args.open,
         #[cfg(feature = "templating")]
         template_config,
+        cache_capacity: args.cache_capacity,
     };
 
     // Create tokio runtime and run server
@@ -116,16 +159,46 @@ fn run_serve_mode(args: Args) -> ExitCode {
 }
 
 fn run() -> Result<bool> {
-    let args = Args::parse();
+    let mut args = Args::parse();
+
+    if args.list_issue_codes {
+        let catalog = flowscope_core::issue_catalog();
+        let output_str = if args.format == OutputFormat::Json {
+            format_json(catalog, args.compact).context("Failed to serialize issue catalog")?
+        } else {
+            issue_catalog::format_issue_catalog(catalog)
+        };
+        write_output(&args.output, &output_str)?;
+        return Ok(false);
+    }
 
-    // Read input files
-    let sources = input::read_input(&args.files)?;
+    expand_file_globs(&mut args)?;
+
+    if args.require_dialect && args.dialect == DialectArg::Generic {
+        bail!("--require-dialect is set but the dialect is generic; choose one with -d/--dialect");
+    }
 
     // Load schema if provided
     let dialect = args.dialect.into();
 
     // Schema can come from DDL file or live database connection
-    let schema_metadata = load_schema_metadata(&args, dialect)?;
+    // --schema-diff analyzes the SQL against the old snapshot
+    let schema_snapshots = match args.schema_diff.as_slice() {
+        [old, new] => Some((
+            schema::load_schema_from_ddl(old, dialect, false)?,
+            schema::load_schema_from_ddl(new, dialect, false)?,
+        )),
+        _ => None,
+    };
+    let schema_metadata = match &schema_snapshots {
+        Some((old, _)) => Some(old.clone()),
+        None => load_schema_metadata(&args, dialect)?,
+    };
+    if args.fail_on_unresolved && schema_metadata.is_none() {
+        bail!(
+            "--fail-on-unresolved needs a schema to check against (--schema, --schema-json or --metadata-url)"
+        );
+    }
 
     // Build template config if specified
     #[cfg(feature = "templating")]
@@ -137,22 +210,340 @@ fn run() -> Result<bool> {
         }
     });
 
-    // Build analysis request
+    // Summarized for --check-only before the schema moves into the request
+    let schema_check = schema_metadata.as_ref().map(|schema| {
+        let source = if schema_snapshots.is_some() {
+            "--schema-diff snapshot"
+        } else if !args.schema.is_empty() {
+            "DDL files"
+        } else if args.schema_json.is_some() {
+            "schema JSON"
+        } else {
+            "--metadata-url"
+        };
+        check_only::SchemaCheck::new(source, schema)
+    });
+
+    // Settings shared by every request
+    let mut builder = AnalyzeRequest::builder()
+        .dialect(dialect)
+        .schema(schema_metadata)
+        .options(analysis_options(&args));
     #[cfg(feature = "templating")]
-    let request = build_request(sources, dialect, schema_metadata, template_config);
-    #[cfg(not(feature = "templating"))]
-    let request = build_request(sources, dialect, schema_metadata);
+    {
+        builder = builder.template_config(template_config);
+    }
+
+    if args.check_only {
+        let report = check_only::CheckReport {
+            dialect,
+            schema: schema_check,
+            #[cfg(feature = "templating")]
+            template: match args.template {
+                Some(mode) => Some(check_only::TemplateCheck {
+                    mode: format!("{mode:?}").to_lowercase(),
+                    variables: check_only::check_template_vars(&args.template_vars)?,
+                }),
+                None => None,
+            },
+            #[cfg(not(feature = "templating"))]
+            template: None,
+            #[cfg(feature = "templating")]
+            template_file: match &args.template_file {
+                Some(path) => {
+                    let template = fs::read_to_string(path).with_context(|| {
+                        format!("Failed to read template file {}", path.display())
+                    })?;
+                    report_template::check_report_template(&template)
+                        .with_context(|| format!("Invalid template file {}", path.display()))?;
+                    Some(path.display().to_string())
+                }
+                None => None,
+            },
+            #[cfg(not(feature = "templating"))]
+            template_file: None,
+            inputs: check_only::check_inputs(&args.files)?,
+        };
+        let output_str = if args.format == OutputFormat::Json {
+            format_json(&report, args.compact).context("Failed to serialize check report")?
+        } else {
+            check_only::format_check_report(&report)
+        };
+        write_output(&args.output, &output_str)?;
+        return Ok(false);
+    }
+
+    #[cfg(feature = "serve")]
+    if !args.watch.is_empty() {
+        if !args.files.is_empty() && !args.quiet {
+            eprintln!("flowscope: warning: ignoring positional files when --watch is provided");
+        }
+        server::stream::stream_snapshots(&args.watch, &builder, io::stdout().lock())?;
+        return Ok(false);
+    }
+
+    if args.ndjson_input {
+        let stdin = io::stdin().lock();
+        let summary = if let Some(path) = &args.output {
+            let file = fs::File::create(path)
+                .with_context(|| format!("Failed to write to {}", path.display()))?;
+            ndjson::analyze_ndjson(stdin, io::BufWriter::new(file), &builder, args.since)?
+        } else {
+            ndjson::analyze_ndjson(stdin, io::stdout().lock(), &builder, args.since)?
+        };
+        if args.since.is_some() && !args.quiet {
+            eprintln!(
+                "flowscope: --since skipped {} older queries and {} without a timestamp",
+                summary.older, summary.without_timestamp
+            );
+        }
+        return Ok(summary.has_errors());
+    }
+
+    // --sample narrows the inputs before any of the file-based modes below run
+    let input_files = match args.sample {
+        Some(count) => {
+            let seed = args.seed.unwrap_or_else(sample::random_seed);
+            let sample = sample::sample_files(&args.files, count as usize, seed)?;
+            if sample.files.is_empty() {
+                bail!("--sample found no .sql files in the given paths");
+            }
+            if !args.quiet {
+                eprintln!(
+                    "flowscope: sampled {} of {} files (seed {seed})",
+                    sample.files.len(),
+                    sample.discovered
+                );
+                for file in &sample.files {
+                    eprintln!("  {}", file.display());
+                }
+            }
+            sample.files
+        }
+        None => args.files.clone(),
+    };
+
+    // Stdin input has no files to split, so `-f jsonl` then writes one record below
+    let json_lines = args.format == OutputFormat::Jsonl && !input_files.is_empty();
+    if args.per_file_output || json_lines {
+        let inputs = per_file::collect_per_file_inputs(&input_files)?;
+        if inputs.is_empty() {
+            let mode = if args.per_file_output {
+                "--per-file-output"
+            } else {
+                "-f jsonl"
+            };
+            bail!("{mode} found no .sql files in the given paths");
+        }
+        let summary = match (&args.per_file_dir, &args.output) {
+            (Some(dir), _) => per_file::analyze_per_file(
+                &inputs,
+                per_file::PerFileSink::<io::Stdout>::Directory(dir.clone()),
+                &builder,
+                args.compact,
+            )?,
+            (None, Some(path)) => {
+                let file = fs::File::create(path)
+                    .with_context(|| format!("Failed to write to {}", path.display()))?;
+                let writer = io::BufWriter::new(file);
+                let sink = if json_lines {
+                    per_file::PerFileSink::JsonLines(writer)
+                } else {
+                    per_file::PerFileSink::Ndjson(writer)
+                };
+                per_file::analyze_per_file(&inputs, sink, &builder, args.compact)?
+            }
+            (None, None) => {
+                let writer = io::stdout().lock();
+                let sink = if json_lines {
+                    per_file::PerFileSink::JsonLines(writer)
+                } else {
+                    per_file::PerFileSink::Ndjson(writer)
+                };
+                per_file::analyze_per_file(&inputs, sink, &builder, args.compact)?
+            }
+        };
+        return Ok(summary.has_errors());
+    }
+
+    if args.bench {
+        let files = bench::collect_bench_files(&input_files)?;
+        if files.is_empty() && !input_files.is_empty() {
+            bail!("--bench found no .sql files in the given paths");
+        }
+        let request = builder.files(input::read_input(&files)?).build();
+        let (report, result) = bench::run_bench(&request, args.bench_iterations as usize);
+        let output_str = if args.format == OutputFormat::Json {
+            format_json(&report, args.compact).context("Failed to serialize benchmark report")?
+        } else {
+            bench::format_bench_report(&report)
+        };
+        write_output(&args.output, &output_str)?;
+        // A broken input fails the benchmark the same way it fails a regular run; JSON
+        // reports carry the issues themselves
+        if !args.quiet && args.format != OutputFormat::Json {
+            print_issues_to_stderr(&result);
+        }
+        return Ok(result.summary.has_errors);
+    }
+
+    if args.dialect_report {
+        let sources = input::read_input(&input_files)?;
+        let report =
+            dialect_report::dialect_report(&sources, dialect, args.target_dialect.map(Into::into));
+        let output_str = if args.format == OutputFormat::Json {
+            format_json(&report, args.compact).context("Failed to serialize dialect report")?
+        } else {
+            dialect_report::format_dialect_report(&report)
+        };
+        write_output(&args.output, &output_str)?;
+        return Ok(report.has_portability_issues());
+    }
+
+    #[cfg(feature = "templating")]
+    if args.fix && args.template.is_some() {
+        bail!("--fix cannot be combined with --template: fixes apply to the rendered SQL");
+    }
+
+    // Read input files
+    let sources = input::read_input(&input_files)?;
+    let request = builder.files(sources.clone()).build();
 
     // Run analysis
     let result = analyze(&request);
 
+    if args.fix {
+        let fixed_sources = fix::fix_sources(&sources, &result, dialect);
+        let json_report = args.format == OutputFormat::Json;
+        // Stdin has no file to rewrite, so its SQL is the output, fixed or not
+        if let Some(stdin) = sources
+            .iter()
+            .find(|source| source.name == input::STDIN_SOURCE_NAME)
+            .filter(|_| !json_report)
+        {
+            let sql = fixed_sources
+                .iter()
+                .find(|fixed| fixed.name == stdin.name)
+                .and_then(|fixed| fixed.sql.as_ref())
+                .unwrap_or(&stdin.content);
+            write_output(&args.output, sql)?;
+        }
+        for fixed in &fixed_sources {
+            let is_stdin = fixed.name == input::STDIN_SOURCE_NAME;
+            if let Some(sql) = fixed.sql.as_ref().filter(|_| !is_stdin) {
+                fs::write(&fixed.name, sql)
+                    .with_context(|| format!("Failed to write fixed SQL to {}", fixed.name))?;
+            }
+            if args.quiet || json_report {
+                continue;
+            }
+            if let Some(err) = &fixed.skipped_due_to_regression {
+                eprintln!(
+                    "flowscope: fixed SQL for {} no longer parses, leaving it unchanged: {err}",
+                    fixed.name
+                );
+            } else if fixed.sql.is_some() && !is_stdin {
+                eprintln!(
+                    "flowscope: fixed {} issue(s) in {}",
+                    fixed.fixes, fixed.name
+                );
+            }
+            if fixed.skipped_due_to_comments > 0 {
+                eprintln!(
+                    "flowscope: skipped {} fix(es) in {} that would delete comments",
+                    fixed.skipped_due_to_comments, fixed.name
+                );
+            }
+        }
+        if json_report {
+            let report = fix::fix_report(&sources, &fixed_sources);
+            let output_str =
+                format_json(&report, args.compact).context("Failed to serialize fix report")?;
+            write_output(&args.output, &output_str)?;
+        }
+        // Say why a broken input fails the run; the fixed warnings need no repeating
+        if !args.quiet && result.summary.has_errors {
+            print_issues_to_stderr(&result);
+        }
+        return Ok(result.summary.has_errors);
+    }
+
+    if let Some(target) = args.explain_lineage.as_deref() {
+        let traces = explain::explain_lineage(&result, target)?;
+        let output_str = if args.format == OutputFormat::Json {
+            format_json(&traces, args.compact).context("Failed to serialize lineage trace")?
+        } else {
+            explain::format_lineage_trace(&traces)
+        };
+        write_output(&args.output, &output_str)?;
+        return Ok(result.summary.has_errors);
+    }
+
+    if let Some((old, new)) = &schema_snapshots {
+        let changes = schema_diff::diff_schemas(old, new, &result);
+        let output_str = if args.format == OutputFormat::Json {
+            format_json(&changes, args.compact).context("Failed to serialize schema diff")?
+        } else {
+            schema_diff::format_schema_diff(&changes)
+        };
+        write_output(&args.output, &output_str)?;
+        return Ok(result.summary.has_errors);
+    }
+
+    if args.build_plan {
+        let plan = build_plan::build_plan(&result);
+        let output_str = if args.format == OutputFormat::Json {
+            format_json(&plan, args.compact).context("Failed to serialize build plan")?
+        } else {
+            build_plan::format_build_plan(&plan)
+        };
+        write_output(&args.output, &output_str)?;
+        return Ok(result.summary.has_errors || plan.has_cycles());
+    }
+
+    #[cfg(feature = "templating")]
+    if let Some(path) = &args.template_file {
+        let template = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read template file {}", path.display()))?;
+        let output_str = report_template::render_report_template(&result, &template)
+            .with_context(|| format!("Failed to render template file {}", path.display()))?;
+        write_output(&args.output, &output_str)?;
+        return Ok(result.summary.has_errors);
+    }
+
     let naming = ExportNaming::new(args.project_name.clone());
 
+    if !args.fields.is_empty() && args.format != OutputFormat::Json && !args.quiet {
+        eprintln!("flowscope: warning: --fields only applies to JSON output (-f json)");
+    }
+
+    // Tabular exports name tables the way the schema-prefixed SQL DDL does
+    let result = match (&args.export_schema, args.format) {
+        (
+            Some(schema),
+            OutputFormat::Sql | OutputFormat::Csv | OutputFormat::Xlsx | OutputFormat::Duckdb,
+        ) => qualify_table_names(&result, schema).context("Invalid export schema")?,
+        _ => result,
+    };
+
     let output_str = match args.format {
+        OutputFormat::Json if !args.fields.is_empty() => {
+            let value = serde_json::to_value(&result).context("Failed to export JSON")?;
+            let (projected, unmatched) = fields::project_fields(&value, &args.fields);
+            if !args.quiet {
+                for field in unmatched {
+                    eprintln!(
+                        "flowscope: warning: --fields path '{field}' matched nothing in the output"
+                    );
+                }
+            }
+            format_json(&projected, args.compact).context("Failed to export JSON")?
+        }
         OutputFormat::Json => {
             export_json(&result, args.compact).context("Failed to export JSON")?
         }
-        OutputFormat::Table => format_table(&result, args.quiet, !args.quiet),
+        OutputFormat::Jsonl => format!("{}\n", per_file::json_lines_record(None, &result)?),
+        OutputFormat::Table => format_table(&result, args.quiet, !args.quiet, args.group_by_file),
         OutputFormat::Mermaid => {
             let view = match args.view {
                 ViewMode::Script => MermaidView::Script,
@@ -160,10 +551,26 @@ fn run() -> Result<bool> {
                 ViewMode::Column => MermaidView::Column,
                 ViewMode::Hybrid => MermaidView::Hybrid,
             };
-            export_mermaid(&result, view).context("Failed to export Mermaid")?
+            match args.color_by {
+                Some(color_by) => export_mermaid_colored(&result, view, color_by.into()),
+                None => export_mermaid(&result, view),
+            }
+            .context("Failed to export Mermaid")?
+        }
+        OutputFormat::Html => match args.color_by {
+            Some(color_by) => export_html_colored(
+                &result,
+                &args.project_name,
+                naming.exported_at(),
+                color_by.into(),
+            ),
+            None => export_html(&result, &args.project_name, naming.exported_at()),
         }
-        OutputFormat::Html => export_html(&result, &args.project_name, naming.exported_at())
-            .context("Failed to export HTML")?,
+        .context("Failed to export HTML")?,
+        OutputFormat::Markdown => {
+            export_markdown(&result, &args.project_name).context("Failed to export Markdown")?
+        }
+        OutputFormat::Turtle => export_turtle(&result).context("Failed to export Turtle")?,
         OutputFormat::Sql => export_sql(&result, args.export_schema.as_deref())
             .context("Failed to export DuckDB SQL")?,
         OutputFormat::Csv => {
@@ -196,22 +603,90 @@ fn run() -> Result<bool> {
                 result.summary.has_errors,
             );
         }
+        OutputFormat::Sarif => {
+            let log = format_sarif(&result, &sources);
+            format_json(&log, args.compact).context("Failed to export SARIF")?
+        }
+        #[cfg(feature = "png")]
+        OutputFormat::Png => {
+            let bytes = flowscope_export::export_png(&result).context("Failed to export PNG")?;
+            return write_binary_output(
+                &args.output,
+                &bytes,
+                &naming,
+                ExportFormat::Png,
+                result.summary.has_errors,
+            );
+        }
     };
 
     write_output(&args.output, &output_str)?;
 
-    if !args.quiet && args.format != OutputFormat::Json {
+    if !args.quiet
+        && !matches!(
+            args.format,
+            OutputFormat::Json | OutputFormat::Jsonl | OutputFormat::Sarif
+        )
+    {
         print_issues_to_stderr(&result);
     }
 
     Ok(result.summary.has_errors)
 }
 
+/// Analysis options selected by the command-line flags.
+fn analysis_options(args: &Args) -> flowscope_core::AnalysisOptions {
+    flowscope_core::AnalysisOptions {
+        enable_column_lineage: (args.granularity == GranularityArg::Table).then_some(false),
+        ignore_tables: (!args.ignore_tables.is_empty()).then(|| args.ignore_tables.clone()),
+        report_dead_columns: args.report_dead_columns.then_some(true),
+        terminal_tables: (!args.terminal_tables.is_empty()).then(|| args.terminal_tables.clone()),
+        check_datetime_consistency: args.check_datetime_consistency.then_some(true),
+        check_unqualified_wildcards: args.check_unqualified_wildcards.then_some(true),
+        max_subquery_depth: args.max_subquery_depth,
+        check_reserved_keywords: args.check_reserved_keywords.then_some(true),
+        redact_literals: args.redact_literals.then_some(true),
+        check_implicit_aliases: args.check_implicit_aliases.then_some(true),
+        allow_implicit_aliases_for: args.allow_implicit_aliases_for.map(Into::into),
+        check_unordered_limits: args.check_unordered_limits.then_some(true),
+        check_join_key_types: args.check_join_key_types.then_some(true),
+        compatible_join_key_types: (!args.compatible_join_key_types.is_empty()).then(|| {
+            args.compatible_join_key_types
+                .iter()
+                .map(|group| {
+                    group
+                        .split(',')
+                        .map(|name| name.trim().to_string())
+                        .collect()
+                })
+                .collect()
+        }),
+        check_duplicate_projections: args.check_duplicate_projections.then_some(true),
+        include_aliased_duplicate_projections: args.include_aliased_duplicates.then_some(true),
+        check_constant_predicates: args.check_constant_predicates.then_some(true),
+        check_aggregate_case_else: args.check_aggregate_case_else.then_some(true),
+        graph_metrics: args.graph_metrics.then_some(true),
+        check_qualification_consistency: args.check_qualification_consistency.then_some(true),
+        check_recursive_termination: args.check_recursive_termination.then_some(true),
+        max_line_length: args.max_line_length,
+        check_redundant_casts: args.check_redundant_casts.then_some(true),
+        collect_timings: args.timings.then_some(true),
+        check_identifier_length: args.check_identifier_length.then_some(true),
+        max_identifier_length: args.max_identifier_length,
+        reserved_identifier_prefixes: (!args.reserved_identifier_prefix.is_empty())
+            .then(|| args.reserved_identifier_prefix.clone()),
+        check_trailing_newline: args.check_trailing_newline.then_some(true),
+        unresolved_as_errors: args.fail_on_unresolved.then_some(true),
+        ..Default::default()
+    }
+}
+
 /// Load schema metadata from DDL file or live database connection.
 ///
 /// Priority:
-/// 1. If `--metadata-url` is provided, connect to the database and fetch schema
-/// 2. If `--schema` is provided, parse the DDL file
+/// 1. If `--metadata-url` is provided, fetch the schema from the database or HTTP
+///    metadata service
+/// 2. If `--schema` is provided, parse and merge the DDL files
 /// 3. Otherwise, return None
 fn load_schema_metadata(
     args: &Args,
@@ -228,15 +703,32 @@ fn load_schema_metadata(
             );
         }
 
-        let schema = metadata::fetch_metadata_from_database(url, args.metadata_schema.clone())?;
+        let fetch = || metadata::fetch_metadata(url, args.metadata_schema.clone());
+        let schema = match &args.metadata_cache {
+            Some(path) => metadata::cache::load_or_fetch(
+                path,
+                &metadata::cache::cache_key(url, args.metadata_schema.as_deref()),
+                std::time::Duration::from_secs(args.metadata_ttl),
+                args.metadata_refresh,
+                fetch,
+            )?,
+            None => fetch()?,
+        };
         return Ok(Some(schema));
     }
 
-    // Fall back to DDL file
-    args.schema
-        .as_ref()
-        .map(|path| schema::load_schema_from_ddl(path, dialect))
-        .transpose()
+    if let Some(path) = &args.schema_json {
+        return schema::load_schema_from_json(path)
+            .map(Some)
+            .context("Failed to load schema");
+    }
+
+    // Fall back to DDL files
+    if args.schema.is_empty() {
+        return Ok(None);
+    }
+    schema::load_schema_from_ddl_files(&args.schema, dialect, args.strict_schema)
+        .map(Some)
         .context("Failed to load schema")
 }
 
@@ -268,60 +760,15 @@ fn parse_template_vars(vars: &[String]) -> std::collections::HashMap<String, ser
     context
 }
 
-#[cfg(feature = "templating")]
-fn build_request(
-    sources: Vec<FileSource>,
-    dialect: flowscope_core::Dialect,
-    schema: Option<flowscope_core::SchemaMetadata>,
-    template_config: Option<flowscope_core::TemplateConfig>,
-) -> AnalyzeRequest {
-    if sources.len() == 1 {
-        AnalyzeRequest {
-            sql: sources[0].content.clone(),
-            files: None,
-            dialect,
-            source_name: Some(sources[0].name.clone()),
-            options: None,
-            schema,
-            template_config,
-        }
-    } else {
-        AnalyzeRequest {
-            sql: String::new(),
-            files: Some(sources),
-            dialect,
-            source_name: None,
-            options: None,
-            schema,
-            template_config,
-        }
-    }
-}
-
-#[cfg(not(feature = "templating"))]
-fn build_request(
-    sources: Vec<FileSource>,
-    dialect: flowscope_core::Dialect,
-    schema: Option<flowscope_core::SchemaMetadata>,
-) -> AnalyzeRequest {
-    if sources.len() == 1 {
-        AnalyzeRequest {
-            sql: sources[0].content.clone(),
-            files: None,
-            dialect,
-            source_name: Some(sources[0].name.clone()),
-            options: None,
-            schema,
-        }
+/// Serializes `value` as JSON, pretty-printed unless `compact` is set.
+fn format_json<T: serde::Serialize + ?Sized>(
+    value: &T,
+    compact: bool,
+) -> serde_json::Result<String> {
+    if compact {
+        serde_json::to_string(value)
     } else {
-        AnalyzeRequest {
-            sql: String::new(),
-            files: Some(sources),
-            dialect,
-            source_name: None,
-            options: None,
-            schema,
-        }
+        serde_json::to_string_pretty(value)
     }
 }
 
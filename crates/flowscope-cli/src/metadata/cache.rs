@@ -0,0 +1,161 @@
+//! On-disk cache of introspected schema metadata for `--metadata-cache`.
+//!
+//! The cache file records when the schema was fetched and which connection and schema
+//! filter it came from, so a stale entry or one written for another database is
+//! fetched again instead of reused.
+
+use anyhow::{Context, Result};
+use flowscope_core::SchemaMetadata;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::redact_url;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    /// Redacted connection URL and schema filter the metadata came from
+    key: String,
+    /// Unix timestamp (seconds) of the fetch
+    fetched_at: u64,
+    schema: SchemaMetadata,
+}
+
+/// Identifies a metadata source: the connection URL without its credentials, query
+/// or fragment (see [`super::redact_url`]), plus the schema filter.
+pub fn cache_key(url: &str, schema_filter: Option<&str>) -> String {
+    format!("{}#{}", redact_url(url), schema_filter.unwrap_or(""))
+}
+
+/// Returns the cached schema for `key` if it is younger than `ttl`, otherwise calls
+/// `fetch` and stores its result in `path`.
+///
+/// `refresh` skips the cache lookup. Unreadable or mismatched cache files are
+/// treated as a miss.
+pub fn load_or_fetch(
+    path: &Path,
+    key: &str,
+    ttl: Duration,
+    refresh: bool,
+    fetch: impl FnOnce() -> Result<SchemaMetadata>,
+) -> Result<SchemaMetadata> {
+    let now = unix_now();
+
+    if !refresh {
+        if let Some(entry) = read_entry(path) {
+            let age = Duration::from_secs(now.saturating_sub(entry.fetched_at));
+            if entry.key == key && age < ttl {
+                return Ok(entry.schema);
+            }
+        }
+    }
+
+    let schema = fetch()?;
+    let entry = CacheEntry {
+        key: key.to_string(),
+        fetched_at: now,
+        schema,
+    };
+    let json = serde_json::to_string(&entry).context("Failed to serialize metadata cache")?;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, json)
+        .with_context(|| format!("Failed to write metadata cache {}", path.display()))?;
+    Ok(entry.schema)
+}
+
+fn read_entry(path: &Path) -> Option<CacheEntry> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowscope_core::SchemaTable;
+    use std::cell::Cell;
+
+    fn schema_with_table(name: &str) -> SchemaMetadata {
+        SchemaMetadata {
+            tables: vec![SchemaTable {
+                catalog: None,
+                schema: None,
+                name: name.to_string(),
+                columns: Vec::new(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn table_names(schema: &SchemaMetadata) -> Vec<&str> {
+        schema.tables.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_cache_written_then_hit_within_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache").join("schema.json");
+        let fetches = Cell::new(0);
+        let fetch = |name: &str| {
+            fetches.set(fetches.get() + 1);
+            Ok(schema_with_table(name))
+        };
+        let ttl = Duration::from_secs(3600);
+
+        let first = load_or_fetch(&path, "db", ttl, false, || fetch("users")).unwrap();
+        assert!(path.exists());
+        let second = load_or_fetch(&path, "db", ttl, false, || fetch("orders")).unwrap();
+
+        assert_eq!(fetches.get(), 1);
+        assert_eq!(table_names(&first), ["users"]);
+        assert_eq!(table_names(&second), ["users"]);
+    }
+
+    #[test]
+    fn test_refresh_expiry_and_key_change_refetch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schema.json");
+        let ttl = Duration::from_secs(3600);
+        load_or_fetch(&path, "db", ttl, false, || Ok(schema_with_table("v1"))).unwrap();
+
+        let refreshed =
+            load_or_fetch(&path, "db", ttl, true, || Ok(schema_with_table("v2"))).unwrap();
+        assert_eq!(table_names(&refreshed), ["v2"]);
+
+        let expired = load_or_fetch(&path, "db", Duration::ZERO, false, || {
+            Ok(schema_with_table("v3"))
+        })
+        .unwrap();
+        assert_eq!(table_names(&expired), ["v3"]);
+
+        let other_db =
+            load_or_fetch(&path, "other", ttl, false, || Ok(schema_with_table("v4"))).unwrap();
+        assert_eq!(table_names(&other_db), ["v4"]);
+    }
+
+    #[test]
+    fn test_cache_key_omits_credentials() {
+        assert_eq!(
+            cache_key("postgres://app:s3cr@t@db.internal/prod", Some("public")),
+            "postgres://<redacted>@db.internal/prod#public"
+        );
+        assert_eq!(
+            cache_key("https://meta.internal/schema?token=abc", None),
+            "https://meta.internal/schema#"
+        );
+        assert_eq!(
+            cache_key("sqlite://data/app.db", None),
+            "sqlite://data/app.db#"
+        );
+    }
+}
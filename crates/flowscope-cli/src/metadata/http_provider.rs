@@ -0,0 +1,221 @@
+//! HTTP metadata provider for schema services that front the database.
+//!
+//! Where direct database access is not allowed, an internal service can expose the
+//! schema instead. `--metadata-url https://...` sends a GET request to the URL and
+//! deserializes the response body as [`SchemaMetadata`] (the same JSON shape as the
+//! `schema` field of an analyze request):
+//!
+//! ```json
+//! {
+//!   "defaultSchema": "public",
+//!   "tables": [
+//!     { "schema": "public", "name": "users",
+//!       "columns": [{ "name": "id", "dataType": "integer" }, { "name": "email" }] }
+//!   ]
+//! }
+//! ```
+//!
+//! If `FLOWSCOPE_METADATA_TOKEN` is set, it is sent as a bearer token.
+
+use anyhow::{bail, Context, Result};
+use flowscope_core::SchemaMetadata;
+use std::time::Duration;
+
+use super::redact_url;
+
+/// Environment variable holding the bearer token for the metadata service.
+pub const TOKEN_ENV_VAR: &str = "FLOWSCOPE_METADATA_TOKEN";
+
+/// Timeout for the whole request, including connecting (seconds).
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Whether `url` points at an HTTP metadata service rather than a database.
+pub fn is_http_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// A read-only metadata provider that fetches the schema from an HTTP endpoint.
+pub struct HttpMetadataProvider {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+    schema_filter: Option<String>,
+}
+
+impl HttpMetadataProvider {
+    /// Create a provider for `url`, authenticating with `token` if given.
+    ///
+    /// # Arguments
+    /// * `url` - Endpoint returning the schema as JSON
+    /// * `token` - Optional bearer token
+    /// * `schema_filter` - Optional schema name; tables in other schemas are dropped
+    pub fn new(url: &str, token: Option<String>, schema_filter: Option<String>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .context("Failed to create HTTP client")?;
+        Ok(Self {
+            client,
+            url: url.to_string(),
+            token,
+            schema_filter,
+        })
+    }
+
+    /// Fetch and deserialize the schema metadata.
+    pub async fn fetch_schema_async(&self) -> Result<SchemaMetadata> {
+        let mut request = self
+            .client
+            .get(&self.url)
+            .header(reqwest::header::ACCEPT, "application/json");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        // reqwest errors include the full URL, so it is dropped from them
+        let url = redact_url(&self.url);
+        let response = request
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .with_context(|| format!("Failed to reach metadata service: {url}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("Metadata service returned {status}: {url}");
+        }
+        let mut schema: SchemaMetadata = response
+            .json()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .with_context(|| {
+                format!("Metadata service response is not valid schema JSON: {url}")
+            })?;
+
+        if let Some(filter) = &self.schema_filter {
+            schema
+                .tables
+                .retain(|table| table.schema.as_deref() == Some(filter.as_str()));
+        }
+        Ok(schema)
+    }
+}
+
+/// Fetch schema metadata from an HTTP metadata service.
+///
+/// The bearer token is read from `FLOWSCOPE_METADATA_TOKEN`.
+pub fn fetch_metadata_from_http(
+    url: &str,
+    schema_filter: Option<String>,
+) -> Result<SchemaMetadata> {
+    let token = std::env::var(TOKEN_ENV_VAR)
+        .ok()
+        .filter(|token| !token.is_empty());
+    let provider = HttpMetadataProvider::new(url, token, schema_filter)?;
+    let rt = tokio::runtime::Runtime::new().context("Failed to create async runtime")?;
+    rt.block_on(provider.fetch_schema_async())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serve one request with `body`, returning the URL and a handle yielding the
+    /// request headers.
+    fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!(
+            "http://{}/schema?name=warehouse",
+            listener.local_addr().unwrap()
+        );
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept");
+            let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).expect("read request");
+                if line.trim().is_empty() {
+                    break;
+                }
+                headers.push(line.trim().to_string());
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .expect("write response");
+            headers
+        });
+        (url, handle)
+    }
+
+    fn fetch(
+        url: &str,
+        token: Option<&str>,
+        schema_filter: Option<&str>,
+    ) -> Result<SchemaMetadata> {
+        let provider = HttpMetadataProvider::new(
+            url,
+            token.map(str::to_string),
+            schema_filter.map(str::to_string),
+        )?;
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(provider.fetch_schema_async())
+    }
+
+    #[test]
+    fn test_fetches_schema_with_bearer_token() {
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"{"tables":[
+                {"schema":"public","name":"users","columns":[{"name":"id","dataType":"integer"},{"name":"email"}]},
+                {"schema":"audit","name":"events","columns":[{"name":"id"}]}
+            ]}"#,
+        );
+
+        let schema = fetch(&url, Some("s3cret"), Some("public")).expect("fetch schema");
+        assert_eq!(schema.tables.len(), 1);
+        let columns: Vec<&str> = schema.tables[0]
+            .columns
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(columns, ["id", "email"]);
+
+        let headers = server.join().unwrap();
+        assert_eq!(headers[0], "GET /schema?name=warehouse HTTP/1.1");
+        assert!(headers
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case("authorization: Bearer s3cret")));
+    }
+
+    #[test]
+    fn test_error_status_is_reported_without_query() {
+        let (url, server) = serve_once("403 Forbidden", "{}");
+        let err = fetch(&url, None, None).unwrap_err();
+        let message = format!("{err:#}");
+        assert!(message.contains("Metadata service returned 403 Forbidden"));
+        assert!(!message.contains("warehouse"));
+        assert!(!server
+            .join()
+            .unwrap()
+            .iter()
+            .any(|h| h.to_ascii_lowercase().starts_with("authorization")));
+    }
+
+    #[test]
+    fn test_is_http_url() {
+        assert!(is_http_url("https://meta.internal/schema?name=dw"));
+        assert!(is_http_url("http://localhost:8080/schema"));
+        assert!(!is_http_url("postgres://localhost/db"));
+        assert!(!is_http_url("sqlite://path/to/db"));
+    }
+}
@@ -0,0 +1,318 @@
+//! Streaming analysis of NDJSON query logs for `--ndjson-input`.
+//!
+//! Each input line is a JSON object with an `sql` string, an optional `id` and an
+//! optional `ts` timestamp. Lines are analyzed one at a time and answered with one
+//! output line each, so memory use does not grow with the size of the log.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use flowscope_core::{analyze, AnalyzeRequestBuilder, AnalyzeResult};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+
+#[derive(Deserialize)]
+struct InputLine {
+    #[serde(default)]
+    id: serde_json::Value,
+    sql: String,
+    /// When the query ran: epoch seconds (or milliseconds), RFC 3339, or
+    /// `YYYY-MM-DD[ HH:MM:SS]` in UTC
+    #[serde(default)]
+    ts: Option<serde_json::Value>,
+}
+
+/// One output line: the analysis of an input query, or why its line was skipped.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OutputLine<'a> {
+    /// `id` of the input line (`null` when absent or unreadable)
+    id: &'a serde_json::Value,
+    /// 1-based input line number
+    line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<&'a AnalyzeResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Counts of processed input lines.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NdjsonSummary {
+    /// Queries that were analyzed
+    pub analyzed: usize,
+    /// Analyzed queries whose result contains errors
+    pub with_errors: usize,
+    /// Lines that were not valid `{"id": ..., "sql": "..."}` objects
+    pub malformed: usize,
+    /// Queries skipped by `--since` because they ran before the cutoff
+    pub older: usize,
+    /// Queries skipped by `--since` because `ts` was missing or unreadable
+    pub without_timestamp: usize,
+}
+
+impl NdjsonSummary {
+    pub fn has_errors(&self) -> bool {
+        self.with_errors > 0 || self.malformed > 0
+    }
+}
+
+/// Analyzes each query in `reader` and writes one NDJSON result line per input line.
+///
+/// `base` carries the dialect, schema and options shared by every query. Blank lines
+/// are skipped; malformed lines produce an output line with an `error` instead of a
+/// `result`. With `since`, queries whose `ts` is before it, missing or unreadable are
+/// skipped without an output line and only counted in the summary.
+pub fn analyze_ndjson(
+    reader: impl BufRead,
+    mut writer: impl Write,
+    base: &AnalyzeRequestBuilder,
+    since: Option<DateTime<Utc>>,
+) -> Result<NdjsonSummary> {
+    let mut summary = NdjsonSummary::default();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.context("Failed to read NDJSON input")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_number = index + 1;
+
+        let output = match serde_json::from_str::<InputLine>(&line) {
+            Ok(input) => {
+                if let Some(cutoff) = since {
+                    match input.ts.as_ref().and_then(parse_timestamp) {
+                        Some(ts) if ts >= cutoff => {}
+                        Some(_) => {
+                            summary.older += 1;
+                            continue;
+                        }
+                        None => {
+                            summary.without_timestamp += 1;
+                            continue;
+                        }
+                    }
+                }
+                let result = analyze(&base.clone().sql(input.sql).build());
+                summary.analyzed += 1;
+                if result.summary.has_errors {
+                    summary.with_errors += 1;
+                }
+                serde_json::to_string(&OutputLine {
+                    id: &input.id,
+                    line: line_number,
+                    result: Some(&result),
+                    error: None,
+                })
+            }
+            Err(e) => {
+                summary.malformed += 1;
+                // Keep the id when the line is valid JSON with a bad or missing `sql`
+                let id = serde_json::from_str::<serde_json::Value>(&line)
+                    .ok()
+                    .and_then(|value| value.get("id").cloned())
+                    .unwrap_or_default();
+                serde_json::to_string(&OutputLine {
+                    id: &id,
+                    line: line_number,
+                    result: None,
+                    error: Some(format!("Malformed input line: {e}")),
+                })
+            }
+        }
+        .context("Failed to serialize NDJSON result")?;
+
+        writeln!(writer, "{output}").context("Failed to write NDJSON result")?;
+    }
+
+    writer.flush().context("Failed to write NDJSON result")?;
+    Ok(summary)
+}
+
+/// Parses a `--since` value relative to `now`: a duration back in time (`30m`, `24h`,
+/// `7d`, `2w`) or an absolute date or timestamp, as accepted for `ts`.
+pub fn parse_since(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Some(unit) = value.chars().last().filter(char::is_ascii_alphabetic) {
+        if let Ok(amount) = value[..value.len() - 1].parse::<i64>() {
+            let duration = match unit.to_ascii_lowercase() {
+                's' => Duration::try_seconds(amount),
+                'm' => Duration::try_minutes(amount),
+                'h' => Duration::try_hours(amount),
+                'd' => Duration::try_days(amount),
+                'w' => Duration::try_weeks(amount),
+                _ => {
+                    return Err(format!(
+                        "unknown duration unit '{unit}' (use s, m, h, d or w)"
+                    ))
+                }
+            };
+            return duration
+                .and_then(|duration| now.checked_sub_signed(duration))
+                .ok_or_else(|| format!("duration '{value}' is out of range"));
+        }
+    }
+    parse_timestamp_str(value).ok_or_else(|| {
+        format!("'{value}' is neither a duration like 24h nor a date like 2024-01-01")
+    })
+}
+
+/// Reads a `ts` value; numbers above 10^12 are taken as milliseconds.
+fn parse_timestamp(value: &serde_json::Value) -> Option<DateTime<Utc>> {
+    match value {
+        serde_json::Value::String(s) => parse_timestamp_str(s),
+        serde_json::Value::Number(n) => {
+            let secs = n.as_f64()?;
+            let millis = if secs.abs() > 1e12 {
+                secs
+            } else {
+                secs * 1000.0
+            };
+            Utc.timestamp_millis_opt(millis as i64).single()
+        }
+        _ => None,
+    }
+}
+
+fn parse_timestamp_str(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"] {
+        if let Ok(ts) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(ts.and_utc());
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|ts| ts.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowscope_core::AnalyzeRequest;
+
+    fn run(input: &str) -> (Vec<serde_json::Value>, NdjsonSummary) {
+        run_since(input, None)
+    }
+
+    fn run_since(
+        input: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> (Vec<serde_json::Value>, NdjsonSummary) {
+        let mut out = Vec::new();
+        let summary = analyze_ndjson(
+            input.as_bytes(),
+            &mut out,
+            &AnalyzeRequest::builder(),
+            since,
+        )
+        .unwrap();
+        let lines = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        (lines, summary)
+    }
+
+    #[test]
+    fn test_one_result_line_per_input_line() {
+        let (lines, summary) = run(concat!(
+            "{\"id\": 1, \"sql\": \"SELECT id FROM users\"}\n",
+            "{\"id\": \"q-2\", \"sql\": \"SELECT * FROM orders\"}\n",
+            "\n",
+            "{\"id\": 3, \"sql\": \"INSERT INTO t SELECT a FROM s\"}\n",
+        ));
+
+        let ids: Vec<&serde_json::Value> = lines.iter().map(|l| &l["id"]).collect();
+        assert_eq!(
+            ids,
+            [
+                &serde_json::json!(1),
+                &serde_json::json!("q-2"),
+                &serde_json::json!(3)
+            ]
+        );
+        assert!(lines.iter().all(|l| l["result"]["statements"].is_array()));
+        assert_eq!(
+            summary,
+            NdjsonSummary {
+                analyzed: 3,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_malformed_lines_are_annotated() {
+        let (lines, summary) = run(concat!(
+            "not json\n",
+            "{\"id\": 7}\n",
+            "{\"id\": 8, \"sql\": \"SELECT 1\"}\n",
+        ));
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["line"], 1);
+        assert!(lines[0]["error"].is_string());
+        assert_eq!(lines[1]["id"], 7);
+        assert!(lines[1]["error"].is_string());
+        assert!(lines[2]["result"].is_object());
+        assert_eq!(summary.malformed, 2);
+        assert!(summary.has_errors());
+    }
+
+    #[test]
+    fn test_since_skips_older_and_untimed_queries() {
+        let since = parse_since("2024-01-01", Utc::now()).unwrap();
+        let (lines, summary) = run_since(
+            concat!(
+                "{\"id\": 1, \"ts\": \"2024-03-05T10:00:00Z\", \"sql\": \"SELECT 1\"}\n",
+                "{\"id\": 2, \"ts\": \"2023-12-31 23:59:59\", \"sql\": \"SELECT 2\"}\n",
+                "{\"id\": 3, \"ts\": 1717200000, \"sql\": \"SELECT 3\"}\n",
+                "{\"id\": 4, \"ts\": 1600000000000, \"sql\": \"SELECT 4\"}\n",
+                "{\"id\": 5, \"sql\": \"SELECT 5\"}\n",
+                "{\"id\": 6, \"ts\": \"yesterday\", \"sql\": \"SELECT 6\"}\n",
+            ),
+            Some(since),
+        );
+
+        let ids: Vec<&serde_json::Value> = lines.iter().map(|l| &l["id"]).collect();
+        assert_eq!(ids, [&serde_json::json!(1), &serde_json::json!(3)]);
+        assert_eq!(
+            summary,
+            NdjsonSummary {
+                analyzed: 2,
+                older: 2,
+                without_timestamp: 2,
+                ..Default::default()
+            }
+        );
+        assert!(!summary.has_errors());
+    }
+
+    #[test]
+    fn test_parse_since_relative_and_absolute() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 2, 12, 0, 0).unwrap();
+        assert_eq!(
+            parse_since("24h", now).unwrap(),
+            Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_since("1w", now).unwrap(),
+            Utc.with_ymd_and_hms(2024, 5, 26, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_since("2024-01-01", now).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_since("2024-01-01T08:30:00+02:00", now).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 1, 6, 30, 0).unwrap()
+        );
+        assert!(parse_since("3y", now).is_err());
+        assert!(parse_since("soon", now).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 //! Output formatting modules.
 
+pub mod sarif;
 pub mod table;
 
+pub use sarif::format_sarif;
 pub use table::format_table;
@@ -0,0 +1,125 @@
+//! SARIF 2.1.0 output of the analysis issues, for code scanning services.
+
+use std::collections::HashMap;
+
+use flowscope_core::{AnalyzeResult, FileSource, Issue, Severity};
+use serde_json::{json, Value};
+
+use crate::fix::issue_source;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Build a SARIF log with one run of the `flowscope` tool.
+///
+/// Each issue code becomes a rule and each issue a result. Issues with a span in a
+/// known input carry a region with 1-based lines and columns counted in code points
+/// (`columnKind: unicodeCodePoints`); others point at their file only, or at nothing.
+pub fn format_sarif(result: &AnalyzeResult, sources: &[FileSource]) -> Value {
+    let contents: HashMap<&str, &str> = sources
+        .iter()
+        .map(|source| (source.name.as_str(), source.content.as_str()))
+        .collect();
+
+    let mut rule_ids: Vec<&str> = Vec::new();
+    let mut results = Vec::new();
+    for issue in &result.issues {
+        let rule_index = match rule_ids.iter().position(|id| *id == issue.code) {
+            Some(index) => index,
+            None => {
+                rule_ids.push(&issue.code);
+                rule_ids.len() - 1
+            }
+        };
+
+        let mut entry = json!({
+            "ruleId": issue.code,
+            "ruleIndex": rule_index,
+            "level": sarif_level(issue.severity),
+            "message": { "text": issue.message },
+        });
+        if let Some(location) = issue_location(result, issue, &contents) {
+            entry["locations"] = json!([location]);
+        }
+        results.push(entry);
+    }
+
+    let rules: Vec<Value> = rule_ids.iter().map(|id| json!({ "id": id })).collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "flowscope",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }]
+    })
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+/// The physical location of an issue, when it was reported against a named input.
+fn issue_location(
+    result: &AnalyzeResult,
+    issue: &Issue,
+    contents: &HashMap<&str, &str>,
+) -> Option<Value> {
+    let name = issue_source(result, issue)?;
+    let mut physical = json!({
+        "artifactLocation": { "uri": name.replace('\\', "/") },
+    });
+
+    let region = issue.span.zip(contents.get(name)).and_then(|(span, sql)| {
+        let (start_line, start_column) = offset_to_line_col(sql, span.start)?;
+        let (end_line, end_column) = offset_to_line_col(sql, span.end.max(span.start))?;
+        Some(json!({
+            "startLine": start_line,
+            "startColumn": start_column,
+            "endLine": end_line,
+            "endColumn": end_column,
+        }))
+    });
+    if let Some(region) = region {
+        physical["region"] = region;
+    }
+
+    Some(json!({ "physicalLocation": physical }))
+}
+
+/// 1-based line and code-point column of a byte offset, `None` when the offset is
+/// past the end of `sql` or inside a character.
+fn offset_to_line_col(sql: &str, offset: usize) -> Option<(usize, usize)> {
+    let before = sql.get(..offset)?;
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+    Some((line, column))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_to_line_col() {
+        let sql = "SELECT 1;\nSELECT é, x";
+        assert_eq!(offset_to_line_col(sql, 0), Some((1, 1)));
+        assert_eq!(offset_to_line_col(sql, 10), Some((2, 1)));
+        // `é` is two bytes but one column
+        assert_eq!(offset_to_line_col(sql, sql.len() - 1), Some((2, 11)));
+        assert_eq!(offset_to_line_col(sql, sql.len() + 1), None);
+    }
+}
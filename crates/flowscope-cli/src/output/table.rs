@@ -1,13 +1,21 @@
 //! Human-readable table output formatting.
 
-use flowscope_core::{AnalyzeResult, NodeType, Severity};
+use flowscope_core::{AnalyzeResult, Issue, NodeType, Severity};
 use is_terminal::IsTerminal;
 use owo_colors::OwoColorize;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Write;
 
 /// Format the analysis result as human-readable text with optional colors.
-pub fn format_table(result: &AnalyzeResult, quiet: bool, use_colors: bool) -> String {
+///
+/// With `group_by_file`, issues are listed under one header per file instead of
+/// as a flat list; files without issues are omitted.
+pub fn format_table(
+    result: &AnalyzeResult,
+    quiet: bool,
+    use_colors: bool,
+    group_by_file: bool,
+) -> String {
     let colored = use_colors && std::io::stdout().is_terminal();
     let mut out = String::new();
 
@@ -16,7 +24,12 @@ pub fn format_table(result: &AnalyzeResult, quiet: bool, use_colors: bool) -> St
     write_lineage(&mut out, result, colored);
 
     if !quiet {
-        write_issues(&mut out, result, colored);
+        write_timings(&mut out, result, colored);
+        if group_by_file {
+            write_issues_by_file(&mut out, result, colored);
+        } else {
+            write_issues(&mut out, result, colored);
+        }
     }
 
     out
@@ -145,6 +158,46 @@ fn write_lineage(out: &mut String, result: &AnalyzeResult, colored: bool) {
     }
 }
 
+fn write_timings(out: &mut String, result: &AnalyzeResult, colored: bool) {
+    let Some(timings) = result.summary.timings.as_deref() else {
+        return;
+    };
+    if timings.is_empty() {
+        return;
+    }
+
+    if colored {
+        writeln!(out, "{}", "Timings (µs):".bold()).unwrap();
+    } else {
+        writeln!(out, "Timings (µs):").unwrap();
+    }
+    writeln!(
+        out,
+        "  {:>9}  {:>10}  {:>10}",
+        "statement", "parse", "analysis"
+    )
+    .unwrap();
+    for timing in timings {
+        writeln!(
+            out,
+            "  {:>9}  {:>10}  {:>10}",
+            timing.statement_index + 1,
+            timing.parse_micros,
+            timing.analysis_micros
+        )
+        .unwrap();
+    }
+    let parse_total: u64 = timings.iter().map(|t| t.parse_micros).sum();
+    let analysis_total: u64 = timings.iter().map(|t| t.analysis_micros).sum();
+    writeln!(
+        out,
+        "  {:>9}  {:>10}  {:>10}",
+        "total", parse_total, analysis_total
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+}
+
 fn write_issues(out: &mut String, result: &AnalyzeResult, colored: bool) {
     if result.issues.is_empty() {
         return;
@@ -174,38 +227,104 @@ fn write_issues(out: &mut String, result: &AnalyzeResult, colored: bool) {
     }
 
     for issue in &result.issues {
-        let severity_str = match issue.severity {
-            Severity::Error => {
-                if colored {
-                    "ERROR".red().to_string()
-                } else {
-                    "ERROR".to_string()
-                }
-            }
-            Severity::Warning => {
-                if colored {
-                    "WARN".yellow().to_string()
-                } else {
-                    "WARN".to_string()
-                }
-            }
-            Severity::Info => {
-                if colored {
-                    "INFO".blue().to_string()
-                } else {
-                    "INFO".to_string()
-                }
-            }
-        };
+        write_issue_line(out, issue, "  ", colored);
+    }
+}
+
+fn write_issues_by_file(out: &mut String, result: &AnalyzeResult, colored: bool) {
+    let mut by_file: BTreeMap<&str, Vec<&Issue>> = BTreeMap::new();
+    for issue in &result.issues {
+        // Statement-level issues may only carry the statement index
+        let file = issue
+            .source_name
+            .as_deref()
+            .or_else(|| {
+                let index = issue.statement_index?;
+                result
+                    .statements
+                    .iter()
+                    .find(|s| s.statement_index == index)?
+                    .source_name
+                    .as_deref()
+            })
+            .unwrap_or("<input>");
+        by_file.entry(file).or_default().push(issue);
+    }
 
-        let location = issue
-            .span
-            .as_ref()
-            .map(|s| format!(" offset {}:", s.start))
-            .unwrap_or_default();
+    let mut files: BTreeSet<&str> = result
+        .statements
+        .iter()
+        .filter_map(|s| s.source_name.as_deref())
+        .collect();
+    files.extend(by_file.keys().copied());
+
+    for (file, issues) in &by_file {
+        let header = format!(
+            "{file} ({} issue{})",
+            issues.len(),
+            if issues.len() == 1 { "" } else { "s" }
+        );
+        if colored {
+            writeln!(out, "{}", header.bold()).unwrap();
+        } else {
+            writeln!(out, "{header}").unwrap();
+        }
+        for issue in issues {
+            write_issue_line(out, issue, "    ", colored);
+        }
+    }
 
-        writeln!(out, "  [{}]{} {}", severity_str, location, issue.message).unwrap();
+    if !by_file.is_empty() {
+        writeln!(out).unwrap();
     }
+    let clean = files.len() - by_file.len();
+    writeln!(
+        out,
+        "{} issue(s) in {} file(s); {} clean file(s)",
+        result.issues.len(),
+        by_file.len(),
+        clean
+    )
+    .unwrap();
+}
+
+fn write_issue_line(out: &mut String, issue: &Issue, indent: &str, colored: bool) {
+    let severity_str = match issue.severity {
+        Severity::Error => {
+            if colored {
+                "ERROR".red().to_string()
+            } else {
+                "ERROR".to_string()
+            }
+        }
+        Severity::Warning => {
+            if colored {
+                "WARN".yellow().to_string()
+            } else {
+                "WARN".to_string()
+            }
+        }
+        Severity::Info => {
+            if colored {
+                "INFO".blue().to_string()
+            } else {
+                "INFO".to_string()
+            }
+        }
+    };
+
+    let location = issue
+        .span
+        .as_ref()
+        .map(|s| format!(" offset {}:", s.start))
+        .unwrap_or_default();
+
+    writeln!(
+        out,
+        "{indent}[{}]{} {}",
+        severity_str, location, issue.message
+    )
+    .unwrap();
 }
 
 #[cfg(test)]
@@ -225,7 +344,7 @@ mod tests {
             template_config: None,
         });
 
-        let output = format_table(&result, false, false);
+        let output = format_table(&result, false, false, false);
         assert!(output.contains("FlowScope Analysis"));
         assert!(output.contains("Summary:"));
     }
@@ -242,10 +361,47 @@ mod tests {
             template_config: None,
         });
 
-        let output_quiet = format_table(&result, true, false);
-        let output_verbose = format_table(&result, false, false);
+        let output_quiet = format_table(&result, true, false, false);
+        let output_verbose = format_table(&result, false, false, false);
 
         // Quiet mode may have fewer issue lines (but both might have none if parsing succeeds)
         assert!(output_quiet.len() <= output_verbose.len() || output_quiet == output_verbose);
     }
+
+    #[test]
+    fn test_format_table_timings() {
+        let request = AnalyzeRequest::builder()
+            .sql("SELECT 1; SELECT 2")
+            .options(flowscope_core::AnalysisOptions {
+                collect_timings: Some(true),
+                ..Default::default()
+            })
+            .build();
+        let result = analyze(&request);
+
+        let output = format_table(&result, false, false, false);
+        assert!(output.contains("Timings (µs):"));
+        assert!(output.contains("total"));
+        assert!(!format_table(&result, true, false, false).contains("Timings"));
+
+        let untimed = analyze(&AnalyzeRequest::builder().sql("SELECT 1").build());
+        assert!(!format_table(&untimed, false, false, false).contains("Timings"));
+    }
+
+    #[test]
+    fn test_format_table_group_by_file() {
+        let result = analyze(
+            &AnalyzeRequest::builder()
+                .file("broken.sql", "SELEC id FROM users")
+                .file("clean.sql", "SELECT id FROM users")
+                .file("also_broken.sql", "SELECT * FROM")
+                .build(),
+        );
+
+        let output = format_table(&result, false, false, true);
+        assert_eq!(output.matches("broken.sql (").count(), 2);
+        assert!(output.contains("also_broken.sql ("));
+        assert!(!output.contains("clean.sql ("));
+        assert!(output.contains("in 2 file(s); 1 clean file(s)"));
+    }
 }
@@ -0,0 +1,310 @@
+//! Independent per-file analysis for `--per-file-output` and `-f jsonl`.
+//!
+//! Each input file is read, analyzed on its own, and its result written out before
+//! the next file is touched, so memory stays flat however large the project is. No
+//! combined lineage is built: references between files are not resolved.
+
+use anyhow::{Context, Result};
+use flowscope_core::{
+    analyze, AnalyzeRequestBuilder, AnalyzeResult, Issue, StatementLineage, Summary,
+};
+use flowscope_export::export_json;
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::input::collect_sql_files;
+
+/// An input file and the path of its result relative to the output directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerFileInput {
+    pub path: PathBuf,
+    /// Mirrors the file's location below the directory it was found in, or its bare
+    /// file name when it was given directly, with a `.json` extension
+    pub output_name: PathBuf,
+}
+
+/// Where per-file results go.
+pub enum PerFileSink<W: Write> {
+    /// One NDJSON line per input file
+    Ndjson(W),
+    /// One JSON Lines record per input file, flushed as soon as it is written
+    JsonLines(W),
+    /// One JSON file per input file below this directory
+    Directory(PathBuf),
+}
+
+/// One NDJSON output line: the analysis of an input file, or why it was skipped.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OutputLine<'a> {
+    file: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<&'a AnalyzeResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// One `-f jsonl` record: the statements, issues and summary of one input, without
+/// the global lineage, or why the input was skipped.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonLinesRecord<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    statements: Option<&'a [StatementLineage]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issues: Option<&'a [Issue]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a Summary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl<'a> JsonLinesRecord<'a> {
+    fn new(source_name: Option<&'a str>, result: &'a AnalyzeResult) -> Self {
+        Self {
+            source_name,
+            statements: Some(&result.statements),
+            issues: Some(&result.issues),
+            summary: Some(&result.summary),
+            error: None,
+        }
+    }
+}
+
+/// The `-f jsonl` record of `result`, as a single line without a trailing newline.
+pub fn json_lines_record(source_name: Option<&str>, result: &AnalyzeResult) -> Result<String> {
+    serde_json::to_string(&JsonLinesRecord::new(source_name, result))
+        .context("Failed to serialize JSON Lines record")
+}
+
+/// Counts of processed input files.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PerFileSummary {
+    /// Files that were analyzed
+    pub analyzed: usize,
+    /// Analyzed files whose result contains errors
+    pub with_errors: usize,
+    /// Files that could not be read
+    pub unreadable: usize,
+}
+
+impl PerFileSummary {
+    pub fn has_errors(&self) -> bool {
+        self.with_errors > 0 || self.unreadable > 0
+    }
+}
+
+/// Expands directories in `paths` into the `.sql` files they contain, pairing each
+/// file with the name of its result.
+pub fn collect_per_file_inputs(paths: &[PathBuf]) -> Result<Vec<PerFileInput>> {
+    let mut inputs = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            for file in collect_sql_files(path)? {
+                let relative = file.strip_prefix(path).unwrap_or(&file);
+                inputs.push(PerFileInput {
+                    output_name: relative.with_extension("json"),
+                    path: file.clone(),
+                });
+            }
+        } else {
+            let name = path.file_name().unwrap_or(path.as_os_str());
+            inputs.push(PerFileInput {
+                path: path.clone(),
+                output_name: Path::new(name).with_extension("json"),
+            });
+        }
+    }
+    Ok(inputs)
+}
+
+/// Analyzes every input on its own and writes each result to `sink` as soon as it
+/// is ready.
+///
+/// `base` carries the dialect, schema and options shared by every file. Files that
+/// cannot be read are reported (as an NDJSON `error` line, or on stderr in directory
+/// mode) and skipped.
+pub fn analyze_per_file<W: Write>(
+    inputs: &[PerFileInput],
+    mut sink: PerFileSink<W>,
+    base: &AnalyzeRequestBuilder,
+    compact: bool,
+) -> Result<PerFileSummary> {
+    let mut summary = PerFileSummary::default();
+
+    for input in inputs {
+        let name = input.path.display().to_string();
+        let result = fs::read_to_string(&input.path)
+            .map(|content| analyze(&base.clone().sql(content).source_name(&name).build()));
+
+        match (&mut sink, &result) {
+            (PerFileSink::Ndjson(writer), _) => {
+                let line = match &result {
+                    Ok(result) => OutputLine {
+                        file: &name,
+                        result: Some(result),
+                        error: None,
+                    },
+                    Err(e) => OutputLine {
+                        file: &name,
+                        result: None,
+                        error: Some(format!("Failed to read file: {e}")),
+                    },
+                };
+                let line =
+                    serde_json::to_string(&line).context("Failed to serialize per-file result")?;
+                writeln!(writer, "{line}").context("Failed to write per-file result")?;
+            }
+            (PerFileSink::JsonLines(writer), _) => {
+                let record = match &result {
+                    Ok(result) => JsonLinesRecord::new(Some(&name), result),
+                    Err(e) => JsonLinesRecord {
+                        source_name: Some(&name),
+                        statements: None,
+                        issues: None,
+                        summary: None,
+                        error: Some(format!("Failed to read file: {e}")),
+                    },
+                };
+                let line = serde_json::to_string(&record)
+                    .context("Failed to serialize JSON Lines record")?;
+                writeln!(writer, "{line}").context("Failed to write JSON Lines record")?;
+                writer
+                    .flush()
+                    .context("Failed to write JSON Lines record")?;
+            }
+            (PerFileSink::Directory(dir), Ok(result)) => {
+                let path = dir.join(&input.output_name);
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent).with_context(|| {
+                        format!("Failed to create directory {}", parent.display())
+                    })?;
+                }
+                let json = export_json(result, compact).context("Failed to export JSON")?;
+                fs::write(&path, json)
+                    .with_context(|| format!("Failed to write to {}", path.display()))?;
+            }
+            (PerFileSink::Directory(_), Err(e)) => {
+                eprintln!("flowscope: Failed to read file {name}: {e}");
+            }
+        }
+
+        match result {
+            Ok(result) => {
+                summary.analyzed += 1;
+                if result.summary.has_errors {
+                    summary.with_errors += 1;
+                }
+            }
+            Err(_) => summary.unreadable += 1,
+        }
+    }
+
+    if let PerFileSink::Ndjson(writer) = &mut sink {
+        writer.flush().context("Failed to write per-file result")?;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowscope_core::AnalyzeRequest;
+
+    #[test]
+    fn test_output_names_mirror_directory_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("staging")).unwrap();
+        fs::write(dir.path().join("staging").join("orders.sql"), "SELECT 1").unwrap();
+        fs::write(dir.path().join("mart.sql"), "SELECT 1").unwrap();
+
+        let inputs =
+            collect_per_file_inputs(&[dir.path().to_path_buf(), PathBuf::from("x/y/adhoc.sql")])
+                .unwrap();
+        let names: Vec<&Path> = inputs.iter().map(|i| i.output_name.as_path()).collect();
+        assert_eq!(
+            names,
+            [
+                Path::new("mart.json"),
+                Path::new("staging/orders.json"),
+                Path::new("adhoc.json")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_lines_records_omit_global_lineage() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = dir.path().join("good.sql");
+        fs::write(&good, "SELECT id FROM users; SELEC").unwrap();
+        let inputs = collect_per_file_inputs(&[good, dir.path().join("missing.sql")]).unwrap();
+
+        let mut out = Vec::new();
+        let summary = analyze_per_file(
+            &inputs,
+            PerFileSink::JsonLines(&mut out),
+            &AnalyzeRequest::builder(),
+            true,
+        )
+        .unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0]["sourceName"]
+            .as_str()
+            .unwrap()
+            .ends_with("good.sql"));
+        assert_eq!(lines[0]["statements"].as_array().unwrap().len(), 1);
+        assert!(!lines[0]["issues"].as_array().unwrap().is_empty());
+        assert!(lines[0].get("globalLineage").is_none());
+        assert!(lines[1]["sourceName"]
+            .as_str()
+            .unwrap()
+            .ends_with("missing.sql"));
+        assert!(lines[1]["error"].is_string());
+        assert_eq!(summary.with_errors, 1);
+        assert_eq!(summary.unreadable, 1);
+    }
+
+    #[test]
+    fn test_unreadable_files_become_error_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = dir.path().join("good.sql");
+        fs::write(&good, "SELECT id FROM users").unwrap();
+        let inputs = collect_per_file_inputs(&[good, dir.path().join("missing.sql")]).unwrap();
+
+        let mut out = Vec::new();
+        let summary = analyze_per_file(
+            &inputs,
+            PerFileSink::Ndjson(&mut out),
+            &AnalyzeRequest::builder(),
+            true,
+        )
+        .unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0]["result"]["statements"].is_array());
+        assert!(lines[1]["error"].is_string());
+        assert_eq!(
+            summary,
+            PerFileSummary {
+                analyzed: 1,
+                with_errors: 0,
+                unreadable: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Custom report output for `--template-file`.
+//!
+//! The template is rendered with MiniJinja against the serialized `AnalyzeResult`, so
+//! its top-level variables are the camelCase fields of the JSON output: `statements`,
+//! `globalLineage`, `issues`, `summary`, and `resolvedSchema` (when a schema is known).
+
+use anyhow::Result;
+use flowscope_core::AnalyzeResult;
+use minijinja::{Environment, Value};
+
+/// Compiles `template` without rendering it, to report syntax errors early.
+pub fn check_report_template(template: &str) -> Result<()> {
+    Environment::new().template_from_str(template)?;
+    Ok(())
+}
+
+/// Renders `template` with the fields of `result` as its context.
+pub fn render_report_template(result: &AnalyzeResult, template: &str) -> Result<String> {
+    let env = Environment::new();
+    Ok(env.render_str(template, Value::from_serialize(result))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowscope_core::{analyze, AnalyzeRequest};
+
+    #[test]
+    fn test_renders_summary_fields() {
+        let result = analyze(
+            &AnalyzeRequest::builder()
+                .sql("SELECT u.id FROM users u JOIN orders o ON u.id = o.user_id")
+                .build(),
+        );
+
+        let rendered = render_report_template(
+            &result,
+            "tables: {{ summary.tableCount }}\n\
+             {% for node in globalLineage.nodes|sort(attribute=\"label\") if node.type == \"table\" %}- {{ node.label }}\n{% endfor %}",
+        )
+        .unwrap();
+
+        assert_eq!(rendered, "tables: 2\n- orders\n- users\n");
+    }
+
+    #[test]
+    fn test_reports_template_syntax_errors() {
+        let result = analyze(&AnalyzeRequest::builder().sql("SELECT 1").build());
+        assert!(render_report_template(&result, "{% for %}").is_err());
+    }
+}
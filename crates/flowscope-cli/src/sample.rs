@@ -0,0 +1,119 @@
+//! Random input sampling for `--sample`.
+//!
+//! Picks a fixed number of files from the discovered input set so a large project
+//! can get a quick sanity pass before a full run. The choice depends only on the
+//! input paths and the seed, so a reported seed reproduces the same sample.
+
+use anyhow::Result;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::bench::collect_bench_files;
+
+/// Files chosen by [`sample_files`], with the numbers needed to report them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample {
+    /// The sampled files, in discovery order
+    pub files: Vec<PathBuf>,
+    /// Number of files discovered before sampling
+    pub discovered: usize,
+    /// Seed the sample was drawn with
+    pub seed: u64,
+}
+
+/// Expands directories in `paths` into their `.sql` files and picks `count` of
+/// them at random using `seed`.
+///
+/// Every file is kept when fewer than `count` are discovered. The sampled files
+/// keep their discovery order so output stays stable across runs.
+pub fn sample_files(paths: &[PathBuf], count: usize, seed: u64) -> Result<Sample> {
+    let files = collect_bench_files(paths)?;
+    let discovered = files.len();
+
+    // Partial Fisher-Yates over the indices: the first `count` slots end up holding
+    // a uniform random selection
+    let mut indices: Vec<usize> = (0..discovered).collect();
+    let mut rng = SplitMix64(seed);
+    let count = count.min(discovered);
+    for i in 0..count {
+        let j = i + (rng.next() % (discovered - i) as u64) as usize;
+        indices.swap(i, j);
+    }
+    let mut chosen = indices[..count].to_vec();
+    chosen.sort_unstable();
+
+    Ok(Sample {
+        files: chosen.into_iter().map(|i| files[i].clone()).collect(),
+        discovered,
+        seed,
+    })
+}
+
+/// A seed for runs without `--seed`, taken from the clock.
+pub fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// SplitMix64: small, fast, and stable across platforms and releases, which is all
+/// reproducible sampling needs.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn project(files: usize) -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        for i in 0..files {
+            std::fs::write(dir.path().join(format!("model_{i:02}.sql")), "SELECT 1").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_same_seed_selects_same_files() {
+        let dir = project(20);
+        let paths = vec![dir.path().to_path_buf()];
+
+        let first = sample_files(&paths, 5, 42).unwrap();
+        let second = sample_files(&paths, 5, 42).unwrap();
+        assert_eq!(first.files.len(), 5);
+        assert_eq!(first.discovered, 20);
+        assert_eq!(first, second);
+
+        let mut sorted = first.files.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted, first.files, "sample is ordered and has no repeats");
+
+        let others: Vec<_> = (0..10)
+            .map(|seed| sample_files(&paths, 5, seed).unwrap().files)
+            .collect();
+        assert!(
+            others.iter().any(|files| *files != first.files),
+            "different seeds should not all pick the same files"
+        );
+    }
+
+    #[test]
+    fn test_sample_larger_than_input_keeps_everything() {
+        let dir = project(3);
+        let sample = sample_files(&[dir.path().to_path_buf()], 10, 7).unwrap();
+        assert_eq!(sample.files.len(), 3);
+        assert_eq!(sample.discovered, 3);
+    }
+}
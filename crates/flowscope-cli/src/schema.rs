@@ -1,24 +1,224 @@
-//! Schema loading from DDL files.
+//! Schema loading from DDL files and schema JSON.
 
 use anyhow::{bail, Context, Result};
+use flowscope_core::generated::normalize_type_name;
 use flowscope_core::{
-    analyze, AnalyzeRequest, ColumnSchema, Dialect, FileSource, SchemaMetadata, SchemaTable,
-    Severity,
+    analyze, parse_sql_with_dialect, AnalyzeRequest, CaseSensitivity, ColumnSchema, Dialect,
+    FileSource, SchemaMetadata, SchemaTable, Severity,
 };
-use std::path::Path;
+use sqlparser::ast::{DataType, Statement};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::input::collect_sql_files;
+
+/// Load and merge schema from several DDL files or directories.
+///
+/// A directory stands for the `.sql` files under it, in sorted order, so numbered
+/// migration files apply in sequence; files among them that define no tables are
+/// skipped. Files are applied in order like migrations: later files can add tables,
+/// and a table defined again replaces the earlier definition, with a warning when
+/// the columns differ.
+pub fn load_schema_from_ddl_files(
+    paths: &[PathBuf],
+    dialect: Dialect,
+    strict: bool,
+) -> Result<SchemaMetadata> {
+    let mut merged: Option<SchemaMetadata> = None;
+    for path in paths {
+        let loaded = if path.is_dir() {
+            let files = collect_sql_files(path)?;
+            if files.is_empty() {
+                bail!(
+                    "No .sql files found in schema directory: {}",
+                    path.display()
+                );
+            }
+            let mut loaded = Vec::new();
+            for file in files {
+                if let Some(schema) = load_ddl_tables(&file, dialect, strict)? {
+                    loaded.push((file, schema));
+                }
+            }
+            if loaded.is_empty() {
+                bail!("Schema directory defines no tables: {}", path.display());
+            }
+            loaded
+        } else {
+            vec![(path.clone(), load_schema_from_ddl(path, dialect, strict)?)]
+        };
+
+        for (file, schema) in loaded {
+            merged = Some(match merged {
+                Some(base) => merge_schemas(base, schema, &file, dialect),
+                None => schema,
+            });
+        }
+    }
+    merged.context("No schema files provided")
+}
 
 /// Load schema from a DDL file containing CREATE TABLE statements.
 ///
 /// Parses the DDL using flowscope-core's analyzer to extract table/column definitions.
-pub fn load_schema_from_ddl(path: &Path, dialect: Dialect) -> Result<SchemaMetadata> {
+/// Columns whose types cannot be mapped to a known type are reported as warnings,
+/// or fail the load when `strict` is set.
+pub fn load_schema_from_ddl(path: &Path, dialect: Dialect, strict: bool) -> Result<SchemaMetadata> {
+    load_ddl_tables(path, dialect, strict)?.context("Schema DDL produced no table definitions")
+}
+
+/// Load schema metadata written as JSON in the `SchemaMetadata` shape the analysis
+/// API takes, e.g. converted from an `information_schema` dump.
+pub fn load_schema_from_json(path: &Path) -> Result<SchemaMetadata> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read schema JSON file: {}", path.display()))?;
+    parse_schema_json(&content)
+        .with_context(|| format!("Invalid schema JSON file: {}", path.display()))
+}
+
+fn parse_schema_json(content: &str) -> Result<SchemaMetadata> {
+    let schema: SchemaMetadata = serde_json::from_str(content).context(
+        r#"expected an object like {"tables": [{"name": "users", "columns": [{"name": "id", "dataType": "integer"}]}]}"#,
+    )?;
+    if schema.tables.is_empty() {
+        bail!("no tables defined");
+    }
+    for (index, table) in schema.tables.iter().enumerate() {
+        if table.name.trim().is_empty() {
+            bail!("table {index} has an empty name");
+        }
+        if table
+            .columns
+            .iter()
+            .any(|column| column.name.trim().is_empty())
+        {
+            bail!("table '{}' has a column with an empty name", table.name);
+        }
+    }
+    Ok(schema)
+}
+
+/// Load the tables a DDL file defines, or `None` if it defines none.
+fn load_ddl_tables(path: &Path, dialect: Dialect, strict: bool) -> Result<Option<SchemaMetadata>> {
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read schema file: {}", path.display()))?;
 
-    parse_schema_ddl(&content, dialect)
+    let schema = parse_ddl_tables(&content, dialect)
+        .with_context(|| format!("Invalid schema file: {}", path.display()))?;
+    check_column_types(&content, dialect, strict)?;
+    Ok(schema)
+}
+
+/// Report columns with unknown types, failing in strict mode.
+fn check_column_types(content: &str, dialect: Dialect, strict: bool) -> Result<()> {
+    let unknown = unknown_column_types(content, dialect);
+    if unknown.is_empty() {
+        return Ok(());
+    }
+
+    if strict {
+        bail!(
+            "Schema DDL has columns with unknown types:\n  {}",
+            unknown.join("\n  ")
+        );
+    }
+
+    for column in &unknown {
+        eprintln!("flowscope: warning: unknown column type in schema: {column}");
+    }
+    Ok(())
+}
+
+/// List `table.column: TYPE` entries whose declared type is not recognized.
+///
+/// sqlparser maps every built-in type to a dedicated variant, so only custom
+/// type names need checking against the canonical type table.
+fn unknown_column_types(content: &str, dialect: Dialect) -> Vec<String> {
+    let Ok(statements) = parse_sql_with_dialect(content, dialect) else {
+        return Vec::new();
+    };
+
+    let mut unknown = Vec::new();
+    for statement in &statements {
+        let Statement::CreateTable(create) = statement else {
+            continue;
+        };
+        for column in &create.columns {
+            if let DataType::Custom(name, _) = &column.data_type {
+                let type_name = name.to_string();
+                if normalize_type_name(type_name.trim_matches('"')).is_none() {
+                    unknown.push(format!(
+                        "{}.{}: {}",
+                        create.name, column.name, column.data_type
+                    ));
+                }
+            }
+        }
+    }
+    unknown
+}
+
+/// Merge the tables of `other`, loaded from `source`, into `base`, deduplicating by
+/// canonical table name. A table defined again replaces the earlier definition.
+fn merge_schemas(
+    mut base: SchemaMetadata,
+    other: SchemaMetadata,
+    source: &Path,
+    dialect: Dialect,
+) -> SchemaMetadata {
+    let mut index: HashMap<String, usize> = base
+        .tables
+        .iter()
+        .enumerate()
+        .map(|(i, table)| (canonical_table_key(table, dialect), i))
+        .collect();
+
+    for table in other.tables {
+        let key = canonical_table_key(&table, dialect);
+        match index.get(&key) {
+            Some(&existing) => {
+                if !same_columns(&base.tables[existing], &table, dialect) {
+                    eprintln!(
+                        "flowscope: warning: table '{key}' is redefined with different columns in {}; using the later definition",
+                        source.display()
+                    );
+                }
+                base.tables[existing] = table;
+            }
+            None => {
+                index.insert(key, base.tables.len());
+                base.tables.push(table);
+            }
+        }
+    }
+    base
+}
+
+/// Build a lookup key for a table, normalized per the dialect's case sensitivity.
+fn canonical_table_key(table: &SchemaTable, dialect: Dialect) -> String {
+    let strategy = CaseSensitivity::Dialect.resolve(dialect);
+    [&table.catalog, &table.schema]
+        .into_iter()
+        .flatten()
+        .chain(std::iter::once(&table.name))
+        .map(|part| strategy.apply(part).into_owned())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Whether two table definitions declare the same columns with the same types.
+fn same_columns(a: &SchemaTable, b: &SchemaTable, dialect: Dialect) -> bool {
+    let strategy = CaseSensitivity::Dialect.resolve(dialect);
+    a.columns.len() == b.columns.len()
+        && a.columns.iter().zip(&b.columns).all(|(x, y)| {
+            strategy.apply(&x.name) == strategy.apply(&y.name)
+                && x.data_type.as_deref().map(str::to_ascii_lowercase)
+                    == y.data_type.as_deref().map(str::to_ascii_lowercase)
+        })
 }
 
-/// Parse DDL content to extract schema metadata.
-fn parse_schema_ddl(content: &str, dialect: Dialect) -> Result<SchemaMetadata> {
+/// Parse DDL content to extract schema metadata, or `None` when it defines no tables.
+fn parse_ddl_tables(content: &str, dialect: Dialect) -> Result<Option<SchemaMetadata>> {
     let request = AnalyzeRequest {
         sql: String::new(),
         files: Some(vec![FileSource {
@@ -51,9 +251,12 @@ fn parse_schema_ddl(content: &str, dialect: Dialect) -> Result<SchemaMetadata> {
     }
 
     // Extract schema from resolved_schema
-    let resolved = result
+    let Some(resolved) = result
         .resolved_schema
-        .context("Schema DDL produced no table definitions")?;
+        .filter(|resolved| !resolved.tables.is_empty())
+    else {
+        return Ok(None);
+    };
 
     let schema = SchemaMetadata {
         default_catalog: None,
@@ -75,6 +278,7 @@ fn parse_schema_ddl(content: &str, dialect: Dialect) -> Result<SchemaMetadata> {
                         data_type: c.data_type,
                         is_primary_key: c.is_primary_key,
                         foreign_key: c.foreign_key,
+                        nullable: c.nullable,
                     })
                     .collect(),
             })
@@ -82,13 +286,48 @@ fn parse_schema_ddl(content: &str, dialect: Dialect) -> Result<SchemaMetadata> {
         allow_implied: false, // Don't allow further implied tables in main analysis
     };
 
-    Ok(schema)
+    Ok(Some(schema))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn parse_schema_ddl(content: &str, dialect: Dialect) -> Result<SchemaMetadata> {
+        parse_ddl_tables(content, dialect)?.context("Schema DDL produced no table definitions")
+    }
+
+    #[test]
+    fn test_parse_schema_json() {
+        let schema = parse_schema_json(
+            r#"{"defaultSchema": "public", "tables": [
+                {"schema": "public", "name": "users", "columns": [
+                    {"name": "id", "dataType": "integer"}, {"name": "email"}
+                ]}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(schema.default_schema.as_deref(), Some("public"));
+        assert_eq!(schema.tables[0].name, "users");
+        assert_eq!(schema.tables[0].columns.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_schema_json_rejects_bad_shapes() {
+        let error = |json: &str| format!("{:#}", parse_schema_json(json).unwrap_err());
+
+        // The hint shows the expected shape along with serde's own message
+        let message = error(r#"{"tables": [{"columns": []}]}"#);
+        assert!(
+            message.contains(r#"expected an object like {"tables""#),
+            "{message}"
+        );
+        assert!(message.contains("missing field `name`"), "{message}");
+        assert!(error(r#""users""#).contains("expected an object like"));
+        assert!(error(r#"{"tables": []}"#).contains("no tables defined"));
+        assert!(error(r#"{"tables": [{"name": " "}]}"#).contains("table 0 has an empty name"));
+    }
+
     #[test]
     fn test_parse_simple_ddl() {
         let ddl = r#"
@@ -134,4 +373,93 @@ mod tests {
             err_msg
         );
     }
+
+    #[test]
+    fn test_merge_schemas_adds_tables_and_keeps_last_definition() {
+        let first = parse_schema_ddl(
+            "CREATE TABLE users (id INT, name VARCHAR); CREATE TABLE orders (id INT);",
+            Dialect::Postgres,
+        )
+        .unwrap();
+        let second = parse_schema_ddl(
+            "CREATE TABLE Users (id INT); CREATE TABLE payments (id INT, order_id INT);",
+            Dialect::Postgres,
+        )
+        .unwrap();
+
+        let merged = merge_schemas(first, second, Path::new("002.sql"), Dialect::Postgres);
+        let names: Vec<&str> = merged.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["orders", "users", "payments"]);
+        let users = merged.tables.iter().find(|t| t.name == "users").unwrap();
+        assert_eq!(users.columns.len(), 1);
+    }
+
+    #[test]
+    fn test_schema_directory_applies_files_in_sorted_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let migrations = dir.path().join("migrations");
+        std::fs::create_dir(&migrations).unwrap();
+        std::fs::write(
+            migrations.join("002_widen_users.sql"),
+            "CREATE TABLE users (id INT, email VARCHAR);",
+        )
+        .unwrap();
+        std::fs::write(
+            migrations.join("001_init.sql"),
+            "CREATE TABLE users (id INT); CREATE TABLE orders (id INT);",
+        )
+        .unwrap();
+        std::fs::write(
+            migrations.join("003_seed.sql"),
+            "INSERT INTO orders VALUES (1);",
+        )
+        .unwrap();
+        std::fs::write(migrations.join("notes.txt"), "not sql").unwrap();
+        let extra = dir.path().join("audit.sql");
+        std::fs::write(&extra, "CREATE TABLE audit (at TIMESTAMP);").unwrap();
+
+        let schema =
+            load_schema_from_ddl_files(&[migrations, extra], Dialect::Generic, false).unwrap();
+        let names: Vec<&str> = schema.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["orders", "users", "audit"]);
+        let users = schema.tables.iter().find(|t| t.name == "users").unwrap();
+        assert_eq!(users.columns.len(), 2);
+    }
+
+    #[test]
+    fn test_schema_directory_without_tables_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(
+            load_schema_from_ddl_files(&[dir.path().to_path_buf()], Dialect::Generic, false)
+                .is_err()
+        );
+
+        std::fs::write(dir.path().join("seed.sql"), "INSERT INTO t VALUES (1);").unwrap();
+        let err = load_schema_from_ddl_files(&[dir.path().to_path_buf()], Dialect::Generic, false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("defines no tables"), "{err}");
+    }
+
+    #[test]
+    fn test_unknown_column_types_detected() {
+        let ddl = r#"
+            CREATE TABLE users (id INT, status STAUTS_ENUM, email STRING, created TIMESTAMP);
+        "#;
+
+        let unknown = unknown_column_types(ddl, Dialect::Generic);
+        assert_eq!(unknown, vec!["users.status: STAUTS_ENUM"]);
+    }
+
+    #[test]
+    fn test_check_column_types_strict_and_lenient() {
+        let ddl = "CREATE TABLE users (id INT, status STAUTS_ENUM);";
+
+        assert!(check_column_types(ddl, Dialect::Generic, false).is_ok());
+
+        let err = check_column_types(ddl, Dialect::Generic, true)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("users.status: STAUTS_ENUM"), "{err}");
+    }
 }
@@ -0,0 +1,268 @@
+//! Schema snapshot comparison for `--schema-diff`.
+//!
+//! Compares the tables of two schema snapshots (before and after a migration),
+//! classifies each column change, and uses the lineage of the analyzed SQL to list
+//! the downstream columns and relations each change affects.
+
+use crate::explain::downstream_columns;
+use flowscope_core::{AnalyzeResult, ColumnSchema, SchemaMetadata, SchemaTable};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// How a column differs between the two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ColumnChangeKind {
+    /// The column (or its whole table) is gone
+    Dropped,
+    /// A new column with the same type took the column's position
+    Renamed { to: String },
+    /// The column's declared type changed
+    TypeChanged { from: String, to: String },
+}
+
+/// A changed column and the lineage it affects.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnChange {
+    /// Qualified table name (`schema.table`)
+    pub table: String,
+    /// Column name in the old snapshot
+    pub column: String,
+    #[serde(flatten)]
+    pub kind: ColumnChangeKind,
+    /// Columns computed from this one, as `relation.column`
+    pub impacted_columns: Vec<String>,
+    /// Relations owning the impacted columns
+    pub impacted_relations: Vec<String>,
+}
+
+/// Compares `old` with `new` and attaches the downstream impact found in `result`,
+/// which should be the analysis of the SQL against the old snapshot.
+pub fn diff_schemas(
+    old: &SchemaMetadata,
+    new: &SchemaMetadata,
+    result: &AnalyzeResult,
+) -> Vec<ColumnChange> {
+    let mut changes = Vec::new();
+    for old_table in &old.tables {
+        let table = qualified_name(old_table);
+        let new_table = new
+            .tables
+            .iter()
+            .find(|t| qualified_name(t).eq_ignore_ascii_case(&table));
+
+        for (position, column) in old_table.columns.iter().enumerate() {
+            let kind = match new_table {
+                None => Some(ColumnChangeKind::Dropped),
+                Some(new_table) => column_change(column, position, old_table, new_table),
+            };
+            let Some(kind) = kind else {
+                continue;
+            };
+
+            let impacted_columns = downstream_columns(result, &format!("{table}.{}", column.name));
+            let impacted_relations: BTreeSet<String> = impacted_columns
+                .iter()
+                .filter_map(|c| c.rsplit_once('.').map(|(relation, _)| relation.to_string()))
+                .collect();
+            changes.push(ColumnChange {
+                table: table.clone(),
+                column: column.name.clone(),
+                kind,
+                impacted_columns,
+                impacted_relations: impacted_relations.into_iter().collect(),
+            });
+        }
+    }
+    changes
+}
+
+/// Classifies the change to `column`, or `None` if it is unchanged.
+fn column_change(
+    column: &ColumnSchema,
+    position: usize,
+    old_table: &SchemaTable,
+    new_table: &SchemaTable,
+) -> Option<ColumnChangeKind> {
+    let find = |table: &'_ SchemaTable, name: &str| {
+        table
+            .columns
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+            .cloned()
+    };
+
+    if let Some(new_column) = find(new_table, &column.name) {
+        let (from, to) = (type_name(column), type_name(&new_column));
+        return (!from.eq_ignore_ascii_case(&to))
+            .then_some(ColumnChangeKind::TypeChanged { from, to });
+    }
+
+    // A column added in the same position with the same type is taken as a rename
+    match new_table.columns.get(position) {
+        Some(candidate)
+            if find(old_table, &candidate.name).is_none()
+                && type_name(candidate).eq_ignore_ascii_case(&type_name(column)) =>
+        {
+            Some(ColumnChangeKind::Renamed {
+                to: candidate.name.clone(),
+            })
+        }
+        _ => Some(ColumnChangeKind::Dropped),
+    }
+}
+
+fn qualified_name(table: &SchemaTable) -> String {
+    [table.catalog.as_deref(), table.schema.as_deref()]
+        .into_iter()
+        .flatten()
+        .chain([table.name.as_str()])
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn type_name(column: &ColumnSchema) -> String {
+    column.data_type.clone().unwrap_or_default()
+}
+
+/// Formats the changes as one line per change followed by its impacted columns.
+pub fn format_schema_diff(changes: &[ColumnChange]) -> String {
+    if changes.is_empty() {
+        return "No column changes\n".to_string();
+    }
+
+    let mut out = String::new();
+    for change in changes {
+        let column = format!("{}.{}", change.table, change.column);
+        match &change.kind {
+            ColumnChangeKind::Dropped => writeln!(out, "{column} dropped"),
+            ColumnChangeKind::Renamed { to } => writeln!(out, "{column} renamed to {to}"),
+            ColumnChangeKind::TypeChanged { from, to } => {
+                writeln!(out, "{column} type changed from {from} to {to}")
+            }
+        }
+        .unwrap();
+        if change.impacted_columns.is_empty() {
+            writeln!(out, "  (no downstream columns)").unwrap();
+        }
+        for impacted in &change.impacted_columns {
+            writeln!(out, "  -> {impacted}").unwrap();
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowscope_core::{analyze, AnalyzeRequest, Dialect};
+
+    fn table(name: &str, columns: &[(&str, &str)]) -> SchemaTable {
+        SchemaTable {
+            catalog: None,
+            schema: Some("raw".to_string()),
+            name: name.to_string(),
+            columns: columns
+                .iter()
+                .map(|(name, data_type)| ColumnSchema {
+                    name: name.to_string(),
+                    data_type: Some(data_type.to_string()),
+                    is_primary_key: None,
+                    foreign_key: None,
+                    nullable: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn schema(tables: Vec<SchemaTable>) -> SchemaMetadata {
+        SchemaMetadata {
+            default_catalog: None,
+            default_schema: None,
+            search_path: None,
+            case_sensitivity: None,
+            tables,
+            allow_implied: true,
+        }
+    }
+
+    fn analyze_sql(sql: &str, schema: &SchemaMetadata) -> AnalyzeResult {
+        analyze(&AnalyzeRequest {
+            sql: sql.to_string(),
+            files: None,
+            dialect: Dialect::Generic,
+            source_name: None,
+            options: None,
+            schema: Some(schema.clone()),
+            template_config: None,
+        })
+    }
+
+    #[test]
+    fn test_dropped_column_reports_downstream_columns() {
+        let old = schema(vec![table(
+            "orders",
+            &[("id", "INT"), ("amount", "DECIMAL"), ("status", "TEXT")],
+        )]);
+        let new = schema(vec![table("orders", &[("id", "INT"), ("status", "TEXT")])]);
+        let result = analyze_sql(
+            "CREATE TABLE staging.orders AS SELECT id, amount * 2 AS doubled FROM raw.orders;
+             CREATE TABLE mart.revenue AS SELECT SUM(doubled) AS total FROM staging.orders;",
+            &old,
+        );
+
+        let changes = diff_schemas(&old, &new, &result);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, ColumnChangeKind::Dropped);
+        assert_eq!(
+            changes[0].impacted_columns,
+            vec!["mart.revenue.total", "staging.orders.doubled"]
+        );
+        assert_eq!(
+            changes[0].impacted_relations,
+            vec!["mart.revenue", "staging.orders"]
+        );
+        assert_eq!(
+            format_schema_diff(&changes),
+            "raw.orders.amount dropped\n  \
+             -> mart.revenue.total\n  \
+             -> staging.orders.doubled\n"
+        );
+    }
+
+    #[test]
+    fn test_renames_and_type_changes_are_classified() {
+        let old = schema(vec![
+            table("users", &[("id", "INT"), ("mail", "TEXT")]),
+            table("legacy", &[("code", "TEXT")]),
+        ]);
+        let new = schema(vec![table("USERS", &[("id", "BIGINT"), ("email", "text")])]);
+        let result = analyze_sql("SELECT 1", &old);
+
+        let kinds: Vec<(String, ColumnChangeKind)> = diff_schemas(&old, &new, &result)
+            .into_iter()
+            .map(|c| (format!("{}.{}", c.table, c.column), c.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (
+                    "raw.users.id".to_string(),
+                    ColumnChangeKind::TypeChanged {
+                        from: "INT".to_string(),
+                        to: "BIGINT".to_string()
+                    }
+                ),
+                (
+                    "raw.users.mail".to_string(),
+                    ColumnChangeKind::Renamed {
+                        to: "email".to_string()
+                    }
+                ),
+                ("raw.legacy.code".to_string(), ColumnChangeKind::Dropped),
+            ]
+        );
+    }
+}
@@ -14,6 +14,7 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 
+use super::cache::CacheStats;
 use super::AppState;
 
 /// Build the API router with all endpoints.
@@ -35,6 +36,12 @@ pub fn api_routes() -> Router<Arc<AppState>> {
 struct HealthResponse {
     status: &'static str,
     version: &'static str,
+    /// Dialects accepted in this build
+    dialects: &'static [flowscope_core::Dialect],
+    /// Formats `/api/export/{format}` serves in this build
+    formats: Vec<&'static str>,
+    /// Hits and misses of the analysis cache
+    cache: CacheStats,
 }
 
 #[derive(Deserialize)]
@@ -80,11 +87,18 @@ struct ExportRequest {
 
 // === Handlers ===
 
-/// GET /api/health - Health check with version
-async fn health() -> Json<HealthResponse> {
+/// GET /api/health - Health check with version, the dialects and export formats
+/// compiled into this build, and analysis cache counters
+async fn health(State(state): State<Arc<AppState>>) -> Json<HealthResponse> {
     Json(HealthResponse {
         status: "ok",
         version: env!("CARGO_PKG_VERSION"),
+        dialects: flowscope_core::supported_dialects(),
+        formats: flowscope_export::supported_formats()
+            .into_iter()
+            .map(flowscope_export::ExportFormat::name)
+            .collect(),
+        cache: state.cache.stats(),
     })
 }
 
@@ -121,8 +135,17 @@ async fn analyze(
         template_config,
     };
 
-    let result = flowscope_core::analyze(&request);
-    Ok(Json(result))
+    // Unchanged inputs are answered with the cached result
+    let result = state
+        .cache
+        .get_or_analyze(&request, |request| {
+            serde_json::to_vec(&flowscope_core::analyze(request))
+        })
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok((
+        [(axum::http::header::CONTENT_TYPE, "application/json")],
+        result,
+    ))
 }
 
 /// POST /api/completion - Get code completion items
@@ -211,6 +234,20 @@ async fn export(
                 .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
             Ok(([(axum::http::header::CONTENT_TYPE, "text/html")], output).into_response())
         }
+        "markdown" => {
+            let output = flowscope_export::export_markdown(&result, "lineage")
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            Ok((
+                [(axum::http::header::CONTENT_TYPE, "text/markdown")],
+                output,
+            )
+                .into_response())
+        }
+        "turtle" => {
+            let output = flowscope_export::export_turtle(&result)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            Ok(([(axum::http::header::CONTENT_TYPE, "text/turtle")], output).into_response())
+        }
         "csv" => {
             let bytes = flowscope_export::export_csv_bundle(&result)
                 .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
@@ -232,10 +269,25 @@ async fn export(
             )
                 .into_response())
         }
-        _ => Err((
-            StatusCode::BAD_REQUEST,
-            format!("Unknown export format: {format}"),
-        )),
+        // Remaining formats of this build (sql, duckdb, png) as raw bytes
+        other => {
+            let Some(export_format) = flowscope_export::supported_formats()
+                .into_iter()
+                .find(|supported| supported.name() == other)
+            else {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("Unknown export format: {format}"),
+                ));
+            };
+            let bytes = flowscope_export::export(&result, export_format)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            Ok((
+                [(axum::http::header::CONTENT_TYPE, "application/octet-stream")],
+                bytes,
+            )
+                .into_response())
+        }
     }
 }
 
@@ -0,0 +1,191 @@
+//! Cache of serialized analysis results for serve mode.
+//!
+//! Results are keyed by a BLAKE3 hash of the whole `AnalyzeRequest` (SQL, files,
+//! dialect, options, schema, and template configuration), so a request is only
+//! analyzed again when one of its inputs changed. After the watcher reloads the
+//! files, requests covering unchanged files are answered from the cache.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use axum::body::Bytes;
+use flowscope_core::AnalyzeRequest;
+use serde::Serialize;
+
+/// Number of analysis results kept when `ServerConfig.cache_capacity` is not set.
+pub const DEFAULT_CACHE_CAPACITY: usize = 64;
+
+type CacheKey = [u8; 32];
+
+/// Least-recently-used cache of serialized `AnalyzeResult`s.
+///
+/// A capacity of 0 disables caching; every lookup is then a miss.
+pub struct AnalysisCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Default)]
+struct Entries {
+    results: HashMap<CacheKey, Bytes>,
+    /// Keys from least to most recently used
+    order: VecDeque<CacheKey>,
+}
+
+/// Counters reported by `/api/health`.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub capacity: usize,
+}
+
+impl AnalysisCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(Entries::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Return the serialized result of `request`, running `analyze` only when no
+    /// result is cached for the same inputs.
+    pub fn get_or_analyze(
+        &self,
+        request: &AnalyzeRequest,
+        analyze: impl FnOnce(&AnalyzeRequest) -> serde_json::Result<Vec<u8>>,
+    ) -> serde_json::Result<Bytes> {
+        let key = request_digest(request)?;
+
+        if let Some(result) = self.lookup(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(result);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        // Analyze without holding the lock so other requests are not blocked
+        let result = Bytes::from(analyze(request)?);
+        self.insert(key, result.clone());
+        Ok(result)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.lock().results.len(),
+            capacity: self.capacity,
+        }
+    }
+
+    fn lookup(&self, key: &CacheKey) -> Option<Bytes> {
+        let mut entries = self.lock();
+        let result = entries.results.get(key)?.clone();
+        entries.touch(key);
+        Some(result)
+    }
+
+    fn insert(&self, key: CacheKey, result: Bytes) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.lock();
+        if entries.results.insert(key, result).is_some() {
+            entries.touch(&key);
+            return;
+        }
+        entries.order.push_back(key);
+        while entries.order.len() > self.capacity {
+            if let Some(evicted) = entries.order.pop_front() {
+                entries.results.remove(&evicted);
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        // The entries stay consistent even if a holder panicked
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Entries {
+    /// Mark `key` as the most recently used.
+    fn touch(&mut self, key: &CacheKey) {
+        if let Some(position) = self.order.iter().position(|k| k == key) {
+            if let Some(key) = self.order.remove(position) {
+                self.order.push_back(key);
+            }
+        }
+    }
+}
+
+/// BLAKE3 hash of the JSON form of `request`, which covers every input of the analysis.
+///
+/// The template context is a map, but it is cloned from the server configuration for
+/// every request and so always serializes in the same order.
+fn request_digest(request: &AnalyzeRequest) -> serde_json::Result<CacheKey> {
+    let serialized = serde_json::to_vec(request)?;
+    Ok(blake3::hash(&serialized).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowscope_core::Dialect;
+
+    fn request(sql: &str) -> AnalyzeRequest {
+        AnalyzeRequest {
+            sql: sql.to_string(),
+            files: None,
+            dialect: Dialect::Generic,
+            source_name: None,
+            options: None,
+            schema: None,
+            #[cfg(feature = "templating")]
+            template_config: None,
+        }
+    }
+
+    fn run(cache: &AnalysisCache, sql: &str) -> Bytes {
+        cache
+            .get_or_analyze(&request(sql), |request| {
+                Ok(request.sql.clone().into_bytes())
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = AnalysisCache::new(2);
+        run(&cache, "SELECT 1");
+        run(&cache, "SELECT 2");
+        // Using the first result keeps it over the second
+        run(&cache, "SELECT 1");
+        run(&cache, "SELECT 3");
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 3, 2));
+
+        run(&cache, "SELECT 1");
+        run(&cache, "SELECT 2");
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 4));
+    }
+
+    #[test]
+    fn test_zero_capacity_disables_caching() {
+        let cache = AnalysisCache::new(0);
+        assert_eq!(run(&cache, "SELECT 1"), run(&cache, "SELECT 1"));
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (0, 2, 0));
+    }
+}
@@ -5,7 +5,9 @@
 
 pub mod api;
 mod assets;
+pub mod cache;
 pub mod state;
+pub mod stream;
 mod watcher;
 
 use std::net::SocketAddr;
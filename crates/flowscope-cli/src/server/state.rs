@@ -1,7 +1,8 @@
 //! Shared application state for the server.
 //!
 //! This module defines the `AppState` struct that holds the server configuration,
-//! watched files, and schema metadata. State is shared across handlers via `Arc`.
+//! watched files, schema metadata, and cached analysis results. State is shared
+//! across handlers via `Arc`.
 
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -13,6 +14,8 @@ use flowscope_core::TemplateConfig;
 use flowscope_core::{Dialect, FileSource, SchemaMetadata};
 use tokio::sync::RwLock;
 
+use super::cache::AnalysisCache;
+
 /// Server configuration derived from CLI arguments.
 #[derive(Debug, Clone)]
 pub struct ServerConfig {
@@ -30,11 +33,15 @@ pub struct ServerConfig {
     pub port: u16,
     /// Whether to open browser on startup
     pub open_browser: bool,
-    /// Optional schema DDL file path
-    pub schema_path: Option<PathBuf>,
+    /// Schema DDL file paths, merged in order
+    pub schema_paths: Vec<PathBuf>,
+    /// Fail schema loading on unknown column types
+    pub strict_schema: bool,
     /// Default template configuration (from CLI flags)
     #[cfg(feature = "templating")]
     pub template_config: Option<TemplateConfig>,
+    /// Number of analysis results to cache (0 disables the cache)
+    pub cache_capacity: usize,
 }
 
 /// Shared application state.
@@ -47,6 +54,8 @@ pub struct AppState {
     pub schema: RwLock<Option<SchemaMetadata>>,
     /// File modification times for change detection
     pub mtimes: RwLock<HashMap<PathBuf, SystemTime>>,
+    /// Serialized analysis results keyed by a digest of their request
+    pub cache: AnalysisCache,
 }
 
 impl AppState {
@@ -75,6 +84,7 @@ impl AppState {
         }
 
         Ok(Self {
+            cache: AnalysisCache::new(config.cache_capacity),
             config,
             files: RwLock::new(files),
             schema: RwLock::new(schema),
@@ -84,7 +94,7 @@ impl AppState {
 
     /// Load schema metadata from database connection.
     ///
-    /// Uses `spawn_blocking` because `fetch_metadata_from_database` internally creates
+    /// Uses `spawn_blocking` because `fetch_metadata` internally creates
     /// a tokio runtime and blocks. Running blocking code on the async executor would
     /// stall other tasks.
     #[cfg(feature = "metadata-provider")]
@@ -93,7 +103,7 @@ impl AppState {
             let url = url.clone();
             let schema_filter = config.metadata_schema.clone();
             let fetch_result = tokio::task::spawn_blocking(move || {
-                crate::metadata::fetch_metadata_from_database(&url, schema_filter)
+                crate::metadata::fetch_metadata(&url, schema_filter)
             })
             .await
             .context("Metadata fetch task was cancelled")?;
@@ -110,12 +120,18 @@ impl AppState {
     }
 
     fn load_schema_from_file(config: &ServerConfig) -> Result<Option<SchemaMetadata>> {
-        if let Some(ref path) = config.schema_path {
-            let schema = crate::schema::load_schema_from_ddl(path, config.dialect)?;
+        if config.schema_paths.is_empty() {
+            return Ok(None);
+        }
+        let schema = crate::schema::load_schema_from_ddl_files(
+            &config.schema_paths,
+            config.dialect,
+            config.strict_schema,
+        )?;
+        for path in &config.schema_paths {
             println!("flowscope: loaded schema from DDL file: {}", path.display());
-            return Ok(Some(schema));
         }
-        Ok(None)
+        Ok(Some(schema))
     }
 
     /// Reload files from watch directories.
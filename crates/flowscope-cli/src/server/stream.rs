@@ -0,0 +1,62 @@
+//! Headless watch mode: `--watch` without `--serve`.
+//!
+//! Instead of pushing updates to the web UI, the watched directories are analyzed
+//! once at startup and again after every debounced change to a SQL file, and each
+//! analysis is written as one NDJSON line. Every line is a complete `AnalyzeResult`,
+//! so consumers can simply keep the latest one.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use anyhow::{bail, Context, Result};
+use flowscope_core::{analyze, AnalyzeRequestBuilder};
+
+use super::scan_sql_files;
+use super::watcher::{changed_sql_files, watch_sql_dirs};
+
+/// Watches `dirs` and writes a JSON snapshot line to `writer` at startup and on
+/// every SQL file change.
+///
+/// `base` carries the dialect, schema and options of every analysis. Runs until the
+/// watcher stops or writing fails, e.g. because the reading end of a pipe closed.
+pub fn stream_snapshots(
+    dirs: &[PathBuf],
+    base: &AnalyzeRequestBuilder,
+    mut writer: impl Write,
+) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+    // Watching starts before the first scan so no change in between is missed
+    let (_debouncer, watched) = watch_sql_dirs(dirs, tx)?;
+    if watched.is_empty() {
+        bail!("None of the --watch directories exist");
+    }
+
+    let mut mtimes = write_snapshot(dirs, base, &mut writer)?;
+    for result in rx {
+        match result {
+            Ok(events) => {
+                if !changed_sql_files(&events, &mtimes).is_empty() {
+                    mtimes = write_snapshot(dirs, base, &mut writer)?;
+                }
+            }
+            Err(error) => eprintln!("flowscope: watcher error: {error}"),
+        }
+    }
+    Ok(())
+}
+
+/// Analyzes the SQL files in `dirs` and writes the result as one line, returning the
+/// modification times of the files analyzed.
+fn write_snapshot(
+    dirs: &[PathBuf],
+    base: &AnalyzeRequestBuilder,
+    writer: &mut impl Write,
+) -> Result<std::collections::HashMap<PathBuf, std::time::SystemTime>> {
+    let (files, mtimes) = scan_sql_files(dirs).context("Failed to scan SQL files")?;
+    let result = analyze(&base.clone().files(files).build());
+    serde_json::to_writer(&mut *writer, &result).context("Failed to serialize snapshot")?;
+    writeln!(writer).context("Failed to write snapshot")?;
+    writer.flush().context("Failed to write snapshot")?;
+    Ok(mtimes)
+}
@@ -3,19 +3,22 @@
 //! This module watches the configured directories for changes to SQL files
 //! and triggers a reload of the application state when changes are detected.
 
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use anyhow::Result;
-use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode, DebouncedEventKind};
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{
+    new_debouncer, DebounceEventHandler, DebounceEventResult, DebouncedEvent, DebouncedEventKind,
+    Debouncer,
+};
 
 use super::AppState;
 
 /// Check if a file's mtime has actually changed compared to stored value.
-fn has_mtime_changed(
-    path: &std::path::Path,
-    stored_mtimes: &std::collections::HashMap<std::path::PathBuf, std::time::SystemTime>,
-) -> bool {
+fn has_mtime_changed(path: &Path, stored_mtimes: &HashMap<PathBuf, SystemTime>) -> bool {
     match std::fs::metadata(path) {
         Ok(meta) => match meta.modified() {
             Ok(current_mtime) => {
@@ -38,6 +41,49 @@ fn has_mtime_changed(
 /// Increase if seeing duplicate reloads; decrease for faster feedback.
 const DEBOUNCE_DURATION: Duration = Duration::from_millis(100);
 
+/// Create a debounced watcher over the existing directories in `dirs`, passing each
+/// batch of events to `handler`.
+///
+/// Returns the watcher, which stops watching when dropped, and the directories watched.
+pub(super) fn watch_sql_dirs<F: DebounceEventHandler>(
+    dirs: &[PathBuf],
+    handler: F,
+) -> Result<(Debouncer<RecommendedWatcher>, Vec<PathBuf>)> {
+    let mut debouncer = new_debouncer(DEBOUNCE_DURATION, handler)
+        .map_err(|e| anyhow::anyhow!("Failed to create file watcher: {e}"))?;
+
+    let mut watched = Vec::new();
+    for dir in dirs {
+        if dir.exists() {
+            debouncer
+                .watcher()
+                .watch(dir, RecursiveMode::Recursive)
+                .map_err(|e| anyhow::anyhow!("Failed to watch {}: {e}", dir.display()))?;
+            watched.push(dir.clone());
+        }
+    }
+    Ok((debouncer, watched))
+}
+
+/// SQL files among `events` whose modification time differs from `stored_mtimes`.
+pub(super) fn changed_sql_files(
+    events: &[DebouncedEvent],
+    stored_mtimes: &HashMap<PathBuf, SystemTime>,
+) -> Vec<PathBuf> {
+    events
+        .iter()
+        .filter(|event| {
+            event.path.extension().is_some_and(|ext| ext == "sql")
+                && matches!(
+                    event.kind,
+                    DebouncedEventKind::Any | DebouncedEventKind::AnyContinuous
+                )
+        })
+        .filter(|event| has_mtime_changed(&event.path, stored_mtimes))
+        .map(|event| event.path.clone())
+        .collect()
+}
+
 /// Start watching directories for SQL file changes.
 ///
 /// This function runs until the task is cancelled. File changes are debounced
@@ -52,23 +98,14 @@ pub async fn start_watcher(state: Arc<AppState>) -> Result<()> {
 
     let (tx, mut rx) = tokio::sync::mpsc::channel(16);
 
-    // Create debounced watcher
-    let mut debouncer = new_debouncer(DEBOUNCE_DURATION, move |result| {
+    // Create debounced watcher over all configured directories
+    let (_debouncer, watched) = watch_sql_dirs(&watch_dirs, move |result: DebounceEventResult| {
         if let Err(e) = tx.blocking_send(result) {
             eprintln!("flowscope: warning: failed to send file event: {e}");
         }
-    })
-    .map_err(|e| anyhow::anyhow!("Failed to create file watcher: {e}"))?;
-
-    // Watch all configured directories
-    for dir in &watch_dirs {
-        if dir.exists() {
-            debouncer
-                .watcher()
-                .watch(dir, RecursiveMode::Recursive)
-                .map_err(|e| anyhow::anyhow!("Failed to watch {}: {e}", dir.display()))?;
-            println!("flowscope: watching {}", dir.display());
-        }
+    })?;
+    for dir in &watched {
+        println!("flowscope: watching {}", dir.display());
     }
 
     // Process file change events
@@ -79,22 +116,11 @@ pub async fn start_watcher(state: Arc<AppState>) -> Result<()> {
                 let stored_mtimes = state.mtimes.read().await.clone();
 
                 // Filter to SQL files with actual mtime changes
-                let changed_files: Vec<_> = events
-                    .iter()
-                    .filter(|event| {
-                        event.path.extension().is_some_and(|ext| ext == "sql")
-                            && matches!(
-                                event.kind,
-                                DebouncedEventKind::Any | DebouncedEventKind::AnyContinuous
-                            )
-                    })
-                    .filter(|event| has_mtime_changed(&event.path, &stored_mtimes))
-                    .map(|e| e.path.display().to_string())
-                    .collect();
+                let changed_files = changed_sql_files(&events, &stored_mtimes);
 
                 if !changed_files.is_empty() {
                     for file in &changed_files {
-                        println!("flowscope: file changed: {file}");
+                        println!("flowscope: file changed: {}", file.display());
                     }
 
                     if let Err(e) = state.reload_files().await {
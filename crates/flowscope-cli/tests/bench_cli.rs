@@ -0,0 +1,93 @@
+//! CLI integration tests for `--bench`.
+
+use std::process::Command;
+use tempfile::tempdir;
+
+#[test]
+fn bench_reports_numeric_metrics_for_directory() {
+    let dir = tempdir().expect("temp dir");
+    std::fs::write(
+        dir.path().join("staging.sql"),
+        "CREATE TABLE staging AS SELECT id, amount FROM raw;",
+    )
+    .expect("write sql");
+    std::fs::write(
+        dir.path().join("mart.sql"),
+        "CREATE TABLE mart AS SELECT id, SUM(amount) AS total FROM staging GROUP BY id;",
+    )
+    .expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(["--bench", "--bench-iterations", "3", "-f", "json"])
+        .arg(dir.path())
+        .output()
+        .expect("run CLI");
+
+    assert!(
+        output.status.success(),
+        "bench should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("bench report JSON");
+    assert_eq!(report["iterations"], 3);
+    assert_eq!(report["files"], 2);
+    assert_eq!(report["statements"], 2);
+    for metric in ["p50Ms", "p95Ms", "statementsPerSec"] {
+        let value = report[metric].as_f64().expect("numeric metric");
+        assert!(value >= 0.0, "{metric} = {value}");
+    }
+    assert!(report["p95Ms"].as_f64() >= report["p50Ms"].as_f64());
+}
+
+#[test]
+fn bench_fails_on_parse_errors_like_a_regular_run() {
+    let dir = tempdir().expect("temp dir");
+    let broken = dir.path().join("broken.sql");
+    std::fs::write(&broken, "SELECT id FROM users;\nSELEC broken;\n").expect("write sql");
+
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_flowscope"))
+            .args(args)
+            .arg(&broken)
+            .output()
+            .expect("run CLI")
+    };
+    let regular = run(&[]);
+    let bench = run(&["--bench", "--bench-iterations", "1"]);
+
+    assert_eq!(regular.status.code(), Some(1));
+    assert_eq!(bench.status.code(), regular.status.code());
+    let parse_error = |stderr: &[u8]| {
+        String::from_utf8_lossy(stderr)
+            .lines()
+            .find(|line| line.contains("Parse error"))
+            .map(str::to_string)
+    };
+    let expected = parse_error(&regular.stderr).expect("regular run reports the parse error");
+    assert_eq!(parse_error(&bench.stderr), Some(expected));
+}
+
+#[test]
+fn bench_json_lists_the_parse_error_it_fails_on() {
+    let dir = tempdir().expect("temp dir");
+    let broken = dir.path().join("broken.sql");
+    std::fs::write(&broken, "SELECT id FROM users;\nSELEC broken;\n").expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(["--bench", "--bench-iterations", "1", "-f", "json"])
+        .arg(&broken)
+        .output()
+        .expect("run CLI");
+
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("bench report JSON");
+    let issues = report["issues"].as_array().expect("issues in the report");
+    assert!(
+        issues
+            .iter()
+            .any(|issue| issue["code"] == "PARSE_ERROR" && issue["severity"] == "error"),
+        "{report}"
+    );
+}
@@ -0,0 +1,113 @@
+//! CLI integration tests for `--check-only`.
+
+use std::process::Command;
+use tempfile::tempdir;
+
+fn run(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(args)
+        .output()
+        .expect("run CLI")
+}
+
+#[test]
+fn check_only_validates_without_analyzing() {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("query.sql");
+    let schema_path = dir.path().join("schema.sql");
+    std::fs::write(&sql_path, "SELECT id FROM users").expect("write sql");
+    std::fs::write(&schema_path, "CREATE TABLE users (id INT);").expect("write schema");
+
+    let output = run(&[
+        "--check-only",
+        "-d",
+        "postgres",
+        "-s",
+        schema_path.to_str().unwrap(),
+        "-f",
+        "json",
+        sql_path.to_str().unwrap(),
+    ]);
+
+    assert!(
+        output.status.success(),
+        "check should pass: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json report");
+    assert_eq!(report["dialect"], "postgres");
+    assert_eq!(report["schema"]["tables"], 1);
+    assert_eq!(report["inputs"]["files"].as_array().unwrap().len(), 1);
+    assert_eq!(report["inputs"]["bytes"], 20);
+    assert!(report.get("statements").is_none(), "no analysis expected");
+
+    let output = run(&["--check-only", sql_path.to_str().unwrap()]);
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.starts_with("Configuration OK"), "{text}");
+    assert!(text.contains("schema:        none"), "{text}");
+}
+
+#[test]
+fn check_only_fails_on_missing_input() {
+    let dir = tempdir().expect("temp dir");
+    let missing = dir.path().join("missing.sql");
+
+    let output = run(&["--check-only", missing.to_str().unwrap()]);
+
+    assert_eq!(output.status.code(), Some(66));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("missing.sql"), "{stderr}");
+}
+
+#[test]
+fn check_only_fails_on_unknown_dialect() {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("query.sql");
+    std::fs::write(&sql_path, "SELECT 1").expect("write sql");
+
+    let output = run(&["--check-only", "-d", "oracle9", sql_path.to_str().unwrap()]);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("oracle9"), "{stderr}");
+}
+
+#[test]
+fn check_only_fails_on_invalid_schema() {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("query.sql");
+    let schema_path = dir.path().join("schema.sql");
+    std::fs::write(&sql_path, "SELECT 1").expect("write sql");
+    std::fs::write(&schema_path, "CREATE TABLE users (id VARCHR(10));").expect("write schema");
+
+    let output = run(&[
+        "--check-only",
+        "--strict-schema",
+        "-s",
+        schema_path.to_str().unwrap(),
+        sql_path.to_str().unwrap(),
+    ]);
+
+    assert_eq!(output.status.code(), Some(66));
+}
+
+#[cfg(feature = "templating")]
+#[test]
+fn check_only_rejects_malformed_template_vars() {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("query.sql");
+    std::fs::write(&sql_path, "SELECT 1").expect("write sql");
+
+    let output = run(&[
+        "--check-only",
+        "--template",
+        "dbt",
+        "--template-var",
+        "schema",
+        sql_path.to_str().unwrap(),
+    ]);
+
+    assert_eq!(output.status.code(), Some(66));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("KEY=VALUE"), "{stderr}");
+}
@@ -0,0 +1,39 @@
+//! CLI integration tests for dialect selection.
+
+use std::process::Command;
+use tempfile::tempdir;
+
+fn run_with_args(args: &[&str]) -> std::process::Output {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("query.sql");
+    std::fs::write(&sql_path, "SELECT id FROM users").expect("write sql");
+
+    Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(args)
+        .arg(sql_path.to_str().expect("sql path"))
+        .output()
+        .expect("run CLI")
+}
+
+#[test]
+fn require_dialect_rejects_generic_dialect() {
+    let output = run_with_args(&["--require-dialect"]);
+
+    assert_eq!(output.status.code(), Some(66));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("--require-dialect"),
+        "error should name the flag: {stderr}"
+    );
+}
+
+#[test]
+fn require_dialect_accepts_explicit_dialect() {
+    let output = run_with_args(&["--require-dialect", "-d", "postgres"]);
+
+    assert!(
+        output.status.success(),
+        "postgres run should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,53 @@
+//! CLI integration tests for `--fields`.
+
+use std::process::Command;
+use tempfile::tempdir;
+
+fn run_json(extra: &[&str]) -> (serde_json::Value, String) {
+    let dir = tempdir().expect("temp dir");
+    let path = dir.path().join("query.sql");
+    std::fs::write(
+        &path,
+        "SELECT u.id FROM users u JOIN orders o ON o.user_id = u.id",
+    )
+    .expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(["-d", "postgres", "-f", "json"])
+        .args(extra)
+        .arg(&path)
+        .output()
+        .expect("run CLI");
+    assert!(
+        output.status.success(),
+        "analysis should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let value = serde_json::from_slice(&output.stdout).expect("json output");
+    (value, String::from_utf8_lossy(&output.stderr).into_owned())
+}
+
+#[test]
+fn fields_summary_keeps_only_the_summary() {
+    let (full, _) = run_json(&[]);
+    let (projected, stderr) = run_json(&["--fields", "summary"]);
+
+    assert!(stderr.is_empty(), "{stderr}");
+    let object = projected.as_object().expect("object output");
+    assert_eq!(object.keys().collect::<Vec<_>>(), vec!["summary"]);
+    assert_eq!(projected["summary"], full["summary"]);
+}
+
+#[test]
+fn fields_project_through_arrays_and_warn_about_unknown_paths() {
+    let (projected, stderr) = run_json(&["--fields", "statements.statementType,summary.bogus"]);
+
+    assert_eq!(
+        projected,
+        serde_json::json!({"statements": [{"statementType": "SELECT"}]})
+    );
+    assert!(
+        stderr.contains("--fields path 'summary.bogus' matched nothing"),
+        "{stderr}"
+    );
+}
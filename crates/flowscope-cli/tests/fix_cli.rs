@@ -0,0 +1,272 @@
+//! CLI integration tests for `--fix`.
+
+use rusqlite::Connection;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+const LATEST_EVENT: &str = "-- latest event per user\n\
+    SELECT user_id, kind FROM events\n\
+    QUALIFY ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY ts DESC) = 1\n\
+    ORDER BY user_id;\n";
+
+#[test]
+fn fix_rewrites_qualify_into_sql_sqlite_runs() {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("latest.sql");
+    std::fs::write(&sql_path, LATEST_EVENT).expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(["-d", "sqlite", "--fix"])
+        .arg(&sql_path)
+        .output()
+        .expect("run CLI");
+    assert!(
+        output.status.success(),
+        "fix should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("fixed 1 issue(s)"));
+
+    let fixed = std::fs::read_to_string(&sql_path).expect("read fixed sql");
+    assert!(fixed.starts_with("-- latest event per user\n"));
+    assert!(!fixed.contains("QUALIFY"), "{fixed}");
+
+    // The rewrite keeps the newest row per user
+    let conn = Connection::open_in_memory().expect("open sqlite");
+    conn.execute_batch(
+        "CREATE TABLE events (user_id INTEGER, kind TEXT, ts INTEGER);
+         INSERT INTO events VALUES (1, 'login', 1), (1, 'logout', 3), (2, 'login', 2), (1, 'view', 2);",
+    )
+    .expect("seed events");
+    let mut statement = conn
+        .prepare(fixed.trim())
+        .expect("fixed SQL runs in SQLite");
+    let rows: Vec<(i64, String)> = statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .expect("query")
+        .collect::<Result<_, _>>()
+        .expect("rows");
+    assert_eq!(
+        rows,
+        vec![(1, "logout".to_string()), (2, "login".to_string())]
+    );
+}
+
+#[test]
+fn fix_prints_fixed_stdin_and_leaves_supported_dialects_alone() {
+    let run = |dialect: &str| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+            .args(["-d", dialect, "--fix"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("run CLI");
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(LATEST_EVENT.as_bytes())
+            .unwrap();
+        let output = child.wait_with_output().expect("wait for CLI");
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert!(run("postgres").contains("AS qualified_rows WHERE _qualify_1 = 1"));
+    assert_eq!(run("snowflake"), LATEST_EVENT);
+}
+
+#[test]
+fn fix_adds_missing_trailing_newline_once() {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("latest.sql");
+    let without_newline = LATEST_EVENT.trim_end();
+    std::fs::write(&sql_path, without_newline).expect("write sql");
+
+    let run = |extra: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_flowscope"))
+            .args(["-d", "sqlite", "--check-trailing-newline"])
+            .args(extra)
+            .arg(&sql_path)
+            .output()
+            .expect("run CLI")
+    };
+
+    // The newline is added alongside the QUALIFY rewrite
+    let output = run(&["--fix"]);
+    assert!(
+        output.status.success(),
+        "fix should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("fixed 2 issue(s)"));
+    let fixed = std::fs::read_to_string(&sql_path).expect("read fixed sql");
+    assert!(fixed.ends_with(";\n"), "{fixed:?}");
+    assert!(!fixed.ends_with("\n\n"), "{fixed:?}");
+
+    let output = run(&["-f", "json"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert!(
+        json["issues"]
+            .as_array()
+            .expect("issues")
+            .iter()
+            .all(|issue| issue["code"] != "MISSING_TRAILING_NEWLINE"),
+        "the fixed file should pass: {}",
+        json["issues"]
+    );
+
+    // Fixing again leaves the file as it is
+    run(&["--fix"]);
+    assert_eq!(std::fs::read_to_string(&sql_path).expect("read sql"), fixed);
+}
+
+#[test]
+fn fix_keeps_crlf_line_endings_when_adding_trailing_newline() {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("latest.sql");
+    let crlf = LATEST_EVENT.trim_end().replace('\n', "\r\n");
+    std::fs::write(&sql_path, &crlf).expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(["-d", "snowflake", "--check-trailing-newline", "--fix"])
+        .arg(&sql_path)
+        .output()
+        .expect("run CLI");
+    assert!(
+        output.status.success(),
+        "fix should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let fixed = std::fs::read_to_string(&sql_path).expect("read fixed sql");
+    assert_eq!(fixed, format!("{crlf}\r\n"));
+    assert_eq!(fixed.matches('\n').count(), fixed.matches("\r\n").count());
+}
+
+#[test]
+fn fix_json_reports_each_file() {
+    let dir = tempdir().expect("temp dir");
+    let latest = dir.path().join("latest.sql");
+    let keyword = dir.path().join("keyword.sql");
+    let clean = dir.path().join("clean.sql");
+    std::fs::write(&latest, LATEST_EVENT).expect("write sql");
+    std::fs::write(
+        &keyword,
+        "SELECT user_id AS order, ts FROM events\n\
+         QUALIFY ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY ts DESC) = 1;\n",
+    )
+    .expect("write sql");
+    std::fs::write(&clean, "SELECT user_id FROM events;\n").expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(["-d", "postgres", "--check-reserved-keywords", "--fix"])
+        .args(["-f", "json", "--compact"])
+        .args([&latest, &keyword, &clean])
+        .output()
+        .expect("run CLI");
+    assert!(
+        output.status.success(),
+        "fix should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("fixed"));
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let records = report.as_array().expect("array");
+    assert_eq!(records.len(), 3);
+    let record = |path: &std::path::Path| {
+        records
+            .iter()
+            .find(|record| record["file"] == path.display().to_string())
+            .unwrap_or_else(|| panic!("no record for {}: {report}", path.display()))
+    };
+    assert_eq!(record(&latest)["changed"], true);
+    assert_eq!(record(&latest)["applied"], 1);
+    assert!(record(&latest)["skippedReason"].is_null());
+    // The alias fix overlaps the QUALIFY rewrite, which quotes the alias itself
+    assert_eq!(record(&keyword)["changed"], true);
+    assert_eq!(record(&keyword)["applied"], 1);
+    assert_eq!(
+        record(&keyword)["skippedReason"],
+        "1 overlapping fix(es) skipped"
+    );
+    assert_eq!(record(&clean)["changed"], false);
+    assert_eq!(record(&clean)["applied"], 0);
+    assert_ne!(
+        std::fs::read_to_string(&latest).expect("read sql"),
+        LATEST_EVENT
+    );
+}
+
+#[test]
+fn fix_keeps_crlf_line_endings_through_qualify_rewrite() {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("latest.sql");
+    let crlf = LATEST_EVENT.trim_end().replace('\n', "\r\n");
+    std::fs::write(&sql_path, &crlf).expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(["-d", "postgres", "--check-trailing-newline", "--fix"])
+        .arg(&sql_path)
+        .output()
+        .expect("run CLI");
+    assert!(
+        output.status.success(),
+        "fix should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let fixed = std::fs::read_to_string(&sql_path).expect("read fixed sql");
+    assert!(
+        fixed.contains("AS qualified_rows WHERE _qualify_1 = 1"),
+        "{fixed:?}"
+    );
+    assert!(
+        fixed.starts_with("-- latest event per user\r\n"),
+        "{fixed:?}"
+    );
+    assert!(fixed.ends_with(";\r\n"), "{fixed:?}");
+    assert_eq!(fixed.matches('\n').count(), fixed.matches("\r\n").count());
+}
+
+#[test]
+fn fix_leaves_commented_qualify_statement_unchanged() {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("latest.sql");
+    let sql = "SELECT user_id, -- the key\n\
+               ts /* keep me */ FROM events\n\
+               QUALIFY ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY ts DESC) = 1;\n";
+    std::fs::write(&sql_path, sql).expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(["-d", "postgres", "--fix"])
+        .arg(&sql_path)
+        .output()
+        .expect("run CLI");
+    assert!(
+        output.status.success(),
+        "fix should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(std::fs::read_to_string(&sql_path).expect("read sql"), sql);
+}
+
+#[test]
+fn fix_reports_why_a_broken_file_fails() {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("broken.sql");
+    std::fs::write(&sql_path, "SELECT id FROM users;\nSELEC broken;\n").expect("write sql");
+
+    for format in ["table", "json"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+            .args(["--fix", "-f", format])
+            .arg(&sql_path)
+            .output()
+            .expect("run CLI");
+        assert_eq!(output.status.code(), Some(1));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("Parse error"), "{format}: {stderr}");
+    }
+}
@@ -0,0 +1,59 @@
+//! CLI integration tests for glob patterns in the positional file arguments.
+
+use serde_json::Value;
+use std::process::Command;
+use tempfile::tempdir;
+
+#[test]
+fn glob_pattern_is_expanded_with_relative_source_names() {
+    let dir = tempdir().expect("temp dir");
+    let models = dir.path().join("models");
+    std::fs::create_dir_all(models.join("staging")).expect("create dirs");
+    std::fs::write(models.join("orders.sql"), "SELECT id FROM raw_orders").expect("write sql");
+    std::fs::write(
+        models.join("staging").join("users.sql"),
+        "SELECT id FROM raw_users",
+    )
+    .expect("write sql");
+    std::fs::write(models.join("README.md"), "# models").expect("write md");
+
+    // Quoted, so the pattern reaches the CLI unexpanded
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .current_dir(dir.path())
+        .args(["-f", "json", "models/**/*", "models/orders.sql"])
+        .output()
+        .expect("run CLI");
+    assert!(
+        output.status.success(),
+        "glob run should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("skipping non-SQL file matched by a glob: models/README.md"),
+        "{stderr}"
+    );
+
+    let result: Value = serde_json::from_slice(&output.stdout).expect("json output");
+    let sources: Vec<&str> = result["statements"]
+        .as_array()
+        .expect("statements")
+        .iter()
+        .map(|statement| statement["sourceName"].as_str().unwrap())
+        .collect();
+    assert_eq!(sources, ["models/orders.sql", "models/staging/users.sql"]);
+}
+
+#[test]
+fn glob_pattern_without_matches_fails() {
+    let dir = tempdir().expect("temp dir");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .current_dir(dir.path())
+        .arg("models/*.sql")
+        .output()
+        .expect("run CLI");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Glob pattern matched no .sql files: models/*.sql"));
+}
@@ -0,0 +1,84 @@
+//! CLI integration tests for `--granularity`.
+
+use std::process::Command;
+use tempfile::tempdir;
+
+const SQL: &str = "CREATE TABLE staging AS SELECT id, amount FROM raw;
+CREATE TABLE mart AS SELECT s.id, SUM(s.amount) AS total
+FROM staging s JOIN customers c ON c.id = s.id GROUP BY s.id;";
+
+fn run_flowscope(args: &[&str]) -> Vec<u8> {
+    let dir = tempdir().expect("temp dir");
+    let path = dir.path().join("models.sql");
+    std::fs::write(&path, SQL).expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(args)
+        .arg(&path)
+        .output()
+        .expect("run CLI");
+    assert!(
+        output.status.success(),
+        "analysis should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output.stdout
+}
+
+/// Node types and `(from, to)` labels of edges between non-column nodes.
+fn lineage(args: &[&str]) -> (Vec<String>, Vec<(String, String)>) {
+    let value: serde_json::Value =
+        serde_json::from_slice(&run_flowscope(args)).expect("json output");
+    let mut node_types = Vec::new();
+    let mut table_edges = Vec::new();
+    for statement in value["statements"].as_array().expect("statements") {
+        let nodes = statement["nodes"].as_array().expect("nodes");
+        node_types.extend(nodes.iter().map(|node| node["type"].to_string()));
+        let label = |id: &serde_json::Value| {
+            nodes
+                .iter()
+                .find(|node| node["id"] == *id && node["type"] != "column")
+                .map(|node| node["label"].as_str().unwrap_or_default().to_string())
+        };
+        for edge in statement["edges"].as_array().expect("edges") {
+            if let (Some(from), Some(to)) = (label(&edge["from"]), label(&edge["to"])) {
+                table_edges.push((from, to));
+            }
+        }
+    }
+    table_edges.sort();
+    (node_types, table_edges)
+}
+
+#[test]
+fn table_granularity_drops_columns_and_keeps_table_edges() {
+    let (column_nodes, column_edges) = lineage(&["-f", "json"]);
+    let (table_nodes, table_edges) = lineage(&["-f", "json", "--granularity", "table"]);
+
+    assert!(column_nodes
+        .iter()
+        .any(|node_type| node_type == "\"column\""));
+    assert!(
+        table_nodes
+            .iter()
+            .all(|node_type| node_type != "\"column\""),
+        "{table_nodes:?}"
+    );
+    assert!(table_edges.contains(&("raw".to_string(), "staging".to_string())));
+    assert_eq!(table_edges, column_edges);
+}
+
+#[test]
+fn bench_accepts_table_granularity() {
+    let stdout = run_flowscope(&[
+        "--bench",
+        "--bench-iterations",
+        "2",
+        "--granularity",
+        "table",
+        "-f",
+        "json",
+    ]);
+    let report: serde_json::Value = serde_json::from_slice(&stdout).expect("bench report JSON");
+    assert_eq!(report["statements"], 2);
+}
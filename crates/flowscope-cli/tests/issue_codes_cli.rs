@@ -0,0 +1,38 @@
+//! CLI integration tests for --list-issue-codes.
+
+use std::process::Command;
+
+fn run_with_args(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(args)
+        .output()
+        .expect("run CLI")
+}
+
+#[test]
+fn list_issue_codes_prints_table_without_input() {
+    let output = run_with_args(&["--list-issue-codes"]);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("CODE "), "header expected: {stdout}");
+    assert!(
+        stdout
+            .lines()
+            .any(|line| line.starts_with("IMPLICIT_TABLE_ALIAS ")
+                && line.contains("checkImplicitAliases")),
+        "IMPLICIT_TABLE_ALIAS row expected: {stdout}"
+    );
+}
+
+#[test]
+fn list_issue_codes_honors_json_format() {
+    let output = run_with_args(&["--list-issue-codes", "-f", "json", "--compact"]);
+
+    assert!(output.status.success());
+    let catalog: serde_json::Value = serde_json::from_slice(&output.stdout).expect("catalog JSON");
+    let entries = catalog.as_array().expect("array");
+    assert_eq!(entries.len(), flowscope_core::issue_catalog().len());
+    assert_eq!(entries[0]["code"], "PARSE_ERROR");
+    assert_eq!(entries[0]["defaultSeverity"], "error");
+}
@@ -200,3 +200,79 @@ fn test_metadata_schema_filter() {
         "CLI should succeed with metadata-schema filter"
     );
 }
+
+/// Test that a schema served over HTTP is used for wildcard expansion.
+///
+/// A one-shot mock server stands in for the metadata service.
+#[cfg(feature = "http-metadata")]
+#[test]
+fn test_http_metadata_provider_expands_wildcards() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let url = format!(
+        "http://{}/schema?name=warehouse",
+        listener.local_addr().unwrap()
+    );
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("accept");
+        let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).expect("read request");
+            if line.trim().is_empty() {
+                break;
+            }
+            headers.push(line.trim().to_string());
+        }
+        let body = r#"{"tables":[{"name":"users","columns":[{"name":"id","dataType":"integer"},{"name":"email","dataType":"text"}]}]}"#;
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .expect("write response");
+        headers
+    });
+
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("query.sql");
+    std::fs::write(&sql_path, "SELECT * FROM users").expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .env("FLOWSCOPE_METADATA_TOKEN", "test-token")
+        .args([
+            "--metadata-url",
+            &url,
+            "-f",
+            "json",
+            sql_path.to_str().expect("sql path"),
+        ])
+        .output()
+        .expect("run CLI");
+    assert!(
+        output.status.success(),
+        "CLI should succeed with an HTTP metadata URL: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let headers = server.join().expect("mock server");
+    assert!(headers
+        .iter()
+        .any(|h| h.eq_ignore_ascii_case("authorization: Bearer test-token")));
+
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json output");
+    // Without the fetched columns the wildcard cannot be expanded at all
+    let mut columns: Vec<&str> = result["statements"][0]["nodes"]
+        .as_array()
+        .expect("nodes")
+        .iter()
+        .filter(|node| node["type"] == "column")
+        .filter_map(|node| node["label"].as_str())
+        .collect();
+    columns.sort_unstable();
+    columns.dedup();
+    assert_eq!(columns, ["email", "id"]);
+}
@@ -0,0 +1,147 @@
+//! CLI integration tests for `--per-file-output` and `-f jsonl`.
+
+use std::process::Command;
+use tempfile::tempdir;
+
+const FILE_COUNT: usize = 200;
+
+/// A chain of models where each one reads the previous one.
+fn write_chain(dir: &std::path::Path) {
+    for i in 0..FILE_COUNT {
+        std::fs::write(
+            dir.join(format!("model_{i:03}.sql")),
+            format!(
+                "CREATE TABLE model_{i} AS SELECT id, amount FROM model_{};",
+                i.wrapping_sub(1)
+            ),
+        )
+        .expect("write sql");
+    }
+}
+
+#[test]
+fn per_file_output_streams_one_independent_result_per_file() {
+    let dir = tempdir().expect("temp dir");
+    write_chain(dir.path());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .arg("--per-file-output")
+        .arg(dir.path())
+        .output()
+        .expect("run CLI");
+
+    assert!(
+        output.status.success(),
+        "per-file analysis should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .expect("utf-8 output")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("NDJSON line"))
+        .collect();
+    assert_eq!(lines.len(), FILE_COUNT);
+
+    for (i, line) in lines.iter().enumerate() {
+        let file = line["file"].as_str().expect("file name");
+        assert!(file.ends_with(&format!("model_{i:03}.sql")), "{file}");
+
+        // Each result covers its own file only, without links to the other files
+        let result = &line["result"];
+        assert_eq!(result["statements"].as_array().map(Vec::len), Some(1));
+        let edges = result["globalLineage"]["edges"].as_array();
+        assert!(
+            edges.is_none_or(|edges| edges.iter().all(|e| e["type"] != "cross_statement")),
+            "{file} links to other files"
+        );
+    }
+}
+
+#[test]
+fn per_file_dir_writes_one_json_file_per_input() {
+    let dir = tempdir().expect("temp dir");
+    let models = dir.path().join("models");
+    std::fs::create_dir(&models).expect("models dir");
+    write_chain(&models);
+    let out = dir.path().join("out");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(["--per-file-output", "--compact", "--per-file-dir"])
+        .arg(&out)
+        .arg(&models)
+        .output()
+        .expect("run CLI");
+
+    assert!(
+        output.status.success(),
+        "per-file analysis should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        std::fs::read_dir(&out).expect("out dir").count(),
+        FILE_COUNT
+    );
+
+    let result: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(out.join("model_007.json")).expect("result file"))
+            .expect("result JSON");
+    assert_eq!(result["statements"].as_array().map(Vec::len), Some(1));
+}
+
+#[test]
+fn jsonl_format_writes_one_record_per_file_without_global_lineage() {
+    let dir = tempdir().expect("temp dir");
+    write_chain(dir.path());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(["-f", "jsonl"])
+        .arg(dir.path())
+        .output()
+        .expect("run CLI");
+
+    assert!(
+        output.status.success(),
+        "jsonl analysis should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .expect("utf-8 output")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("JSON Lines record"))
+        .collect();
+    assert_eq!(lines.len(), FILE_COUNT);
+
+    for (i, line) in lines.iter().enumerate() {
+        let source = line["sourceName"].as_str().expect("source name");
+        assert!(source.ends_with(&format!("model_{i:03}.sql")), "{source}");
+        assert_eq!(line["statements"].as_array().map(Vec::len), Some(1));
+        assert!(line["issues"].is_array());
+        assert!(line.get("globalLineage").is_none());
+    }
+}
+
+#[test]
+fn jsonl_format_writes_records_for_sampled_files_only() {
+    let dir = tempdir().expect("temp dir");
+    write_chain(dir.path());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args(["--sample", "2", "--seed", "1", "-f", "jsonl"])
+        .arg(dir.path())
+        .output()
+        .expect("run CLI");
+
+    assert!(
+        output.status.success(),
+        "sampled jsonl analysis should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("sampled 2 of {FILE_COUNT} files")),
+        "{stderr}"
+    );
+    let stdout = String::from_utf8(output.stdout).expect("utf-8 output");
+    assert_eq!(stdout.lines().count(), 2, "{stdout}");
+}
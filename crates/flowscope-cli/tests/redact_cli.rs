@@ -0,0 +1,37 @@
+//! CLI integration tests for `--redact-literals`.
+
+use std::process::Command;
+use tempfile::tempdir;
+
+const SQL: &str = "SELECT id FROM users WHERE email = 'alice@example.com' AND id > 100000";
+
+fn run_json(redact: bool) -> String {
+    let dir = tempdir().expect("temp dir");
+    let path = dir.path().join("query.sql");
+    std::fs::write(&path, SQL).expect("write sql");
+
+    let mut command = Command::new(env!("CARGO_BIN_EXE_flowscope"));
+    command.args(["-d", "postgres", "-f", "json"]);
+    if redact {
+        command.arg("--redact-literals");
+    }
+    let output = command.arg(&path).output().expect("run CLI");
+    assert!(
+        output.status.success(),
+        "analysis should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("utf-8 output")
+}
+
+#[test]
+fn redact_literals_replaces_values_in_echoed_filters() {
+    let plain = run_json(false);
+    assert!(plain.contains("alice@example.com"), "{plain}");
+
+    let redacted = run_json(true);
+    assert!(!redacted.contains("alice@example.com"), "{redacted}");
+    assert!(!redacted.contains("100000"), "{redacted}");
+    assert!(redacted.contains("email = '<str>'"), "{redacted}");
+    assert!(redacted.contains("id > <num>"), "{redacted}");
+}
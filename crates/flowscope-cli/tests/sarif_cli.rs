@@ -0,0 +1,82 @@
+//! CLI integration tests for SARIF output.
+
+use serde_json::Value;
+use std::process::Command;
+use tempfile::tempdir;
+
+/// Checks the parts of the SARIF 2.1.0 schema the output uses: required properties,
+/// their types, the `level` enum, and positive region coordinates.
+fn assert_valid_sarif(log: &Value) {
+    assert_eq!(log["version"], "2.1.0");
+    assert!(log["$schema"].as_str().unwrap().contains("sarif-2.1.0"));
+    let runs = log["runs"].as_array().expect("runs array");
+    for run in runs {
+        let driver = &run["tool"]["driver"];
+        assert!(driver["name"].is_string(), "tool.driver.name is required");
+        let rules = driver["rules"].as_array().expect("rules array");
+        for rule in rules {
+            assert!(rule["id"].is_string(), "rule id is required");
+        }
+        for result in run["results"].as_array().expect("results array") {
+            assert!(
+                result["message"]["text"].is_string(),
+                "message.text is required"
+            );
+            let rule_index = result["ruleIndex"].as_u64().expect("ruleIndex") as usize;
+            assert_eq!(rules[rule_index]["id"], result["ruleId"]);
+            assert!(["none", "note", "warning", "error"]
+                .contains(&result["level"].as_str().expect("level")));
+            for location in result["locations"].as_array().into_iter().flatten() {
+                let physical = &location["physicalLocation"];
+                assert!(physical["artifactLocation"]["uri"].is_string());
+                if let Some(region) = physical.get("region") {
+                    for key in ["startLine", "startColumn", "endLine", "endColumn"] {
+                        assert!(region[key].as_u64().expect(key) >= 1, "{key} must be >= 1");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn sarif_output_reports_issues_with_regions() {
+    let dir = tempdir().expect("temp dir");
+    std::fs::write(
+        dir.path().join("orders.sql"),
+        "SELECT id FROM raw_orders;\nSELECT status\nFROM a JOIN b ON a.id = b.id\nLIMIT 5;\n",
+    )
+    .expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .current_dir(dir.path())
+        .args(["-f", "sarif", "--check-unordered-limits", "orders.sql"])
+        .output()
+        .expect("run CLI");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let log: Value = serde_json::from_slice(&output.stdout).expect("json output");
+    assert_valid_sarif(&log);
+
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "flowscope");
+    let results = run["results"].as_array().unwrap();
+    let limit = results
+        .iter()
+        .find(|result| result["ruleId"] == "UNORDERED_ROW_LIMIT")
+        .expect("unordered limit result");
+    assert_eq!(limit["level"], "warning");
+    let ambiguous = results
+        .iter()
+        .find(|result| result["ruleId"] == "UNRESOLVED_REFERENCE")
+        .expect("ambiguous column result");
+    let location = &ambiguous["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "orders.sql");
+    assert_eq!(location["region"]["startLine"], 2);
+    assert_eq!(location["region"]["startColumn"], 8);
+    assert_eq!(location["region"]["endColumn"], 14);
+}
@@ -0,0 +1,303 @@
+//! CLI integration tests for schema DDL and schema JSON loading.
+
+use std::process::Command;
+use tempfile::tempdir;
+
+const BOGUS_TYPE_DDL: &str = "CREATE TABLE users (id INT, status STAUTS_ENUM);";
+
+#[test]
+fn strict_schema_rejects_unknown_column_type() {
+    let dir = tempdir().expect("temp dir");
+    let schema_path = dir.path().join("schema.sql");
+    let sql_path = dir.path().join("query.sql");
+    std::fs::write(&schema_path, BOGUS_TYPE_DDL).expect("write schema");
+    std::fs::write(&sql_path, "SELECT id FROM users").expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args([
+            "-s",
+            schema_path.to_str().expect("schema path"),
+            "--strict-schema",
+            sql_path.to_str().expect("sql path"),
+        ])
+        .output()
+        .expect("run CLI");
+
+    assert!(!output.status.success(), "strict mode should fail");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("users.status: STAUTS_ENUM"),
+        "error should list the offending column: {stderr}"
+    );
+}
+
+#[test]
+fn lenient_schema_warns_on_unknown_column_type() {
+    let dir = tempdir().expect("temp dir");
+    let schema_path = dir.path().join("schema.sql");
+    let sql_path = dir.path().join("query.sql");
+    std::fs::write(&schema_path, BOGUS_TYPE_DDL).expect("write schema");
+    std::fs::write(&sql_path, "SELECT id FROM users").expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args([
+            "-s",
+            schema_path.to_str().expect("schema path"),
+            "-f",
+            "json",
+            sql_path.to_str().expect("sql path"),
+        ])
+        .output()
+        .expect("run CLI");
+
+    assert!(output.status.success(), "lenient mode should succeed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("warning: unknown column type in schema: users.status: STAUTS_ENUM"),
+        "should warn about the offending column: {stderr}"
+    );
+}
+
+#[test]
+fn multiple_schema_files_are_merged_for_star_expansion() {
+    let dir = tempdir().expect("temp dir");
+    let users_path = dir.path().join("users.sql");
+    let orders_path = dir.path().join("orders.sql");
+    let sql_path = dir.path().join("query.sql");
+    std::fs::write(&users_path, "CREATE TABLE users (id INT, email VARCHAR);")
+        .expect("write users schema");
+    std::fs::write(
+        &orders_path,
+        "CREATE TABLE orders (order_id INT, total DECIMAL);",
+    )
+    .expect("write orders schema");
+    std::fs::write(&sql_path, "SELECT * FROM users CROSS JOIN orders").expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .args([
+            "-s",
+            users_path.to_str().expect("users path"),
+            "-s",
+            orders_path.to_str().expect("orders path"),
+            "-f",
+            "json",
+            sql_path.to_str().expect("sql path"),
+        ])
+        .output()
+        .expect("run CLI");
+
+    assert!(output.status.success(), "CLI should succeed");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let columns: Vec<&str> = json["statements"]
+        .as_array()
+        .expect("statements")
+        .iter()
+        .flat_map(|statement| statement["nodes"].as_array().expect("nodes"))
+        .filter(|node| node["type"] == "column")
+        .filter_map(|node| node["label"].as_str())
+        .collect();
+
+    for expected in ["id", "email", "order_id", "total"] {
+        assert!(
+            columns.contains(&expected),
+            "expected column '{expected}' from merged schema, got: {columns:?}"
+        );
+    }
+}
+
+#[test]
+fn schema_directory_applies_migrations_in_order() {
+    let dir = tempdir().expect("temp dir");
+    let migrations = dir.path().join("migrations");
+    std::fs::create_dir(&migrations).expect("create migrations dir");
+    std::fs::write(
+        migrations.join("001_init.sql"),
+        "CREATE TABLE users (id INT);",
+    )
+    .expect("write first migration");
+    std::fs::write(
+        migrations.join("002_add_email.sql"),
+        "CREATE TABLE users (id INT, email VARCHAR);",
+    )
+    .expect("write second migration");
+    let sql_path = dir.path().join("query.sql");
+    std::fs::write(&sql_path, "SELECT * FROM users").expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .arg("--schema")
+        .arg(&migrations)
+        .args(["-f", "json"])
+        .arg(&sql_path)
+        .output()
+        .expect("run CLI");
+
+    assert!(output.status.success(), "CLI should succeed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("table 'users' is redefined with different columns in")
+            && stderr.contains("002_add_email.sql"),
+        "should warn about the redefinition: {stderr}"
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let columns: Vec<&str> = json["statements"][0]["nodes"]
+        .as_array()
+        .expect("nodes")
+        .iter()
+        .filter(|node| node["type"] == "column")
+        .filter_map(|node| node["label"].as_str())
+        .collect();
+    assert!(
+        columns.contains(&"email"),
+        "the later definition should be used: {columns:?}"
+    );
+}
+
+#[test]
+fn schema_json_drives_star_expansion() {
+    let dir = tempdir().expect("temp dir");
+    let schema_path = dir.path().join("schema.json");
+    std::fs::write(
+        &schema_path,
+        r#"{"tables": [{"name": "users", "columns": [
+            {"name": "id", "dataType": "integer"},
+            {"name": "email", "dataType": "varchar"}
+        ]}]}"#,
+    )
+    .expect("write schema JSON");
+    let sql_path = dir.path().join("query.sql");
+    std::fs::write(&sql_path, "SELECT * FROM users").expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .arg("--schema-json")
+        .arg(&schema_path)
+        .args(["-f", "json"])
+        .arg(&sql_path)
+        .output()
+        .expect("run CLI");
+
+    assert!(
+        output.status.success(),
+        "CLI should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let columns: Vec<&str> = json["statements"][0]["nodes"]
+        .as_array()
+        .expect("nodes")
+        .iter()
+        .filter(|node| node["type"] == "column")
+        .filter_map(|node| node["label"].as_str())
+        .collect();
+    assert!(
+        columns.contains(&"id") && columns.contains(&"email"),
+        "expected columns from the schema JSON, got: {columns:?}"
+    );
+}
+
+#[test]
+fn malformed_schema_json_is_rejected() {
+    let dir = tempdir().expect("temp dir");
+    let schema_path = dir.path().join("schema.json");
+    std::fs::write(&schema_path, r#"{"tables": {"users": ["id"]}}"#).expect("write schema JSON");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .arg("--schema-json")
+        .arg(&schema_path)
+        .arg(dir.path())
+        .output()
+        .expect("run CLI");
+
+    assert_eq!(output.status.code(), Some(66));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Invalid schema JSON file") && stderr.contains("expected an object like"),
+        "should explain the expected shape: {stderr}"
+    );
+}
+
+/// Runs `flowscope -s schema.sql <extra> query.sql`, returning success and stderr.
+fn run_against_schema(sql: &str, extra: &[&str]) -> (bool, String) {
+    let dir = tempdir().expect("temp dir");
+    let schema_path = dir.path().join("schema.sql");
+    let sql_path = dir.path().join("query.sql");
+    std::fs::write(&schema_path, "CREATE TABLE users (id INT, name TEXT);").expect("write schema");
+    std::fs::write(&sql_path, sql).expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .arg("-s")
+        .arg(&schema_path)
+        .args(extra)
+        .arg(&sql_path)
+        .output()
+        .expect("run CLI");
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    )
+}
+
+#[test]
+fn fail_on_unresolved_rejects_unknown_column() {
+    let sql = "SELECT id, nmae FROM users";
+
+    let (success, stderr) = run_against_schema(sql, &[]);
+    assert!(success, "unknown columns are warnings by default: {stderr}");
+
+    let (success, stderr) = run_against_schema(sql, &["--fail-on-unresolved"]);
+    assert!(!success, "unknown column should fail the run");
+    assert!(
+        stderr.contains("error: Column 'nmae' not found"),
+        "{stderr}"
+    );
+
+    let (success, stderr) =
+        run_against_schema("SELECT id, name FROM users", &["--fail-on-unresolved"]);
+    assert!(success, "known references pass: {stderr}");
+}
+
+#[test]
+fn fail_on_unresolved_requires_schema() {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("query.sql");
+    std::fs::write(&sql_path, "SELECT id FROM users").expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .arg("--fail-on-unresolved")
+        .arg(&sql_path)
+        .output()
+        .expect("run CLI");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("needs a schema"));
+}
+
+#[test]
+fn schema_diff_lists_columns_downstream_of_a_dropped_column() {
+    let dir = tempdir().expect("temp dir");
+    let old_path = dir.path().join("old.sql");
+    let new_path = dir.path().join("new.sql");
+    let sql_path = dir.path().join("models.sql");
+    std::fs::write(&old_path, "CREATE TABLE orders (id INT, amount DECIMAL);").expect("write old");
+    std::fs::write(&new_path, "CREATE TABLE orders (id INT);").expect("write new");
+    std::fs::write(
+        &sql_path,
+        "CREATE TABLE staging AS SELECT id, amount * 2 AS doubled FROM orders;
+         CREATE TABLE mart AS SELECT SUM(doubled) AS total FROM staging;",
+    )
+    .expect("write sql");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .arg("--schema-diff")
+        .args([&old_path, &new_path])
+        .arg(&sql_path)
+        .output()
+        .expect("run CLI");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "orders.amount dropped\n  -> mart.total\n  -> staging.doubled\n"
+    );
+}
@@ -13,6 +13,7 @@ use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
+use flowscope_cli::server::cache::{AnalysisCache, DEFAULT_CACHE_CAPACITY};
 use flowscope_cli::server::{build_router, state::AppState, state::ServerConfig};
 use flowscope_core::{Dialect, FileSource};
 use serde_json::{json, Value};
@@ -26,6 +27,7 @@ fn test_state(config: ServerConfig, files: Vec<FileSource>) -> Arc<AppState> {
         files: RwLock::new(files),
         schema: RwLock::new(None),
         mtimes: RwLock::new(HashMap::new()),
+        cache: AnalysisCache::new(DEFAULT_CACHE_CAPACITY),
     })
 }
 
@@ -38,9 +40,11 @@ fn default_config() -> ServerConfig {
         metadata_schema: None,
         port: 3000,
         open_browser: false,
-        schema_path: None,
+        schema_paths: Vec::new(),
+        strict_schema: false,
         #[cfg(feature = "templating")]
         template_config: None,
+        cache_capacity: DEFAULT_CACHE_CAPACITY,
     }
 }
 
@@ -65,6 +69,15 @@ async fn health_returns_ok_status() {
 
     assert_eq!(json["status"], "ok");
     assert!(json["version"].is_string());
+    assert_eq!(json["dialects"][0], "generic");
+    assert!(json["dialects"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("snowflake")));
+    let formats = json["formats"].as_array().unwrap();
+    assert!(formats.contains(&json!("json")));
+    assert!(formats.contains(&json!("duckdb")));
+    assert_eq!(formats.contains(&json!("png")), cfg!(feature = "png"));
 }
 
 // === Analyze endpoint tests ===
@@ -132,6 +145,60 @@ async fn analyze_with_join() {
     assert!(!json["statements"].as_array().unwrap().is_empty());
 }
 
+#[tokio::test]
+async fn repeated_analysis_is_served_from_cache() {
+    let state = test_state(default_config(), vec![]);
+    let app = build_router(Arc::clone(&state), 3000);
+
+    let analyze = |sql: &str| {
+        Request::post("/api/analyze")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "sql": sql }).to_string()))
+            .unwrap()
+    };
+
+    let first = app
+        .clone()
+        .oneshot(analyze("SELECT id FROM users"))
+        .await
+        .unwrap();
+    let first = axum::body::to_bytes(first.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let second = app
+        .clone()
+        .oneshot(analyze("SELECT id FROM users"))
+        .await
+        .unwrap();
+    assert_eq!(
+        second.headers()["content-type"],
+        "application/json",
+        "cached results keep the JSON content type"
+    );
+    let second = axum::body::to_bytes(second.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(first, second);
+
+    app.clone()
+        .oneshot(analyze("SELECT id FROM orders"))
+        .await
+        .unwrap();
+
+    let response = app
+        .oneshot(Request::get("/api/health").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["cache"]["hits"], 1);
+    assert_eq!(json["cache"]["misses"], 2);
+    assert_eq!(json["cache"]["entries"], 2);
+    assert_eq!(json["cache"]["capacity"], DEFAULT_CACHE_CAPACITY);
+}
+
 // === Completion endpoint tests ===
 
 #[tokio::test]
@@ -293,9 +360,11 @@ async fn config_returns_server_configuration() {
         metadata_schema: None,
         port: 8080,
         open_browser: false,
-        schema_path: None,
+        schema_paths: Vec::new(),
+        strict_schema: false,
         #[cfg(feature = "templating")]
         template_config: None,
+        cache_capacity: DEFAULT_CACHE_CAPACITY,
     };
 
     let state = test_state(config, vec![]);
@@ -374,6 +443,32 @@ async fn export_mermaid_format() {
     );
 }
 
+#[tokio::test]
+async fn every_format_listed_by_health_exports() {
+    let state = test_state(default_config(), vec![]);
+    let app = build_router(state, 3000);
+
+    for format in flowscope_export::supported_formats() {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::post(format!("/api/export/{}", format.name()))
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        json!({
+                            "sql": "SELECT id FROM users"
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK, "{}", format.name());
+    }
+}
+
 #[tokio::test]
 async fn export_unknown_format_returns_error() {
     let state = test_state(default_config(), vec![]);
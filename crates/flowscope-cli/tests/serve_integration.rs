@@ -9,6 +9,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use flowscope_cli::server::cache::{AnalysisCache, DEFAULT_CACHE_CAPACITY};
 use flowscope_cli::server::{build_router, scan_sql_files, state::AppState, state::ServerConfig};
 use flowscope_core::{Dialect, FileSource};
 use serde_json::{json, Value};
@@ -28,6 +29,7 @@ fn test_state_from_files(config: ServerConfig, files: Vec<FileSource>) -> Arc<Ap
         files: RwLock::new(files),
         schema: RwLock::new(None),
         mtimes: RwLock::new(HashMap::new()),
+        cache: AnalysisCache::new(DEFAULT_CACHE_CAPACITY),
     })
 }
 
@@ -66,9 +68,11 @@ async fn server_starts_and_responds_to_health() {
         metadata_schema: None,
         port,
         open_browser: false,
-        schema_path: None,
+        schema_paths: Vec::new(),
+        strict_schema: false,
         #[cfg(feature = "templating")]
         template_config: None,
+        cache_capacity: DEFAULT_CACHE_CAPACITY,
     };
 
     let (base_url, server_handle) = spawn_test_server(config, vec![]).await;
@@ -102,9 +106,11 @@ async fn analyze_endpoint_processes_complex_query() {
         metadata_schema: None,
         port,
         open_browser: false,
-        schema_path: None,
+        schema_paths: Vec::new(),
+        strict_schema: false,
         #[cfg(feature = "templating")]
         template_config: None,
+        cache_capacity: DEFAULT_CACHE_CAPACITY,
     };
 
     let (base_url, server_handle) = spawn_test_server(config, vec![]).await;
@@ -162,9 +168,11 @@ async fn analyze_endpoint_with_multiple_files() {
         metadata_schema: None,
         port,
         open_browser: false,
-        schema_path: None,
+        schema_paths: Vec::new(),
+        strict_schema: false,
         #[cfg(feature = "templating")]
         template_config: None,
+        cache_capacity: DEFAULT_CACHE_CAPACITY,
     };
 
     let files = vec![
@@ -318,9 +326,11 @@ async fn app_state_reload_updates_files() {
         metadata_schema: None,
         port: 3000,
         open_browser: false,
-        schema_path: None,
+        schema_paths: Vec::new(),
+        strict_schema: false,
         #[cfg(feature = "templating")]
         template_config: None,
+        cache_capacity: DEFAULT_CACHE_CAPACITY,
     };
 
     // Create state with initial files
@@ -330,6 +340,7 @@ async fn app_state_reload_updates_files() {
         files: RwLock::new(files),
         schema: RwLock::new(None),
         mtimes: RwLock::new(mtimes),
+        cache: AnalysisCache::new(DEFAULT_CACHE_CAPACITY),
     });
 
     // Verify initial state
@@ -365,9 +376,11 @@ async fn export_html_returns_valid_html() {
         metadata_schema: None,
         port,
         open_browser: false,
-        schema_path: None,
+        schema_paths: Vec::new(),
+        strict_schema: false,
         #[cfg(feature = "templating")]
         template_config: None,
+        cache_capacity: DEFAULT_CACHE_CAPACITY,
     };
 
     let (base_url, server_handle) = spawn_test_server(config, vec![]).await;
@@ -402,9 +415,11 @@ async fn export_csv_returns_zip() {
         metadata_schema: None,
         port,
         open_browser: false,
-        schema_path: None,
+        schema_paths: Vec::new(),
+        strict_schema: false,
         #[cfg(feature = "templating")]
         template_config: None,
+        cache_capacity: DEFAULT_CACHE_CAPACITY,
     };
 
     let (base_url, server_handle) = spawn_test_server(config, vec![]).await;
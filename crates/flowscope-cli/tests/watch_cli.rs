@@ -0,0 +1,68 @@
+//! CLI integration tests for headless `--watch` (without `--serve`).
+
+#![cfg(feature = "serve")]
+
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::Value;
+use tempfile::tempdir;
+
+/// How long to wait for a snapshot before failing instead of hanging.
+const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(20);
+
+fn table_labels(snapshot: &Value) -> Vec<String> {
+    let mut labels: Vec<String> = snapshot["globalLineage"]["nodes"]
+        .as_array()
+        .expect("global nodes")
+        .iter()
+        .filter(|node| node["type"] == "table")
+        .map(|node| node["label"].as_str().unwrap().to_string())
+        .collect();
+    labels.sort();
+    labels
+}
+
+#[test]
+fn watch_emits_a_snapshot_line_after_each_change() {
+    let dir = tempdir().expect("temp dir");
+    let sql_path = dir.path().join("model.sql");
+    std::fs::write(&sql_path, "SELECT id FROM users").expect("write sql");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_flowscope"))
+        .arg("--watch")
+        .arg(dir.path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("run CLI");
+
+    let stdout = child.stdout.take().unwrap();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if tx.send(line.expect("read stdout")).is_err() {
+                break;
+            }
+        }
+    });
+    let next_snapshot = || -> Value {
+        let line = rx
+            .recv_timeout(SNAPSHOT_TIMEOUT)
+            .expect("snapshot line in time");
+        serde_json::from_str(&line).expect("snapshot is a JSON line")
+    };
+
+    let first = next_snapshot();
+    assert_eq!(table_labels(&first), vec!["users"]);
+
+    std::fs::write(&sql_path, "SELECT id FROM users JOIN orders USING (id)").expect("modify sql");
+    let second = next_snapshot();
+    assert_eq!(table_labels(&second), vec!["orders", "users"]);
+
+    child.kill().expect("stop CLI");
+    let _ = child.wait();
+}
@@ -1,30 +1,51 @@
 use crate::types::*;
-use sqlparser::ast::Statement;
+use sqlparser::ast::{Query, Statement};
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ops::Range;
 use std::sync::Arc;
 #[cfg(feature = "tracing")]
 use tracing::info_span;
+use web_time::Instant;
 
 /// Maximum SQL input size (10MB) to prevent memory exhaustion.
 /// This matches the TypeScript validation limit.
 const MAX_SQL_LENGTH: usize = 10 * 1024 * 1024;
 
+mod aggregate_case;
 mod complexity;
+mod constant_predicates;
 mod context;
 pub(crate) mod cross_statement;
+mod datetime_style;
 mod ddl;
+mod dead_columns;
 mod diagnostics;
+mod duplicate_projections;
 mod expression;
 mod functions;
 mod global;
+mod graph_metrics;
 pub mod helpers;
+mod identifier_length;
+mod implicit_aliases;
 mod input;
+mod insert_order;
+mod join_key_types;
+mod line_length;
+mod merge;
+mod nesting;
+mod qualification;
+mod qualify;
 mod query;
+mod recursive_ctes;
+mod redundant_casts;
+mod reserved_keywords;
+mod row_limits;
 pub(crate) mod schema_registry;
 mod select_analyzer;
 mod statements;
+mod trailing_newline;
 mod transform;
 pub mod visitor;
 
@@ -90,6 +111,13 @@ pub(crate) struct Analyzer<'a> {
     current_statement_source: Option<StatementSourceSlice<'a>>,
     /// Statements that already emitted a recursion-depth warning.
     depth_limit_statements: HashSet<usize>,
+    /// Defining queries of materialized views created so far, keyed by canonical name.
+    /// Replayed when a later `REFRESH MATERIALIZED VIEW` rebuilds the view.
+    materialized_view_definitions: HashMap<String, Query>,
+    /// Rendered SQL of each input source, when `return_rendered_sql` is set.
+    rendered_sql: Vec<RenderedSql>,
+    /// Per-statement durations, when `collect_timings` is set.
+    timings: Option<Vec<StatementTiming>>,
 }
 
 impl<'a> Analyzer<'a> {
@@ -100,6 +128,11 @@ impl<'a> Analyzer<'a> {
             .as_ref()
             .and_then(|o| o.enable_column_lineage)
             .unwrap_or(true);
+        let collect_timings = request
+            .options
+            .as_ref()
+            .and_then(|o| o.collect_timings)
+            .unwrap_or(false);
 
         let (schema, init_issues) = SchemaRegistry::new(request.schema.as_ref(), request.dialect);
 
@@ -112,6 +145,9 @@ impl<'a> Analyzer<'a> {
             column_lineage_enabled,
             current_statement_source: None,
             depth_limit_statements: HashSet::new(),
+            materialized_view_definitions: HashMap::new(),
+            rendered_sql: Vec::new(),
+            timings: collect_timings.then(Vec::new),
         }
     }
 
@@ -180,14 +216,47 @@ impl<'a> Analyzer<'a> {
 
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(dialect = ?self.request.dialect, stmt_count)))]
     fn analyze(&mut self) -> AnalyzeResult {
-        let (all_statements, mut preflight_issues) = collect_statements(self.request);
+        let (all_statements, mut preflight_issues, rendered_sql) = collect_statements(self.request);
         self.issues.append(&mut preflight_issues);
+        self.rendered_sql = rendered_sql;
 
         #[cfg(feature = "tracing")]
         tracing::Span::current().record("stmt_count", all_statements.len());
 
         self.precollect_ddl(&all_statements);
 
+        let check_datetime = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.check_datetime_consistency)
+            .unwrap_or(false);
+        if check_datetime {
+            let mut datetime_issues = datetime_style::check_datetime_consistency(&all_statements);
+            self.issues.append(&mut datetime_issues);
+        }
+
+        let max_line_length = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.max_line_length);
+        if let Some(max_length) = max_line_length {
+            let mut line_issues = line_length::check_line_length(&all_statements, max_length);
+            self.issues.append(&mut line_issues);
+        }
+
+        let check_trailing_newline = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.check_trailing_newline)
+            .unwrap_or(false);
+        if check_trailing_newline {
+            let mut newline_issues = trailing_newline::check_trailing_newline(self.request);
+            self.issues.append(&mut newline_issues);
+        }
+
         if all_statements.is_empty() {
             return self.build_result();
         }
@@ -201,6 +270,8 @@ impl<'a> Analyzer<'a> {
                 source_sql,
                 source_range,
                 templating_applied,
+                do_block,
+                parse_micros,
             },
         ) in all_statements.into_iter().enumerate()
         {
@@ -223,9 +294,11 @@ impl<'a> Analyzer<'a> {
             self.current_statement_source = Some(StatementSourceSlice {
                 sql: source_sql,
                 range: source_range.clone(),
+                do_block,
             });
 
             let source_name_owned = source_name.as_deref().map(String::from);
+            let started = self.timings.is_some().then(Instant::now);
             let result = self.analyze_statement(
                 index,
                 &statement,
@@ -234,6 +307,13 @@ impl<'a> Analyzer<'a> {
                 resolved_sql,
             );
             self.current_statement_source = None;
+            if let (Some(timings), Some(started)) = (self.timings.as_mut(), started) {
+                timings.push(StatementTiming {
+                    statement_index: index,
+                    parse_micros,
+                    analysis_micros: started.elapsed().as_micros() as u64,
+                });
+            }
 
             match result {
                 Ok(lineage) => {
@@ -254,6 +334,8 @@ impl<'a> Analyzer<'a> {
 struct StatementSourceSlice<'a> {
     sql: Cow<'a, str>,
     range: Range<usize>,
+    /// Range of the enclosing `DO` block for statements extracted from its body
+    do_block: Option<Range<usize>>,
 }
 
 impl<'a> Analyzer<'a> {
@@ -0,0 +1,94 @@
+//! CASE expressions without ELSE passed to SUM or AVG (`SUM(CASE WHEN paid THEN 1 END)`).
+//!
+//! Rows that match no WHEN branch become NULL, which the aggregate skips: SUM returns
+//! NULL rather than 0 when nothing matches, and AVG averages over the matching rows
+//! only. Both are usually meant as `ELSE 0`. COUNT, MIN, MAX and friends rely on the
+//! NULLs on purpose (`COUNT(CASE WHEN ... THEN 1 END)`), so they are not checked.
+
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{
+    Expr, FunctionArg, FunctionArgExpr, FunctionArguments, Statement, Visit, Visitor,
+};
+
+use super::Analyzer;
+use crate::types::{issue_codes, Issue};
+
+/// Aggregates whose result changes when CASE yields NULL instead of zero.
+const NULL_SKIPPING_AGGREGATES: [&str; 2] = ["SUM", "AVG"];
+
+impl<'a> Analyzer<'a> {
+    /// With `check_aggregate_case_else`, warns about every SUM or AVG in `statement`
+    /// whose argument is a CASE without ELSE.
+    pub(super) fn check_aggregate_case_else(
+        &mut self,
+        statement: &Statement,
+        statement_index: usize,
+    ) {
+        let enabled = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.check_aggregate_case_else)
+            .unwrap_or(false);
+        if !enabled {
+            return;
+        }
+
+        let mut collector = AggregateCaseCollector::default();
+        let _ = statement.visit(&mut collector);
+
+        for function in collector.functions {
+            let message = format!(
+                "{function}(CASE ...) has no ELSE, so rows matching no WHEN are NULL and skipped by {function}; add ELSE 0 if they should count as zero"
+            );
+            self.issues.push(
+                Issue::warning(issue_codes::AGGREGATE_CASE_WITHOUT_ELSE, message)
+                    .with_statement(statement_index),
+            );
+        }
+    }
+}
+
+#[derive(Default)]
+struct AggregateCaseCollector {
+    /// Uppercased name of each aggregate called with a CASE lacking ELSE
+    functions: Vec<String>,
+}
+
+impl Visitor for AggregateCaseCollector {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+        let Expr::Function(function) = expr else {
+            return ControlFlow::Continue(());
+        };
+        let name = function.name.to_string();
+        if !NULL_SKIPPING_AGGREGATES
+            .iter()
+            .any(|aggregate| name.eq_ignore_ascii_case(aggregate))
+        {
+            return ControlFlow::Continue(());
+        }
+        let FunctionArguments::List(list) = &function.args else {
+            return ControlFlow::Continue(());
+        };
+        if list.args.iter().any(|arg| {
+            matches!(
+                arg,
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) if is_case_without_else(expr)
+            )
+        }) {
+            self.functions.push(name.to_uppercase());
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+fn is_case_without_else(expr: &Expr) -> bool {
+    match expr {
+        Expr::Nested(inner) => is_case_without_else(inner),
+        Expr::Case { else_result, .. } => else_result.is_none(),
+        _ => false,
+    }
+}
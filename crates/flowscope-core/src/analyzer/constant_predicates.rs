@@ -0,0 +1,189 @@
+//! WHERE and HAVING conditions whose value is fixed by literals alone
+//! (`WHERE 5 > 3`, `WHERE 'a' = 'a'`).
+//!
+//! A tautology filters nothing and is often a leftover from query generation
+//! (`WHERE 1 = 1 AND ...` with the rest removed); a contradiction makes the query
+//! return no rows. Conditions are folded on the syntax tree, so only comparisons
+//! between literals and the logical operators combining them are evaluated. A
+//! condition that also reads a column is reported only when the literal part
+//! decides it (`WHERE x > 0 AND 1 = 2`).
+
+use std::cmp::Ordering;
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{
+    BinaryOperator, Expr, Query, SetExpr, Statement, UnaryOperator, Value, Visit, Visitor,
+};
+
+use super::Analyzer;
+use crate::types::{issue_codes, Issue};
+
+impl<'a> Analyzer<'a> {
+    /// With `check_constant_predicates`, warns about every WHERE or HAVING condition
+    /// in `statement` that is always true or always false.
+    pub(super) fn check_constant_predicates(
+        &mut self,
+        statement: &Statement,
+        statement_index: usize,
+    ) {
+        let enabled = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.check_constant_predicates)
+            .unwrap_or(false);
+        if !enabled {
+            return;
+        }
+
+        let mut collector = ConstantPredicateCollector::default();
+        let _ = statement.visit(&mut collector);
+
+        for predicate in collector.predicates {
+            let message = if predicate.value {
+                format!(
+                    "{} condition `{}` is always true and filters nothing",
+                    predicate.clause, predicate.condition
+                )
+            } else {
+                format!(
+                    "{} condition `{}` is always false; the query returns no rows",
+                    predicate.clause, predicate.condition
+                )
+            };
+            self.issues.push(
+                Issue::warning(issue_codes::CONSTANT_PREDICATE, message)
+                    .with_statement(statement_index),
+            );
+        }
+    }
+}
+
+/// A WHERE or HAVING condition with a constant value.
+struct ConstantPredicate {
+    clause: &'static str,
+    condition: String,
+    value: bool,
+}
+
+#[derive(Default)]
+struct ConstantPredicateCollector {
+    predicates: Vec<ConstantPredicate>,
+}
+
+impl ConstantPredicateCollector {
+    fn collect(&mut self, body: &SetExpr) {
+        match body {
+            SetExpr::Select(select) => {
+                for (clause, condition) in
+                    [("WHERE", &select.selection), ("HAVING", &select.having)]
+                {
+                    let Some(condition) = condition else {
+                        continue;
+                    };
+                    if let Some(value) = fold(condition) {
+                        self.predicates.push(ConstantPredicate {
+                            clause,
+                            condition: condition.to_string(),
+                            value,
+                        });
+                    }
+                }
+            }
+            // Nested queries are visited on their own
+            SetExpr::SetOperation { left, right, .. } => {
+                self.collect(left);
+                self.collect(right);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visitor for ConstantPredicateCollector {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        self.collect(&query.body);
+        ControlFlow::Continue(())
+    }
+}
+
+/// A literal that takes part in a comparison.
+#[derive(Debug, PartialEq)]
+enum Literal {
+    Number(f64),
+    String(String),
+    Boolean(bool),
+}
+
+/// The truth value of `expr` when literals alone decide it.
+fn fold(expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Nested(inner) => fold(inner),
+        Expr::Value(value) => match value.value {
+            Value::Boolean(b) => Some(b),
+            _ => None,
+        },
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr,
+        } => fold(expr).map(|value| !value),
+        Expr::BinaryOp { left, op, right } => match op {
+            // One decided side can settle the whole condition
+            BinaryOperator::And => match (fold(left), fold(right)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            BinaryOperator::Or => match (fold(left), fold(right)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            _ => compare(&literal(left)?, op, &literal(right)?),
+        },
+        _ => None,
+    }
+}
+
+fn literal(expr: &Expr) -> Option<Literal> {
+    match expr {
+        Expr::Nested(inner) => literal(inner),
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => match literal(expr)? {
+            Literal::Number(n) => Some(Literal::Number(-n)),
+            _ => None,
+        },
+        Expr::Value(value) => match &value.value {
+            Value::Number(n, _) => n.parse().ok().map(Literal::Number),
+            Value::SingleQuotedString(s) => Some(Literal::String(s.clone())),
+            Value::Boolean(b) => Some(Literal::Boolean(*b)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Evaluate a comparison between two literals of the same kind.
+fn compare(left: &Literal, op: &BinaryOperator, right: &Literal) -> Option<bool> {
+    let ordering = match (left, right) {
+        (Literal::Number(a), Literal::Number(b)) => a.partial_cmp(b)?,
+        (Literal::String(a), Literal::String(b)) if a == b => Ordering::Equal,
+        (Literal::Boolean(a), Literal::Boolean(b)) => a.cmp(b),
+        // Distinct strings compare by collation, which may ignore case or padding,
+        // and mixed kinds depend on the database's implicit casts
+        _ => return None,
+    };
+    match op {
+        BinaryOperator::Eq => Some(ordering == Ordering::Equal),
+        BinaryOperator::NotEq => Some(ordering != Ordering::Equal),
+        BinaryOperator::Lt => Some(ordering == Ordering::Less),
+        BinaryOperator::LtEq => Some(ordering != Ordering::Greater),
+        BinaryOperator::Gt => Some(ordering == Ordering::Greater),
+        BinaryOperator::GtEq => Some(ordering != Ordering::Less),
+        _ => None,
+    }
+}
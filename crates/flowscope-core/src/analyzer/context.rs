@@ -18,6 +18,20 @@ pub(crate) struct PendingWildcard {
     pub(crate) source_node_id: Arc<str>,
 }
 
+/// Column list of an INSERT target, used to name projected columns by position.
+///
+/// Populated from the explicit `INSERT INTO t (a, b)` column list, or from schema
+/// metadata for the target table when no list is given.
+#[derive(Debug, Clone)]
+pub(crate) struct InsertTarget {
+    /// Node ID of the target table
+    pub(crate) node_id: Arc<str>,
+    /// Target column names in declaration order
+    pub(crate) columns: Vec<String>,
+    /// Position of the next projected column within the current SELECT list
+    pub(crate) position: usize,
+}
+
 /// Represents a single scope level for column resolution.
 /// Each SELECT/subquery/CTE body gets its own scope.
 #[derive(Debug, Clone, Default)]
@@ -105,6 +119,16 @@ pub(crate) struct StatementContext {
     /// Pending wildcards that couldn't be expanded due to missing schema.
     /// Used for backward column inference from downstream references.
     pub(crate) pending_wildcards: Vec<PendingWildcard>,
+    /// Target columns of the INSERT being analyzed, if known
+    pub(crate) insert_target: Option<InsertTarget>,
+    /// True while analyzing a SELECT with `CONNECT BY`, where `LEVEL` and friends
+    /// are pseudo-columns rather than table columns
+    pub(crate) in_hierarchical_query: bool,
+    /// False when only table-level lineage is requested: column nodes and the edges
+    /// touching them are not recorded
+    pub(crate) column_lineage: bool,
+    /// Column nodes left out because column lineage is disabled
+    pub(crate) skipped_column_ids: HashSet<Arc<str>>,
 }
 
 /// Represents an output column in the SELECT list
@@ -114,6 +138,8 @@ pub(crate) struct OutputColumn {
     pub(crate) name: String,
     /// Inferred data type of the column
     pub(crate) data_type: Option<String>,
+    /// Whether the column may be NULL (`None` when unknown)
+    pub(crate) nullable: Option<bool>,
     /// Node ID for this column
     pub(crate) node_id: Arc<str>,
 }
@@ -123,10 +149,15 @@ pub(crate) struct OutputColumn {
 pub(crate) struct ColumnRef {
     /// Table name or alias
     pub(crate) table: Option<String>,
-    /// Column name
+    /// Column name, or a dotted path for struct field access (`address.city`)
     pub(crate) column: String,
 }
 
+/// Returns the top-level column of a column path (`address` for `address.city`).
+pub(crate) fn root_column(column: &str) -> &str {
+    column.split_once('.').map_or(column, |(root, _)| root)
+}
+
 impl StatementContext {
     pub(crate) fn new(statement_index: usize) -> Self {
         Self {
@@ -153,6 +184,33 @@ impl StatementContext {
             source_table_columns: HashMap::new(),
             implied_foreign_keys: HashMap::new(),
             pending_wildcards: Vec::new(),
+            insert_target: None,
+            in_hierarchical_query: false,
+            column_lineage: true,
+            skipped_column_ids: HashSet::new(),
+        }
+    }
+
+    /// Returns the INSERT target column name for the next projected column of `target_node`.
+    ///
+    /// Projected columns map onto the target's columns positionally; once the
+    /// target's column list is exhausted, `None` is returned.
+    pub(crate) fn next_insert_column(&mut self, target_node: Option<&str>) -> Option<String> {
+        let insert = self.insert_target.as_mut()?;
+        if target_node != Some(&*insert.node_id) {
+            return None;
+        }
+        let column = insert.columns.get(insert.position).cloned();
+        insert.position += 1;
+        column
+    }
+
+    /// Restart positional INSERT column mapping for a new SELECT list of `target_node`.
+    pub(crate) fn reset_insert_position(&mut self, target_node: Option<&str>) {
+        if let Some(insert) = self.insert_target.as_mut() {
+            if target_node == Some(&*insert.node_id) {
+                insert.position = 0;
+            }
         }
     }
 
@@ -177,12 +235,16 @@ impl StatementContext {
     ///
     /// This is used to build implied schema for source tables. If the column
     /// already exists without a type and a type is provided, the type is updated.
+    /// Struct field paths record their top-level column, whose type is unknown.
     pub(crate) fn record_source_column(
         &mut self,
         canonical_table: &str,
         column_name: &str,
         data_type: Option<String>,
     ) {
+        let root = root_column(column_name);
+        let data_type = if root == column_name { data_type } else { None };
+        let column_name = root;
         let columns = self
             .source_table_columns
             .entry(canonical_table.to_string())
@@ -254,6 +316,10 @@ impl StatementContext {
 
     pub(crate) fn add_node(&mut self, node: Node) -> Arc<str> {
         let id = node.id.clone();
+        if !self.column_lineage && node.node_type == NodeType::Column {
+            self.skipped_column_ids.insert(id.clone());
+            return id;
+        }
         if self.node_ids.insert(id.clone()) {
             self.nodes.push(node);
         }
@@ -261,6 +327,11 @@ impl StatementContext {
     }
 
     pub(crate) fn add_edge(&mut self, edge: Edge) {
+        if self.skipped_column_ids.contains(&edge.from)
+            || self.skipped_column_ids.contains(&edge.to)
+        {
+            return;
+        }
         let id = edge.id.clone();
         if self.edge_ids.insert(id) {
             self.edges.push(edge);
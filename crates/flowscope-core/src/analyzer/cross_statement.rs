@@ -29,7 +29,7 @@
 //! `CrossStatementTracker` is designed for single-threaded use within an analysis pass.
 //! Each analysis pass should create a fresh tracker instance.
 
-use crate::types::{EdgeType, GlobalEdge, NodeType, StatementRef};
+use crate::types::{EdgeType, GlobalEdge, NodeType, StatementLineage, StatementRef};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
@@ -137,6 +137,78 @@ impl CrossStatementTracker {
         self.all_relations.insert(canonical.to_string());
     }
 
+    /// Rebuilds the producer/consumer relationships of finished statement lineages, for
+    /// results that were analyzed separately (see [`crate::AnalyzeResult::merge`]).
+    ///
+    /// A table or view whose node or columns receive data in a statement counts as
+    /// produced by it; one whose node or columns feed data counts as consumed. Relations
+    /// a statement only mentions are produced by CREATE, ALTER and COPY statements and
+    /// consumed by all others. This misreads `COPY ... TO` exports, which leave no edges
+    /// to tell them from loads, and DROP statements carry no nodes, so drops are not
+    /// replayed.
+    pub(crate) fn from_statements(statements: &[StatementLineage]) -> Self {
+        let mut tracker = Self::new();
+
+        for lineage in statements {
+            let relations: HashSet<&str> = lineage
+                .nodes
+                .iter()
+                .filter(|node| node.node_type.is_table_or_view())
+                .map(|node| node.id.as_ref())
+                .collect();
+            let owners: HashMap<&str, &str> = lineage
+                .edges
+                .iter()
+                .filter(|edge| {
+                    edge.edge_type == EdgeType::Ownership && relations.contains(edge.from.as_ref())
+                })
+                .map(|edge| (edge.to.as_ref(), edge.from.as_ref()))
+                .collect();
+            let relation_of = |id: &str| {
+                relations
+                    .get(id)
+                    .copied()
+                    .or_else(|| owners.get(id).copied())
+            };
+
+            let mut written = HashSet::new();
+            let mut read = HashSet::new();
+            for edge in &lineage.edges {
+                if edge.edge_type == EdgeType::Ownership {
+                    continue;
+                }
+                written.extend(relation_of(&edge.to));
+                read.extend(relation_of(&edge.from));
+            }
+
+            let creates = lineage.statement_type.starts_with("CREATE")
+                || lineage.statement_type.starts_with("ALTER")
+                || lineage.statement_type == "COPY";
+            for node in &lineage.nodes {
+                if !node.node_type.is_table_or_view() {
+                    continue;
+                }
+                let canonical = node.qualified_name.as_deref().unwrap_or(&node.label);
+                let id = node.id.as_ref();
+                let (produced, consumed) = if written.contains(id) || read.contains(id) {
+                    (written.contains(id), read.contains(id))
+                } else {
+                    (creates, !creates)
+                };
+                if consumed {
+                    tracker.record_consumed(canonical, lineage.statement_index);
+                }
+                if produced && node.node_type == NodeType::View {
+                    tracker.record_view_produced(canonical, lineage.statement_index);
+                } else if produced {
+                    tracker.record_produced(canonical, lineage.statement_index);
+                }
+            }
+        }
+
+        tracker
+    }
+
     /// Records a CTE definition for global tracking.
     ///
     /// CTEs are tracked separately from tables/views as they have statement-scoped lifetime.
@@ -213,10 +285,14 @@ impl CrossStatementTracker {
     /// The hash uses `DefaultHasher` which is fast but not guaranteed to be stable across
     /// Rust versions. This is acceptable because edge IDs are ephemeral within a single
     /// analysis run and are not persisted or compared across runs.
+    ///
+    /// Edges are ordered by table name, then consumer, so the output is deterministic.
     pub(crate) fn build_cross_statement_edges(&self) -> Vec<GlobalEdge> {
         let mut edges = Vec::new();
 
-        for (table_name, consumers) in &self.consumed_tables {
+        let mut consumed: Vec<_> = self.consumed_tables.iter().collect();
+        consumed.sort_by(|a, b| a.0.cmp(b.0));
+        for (table_name, consumers) in consumed {
             if let Some(&producer_idx) = self.produced_tables.get(table_name) {
                 for &consumer_idx in consumers {
                     if consumer_idx > producer_idx {
@@ -243,6 +319,8 @@ impl CrossStatementTracker {
                                 statement_index: consumer_idx,
                                 node_id: None,
                             }),
+                            source_statement: Some(consumer_idx),
+                            source_file: None,
                             metadata: None,
                         });
                     }
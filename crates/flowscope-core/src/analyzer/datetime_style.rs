@@ -0,0 +1,146 @@
+//! Project-wide consistency check for current date/time functions.
+//!
+//! Dialects offer several spellings for "now": ANSI `CURRENT_DATE`/`CURRENT_TIMESTAMP`,
+//! `NOW()`, T-SQL `GETDATE()`, Oracle `SYSDATE`, and so on. This pass counts which
+//! style each file uses and flags files that use a style other than the one most
+//! files in the project use.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{visit_expressions, Expr};
+
+use super::input::StatementInput;
+use crate::types::{issue_codes, Issue};
+
+/// A family of equivalent current date/time spellings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum DateTimeStyle {
+    Ansi,
+    Now,
+    GetDate,
+    SysDate,
+    Today,
+}
+
+impl DateTimeStyle {
+    fn from_function(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "CURRENT_DATE" | "CURRENT_TIME" | "CURRENT_TIMESTAMP" | "LOCALTIME"
+            | "LOCALTIMESTAMP" => Some(Self::Ansi),
+            "NOW" => Some(Self::Now),
+            "GETDATE" | "GETUTCDATE" | "SYSDATETIME" => Some(Self::GetDate),
+            "SYSDATE" | "SYSTIMESTAMP" => Some(Self::SysDate),
+            "TODAY" => Some(Self::Today),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Ansi => "CURRENT_DATE/CURRENT_TIMESTAMP",
+            Self::Now => "NOW()",
+            Self::GetDate => "GETDATE()",
+            Self::SysDate => "SYSDATE",
+            Self::Today => "TODAY()",
+        }
+    }
+}
+
+/// Styles used by one file, with the first statement using each.
+#[derive(Default)]
+struct FileUsage {
+    styles: BTreeMap<DateTimeStyle, usize>,
+}
+
+/// Flag files whose current date/time style differs from the project's dominant one.
+///
+/// The dominant style is the one used by the most files; ties go to the style with
+/// more calls overall, then to ANSI. Statements without a source name are grouped
+/// together as one unnamed file.
+pub(crate) fn check_datetime_consistency(statements: &[StatementInput]) -> Vec<Issue> {
+    let mut files: BTreeMap<Option<&str>, FileUsage> = BTreeMap::new();
+    let mut call_counts: BTreeMap<DateTimeStyle, usize> = BTreeMap::new();
+
+    for (index, input) in statements.iter().enumerate() {
+        let usage = files
+            .entry(input.source_name.as_deref().map(String::as_str))
+            .or_default();
+        let _ = visit_expressions(&input.statement, |expr| {
+            if let Some(style) = expr_style(expr) {
+                usage.styles.entry(style).or_insert(index);
+                *call_counts.entry(style).or_default() += 1;
+            }
+            ControlFlow::<()>::Continue(())
+        });
+    }
+
+    let mut file_counts: BTreeMap<DateTimeStyle, usize> = BTreeMap::new();
+    for usage in files.values() {
+        for style in usage.styles.keys() {
+            *file_counts.entry(*style).or_default() += 1;
+        }
+    }
+    if file_counts.len() < 2 {
+        return Vec::new();
+    }
+
+    let Some((&dominant, &dominant_files)) =
+        file_counts.iter().max_by(|(a, a_files), (b, b_files)| {
+            a_files
+                .cmp(b_files)
+                .then_with(|| call_counts[a].cmp(&call_counts[b]))
+                .then_with(|| b.cmp(a))
+        })
+    else {
+        return Vec::new();
+    };
+    let files_using_any = files.values().filter(|u| !u.styles.is_empty()).count();
+
+    let mut issues = Vec::new();
+    for (source_name, usage) in &files {
+        let others: BTreeSet<DateTimeStyle> = usage
+            .styles
+            .keys()
+            .copied()
+            .filter(|style| *style != dominant)
+            .collect();
+        let Some(first_index) = others.iter().map(|style| usage.styles[style]).min() else {
+            continue;
+        };
+        let used: Vec<&str> = others.iter().map(|style| style.label()).collect();
+        let mut issue = Issue::warning(
+            issue_codes::INCONSISTENT_DATETIME_STYLE,
+            format!(
+                "Uses {} for the current date/time; the project mostly uses {} ({} of {} files)",
+                used.join(", "),
+                dominant.label(),
+                dominant_files,
+                files_using_any
+            ),
+        )
+        .with_statement(first_index);
+        if let Some(name) = source_name {
+            issue = issue.with_source_name(*name);
+        }
+        issues.push(issue);
+    }
+    issues
+}
+
+fn expr_style(expr: &Expr) -> Option<DateTimeStyle> {
+    match expr {
+        Expr::Function(function) => {
+            let name = function.name.0.last()?.as_ident()?;
+            DateTimeStyle::from_function(&name.value)
+        }
+        // Oracle-style SYSDATE/SYSTIMESTAMP are bare keywords, parsed as identifiers
+        Expr::Identifier(ident) if ident.quote_style.is_none() => {
+            match DateTimeStyle::from_function(&ident.value) {
+                Some(DateTimeStyle::SysDate) => Some(DateTimeStyle::SysDate),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
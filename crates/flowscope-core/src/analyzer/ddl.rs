@@ -6,14 +6,22 @@
 
 use super::context::StatementContext;
 use super::helpers::{
-    build_column_schemas_with_constraints, extract_simple_name, generate_node_id,
+    build_column_schemas_with_constraints, extract_simple_name, generate_column_node_id,
+    generate_edge_id, generate_node_id,
 };
 use super::Analyzer;
+use crate::parser::refresh::is_refresh_materialized_view;
+use crate::parser::spark::{find_spark_table_clauses, has_spark_table_clauses, SparkTableClause};
+use crate::parser::view_options::{find_view_clauses, ViewClause};
 use crate::types::{
-    ColumnSchema, ConstraintType, ForeignKeyRef, Node, NodeType, TableConstraintInfo,
+    ColumnSchema, ConstraintType, Edge, EdgeType, ForeignKeyRef, Node, NodeType,
+    TableConstraintInfo,
 };
-use sqlparser::ast::{ObjectName, Query, TableConstraint};
-use std::collections::BTreeMap;
+use sqlparser::ast::{
+    CreateTable, CreateTableOptions, Expr, ObjectName, Query, SetExpr, SqlOption, TableConstraint,
+    TableFactor, Value,
+};
+use std::collections::{BTreeMap, HashMap};
 
 /// Statement type used when registering source tables (tables being read from).
 /// Source tables are always used in a SELECT-like context, regardless of the
@@ -98,6 +106,7 @@ impl<'a> Analyzer<'a> {
                         data_type: data_type.clone(),
                         is_primary_key: None,
                         foreign_key,
+                        nullable: None,
                     }
                 })
                 .collect();
@@ -140,12 +149,106 @@ impl<'a> Analyzer<'a> {
         }
     }
 
+    /// Collects a table's storage properties as node metadata.
+    ///
+    /// Records `LOCATION` and, for Spark dialects, the `USING` data source format and
+    /// `OPTIONS`, which the parser masks out and are read back from the statement text.
+    pub(super) fn table_storage_metadata(
+        &self,
+        create: &CreateTable,
+    ) -> Option<HashMap<String, serde_json::Value>> {
+        let mut metadata = HashMap::new();
+
+        let location = create.location.clone().or_else(|| {
+            create
+                .hive_formats
+                .as_ref()
+                .and_then(|formats| formats.location.clone())
+        });
+        if let Some(location) = location {
+            metadata.insert("location".to_string(), location.into());
+        }
+
+        let dialect = self.request.dialect;
+        if has_spark_table_clauses(dialect) {
+            if let Some(source) = &self.current_statement_source {
+                let statement_sql = &source.sql[source.range.clone()];
+                for (_, clause) in find_spark_table_clauses(statement_sql, dialect) {
+                    match clause {
+                        SparkTableClause::Using(format) => {
+                            metadata.insert("format".to_string(), format.to_lowercase().into());
+                        }
+                        SparkTableClause::Options(options) => {
+                            let options = options
+                                .into_iter()
+                                .map(|(key, value)| (key, value.into()))
+                                .collect();
+                            metadata
+                                .insert("options".to_string(), serde_json::Value::Object(options));
+                        }
+                    }
+                }
+            }
+        }
+
+        (!metadata.is_empty()).then_some(metadata)
+    }
+
+    /// Collects a view's options as node metadata.
+    ///
+    /// Records `SECURE`, `WITH (...)`/`OPTIONS (...)` entries under `options`, and the
+    /// `WITH CHECK OPTION` mode under `check_option`. Postgres-style options the parser
+    /// masks out are read back from the statement text.
+    pub(super) fn view_metadata(
+        &self,
+        options: &CreateTableOptions,
+        secure: bool,
+    ) -> Option<HashMap<String, serde_json::Value>> {
+        let mut metadata = HashMap::new();
+        if secure {
+            metadata.insert("secure".to_string(), true.into());
+        }
+
+        let mut view_options = serde_json::Map::new();
+        if let CreateTableOptions::With(parsed) | CreateTableOptions::Options(parsed) = options {
+            for option in parsed {
+                if let SqlOption::KeyValue { key, value } = option {
+                    view_options.insert(key.value.clone(), option_value(value).into());
+                }
+            }
+        }
+        if let Some(source) = &self.current_statement_source {
+            let statement_sql = &source.sql[source.range.clone()];
+            for (_, clause) in find_view_clauses(statement_sql, self.request.dialect) {
+                match clause {
+                    ViewClause::Options(options) => {
+                        for (key, value) in options {
+                            view_options.insert(key, value.into());
+                        }
+                    }
+                    ViewClause::CheckOption(mode) => {
+                        metadata.insert("check_option".to_string(), mode.into());
+                    }
+                }
+            }
+        }
+        if !view_options.is_empty() {
+            metadata.insert(
+                "options".to_string(),
+                serde_json::Value::Object(view_options),
+            );
+        }
+
+        (!metadata.is_empty()).then_some(metadata)
+    }
+
     pub(super) fn analyze_create_table_as(
         &mut self,
         ctx: &mut StatementContext,
         table_name: &ObjectName,
         query: &Query,
         is_temporary: bool,
+        storage: Option<HashMap<String, serde_json::Value>>,
     ) {
         let target_name = table_name.to_string();
         let canonical = self.normalize_table_name(&target_name);
@@ -158,7 +261,7 @@ impl<'a> Analyzer<'a> {
             qualified_name: Some(canonical.clone().into()),
             expression: None,
             span: None,
-            metadata: None,
+            metadata: storage,
             resolution_source: None,
             filters: Vec::new(),
             join_type: None,
@@ -182,6 +285,7 @@ impl<'a> Analyzer<'a> {
                 data_type: col.data_type.clone(),
                 is_primary_key: None,
                 foreign_key: None,
+                nullable: None,
             })
             .collect();
 
@@ -203,6 +307,57 @@ impl<'a> Analyzer<'a> {
         // No need to create redundant table-to-table edges here.
     }
 
+    /// Returns true when the current statement is a `REFRESH MATERIALIZED VIEW`, which
+    /// reaches the analyzer rewritten as `SELECT * FROM <view>`.
+    pub(super) fn is_refresh_materialized_view(&self) -> bool {
+        self.current_statement_source
+            .as_ref()
+            .is_some_and(|source| {
+                is_refresh_materialized_view(
+                    &source.sql[source.range.clone()],
+                    self.request.dialect,
+                )
+            })
+    }
+
+    /// Records a refreshed materialized view as rebuilt by this statement.
+    ///
+    /// When the view was created earlier in the request, its defining query is
+    /// analyzed again so the refresh carries the view's source lineage.
+    pub(super) fn analyze_refresh_materialized_view(
+        &mut self,
+        ctx: &mut StatementContext,
+        query: &Query,
+    ) {
+        let Some(name) = refreshed_view_name(query) else {
+            return;
+        };
+        let target_name = name.to_string();
+        let canonical = self.normalize_table_name(&target_name);
+
+        let target_id = ctx.add_node(Node {
+            id: generate_node_id("view", &canonical),
+            node_type: NodeType::View,
+            label: extract_simple_name(&target_name).into(),
+            qualified_name: Some(canonical.clone().into()),
+            expression: None,
+            span: self.find_span(&target_name),
+            metadata: None,
+            resolution_source: None,
+            filters: Vec::new(),
+            join_type: None,
+            join_condition: None,
+            aggregation: None,
+        });
+
+        self.tracker
+            .record_view_produced(&canonical, ctx.statement_index);
+
+        if let Some(definition) = self.materialized_view_definitions.get(&canonical).cloned() {
+            self.analyze_query(ctx, &definition, Some(&target_id));
+        }
+    }
+
     pub(super) fn analyze_create_table(
         &mut self,
         ctx: &mut StatementContext,
@@ -210,6 +365,7 @@ impl<'a> Analyzer<'a> {
         columns: &[sqlparser::ast::ColumnDef],
         table_constraints: &[TableConstraint],
         is_temporary: bool,
+        storage: Option<HashMap<String, serde_json::Value>>,
     ) {
         let target_name = name.to_string();
 
@@ -240,7 +396,7 @@ impl<'a> Analyzer<'a> {
             qualified_name: Some(canonical.clone().into()),
             expression: None,
             span: None,
-            metadata: None,
+            metadata: storage,
             resolution_source: None,
             filters: Vec::new(),
             join_type: None,
@@ -257,12 +413,81 @@ impl<'a> Analyzer<'a> {
             .record_produced(&canonical, ctx.statement_index);
     }
 
+    /// Analyzes `CREATE TABLE copy LIKE original` and `CREATE TABLE copy CLONE original`.
+    ///
+    /// The copy is linked to the original by an edge labelled with `operation`, and
+    /// inherits the original's columns when they are known, each fed by its original.
+    pub(super) fn analyze_create_table_copy(
+        &mut self,
+        ctx: &mut StatementContext,
+        name: &ObjectName,
+        source: &ObjectName,
+        operation: &str,
+        is_temporary: bool,
+    ) {
+        let target_name = name.to_string();
+        let canonical = self.canonicalize_table_reference(&target_name).canonical;
+        let node_id = ctx.add_node(Node {
+            id: generate_node_id("table", &canonical),
+            node_type: NodeType::Table,
+            label: extract_simple_name(&target_name).into(),
+            qualified_name: Some(canonical.clone().into()),
+            expression: None,
+            span: None,
+            metadata: None,
+            resolution_source: None,
+            filters: Vec::new(),
+            join_type: None,
+            join_condition: None,
+            aggregation: None,
+        });
+
+        ctx.last_operation = Some(operation.to_string());
+        let source_canonical = self.add_source_table(ctx, &source.to_string(), Some(&node_id));
+
+        let source_columns = source_canonical
+            .as_deref()
+            .and_then(|source| self.schema.get(source))
+            .map(|entry| entry.table.columns.clone());
+        if let (Some(source_canonical), Some(columns)) = (source_canonical, source_columns) {
+            self.register_implied_schema(ctx, &canonical, columns.clone(), is_temporary, "DDL");
+            self.add_table_columns_from_schema(ctx, &canonical, &node_id);
+
+            let source_node_id = ctx
+                .table_node_ids
+                .get(&source_canonical)
+                .cloned()
+                .unwrap_or_else(|| self.relation_node_id(&source_canonical));
+            self.add_table_columns_from_schema(ctx, &source_canonical, &source_node_id);
+            for column in &columns {
+                let from = generate_column_node_id(Some(&source_node_id), &column.name);
+                let to = generate_column_node_id(Some(&node_id), &column.name);
+                ctx.add_edge(Edge {
+                    id: generate_edge_id(&from, &to),
+                    from,
+                    to,
+                    edge_type: EdgeType::DataFlow,
+                    expression: None,
+                    operation: Some(operation.into()),
+                    join_type: None,
+                    join_condition: None,
+                    metadata: None,
+                    approximate: None,
+                });
+            }
+        }
+
+        self.tracker
+            .record_produced(&canonical, ctx.statement_index);
+    }
+
     pub(super) fn analyze_create_view(
         &mut self,
         ctx: &mut StatementContext,
         name: &ObjectName,
         query: &Query,
         is_temporary: bool,
+        options: Option<HashMap<String, serde_json::Value>>,
     ) {
         let target_name = name.to_string();
         let canonical = self.normalize_table_name(&target_name);
@@ -275,7 +500,7 @@ impl<'a> Analyzer<'a> {
             qualified_name: Some(canonical.clone().into()),
             expression: None,
             span: None,
-            metadata: None,
+            metadata: options,
             resolution_source: None,
             filters: Vec::new(),
             join_type: None,
@@ -299,6 +524,7 @@ impl<'a> Analyzer<'a> {
                 data_type: col.data_type.clone(),
                 is_primary_key: None,
                 foreign_key: None,
+                nullable: None,
             })
             .collect();
 
@@ -345,3 +571,25 @@ impl<'a> Analyzer<'a> {
         }
     }
 }
+
+/// The view named by a `REFRESH MATERIALIZED VIEW` rewritten to `SELECT * FROM <view>`.
+/// An option value as text, without the quotes of string literals.
+fn option_value(value: &Expr) -> String {
+    match value {
+        Expr::Value(value) => match &value.value {
+            Value::SingleQuotedString(text) | Value::DoubleQuotedString(text) => text.clone(),
+            other => other.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+fn refreshed_view_name(query: &Query) -> Option<&ObjectName> {
+    let SetExpr::Select(select) = query.body.as_ref() else {
+        return None;
+    };
+    match &select.from.first()?.relation {
+        TableFactor::Table { name, .. } => Some(name),
+        _ => None,
+    }
+}
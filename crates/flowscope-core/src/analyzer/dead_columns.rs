@@ -0,0 +1,164 @@
+//! Project-wide detection of dead columns.
+//!
+//! A dead column is an output column of an intermediate model (a table or view that one
+//! statement writes and a later statement reads) that nothing downstream consumes. Terminal
+//! models, which no statement reads, are skipped since their columns are the product.
+//!
+//! Downstream usage is taken from column-level data flow in the global lineage. Columns
+//! referenced only in predicates (WHERE, JOIN ... ON) do not get column edges, so a column
+//! whose name appears in a consuming statement's predicates is also treated as used.
+
+use super::transform::TableFilter;
+use crate::types::{EdgeType, GlobalLineage, NodeType, StatementLineage};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+/// A column of an intermediate model with no downstream consumer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DeadColumn {
+    /// Qualified name of the owning table or view.
+    pub(crate) table: String,
+    pub(crate) column: String,
+    /// Statement that produces the owning table.
+    pub(crate) statement_index: usize,
+}
+
+#[derive(Default)]
+struct Relation {
+    name: String,
+    producer: Option<usize>,
+    consumers: Vec<usize>,
+}
+
+/// Find dead columns across all statements, skipping tables matching `terminal`.
+///
+/// Results are sorted by table and column name.
+pub(crate) fn find_dead_columns(
+    statements: &[StatementLineage],
+    global_lineage: &GlobalLineage,
+    terminal: &TableFilter,
+) -> Vec<DeadColumn> {
+    let relations = collect_relations(statements);
+
+    let used: HashSet<&Arc<str>> = global_lineage
+        .edges
+        .iter()
+        .filter(|e| matches!(e.edge_type, EdgeType::DataFlow | EdgeType::Derivation))
+        .map(|e| &e.from)
+        .collect();
+
+    let column_labels: HashMap<&Arc<str>, &Arc<str>> = global_lineage
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Column)
+        .map(|n| (&n.id, &n.label))
+        .collect();
+
+    let mut owned: BTreeMap<&Arc<str>, Vec<&Arc<str>>> = BTreeMap::new();
+    for edge in &global_lineage.edges {
+        if edge.edge_type == EdgeType::Ownership && column_labels.contains_key(&edge.to) {
+            let columns = owned.entry(&edge.from).or_default();
+            if !columns.contains(&&edge.to) {
+                columns.push(&edge.to);
+            }
+        }
+    }
+
+    let mut dead = Vec::new();
+    for (table_id, columns) in owned {
+        let Some(relation) = relations.get(table_id) else {
+            continue;
+        };
+        let Some(producer) = relation.producer else {
+            continue;
+        };
+        if relation.consumers.is_empty() || terminal.matches(&relation.name) {
+            continue;
+        }
+
+        let predicate_idents = predicate_identifiers(statements, &relation.consumers);
+        for column_id in columns {
+            if used.contains(column_id) {
+                continue;
+            }
+            let column = column_labels[column_id];
+            if predicate_idents.contains(&column.to_lowercase()) {
+                continue;
+            }
+            dead.push(DeadColumn {
+                table: relation.name.clone(),
+                column: column.to_string(),
+                statement_index: producer,
+            });
+        }
+    }
+
+    dead.sort_by(|a, b| (&a.table, &a.column).cmp(&(&b.table, &b.column)));
+    dead.dedup();
+    dead
+}
+
+/// Index table and view nodes by ID with the statements that write and read them.
+///
+/// A statement writes a relation when another relation flows into it; any other
+/// statement that mentions the relation after it was written reads it.
+fn collect_relations(statements: &[StatementLineage]) -> HashMap<Arc<str>, Relation> {
+    let mut relations: HashMap<Arc<str>, Relation> = HashMap::new();
+
+    for lineage in statements {
+        let relation_ids: HashSet<&Arc<str>> = lineage
+            .nodes
+            .iter()
+            .filter(|n| n.node_type.is_table_or_view())
+            .map(|n| &n.id)
+            .collect();
+        let written: HashSet<&Arc<str>> = lineage
+            .edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::DataFlow && relation_ids.contains(&e.from))
+            .map(|e| &e.to)
+            .collect();
+
+        for node in lineage
+            .nodes
+            .iter()
+            .filter(|n| n.node_type.is_table_or_view())
+        {
+            let relation = relations.entry(node.id.clone()).or_default();
+            if relation.name.is_empty() {
+                relation.name = node
+                    .qualified_name
+                    .as_deref()
+                    .unwrap_or(&node.label)
+                    .to_string();
+            }
+            if written.contains(&node.id) {
+                relation.producer = Some(lineage.statement_index);
+            } else if relation.producer.is_some() {
+                relation.consumers.push(lineage.statement_index);
+            }
+        }
+    }
+
+    relations
+}
+
+/// Lowercased identifiers referenced by filter predicates and join conditions.
+fn predicate_identifiers(statements: &[StatementLineage], indices: &[usize]) -> HashSet<String> {
+    statements
+        .iter()
+        .filter(|lineage| indices.contains(&lineage.statement_index))
+        .flat_map(|lineage| &lineage.nodes)
+        .flat_map(|node| {
+            node.filters
+                .iter()
+                .map(|f| f.expression.as_str())
+                .chain(node.join_condition.as_deref())
+        })
+        .flat_map(|expr| {
+            expr.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .filter(|token| !token.is_empty())
+                .map(str::to_lowercase)
+        })
+        .collect()
+}
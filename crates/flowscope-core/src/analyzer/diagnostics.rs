@@ -1,4 +1,4 @@
-use super::context::StatementContext;
+use super::context::{root_column, StatementContext};
 use super::Analyzer;
 
 impl<'a> Analyzer<'a> {
@@ -17,9 +17,9 @@ impl<'a> Analyzer<'a> {
         canonical: &str,
         column: &str,
     ) {
-        if let Some(issue) = self
-            .schema
-            .validate_column(canonical, column, ctx.statement_index)
+        if let Some(issue) =
+            self.schema
+                .validate_column(canonical, root_column(column), ctx.statement_index)
         {
             self.issues.push(issue);
         }
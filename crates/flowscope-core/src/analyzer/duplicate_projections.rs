@@ -0,0 +1,127 @@
+//! SELECT lists that project the same expression more than once (`SELECT id, id, name`).
+//!
+//! A repeated expression is usually a copy-paste slip. Expressions are compared by
+//! their syntax tree, ignoring layout. Repeats under different aliases
+//! (`SELECT amount AS gross, amount AS net`) tend to be deliberate and are only
+//! reported with `include_aliased_duplicate_projections`.
+
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{Expr, Ident, Query, SelectItem, SetExpr, Statement, Visit, Visitor};
+
+use super::Analyzer;
+use crate::types::{issue_codes, Issue};
+
+impl<'a> Analyzer<'a> {
+    /// With `check_duplicate_projections`, warns about every expression projected
+    /// more than once by a SELECT in `statement`.
+    pub(super) fn check_duplicate_projections(
+        &mut self,
+        statement: &Statement,
+        statement_index: usize,
+    ) {
+        let Some(options) = self.request.options.as_ref() else {
+            return;
+        };
+        if options.check_duplicate_projections != Some(true) {
+            return;
+        }
+        let include_aliased = options.include_aliased_duplicate_projections == Some(true);
+
+        let mut collector = DuplicateProjectionCollector::default();
+        let _ = statement.visit(&mut collector);
+
+        for duplicate in collector.duplicates {
+            let aliases: Vec<String> = duplicate
+                .aliases
+                .iter()
+                .map(|alias| {
+                    alias
+                        .as_ref()
+                        .map_or_else(|| duplicate.expr.clone(), ToString::to_string)
+                })
+                .collect();
+            let aliased = duplicate.aliases.iter().any(Option::is_some)
+                && duplicate.aliases.windows(2).any(|pair| pair[0] != pair[1]);
+            if aliased && !include_aliased {
+                continue;
+            }
+
+            let message = if aliased {
+                format!(
+                    "SELECT projects {} {} times (as {})",
+                    duplicate.expr,
+                    duplicate.aliases.len(),
+                    aliases.join(", ")
+                )
+            } else {
+                format!(
+                    "SELECT projects {} {} times; remove the repeated column",
+                    duplicate.expr,
+                    duplicate.aliases.len()
+                )
+            };
+            self.issues.push(
+                Issue::warning(issue_codes::DUPLICATE_PROJECTION, message)
+                    .with_statement(statement_index),
+            );
+        }
+    }
+}
+
+/// An expression projected more than once by one SELECT, with the alias of each
+/// occurrence.
+struct DuplicateProjection {
+    expr: String,
+    aliases: Vec<Option<Ident>>,
+}
+
+#[derive(Default)]
+struct DuplicateProjectionCollector {
+    duplicates: Vec<DuplicateProjection>,
+}
+
+impl DuplicateProjectionCollector {
+    fn collect(&mut self, body: &SetExpr) {
+        match body {
+            SetExpr::Select(select) => {
+                let mut groups: Vec<(&Expr, Vec<Option<Ident>>)> = Vec::new();
+                for item in &select.projection {
+                    let (expr, alias) = match item {
+                        SelectItem::UnnamedExpr(expr) => (expr, None),
+                        SelectItem::ExprWithAlias { expr, alias } => (expr, Some(alias.clone())),
+                        _ => continue,
+                    };
+                    match groups.iter_mut().find(|(seen, _)| *seen == expr) {
+                        Some((_, aliases)) => aliases.push(alias),
+                        None => groups.push((expr, vec![alias])),
+                    }
+                }
+                self.duplicates.extend(
+                    groups
+                        .into_iter()
+                        .filter(|(_, aliases)| aliases.len() > 1)
+                        .map(|(expr, aliases)| DuplicateProjection {
+                            expr: expr.to_string(),
+                            aliases,
+                        }),
+                );
+            }
+            // Nested queries are visited on their own
+            SetExpr::SetOperation { left, right, .. } => {
+                self.collect(left);
+                self.collect(right);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visitor for DuplicateProjectionCollector {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        self.collect(&query.body);
+        ControlFlow::Continue(())
+    }
+}
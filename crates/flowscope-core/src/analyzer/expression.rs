@@ -16,7 +16,7 @@ use super::functions;
 use super::helpers::check_expr_types;
 use super::Analyzer;
 use crate::generated;
-use crate::types::{AggregationInfo, FilterClauseType};
+use crate::types::{issue_codes, AggregationInfo, FilterClauseType, Issue};
 use crate::Dialect;
 use sqlparser::ast::{self, Expr, FunctionArg, FunctionArgExpr};
 use std::collections::HashSet;
@@ -27,6 +27,10 @@ use tracing::debug;
 /// on maliciously crafted or deeply nested SQL expressions.
 pub(super) const MAX_RECURSION_DEPTH: usize = 100;
 
+/// Pseudo-columns available in `CONNECT BY` hierarchical queries (Oracle, Snowflake).
+const HIERARCHICAL_PSEUDOCOLUMNS: &[&str] =
+    &["LEVEL", "ROWNUM", "CONNECT_BY_ISLEAF", "CONNECT_BY_ISCYCLE"];
+
 /// Analyzes SQL expressions to extract column references, detect aggregations,
 /// and capture filter predicates.
 ///
@@ -72,6 +76,120 @@ impl<'a, 'b> ExpressionAnalyzer<'a, 'b> {
         self.analyzer.issues.extend(issues);
     }
 
+    /// Warns about `NOT IN (subquery)` whose projected column may be NULL.
+    ///
+    /// A single NULL returned by the subquery makes `x NOT IN (...)` unknown for
+    /// every row, so the predicate silently matches nothing. The warning is skipped
+    /// only when the column is known to be non-null: declared `NOT NULL` (or primary
+    /// key) in the schema, filtered with `IS NOT NULL` in the subquery, or a non-null
+    /// expression such as a literal or `COUNT(...)`.
+    fn check_not_in_nullable(&mut self, subquery: &ast::Query) {
+        let ast::SetExpr::Select(select) = subquery.body.as_ref() else {
+            return;
+        };
+        let [item] = select.projection.as_slice() else {
+            return;
+        };
+        let expr = match item {
+            ast::SelectItem::UnnamedExpr(expr) | ast::SelectItem::ExprWithAlias { expr, .. } => {
+                expr
+            }
+            _ => return,
+        };
+
+        let message = match self.projected_nullability(select, expr) {
+            Some(false) => return,
+            Some(true) => format!(
+                "NOT IN subquery returns nullable column '{expr}'; a single NULL makes the predicate match no rows. Use NOT EXISTS instead"
+            ),
+            None => format!(
+                "NOT IN subquery returns '{expr}', which may be NULL; a single NULL makes the predicate match no rows. Use NOT EXISTS or filter with IS NOT NULL"
+            ),
+        };
+        self.analyzer.issues.push(
+            Issue::warning(issue_codes::NOT_IN_NULLABLE_SUBQUERY, message)
+                .with_statement(self.ctx.statement_index),
+        );
+    }
+
+    /// Whether a subquery's projected expression may be NULL (`None` when unknown).
+    fn projected_nullability(&self, select: &ast::Select, expr: &Expr) -> Option<bool> {
+        let (qualifier, column) = match expr {
+            Expr::Nested(inner) => return self.projected_nullability(select, inner),
+            Expr::Value(value) => return Some(matches!(value.value, ast::Value::Null)),
+            Expr::Function(func) if func.name.to_string().eq_ignore_ascii_case("COUNT") => {
+                return Some(false)
+            }
+            Expr::Identifier(ident) => (None, ident),
+            Expr::CompoundIdentifier(parts) if parts.len() >= 2 => {
+                (Some(&parts[parts.len() - 2]), &parts[parts.len() - 1])
+            }
+            _ => return None,
+        };
+
+        if let Some(selection) = &select.selection {
+            if self.filters_not_null(selection, &column.value) {
+                return Some(false);
+            }
+        }
+
+        let mut tables = select.from.iter().flat_map(|from| {
+            std::iter::once(&from.relation).chain(from.joins.iter().map(|join| &join.relation))
+        });
+        let table_name = match qualifier {
+            Some(qualifier) => {
+                let qualifier = self.analyzer.normalize_identifier(&qualifier.value);
+                tables.find_map(|factor| match factor {
+                    ast::TableFactor::Table { name, alias, .. } => {
+                        let visible = match alias {
+                            Some(alias) => &alias.name.value,
+                            None => &name.0.last()?.as_ident()?.value,
+                        };
+                        (self.analyzer.normalize_identifier(visible) == qualifier).then_some(name)
+                    }
+                    _ => None,
+                })?
+            }
+            None => match (tables.next(), tables.next()) {
+                (Some(ast::TableFactor::Table { name, .. }), None) => name,
+                _ => return None,
+            },
+        };
+
+        let canonical = self
+            .analyzer
+            .canonicalize_table_reference(&table_name.to_string())
+            .canonical;
+        self.analyzer
+            .schema
+            .lookup_column_nullable(&canonical, &column.value)
+    }
+
+    /// Returns true if `predicate` requires `column IS NOT NULL` in one of its top-level conjuncts.
+    fn filters_not_null(&self, predicate: &Expr, column: &str) -> bool {
+        match predicate {
+            Expr::BinaryOp {
+                left,
+                op: ast::BinaryOperator::And,
+                right,
+            } => self.filters_not_null(left, column) || self.filters_not_null(right, column),
+            Expr::Nested(inner) => self.filters_not_null(inner, column),
+            Expr::IsNotNull(inner) => {
+                let name = match inner.as_ref() {
+                    Expr::Identifier(ident) => ident,
+                    Expr::CompoundIdentifier(parts) => match parts.last() {
+                        Some(ident) => ident,
+                        None => return false,
+                    },
+                    _ => return false,
+                };
+                self.analyzer.normalize_identifier(&name.value)
+                    == self.analyzer.normalize_identifier(column)
+            }
+            _ => false,
+        }
+    }
+
     /// Extracts column references from an expression and validates each one.
     /// Also records column references for implied schema tracking.
     fn validate_column_refs(&mut self, expr: &Expr) {
@@ -109,7 +227,12 @@ impl<'a, 'b> ExpressionAnalyzer<'a, 'b> {
 
         match expr {
             Expr::Subquery(query) => self.analyzer.analyze_query(self.ctx, query, None),
-            Expr::InSubquery { subquery, .. } => {
+            Expr::InSubquery {
+                subquery, negated, ..
+            } => {
+                if *negated {
+                    self.check_not_in_nullable(subquery);
+                }
                 self.analyzer.analyze_query(self.ctx, subquery, None)
             }
             Expr::Exists { subquery, .. } => self.analyzer.analyze_query(self.ctx, subquery, None),
@@ -161,14 +284,61 @@ impl<'a, 'b> ExpressionAnalyzer<'a, 'b> {
     /// when you have access to an `ExpressionAnalyzer` instance.
     pub(crate) fn extract_column_refs_with_warning(&mut self, expr: &Expr) -> Vec<ColumnRef> {
         let dialect = self.analyzer.request.dialect;
-        let (refs, depth_limited) = Self::extract_column_refs_with_dialect(expr, dialect);
+        let (mut refs, depth_limited) = Self::extract_column_refs_with_dialect(expr, dialect);
         if depth_limited {
             self.analyzer
                 .emit_depth_limit_warning(self.ctx.statement_index);
         }
+        self.resolve_struct_field_refs(&mut refs);
+        if self.ctx.in_hierarchical_query {
+            refs.retain(|r| {
+                r.table.is_some()
+                    || !HIERARCHICAL_PSEUDOCOLUMNS
+                        .iter()
+                        .any(|p| p.eq_ignore_ascii_case(&r.column))
+            });
+        }
         refs
     }
 
+    /// Reinterprets dotted references whose qualifier is not a relation in scope as
+    /// struct field access, e.g. `u.address.city` or `address.city`.
+    ///
+    /// The longest leading prefix that names a relation becomes the qualifier and the
+    /// remaining parts the column path (`u` + `address.city`). Without such a prefix
+    /// the whole path is an unqualified column (`address.city`).
+    fn resolve_struct_field_refs(&self, refs: &mut [ColumnRef]) {
+        if self
+            .ctx
+            .scope_stack
+            .iter()
+            .all(|scope| scope.tables.is_empty())
+        {
+            return;
+        }
+
+        for col_ref in refs {
+            let Some(table) = col_ref.table.as_deref() else {
+                continue;
+            };
+            if self.analyzer.is_relation_qualifier(self.ctx, table) {
+                continue;
+            }
+
+            let parts: Vec<&str> = table.split('.').collect();
+            let relation_len = (1..parts.len())
+                .rev()
+                .find(|&len| {
+                    self.analyzer
+                        .is_relation_qualifier(self.ctx, &parts[..len].join("."))
+                })
+                .unwrap_or(0);
+            let path = parts[relation_len..].join(".");
+            col_ref.column = format!("{path}.{}", col_ref.column);
+            col_ref.table = (relation_len > 0).then(|| parts[..relation_len].join("."));
+        }
+    }
+
     /// Extracts all column references from an expression (dialect-aware).
     ///
     /// Returns a vector of `ColumnRef` structs representing each column
@@ -209,25 +379,23 @@ impl<'a, 'b> ExpressionAnalyzer<'a, 'b> {
                     column: ident.value.clone(),
                 });
             }
-            Expr::CompoundIdentifier(parts) => {
-                if parts.len() >= 2 {
-                    let table = parts[..parts.len() - 1]
-                        .iter()
-                        .map(|i| i.value.as_str())
-                        .collect::<Vec<_>>()
-                        .join(".");
-                    let column = parts.last().unwrap().value.clone();
-                    refs.push(ColumnRef {
-                        table: Some(table),
-                        column,
-                    });
-                }
+            Expr::CompoundIdentifier(parts) if parts.len() >= 2 => {
+                let table = parts[..parts.len() - 1]
+                    .iter()
+                    .map(|i| i.value.as_str())
+                    .collect::<Vec<_>>()
+                    .join(".");
+                let column = parts.last().unwrap().value.clone();
+                refs.push(ColumnRef {
+                    table: Some(table),
+                    column,
+                });
             }
             Expr::BinaryOp { left, right, .. } => {
                 depth_limited |= Self::collect_column_refs(left, refs, dialect, next_depth);
                 depth_limited |= Self::collect_column_refs(right, refs, dialect, next_depth);
             }
-            Expr::UnaryOp { expr, .. } => {
+            Expr::UnaryOp { expr, .. } | Expr::Prior(expr) => {
                 depth_limited |= Self::collect_column_refs(expr, refs, dialect, next_depth);
             }
             Expr::Function(func) => {
@@ -259,6 +427,16 @@ impl<'a, 'b> ExpressionAnalyzer<'a, 'b> {
                     ast::FunctionArguments::Subquery(_) => {}
                     ast::FunctionArguments::None => {}
                 }
+                // Window partition/order columns shape the result, so they are sources too
+                if let Some(ast::WindowType::WindowSpec(spec)) = &func.over {
+                    for e in &spec.partition_by {
+                        depth_limited |= Self::collect_column_refs(e, refs, dialect, next_depth);
+                    }
+                    for order in &spec.order_by {
+                        depth_limited |=
+                            Self::collect_column_refs(&order.expr, refs, dialect, next_depth);
+                    }
+                }
             }
             Expr::Case {
                 operand,
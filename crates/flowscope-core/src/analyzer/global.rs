@@ -1,10 +1,12 @@
+use super::graph_metrics::build_graph_metrics;
 use super::helpers::parse_canonical_name;
 use super::Analyzer;
 use crate::types::{
-    GlobalEdge, GlobalLineage, GlobalNode, IssueCount, NodeType, ResolvedColumnSchema,
-    ResolvedSchemaMetadata, ResolvedSchemaTable, StatementRef, Summary,
+    issue_codes, GlobalEdge, GlobalLineage, GlobalNode, Issue, IssueCount, NodeType,
+    ResolvedColumnSchema, ResolvedSchemaMetadata, ResolvedSchemaTable, StatementLineage,
+    StatementRef, Summary,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 impl<'a> Analyzer<'a> {
@@ -16,8 +18,20 @@ impl<'a> Analyzer<'a> {
             .as_ref()
             .and_then(|o| o.hide_ctes)
             .unwrap_or(false);
+        let inline_ctes = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.inline_ctes)
+            .unwrap_or(false);
 
-        let statements = if hide_ctes {
+        let mut statements = if inline_ctes {
+            let mut inlined = self.statement_lineages.clone();
+            for lineage in &mut inlined {
+                super::transform::inline_cte_nodes(lineage, self.request.dialect);
+            }
+            inlined
+        } else if hide_ctes {
             let mut filtered = self.statement_lineages.clone();
             for lineage in &mut filtered {
                 super::transform::filter_cte_nodes(lineage);
@@ -27,16 +41,179 @@ impl<'a> Analyzer<'a> {
             self.statement_lineages.clone()
         };
 
-        let global_lineage = self.build_global_lineage_from(&statements);
-        let summary = self.build_summary(&global_lineage);
+        let mut issues = self.issues.clone();
+        let ignore_patterns = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.ignore_tables.as_deref())
+            .unwrap_or_default();
+        let ignored_tables = if ignore_patterns.is_empty() {
+            Vec::new()
+        } else {
+            self.filter_ignored_tables(&mut statements, ignore_patterns, &mut issues)
+        };
+
+        let unresolved_as_errors = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.unresolved_as_errors)
+            .unwrap_or(false);
+        // Without a schema every reference is unresolved, so there is nothing to check against
+        if unresolved_as_errors && self.request.schema.is_some() {
+            for issue in &mut issues {
+                if issue.code == issue_codes::UNRESOLVED_REFERENCE
+                    || issue.code == issue_codes::UNKNOWN_COLUMN
+                {
+                    issue.severity = crate::Severity::Error;
+                }
+            }
+        }
+
+        let redact_literals = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.redact_literals)
+            .unwrap_or(false);
+        if redact_literals {
+            for lineage in &mut statements {
+                super::transform::redact_statement_literals(lineage, self.request.dialect);
+            }
+        }
+
+        let mut global_lineage =
+            build_global_lineage(&statements, self.tracker.build_cross_statement_edges());
+        if !ignored_tables.is_empty() {
+            // Cross-statement edges come from the tracker and may still reference ignored tables
+            let node_ids: HashSet<Arc<str>> =
+                global_lineage.nodes.iter().map(|n| n.id.clone()).collect();
+            global_lineage
+                .edges
+                .retain(|e| node_ids.contains(&e.from) && node_ids.contains(&e.to));
+        }
+        let report_dead_columns = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.report_dead_columns)
+            .unwrap_or(false);
+        if report_dead_columns {
+            self.report_dead_columns(&statements, &global_lineage, &mut issues);
+        }
+        let check_qualification = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.check_qualification_consistency)
+            .unwrap_or(false);
+        if check_qualification {
+            issues.extend(super::qualification::check_qualification_consistency(
+                &statements,
+                &global_lineage,
+            ));
+        }
+        let mut summary = build_summary(&self.statement_lineages, &global_lineage, &issues);
+        summary.timings = self.timings.clone();
         let resolved_schema = self.build_resolved_schema();
+        let graph_metrics = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.graph_metrics)
+            .unwrap_or(false)
+            .then(|| build_graph_metrics(&global_lineage));
 
         crate::AnalyzeResult {
             statements,
             global_lineage,
-            issues: self.issues.clone(),
+            issues,
             summary,
             resolved_schema,
+            rendered_sql: self.rendered_sql.clone(),
+            graph_metrics,
+        }
+    }
+
+    /// Remove tables matching the `ignore_tables` patterns from every statement.
+    ///
+    /// Returns the distinct names of removed tables and records them in an info issue.
+    fn filter_ignored_tables(
+        &self,
+        statements: &mut [StatementLineage],
+        patterns: &[String],
+        issues: &mut Vec<Issue>,
+    ) -> Vec<String> {
+        let (filter, invalid) = super::transform::TableFilter::new(patterns);
+        for pattern in invalid {
+            issues.push(Issue::warning(
+                issue_codes::INVALID_REQUEST,
+                format!("Invalid ignore_tables pattern '{pattern}'"),
+            ));
+        }
+        if filter.is_empty() {
+            return Vec::new();
+        }
+
+        let mut ignored: Vec<String> = statements
+            .iter_mut()
+            .flat_map(|lineage| super::transform::filter_ignored_tables(lineage, &filter))
+            .collect();
+        ignored.sort();
+        ignored.dedup();
+
+        if !ignored.is_empty() {
+            issues.push(Issue::info(
+                issue_codes::IGNORED_TABLES,
+                format!(
+                    "Ignored {} table(s) matching ignore_tables: {}",
+                    ignored.len(),
+                    ignored.join(", ")
+                ),
+            ));
+        }
+        ignored
+    }
+
+    /// Add an info issue for each intermediate-model column that nothing downstream uses.
+    fn report_dead_columns(
+        &self,
+        statements: &[StatementLineage],
+        global_lineage: &GlobalLineage,
+        issues: &mut Vec<Issue>,
+    ) {
+        let terminal_patterns = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.terminal_tables.as_deref())
+            .unwrap_or_default();
+        let (terminal, invalid) = super::transform::TableFilter::new(terminal_patterns);
+        for pattern in invalid {
+            issues.push(Issue::warning(
+                issue_codes::INVALID_REQUEST,
+                format!("Invalid terminal_tables pattern '{pattern}'"),
+            ));
+        }
+
+        for dead in super::dead_columns::find_dead_columns(statements, global_lineage, &terminal) {
+            let mut issue = Issue::info(
+                issue_codes::DEAD_COLUMN,
+                format!(
+                    "Column '{}.{}' is never used downstream",
+                    dead.table, dead.column
+                ),
+            )
+            .with_statement(dead.statement_index);
+            if let Some(source_name) = statements
+                .iter()
+                .find(|s| s.statement_index == dead.statement_index)
+                .and_then(|s| s.source_name.as_deref())
+            {
+                issue = issue.with_source_name(source_name);
+            }
+            issues.push(issue);
         }
     }
 
@@ -59,6 +236,7 @@ impl<'a> Analyzer<'a> {
                         origin: Some(entry.origin),
                         is_primary_key: col.is_primary_key,
                         foreign_key: col.foreign_key.clone(),
+                        nullable: col.nullable,
                     })
                     .collect();
 
@@ -81,131 +259,146 @@ impl<'a> Analyzer<'a> {
 
         Some(ResolvedSchemaMetadata { tables })
     }
+}
 
-    fn build_global_lineage_from(
-        &self,
-        statements: &[crate::types::StatementLineage],
-    ) -> GlobalLineage {
-        let mut global_nodes: HashMap<Arc<str>, GlobalNode> = HashMap::new();
-        let mut global_edges: Vec<GlobalEdge> = Vec::new();
-
-        // Collect all nodes from all statements
-        for lineage in statements {
-            for node in &lineage.nodes {
-                let canonical = node.qualified_name.clone().unwrap_or(node.label.clone());
-                let canonical_name = parse_canonical_name(&canonical);
-
-                global_nodes
-                    .entry(node.id.clone())
-                    .and_modify(|existing| {
-                        existing.statement_refs.push(StatementRef {
-                            statement_index: lineage.statement_index,
-                            node_id: Some(node.id.clone()),
-                        });
-                    })
-                    .or_insert_with(|| GlobalNode {
-                        id: node.id.clone(),
-                        node_type: node.node_type,
-                        label: node.label.clone(),
-                        canonical_name,
-                        statement_refs: vec![StatementRef {
-                            statement_index: lineage.statement_index,
-                            node_id: Some(node.id.clone()),
-                        }],
-                        metadata: None,
-                        resolution_source: node.resolution_source,
-                    });
-            }
+/// Builds the global lineage graph from statement lineages and the cross-statement
+/// edges between them.
+///
+/// Nodes keep the order in which statements first mention them.
+pub(super) fn build_global_lineage(
+    statements: &[StatementLineage],
+    cross_statement_edges: Vec<GlobalEdge>,
+) -> GlobalLineage {
+    let mut global_nodes: Vec<GlobalNode> = Vec::new();
+    let mut node_positions: HashMap<Arc<str>, usize> = HashMap::new();
+    let mut global_edges: Vec<GlobalEdge> = Vec::new();
 
-            // Collect edges
-            for edge in &lineage.edges {
-                global_edges.push(GlobalEdge {
-                    id: edge.id.clone(),
-                    from: edge.from.clone(),
-                    to: edge.to.clone(),
-                    edge_type: edge.edge_type,
-                    producer_statement: Some(StatementRef {
-                        statement_index: lineage.statement_index,
-                        node_id: None,
-                    }),
-                    consumer_statement: None,
-                    metadata: None,
-                });
+    // Collect all nodes from all statements
+    for lineage in statements {
+        for node in &lineage.nodes {
+            let statement_ref = StatementRef {
+                statement_index: lineage.statement_index,
+                node_id: Some(node.id.clone()),
+            };
+            if let Some(&position) = node_positions.get(&node.id) {
+                global_nodes[position].statement_refs.push(statement_ref);
+                continue;
             }
-        }
 
-        // Detect cross-statement edges using the tracker
-        global_edges.extend(self.tracker.build_cross_statement_edges());
+            let canonical = node.qualified_name.clone().unwrap_or(node.label.clone());
+            node_positions.insert(node.id.clone(), global_nodes.len());
+            global_nodes.push(GlobalNode {
+                id: node.id.clone(),
+                node_type: node.node_type,
+                label: node.label.clone(),
+                canonical_name: parse_canonical_name(&canonical),
+                statement_refs: vec![statement_ref],
+                metadata: None,
+                resolution_source: node.resolution_source,
+            });
+        }
 
-        GlobalLineage {
-            nodes: global_nodes.into_values().collect(),
-            edges: global_edges,
+        // Collect edges
+        for edge in &lineage.edges {
+            global_edges.push(GlobalEdge {
+                id: edge.id.clone(),
+                from: edge.from.clone(),
+                to: edge.to.clone(),
+                edge_type: edge.edge_type,
+                producer_statement: Some(StatementRef {
+                    statement_index: lineage.statement_index,
+                    node_id: None,
+                }),
+                consumer_statement: None,
+                source_statement: Some(lineage.statement_index),
+                source_file: lineage.source_name.clone(),
+                metadata: None,
+            });
         }
     }
 
-    pub(super) fn build_summary(&self, global_lineage: &GlobalLineage) -> Summary {
-        let error_count = self
-            .issues
-            .iter()
-            .filter(|i| i.severity == crate::Severity::Error)
-            .count();
-        let warning_count = self
-            .issues
-            .iter()
-            .filter(|i| i.severity == crate::Severity::Warning)
-            .count();
-        let info_count = self
-            .issues
-            .iter()
-            .filter(|i| i.severity == crate::Severity::Info)
-            .count();
-
-        let table_count = global_lineage
-            .nodes
-            .iter()
-            .filter(|n| n.node_type.is_table_or_view())
-            .count();
-
-        let cte_count = global_lineage
-            .nodes
-            .iter()
-            .filter(|n| n.node_type == NodeType::Cte)
-            .count();
-
-        let column_count = global_lineage
-            .nodes
-            .iter()
-            .filter(|n| n.node_type == NodeType::Column)
-            .count();
-
-        // Aggregate join count from all statements
-        let join_count: usize = self.statement_lineages.iter().map(|s| s.join_count).sum();
-
-        // Calculate project-level complexity from global lineage
-        // Uses table/CTE counts since GlobalNode doesn't track per-node join info
-        let filter_count: usize = self
-            .statement_lineages
-            .iter()
-            .flat_map(|s| s.nodes.iter())
-            .map(|n| n.filters.len())
-            .sum();
-
-        let complexity_score =
-            calculate_global_complexity(table_count, cte_count, join_count, filter_count);
-
-        Summary {
-            statement_count: self.statement_lineages.len(),
-            table_count: table_count + cte_count, // Keep combined for backwards compat
-            column_count,
-            join_count,
-            complexity_score,
-            issue_count: IssueCount {
-                errors: error_count,
-                warnings: warning_count,
-                infos: info_count,
-            },
-            has_errors: error_count > 0,
-        }
+    // Cross-statement edges are built from table names alone, so their file is
+    // looked up from the statement they are attributed to
+    global_edges.extend(cross_statement_edges.into_iter().map(|mut edge| {
+        edge.source_file = edge.source_statement.and_then(|index| {
+            statements
+                .iter()
+                .find(|lineage| lineage.statement_index == index)
+                .and_then(|lineage| lineage.source_name.clone())
+        });
+        edge
+    }));
+
+    GlobalLineage {
+        nodes: global_nodes,
+        edges: global_edges,
+    }
+}
+
+/// Computes the summary statistics of an analysis result.
+pub(super) fn build_summary(
+    statements: &[StatementLineage],
+    global_lineage: &GlobalLineage,
+    issues: &[Issue],
+) -> Summary {
+    let error_count = issues
+        .iter()
+        .filter(|i| i.severity == crate::Severity::Error)
+        .count();
+    let warning_count = issues
+        .iter()
+        .filter(|i| i.severity == crate::Severity::Warning)
+        .count();
+    let info_count = issues
+        .iter()
+        .filter(|i| i.severity == crate::Severity::Info)
+        .count();
+
+    let table_count = global_lineage
+        .nodes
+        .iter()
+        .filter(|n| n.node_type.is_table_or_view())
+        .count();
+
+    let cte_count = global_lineage
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Cte)
+        .count();
+
+    let column_count = global_lineage
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Column)
+        .count();
+
+    // Aggregate join count from all statements
+    let join_count: usize = statements.iter().map(|s| s.join_count).sum();
+
+    // Calculate project-level complexity from global lineage
+    // Uses table/CTE counts since GlobalNode doesn't track per-node join info
+    let filter_count: usize = statements
+        .iter()
+        .flat_map(|s| s.nodes.iter())
+        .map(|n| n.filters.len())
+        .sum();
+
+    let complexity_score =
+        calculate_global_complexity(table_count, cte_count, join_count, filter_count);
+
+    Summary {
+        statement_count: statements.len(),
+        table_count: table_count + cte_count, // Keep combined for backwards compat
+        column_count,
+        join_count,
+        complexity_score,
+        issue_count: IssueCount {
+            errors: error_count,
+            warnings: warning_count,
+            infos: info_count,
+        },
+        has_errors: error_count > 0,
+        timings: None,
     }
 }
 
@@ -0,0 +1,194 @@
+//! Fan-in, fan-out, and centrality of tables and views (`graph_metrics`).
+//!
+//! Metrics are computed over the table-level data flow of the global lineage, read as
+//! a dependency graph: each relation points to the relations it reads from. Fan-in
+//! counts the relations reading a table and fan-out the relations it reads, so a
+//! source table has no fan-out and a table shared by many models has a high fan-in.
+//! Betweenness (Brandes' algorithm) finds the relations that connect otherwise
+//! separate parts of the pipeline, and PageRank ranks relations by how much of the
+//! graph ultimately depends on them.
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use crate::types::{EdgeType, GlobalLineage, GraphMetrics, TableGraphMetrics};
+
+/// Probability of following a dependency edge rather than jumping to a random relation.
+const DAMPING: f64 = 0.85;
+/// Iteration cap for PageRank; the graphs are small and converge well before this.
+const MAX_ITERATIONS: usize = 100;
+/// PageRank stops once no score moves by more than this.
+const TOLERANCE: f64 = 1e-10;
+
+/// Computes the graph metrics of every table and view in `global`.
+pub(crate) fn build_graph_metrics(global: &GlobalLineage) -> GraphMetrics {
+    let relations: Vec<_> = global
+        .nodes
+        .iter()
+        .filter(|node| node.node_type.is_table_or_view())
+        .collect();
+    let index: HashMap<&str, usize> = relations
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id.as_ref(), i))
+        .collect();
+
+    // depends_on[i]: relations that relation i reads from
+    let mut depends_on: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); relations.len()];
+    for edge in &global.edges {
+        if !matches!(edge.edge_type, EdgeType::DataFlow | EdgeType::Derivation) {
+            continue;
+        }
+        if let (Some(&source), Some(&target)) =
+            (index.get(edge.from.as_ref()), index.get(edge.to.as_ref()))
+        {
+            if source != target {
+                depends_on[target].insert(source);
+            }
+        }
+    }
+    let graph: Vec<Vec<usize>> = depends_on
+        .into_iter()
+        .map(|deps| deps.into_iter().collect())
+        .collect();
+
+    let mut fan_in = vec![0; graph.len()];
+    for deps in &graph {
+        for &dep in deps {
+            fan_in[dep] += 1;
+        }
+    }
+    let betweenness = betweenness(&graph);
+    let page_rank = page_rank(&graph);
+
+    let mut tables: Vec<TableGraphMetrics> = relations
+        .iter()
+        .enumerate()
+        .map(|(i, node)| TableGraphMetrics {
+            id: node.id.to_string(),
+            name: node.canonical_name.to_qualified_string(),
+            node_type: node.node_type,
+            fan_in: fan_in[i],
+            fan_out: graph[i].len(),
+            betweenness: round(betweenness[i]),
+            page_rank: round(page_rank[i]),
+        })
+        .collect();
+    tables.sort_by(|a, b| b.fan_in.cmp(&a.fan_in).then_with(|| a.name.cmp(&b.name)));
+    GraphMetrics { tables }
+}
+
+/// Normalized betweenness centrality of each node of a directed, unweighted graph
+/// (Brandes, 2001).
+fn betweenness(graph: &[Vec<usize>]) -> Vec<f64> {
+    let n = graph.len();
+    let mut centrality = vec![0.0; n];
+
+    for source in 0..n {
+        // Breadth-first search counting shortest paths from `source`
+        let mut order = Vec::with_capacity(n);
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut paths = vec![0.0; n];
+        let mut distance: Vec<Option<usize>> = vec![None; n];
+        paths[source] = 1.0;
+        distance[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            let next_distance = distance[node].map(|d| d + 1);
+            for &next in &graph[node] {
+                if distance[next].is_none() {
+                    distance[next] = next_distance;
+                    queue.push_back(next);
+                }
+                if distance[next] == next_distance {
+                    paths[next] += paths[node];
+                    predecessors[next].push(node);
+                }
+            }
+        }
+
+        // Accumulate each node's share of the paths in reverse BFS order
+        let mut dependency = vec![0.0; n];
+        for &node in order.iter().rev() {
+            for &pred in &predecessors[node] {
+                dependency[pred] += paths[pred] / paths[node] * (1.0 + dependency[node]);
+            }
+            if node != source {
+                centrality[node] += dependency[node];
+            }
+        }
+    }
+
+    if n > 2 {
+        let pairs = ((n - 1) * (n - 2)) as f64;
+        for value in &mut centrality {
+            *value /= pairs;
+        }
+    }
+    centrality
+}
+
+/// PageRank of each node, with the rank of nodes without outgoing edges spread
+/// evenly over all nodes.
+fn page_rank(graph: &[Vec<usize>]) -> Vec<f64> {
+    let n = graph.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let uniform = 1.0 / n as f64;
+    let mut rank = vec![uniform; n];
+
+    for _ in 0..MAX_ITERATIONS {
+        let dangling: f64 = graph
+            .iter()
+            .zip(&rank)
+            .filter(|(edges, _)| edges.is_empty())
+            .map(|(_, r)| r)
+            .sum();
+        let base = (1.0 - DAMPING) * uniform + DAMPING * dangling * uniform;
+        let mut next = vec![base; n];
+        for (node, edges) in graph.iter().enumerate() {
+            let share = DAMPING * rank[node] / edges.len().max(1) as f64;
+            for &target in edges {
+                next[target] += share;
+            }
+        }
+
+        let delta = next
+            .iter()
+            .zip(&rank)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f64::max);
+        rank = next;
+        if delta < TOLERANCE {
+            break;
+        }
+    }
+    rank
+}
+
+/// Rounds a score to six decimals so the JSON output is stable across platforms.
+fn round(value: f64) -> f64 {
+    (value * 1e6).round() / 1e6
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_betweenness_of_a_chain() {
+        // 0 -> 1 -> 2: only the middle node lies between the others
+        let graph = vec![vec![1], vec![2], vec![]];
+        assert_eq!(betweenness(&graph), vec![0.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_page_rank_favours_shared_dependencies() {
+        // 1, 2 and 3 all depend on 0
+        let graph = vec![vec![], vec![0], vec![0], vec![0]];
+        let rank = page_rank(&graph);
+        assert!((rank.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(rank[1..].iter().all(|&r| rank[0] > r));
+    }
+}
@@ -44,6 +44,19 @@ pub fn extract_column_constraints(
     (is_pk, fk_ref)
 }
 
+/// Nullability declared by column options: `NOT NULL` and primary keys are not
+/// nullable, an explicit `NULL` is. Returns `None` when nothing is declared.
+fn column_nullability(options: &[ColumnOptionDef], is_primary_key: bool) -> Option<bool> {
+    if is_primary_key {
+        return Some(false);
+    }
+    options.iter().find_map(|opt| match opt.option {
+        ColumnOption::NotNull => Some(false),
+        ColumnOption::Null => Some(true),
+        _ => None,
+    })
+}
+
 /// Extract table-level constraints (composite PRIMARY KEY, FOREIGN KEY, UNIQUE).
 ///
 /// Returns a tuple of (pk_column_names, constraint_infos).
@@ -134,11 +147,13 @@ pub fn build_column_schemas_with_constraints(
                 } else {
                     None
                 };
+            let nullable = column_nullability(&c.options, is_primary_key.is_some());
             ColumnSchema {
                 name: c.name.value.clone(),
                 data_type: Some(c.data_type.to_string()),
                 is_primary_key,
                 foreign_key: fk_ref,
+                nullable,
             }
         })
         .collect();
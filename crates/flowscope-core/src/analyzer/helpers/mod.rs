@@ -6,6 +6,7 @@ mod query;
 mod span;
 mod type_check;
 mod types;
+mod window;
 
 pub use alias::{alias_visibility_warning, lateral_alias_warning};
 pub use constraints::{
@@ -16,13 +17,14 @@ pub use id::{
 };
 pub use naming::{
     canonical_name_from_object_name, extract_simple_name, extract_simple_name_from_object_name,
-    ident_value, is_quoted_identifier, parse_canonical_name, split_qualified_identifiers,
-    unquote_identifier,
+    ident_value, identifier_quote_chars, is_quoted_identifier, parse_canonical_name,
+    split_qualified_identifiers, unquote_identifier,
 };
 pub use query::{classify_query_type, is_simple_column_ref};
 pub use span::{
     find_cte_definition_span, find_derived_table_alias_span, find_identifier_span,
     line_col_to_offset,
 };
-pub use type_check::check_expr_types;
+pub use type_check::{are_types_comparable, check_expr_types};
 pub use types::{canonical_type_from_data_type, infer_expr_type, normalize_schema_type};
+pub use window::resolve_named_windows;
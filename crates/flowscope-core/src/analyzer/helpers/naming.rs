@@ -1,6 +1,6 @@
 use sqlparser::ast::{Ident, ObjectName};
 
-use crate::types::CanonicalName;
+use crate::types::{CanonicalName, Dialect};
 
 // =============================================================================
 // ObjectName-based helpers (work directly with AST types)
@@ -143,6 +143,17 @@ pub fn split_qualified_identifiers(name: &str) -> Vec<String> {
     }
 }
 
+/// Opening and closing characters of the identifier quotes `dialect` accepts
+/// (`"` for Postgres, `` ` `` for MySQL, `[` and `"` for SQL Server), the one it
+/// writes by default first.
+pub fn identifier_quote_chars(dialect: Dialect) -> impl Iterator<Item = (char, char)> {
+    dialect
+        .identifier_quotes()
+        .iter()
+        .filter_map(|quote| quote.chars().next())
+        .map(|open| (open, if open == '[' { ']' } else { open }))
+}
+
 pub fn is_quoted_identifier(part: &str) -> bool {
     let trimmed = part.trim();
     if trimmed.len() < 2 {
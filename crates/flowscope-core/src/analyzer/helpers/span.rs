@@ -42,11 +42,8 @@ pub fn find_identifier_span(sql: &str, identifier: &str, search_start: usize) ->
 
     // For qualified names like "schema.table", try to find the full pattern
     if identifier.contains('.') {
-        if let Some(pos) = find_qualified_name(search_text, identifier) {
-            return Some(Span::new(
-                search_start + pos,
-                search_start + pos + identifier.len(),
-            ));
+        if let Some((start, end)) = find_qualified_name(search_text, identifier) {
+            return Some(Span::new(search_start + start, search_start + end));
         }
     }
 
@@ -287,12 +284,34 @@ fn match_identifier_at(text: &str, pos: usize, identifier: &str) -> Option<(usiz
     None
 }
 
+/// Wraps an escaped identifier pattern in `\b` at each end that is a word character.
+///
+/// `\b` cannot match before `#tmp` (SQL Server temp tables) or after a quoted name
+/// ending in punctuation, since there is no word character on either side there.
+fn with_word_boundaries(identifier: &str, pattern: &str) -> String {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let start = if is_word(identifier.chars().next()) {
+        r"\b"
+    } else {
+        ""
+    };
+    let end = if is_word(identifier.chars().last()) {
+        r"\b"
+    } else {
+        ""
+    };
+    format!("{start}{pattern}{end}")
+}
+
 /// Finds an identifier at a word boundary (not part of another word).
 /// Word boundaries consider underscores as part of identifiers (SQL convention).
 fn find_word_boundary_match(text: &str, identifier: &str) -> Option<usize> {
     // For simple identifiers, use word boundary matching
     // Note: \b in regex considers underscore as a word character, which is correct for SQL
-    let pattern = format!(r"(?i)\b{}\b", regex::escape(identifier));
+    let pattern = format!(
+        "(?i){}",
+        with_word_boundaries(identifier, &regex::escape(identifier))
+    );
 
     // Try to compile the pattern
     if let Ok(re) = Regex::new(&pattern) {
@@ -306,29 +325,34 @@ fn find_word_boundary_match(text: &str, identifier: &str) -> Option<usize> {
     None
 }
 
-/// Finds a qualified identifier (e.g., "schema.table") in text.
-fn find_qualified_name(text: &str, qualified_name: &str) -> Option<usize> {
+/// Finds a qualified identifier (e.g., "schema.table") in text, returning the byte
+/// range of the whole reference including any quotes.
+fn find_qualified_name(text: &str, qualified_name: &str) -> Option<(usize, usize)> {
     // Split the qualified name and search for the pattern
     let parts: Vec<&str> = qualified_name.split('.').collect();
     if parts.is_empty() {
         return None;
     }
 
-    // Build a pattern that matches the qualified name with optional quotes
-    // e.g., "public.users" should match: public.users, "public"."users", public."users", etc.
+    // Build a pattern that matches each part bare or quoted in any dialect's style
+    // e.g., "dbo.order items" should match: dbo."order items", [dbo].[Order Items],
+    // `dbo`.`order items`, etc.
     let pattern_parts: Vec<String> = parts
         .iter()
         .map(|part| {
-            // Match the part with optional surrounding quotes
-            format!(r#"(?:"?{}\"?)"#, regex::escape(part))
+            let escaped = regex::escape(part);
+            format!(
+                r#"(?:"{escaped}"|`{escaped}`|\[{escaped}\]|{})"#,
+                with_word_boundaries(part, &escaped)
+            )
         })
         .collect();
 
-    let pattern = format!(r"(?i){}", pattern_parts.join(r"\."));
+    let pattern = format!(r"(?i){}", pattern_parts.join(r"\s*\.\s*"));
 
     if let Ok(re) = Regex::new(&pattern) {
         if let Some(m) = re.find(text) {
-            return Some(m.start());
+            return Some((m.start(), m.end()));
         }
     }
 
@@ -413,6 +437,29 @@ mod tests {
         assert_eq!(span, Some(Span::new(14, 26)));
     }
 
+    #[test]
+    fn test_find_identifier_span_quoted_qualified_with_spaces() {
+        let sql = "SELECT * FROM [dbo].[Order Items] JOIN `sales`.`order items` ON 1 = 1";
+        let span = find_identifier_span(sql, "dbo.Order Items", 0).unwrap();
+        assert_eq!(&sql[span.start..span.end], "[dbo].[Order Items]");
+        let span = find_identifier_span(sql, "sales.order items", 0).unwrap();
+        assert_eq!(&sql[span.start..span.end], "`sales`.`order items`");
+
+        // The span covers the quotes rather than the unquoted length
+        let sql = r#"SELECT * FROM "public"."Order Items""#;
+        let span = find_identifier_span(sql, "public.Order Items", 0).unwrap();
+        assert_eq!(&sql[span.start..span.end], r#""public"."Order Items""#);
+    }
+
+    #[test]
+    fn test_find_identifier_span_temp_table() {
+        let sql = "SELECT * FROM #staging s JOIN staging t ON s.id = t.id";
+        assert_eq!(
+            find_identifier_span(sql, "#staging", 0),
+            Some(Span::new(14, 22))
+        );
+    }
+
     #[test]
     fn test_find_identifier_span_with_offset() {
         let sql = "SELECT 1; SELECT * FROM users";
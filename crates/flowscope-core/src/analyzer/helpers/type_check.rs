@@ -208,14 +208,13 @@ fn check_binary_op_types(
         | ast::BinaryOperator::Lt
         | ast::BinaryOperator::LtEq
         | ast::BinaryOperator::Gt
-        | ast::BinaryOperator::GtEq => {
-            if !are_types_comparable(l_type, r_type, dialect) {
-                let message = format!("Type mismatch in comparison: {} {} {}", l_type, op, r_type);
-                issues.push(
-                    Issue::warning(issue_codes::TYPE_MISMATCH, message)
-                        .with_statement(statement_index),
-                );
-            }
+        | ast::BinaryOperator::GtEq
+            if !are_types_comparable(l_type, r_type, dialect) =>
+        {
+            let message = format!("Type mismatch in comparison: {} {} {}", l_type, op, r_type);
+            issues.push(
+                Issue::warning(issue_codes::TYPE_MISMATCH, message).with_statement(statement_index),
+            );
         }
         // Arithmetic operators: both operands must be numeric
         ast::BinaryOperator::Plus
@@ -287,7 +286,7 @@ fn check_binary_op_types(
 /// - Boolean/Integer comparison is allowed in MySQL, MSSQL, and SQLite (which
 ///   represent booleans as integers 0/1), but not in PostgreSQL, BigQuery,
 ///   or Snowflake (which have strict boolean types).
-fn are_types_comparable(left: CanonicalType, right: CanonicalType, dialect: Dialect) -> bool {
+pub fn are_types_comparable(left: CanonicalType, right: CanonicalType, dialect: Dialect) -> bool {
     // Same types are always comparable
     if left == right {
         return true;
@@ -30,7 +30,7 @@ pub fn normalize_schema_type(type_name: &str) -> String {
 ///
 /// # Supported expressions
 /// - Literals: numbers → Float, strings → Text, booleans → Boolean, NULL → None
-/// - CAST/type annotations
+/// - CAST, TRY_CAST, SAFE_CAST, and `::` type annotations (all yield the target type)
 /// - Unary operators (NOT → Boolean, +/- → preserves operand type)
 /// - Binary operators (comparisons → Boolean, arithmetic → Float)
 /// - Function calls (via `infer_function_return_type`)
@@ -393,6 +393,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_infer_try_and_safe_cast() {
+        assert_eq!(
+            infer_expr_type(&parse_expr("TRY_CAST(x AS INT)")),
+            Some(CanonicalType::Integer)
+        );
+        assert_eq!(
+            infer_expr_type(&parse_expr("SAFE_CAST(x AS INT64)")),
+            Some(CanonicalType::Integer)
+        );
+        assert_eq!(
+            infer_expr_type(&parse_expr("x::DATE")),
+            Some(CanonicalType::Date)
+        );
+    }
+
     #[test]
     fn test_unknown_function_returns_none() {
         assert_eq!(infer_expr_type(&parse_expr("UNKNOWN_FUNC(x)")), None);
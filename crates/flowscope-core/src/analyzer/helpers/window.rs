@@ -0,0 +1,184 @@
+use sqlparser::ast::{
+    self as ast, Expr, FunctionArg, FunctionArgExpr, NamedWindowDefinition, NamedWindowExpr,
+    SelectItem, WindowSpec, WindowType,
+};
+use std::collections::HashMap;
+
+/// Maximum number of `WINDOW a AS b` hops followed when resolving a named window.
+const MAX_WINDOW_CHAIN: usize = 16;
+
+/// Rewrites `OVER w` references in a projection using the SELECT's `WINDOW` clause.
+///
+/// Returns `None` when the SELECT defines no named windows, so callers can keep
+/// borrowing the original projection. Window references that cannot be resolved
+/// are left untouched.
+pub fn resolve_named_windows(
+    projection: &[SelectItem],
+    named_windows: &[NamedWindowDefinition],
+) -> Option<Vec<SelectItem>> {
+    if named_windows.is_empty() {
+        return None;
+    }
+
+    let definitions: HashMap<String, &NamedWindowExpr> = named_windows
+        .iter()
+        .map(|NamedWindowDefinition(name, expr)| (name.value.to_lowercase(), expr))
+        .collect();
+
+    let mut resolved = projection.to_vec();
+    for item in &mut resolved {
+        match item {
+            SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                substitute_windows(expr, &definitions);
+            }
+            _ => {}
+        }
+    }
+    Some(resolved)
+}
+
+/// Looks up a named window, following `WINDOW a AS b` aliases.
+fn lookup_window(
+    name: &str,
+    definitions: &HashMap<String, &NamedWindowExpr>,
+) -> Option<WindowSpec> {
+    let mut current = name.to_lowercase();
+    for _ in 0..MAX_WINDOW_CHAIN {
+        match definitions.get(&current)? {
+            NamedWindowExpr::WindowSpec(spec) => return Some(resolve_spec(spec, definitions)),
+            NamedWindowExpr::NamedWindow(next) => current = next.value.to_lowercase(),
+        }
+    }
+    None
+}
+
+/// Merges a spec that extends a named window (`OVER (w ORDER BY x)`) with its base.
+fn resolve_spec(spec: &WindowSpec, definitions: &HashMap<String, &NamedWindowExpr>) -> WindowSpec {
+    let Some(base_name) = &spec.window_name else {
+        return spec.clone();
+    };
+    let Some(base) = lookup_window(&base_name.value, definitions) else {
+        return spec.clone();
+    };
+
+    WindowSpec {
+        window_name: None,
+        partition_by: if spec.partition_by.is_empty() {
+            base.partition_by
+        } else {
+            spec.partition_by.clone()
+        },
+        order_by: if spec.order_by.is_empty() {
+            base.order_by
+        } else {
+            spec.order_by.clone()
+        },
+        window_frame: spec.window_frame.clone().or(base.window_frame),
+    }
+}
+
+fn substitute_windows(expr: &mut Expr, definitions: &HashMap<String, &NamedWindowExpr>) {
+    match expr {
+        Expr::Function(func) => {
+            match &func.over {
+                Some(WindowType::NamedWindow(name)) => {
+                    if let Some(spec) = lookup_window(&name.value, definitions) {
+                        func.over = Some(WindowType::WindowSpec(spec));
+                    }
+                }
+                Some(WindowType::WindowSpec(spec)) if spec.window_name.is_some() => {
+                    func.over = Some(WindowType::WindowSpec(resolve_spec(spec, definitions)));
+                }
+                _ => {}
+            }
+            if let ast::FunctionArguments::List(args) = &mut func.args {
+                for arg in &mut args.args {
+                    match arg {
+                        FunctionArg::Unnamed(FunctionArgExpr::Expr(e))
+                        | FunctionArg::Named {
+                            arg: FunctionArgExpr::Expr(e),
+                            ..
+                        } => substitute_windows(e, definitions),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Expr::BinaryOp { left, right, .. } => {
+            substitute_windows(left, definitions);
+            substitute_windows(right, definitions);
+        }
+        Expr::UnaryOp { expr, .. } | Expr::Nested(expr) | Expr::Cast { expr, .. } => {
+            substitute_windows(expr, definitions);
+        }
+        Expr::Case {
+            operand,
+            conditions,
+            else_result,
+            ..
+        } => {
+            if let Some(op) = operand {
+                substitute_windows(op, definitions);
+            }
+            for case_when in conditions {
+                substitute_windows(&mut case_when.condition, definitions);
+                substitute_windows(&mut case_when.result, definitions);
+            }
+            if let Some(el) = else_result {
+                substitute_windows(el, definitions);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlparser::ast::{SetExpr, Statement};
+    use sqlparser::dialect::GenericDialect;
+    use sqlparser::parser::Parser;
+
+    fn parse_select(sql: &str) -> ast::Select {
+        let stmt = Parser::parse_sql(&GenericDialect {}, sql)
+            .unwrap()
+            .into_iter()
+            .next()
+            .unwrap();
+        let Statement::Query(query) = stmt else {
+            panic!("expected query");
+        };
+        let SetExpr::Select(select) = *query.body else {
+            panic!("expected select");
+        };
+        *select
+    }
+
+    #[test]
+    fn test_no_named_windows_returns_none() {
+        let select = parse_select("SELECT SUM(x) OVER (PARTITION BY y) FROM t");
+        assert!(resolve_named_windows(&select.projection, &select.named_window).is_none());
+    }
+
+    #[test]
+    fn test_named_window_substituted() {
+        let select = parse_select(
+            "SELECT SUM(x) OVER w AS s FROM t WINDOW w AS (PARTITION BY y ORDER BY z)",
+        );
+        let resolved = resolve_named_windows(&select.projection, &select.named_window).unwrap();
+        let text = resolved[0].to_string();
+        assert!(text.contains("PARTITION BY y"), "{text}");
+        assert!(text.contains("ORDER BY z"), "{text}");
+    }
+
+    #[test]
+    fn test_window_extending_named_window() {
+        let select = parse_select(
+            "SELECT SUM(x) OVER (w ORDER BY z) AS s FROM t WINDOW w AS (PARTITION BY y)",
+        );
+        let resolved = resolve_named_windows(&select.projection, &select.named_window).unwrap();
+        let text = resolved[0].to_string();
+        assert!(text.contains("PARTITION BY y"), "{text}");
+        assert!(text.contains("ORDER BY z"), "{text}");
+    }
+}
@@ -0,0 +1,172 @@
+//! Identifier length and reserved prefix checks.
+//!
+//! Warehouses cap identifier length and handle longer names differently: Postgres
+//! silently truncates them to 63 bytes, so two long names sharing a prefix collide and
+//! references spelled in full still resolve to the truncated object, while Snowflake
+//! and SQL Server reject them. With `check_identifier_length`, tables, views, columns,
+//! CTEs and aliases introduced by a statement are measured against the dialect's limit
+//! (or `max_identifier_length`), along with the column names engines generate for
+//! unaliased function calls. `reserved_identifier_prefixes` separately flags names
+//! starting with a prefix the platform keeps for itself, such as `pg_` or `sys_`.
+
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{Expr, Ident, Query, SelectItem, SetExpr, Statement, Visit, Visitor};
+
+use super::reserved_keywords::IdentifierCollector;
+use super::Analyzer;
+use crate::types::{issue_codes, Dialect, Issue};
+
+/// How a dialect measures identifiers against its limit.
+#[derive(Clone, Copy)]
+enum LengthUnit {
+    Bytes,
+    Characters,
+}
+
+/// The identifier length limit of `dialect`, if it documents one.
+fn dialect_identifier_limit(dialect: Dialect) -> Option<(usize, LengthUnit)> {
+    match dialect {
+        // NAMEDATALEN - 1
+        Dialect::Postgres => Some((63, LengthUnit::Bytes)),
+        Dialect::Redshift => Some((127, LengthUnit::Bytes)),
+        Dialect::Mysql => Some((64, LengthUnit::Characters)),
+        Dialect::Mssql | Dialect::Hive | Dialect::Ansi | Dialect::Generic => {
+            Some((128, LengthUnit::Characters))
+        }
+        Dialect::Snowflake | Dialect::Databricks => Some((255, LengthUnit::Characters)),
+        Dialect::Bigquery => Some((300, LengthUnit::Characters)),
+        Dialect::Duckdb | Dialect::Clickhouse | Dialect::Sqlite => None,
+    }
+}
+
+const GENERATED_COLUMN_NAME: &str = "Generated column name";
+
+impl<'a> Analyzer<'a> {
+    /// With `check_identifier_length`, reports names introduced by `statement` that
+    /// are longer than the identifier limit, and with `reserved_identifier_prefixes`,
+    /// names starting with a reserved prefix; once per name per statement.
+    pub(super) fn check_identifier_names(&mut self, statement: &Statement, statement_index: usize) {
+        let options = self.request.options.as_ref();
+        let dialect = self.request.dialect;
+        let limit = options
+            .and_then(|o| o.check_identifier_length)
+            .unwrap_or(false)
+            .then(|| {
+                let default = dialect_identifier_limit(dialect);
+                match options.and_then(|o| o.max_identifier_length) {
+                    Some(max) => Some((max, default.map_or(LengthUnit::Characters, |d| d.1))),
+                    None => default,
+                }
+            })
+            .flatten();
+        let prefixes: Vec<String> = options
+            .and_then(|o| o.reserved_identifier_prefixes.as_ref())
+            .map(|prefixes| {
+                prefixes
+                    .iter()
+                    .filter(|prefix| !prefix.is_empty())
+                    .map(|prefix| prefix.to_lowercase())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if limit.is_none() && prefixes.is_empty() {
+            return;
+        }
+
+        let mut collector = IdentifierCollector::default();
+        let _ = statement.visit(&mut collector);
+        let mut generated = GeneratedNameCollector::default();
+        let _ = statement.visit(&mut generated);
+
+        let mut seen = HashSet::new();
+        for (ident, kind) in collector.identifiers.into_iter().chain(generated.names) {
+            if !seen.insert(ident.value.clone()) {
+                continue;
+            }
+            if let Some((max, unit)) = limit {
+                let (length, unit_name) = match unit {
+                    LengthUnit::Bytes => (ident.value.len(), "bytes"),
+                    LengthUnit::Characters => (ident.value.chars().count(), "characters"),
+                };
+                if length > max {
+                    let message = format!(
+                        "{kind} {} is {length} {unit_name} long, over the identifier limit of {max} in {dialect:?}; it may be truncated or rejected",
+                        ident.value
+                    );
+                    self.push_identifier_issue(
+                        issue_codes::IDENTIFIER_TOO_LONG,
+                        message,
+                        &ident,
+                        statement_index,
+                    );
+                }
+            }
+            let lower = ident.value.to_lowercase();
+            if let Some(prefix) = prefixes.iter().find(|prefix| lower.starts_with(*prefix)) {
+                let message = format!(
+                    "{kind} {} starts with the reserved prefix {prefix}; it may clash with system objects",
+                    ident.value
+                );
+                self.push_identifier_issue(
+                    issue_codes::RESERVED_IDENTIFIER_PREFIX,
+                    message,
+                    &ident,
+                    statement_index,
+                );
+            }
+        }
+    }
+
+    fn push_identifier_issue(
+        &mut self,
+        code: &str,
+        message: String,
+        ident: &Ident,
+        statement_index: usize,
+    ) {
+        let mut issue = Issue::warning(code, message).with_statement(statement_index);
+        if let Some(span) = self.source_span(ident.span) {
+            issue = issue.with_span(span);
+        }
+        self.issues.push(issue);
+    }
+}
+
+/// Column names generated for unaliased function calls in SELECT lists, which
+/// engines name after the function.
+#[derive(Default)]
+struct GeneratedNameCollector {
+    names: Vec<(Ident, &'static str)>,
+}
+
+impl GeneratedNameCollector {
+    fn push_select_names(&mut self, body: &SetExpr) {
+        match body {
+            SetExpr::Select(select) => {
+                for item in &select.projection {
+                    if let SelectItem::UnnamedExpr(Expr::Function(function)) = item {
+                        if let Some(ident) = function.name.0.last().and_then(|p| p.as_ident()) {
+                            self.names.push((ident.clone(), GENERATED_COLUMN_NAME));
+                        }
+                    }
+                }
+            }
+            SetExpr::SetOperation { left, right, .. } => {
+                self.push_select_names(left);
+                self.push_select_names(right);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visitor for GeneratedNameCollector {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        self.push_select_names(&query.body);
+        ControlFlow::Continue(())
+    }
+}
@@ -0,0 +1,128 @@
+//! Implicit table alias check (`FROM orders o` instead of `FROM orders AS o`).
+//!
+//! The AST does not record whether `AS` was written, so the source text in front of
+//! each alias is inspected.
+
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{Statement, TableAlias, TableFactor, Visit, Visitor};
+
+use super::Analyzer;
+use crate::types::{issue_codes, ImplicitAliasContext, Issue};
+
+impl<'a> Analyzer<'a> {
+    /// With `check_implicit_aliases`, warns about table and derived-table aliases
+    /// written without `AS`, except in the context `allow_implicit_aliases_for` allows.
+    pub(super) fn check_implicit_table_aliases(
+        &mut self,
+        statement: &Statement,
+        statement_index: usize,
+    ) {
+        let Some(options) = self.request.options.as_ref() else {
+            return;
+        };
+        if !options.check_implicit_aliases.unwrap_or(false) {
+            return;
+        }
+        let allowed = options.allow_implicit_aliases_for.unwrap_or_default();
+
+        let mut collector = AliasCollector::default();
+        let _ = statement.visit(&mut collector);
+
+        for (alias, context) in collector.aliases {
+            if context == allowed {
+                continue;
+            }
+            let Some(span) = self.source_span(alias.name.span) else {
+                continue;
+            };
+            let explicit = self
+                .current_statement_source
+                .as_ref()
+                .is_some_and(|source| preceded_by_as(&source.sql[..span.start]));
+            if explicit {
+                continue;
+            }
+
+            let what = match context {
+                ImplicitAliasContext::Derived => "Derived table",
+                _ => "Table",
+            };
+            self.issues.push(
+                Issue::warning(
+                    issue_codes::IMPLICIT_TABLE_ALIAS,
+                    format!(
+                        "{what} alias {} is implicit; write AS {} instead",
+                        alias.name, alias.name
+                    ),
+                )
+                .with_statement(statement_index)
+                .with_span(span),
+            );
+        }
+    }
+}
+
+/// Whether `prefix`, the text in front of an alias, ends with the `AS` keyword.
+fn preceded_by_as(prefix: &str) -> bool {
+    let trimmed = prefix.trim_end();
+    let Some(before) = trimmed
+        .len()
+        .checked_sub(2)
+        .filter(|&start| {
+            trimmed
+                .get(start..)
+                .is_some_and(|w| w.eq_ignore_ascii_case("AS"))
+        })
+        .map(|start| &trimmed[..start])
+    else {
+        return false;
+    };
+    // `AS` must be a whole word, not the end of `alias` or `has`
+    !before
+        .chars()
+        .next_back()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Aliases of base tables and derived tables, in source order.
+#[derive(Default)]
+struct AliasCollector {
+    aliases: Vec<(TableAlias, ImplicitAliasContext)>,
+}
+
+impl Visitor for AliasCollector {
+    type Break = ();
+
+    fn pre_visit_table_factor(&mut self, table_factor: &TableFactor) -> ControlFlow<Self::Break> {
+        match table_factor {
+            TableFactor::Table {
+                alias: Some(alias), ..
+            } => self
+                .aliases
+                .push((alias.clone(), ImplicitAliasContext::Base)),
+            TableFactor::Derived {
+                alias: Some(alias), ..
+            } => self
+                .aliases
+                .push((alias.clone(), ImplicitAliasContext::Derived)),
+            _ => {}
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_as_keyword() {
+        assert!(preceded_by_as("FROM orders AS "));
+        assert!(preceded_by_as("FROM orders as\n  "));
+        assert!(preceded_by_as("FROM (SELECT 1)as "));
+        assert!(!preceded_by_as("FROM orders "));
+        assert!(!preceded_by_as("FROM alias "));
+        assert!(!preceded_by_as("FROM has "));
+    }
+}
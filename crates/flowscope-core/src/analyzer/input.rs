@@ -3,13 +3,17 @@
 //! This module handles the parsing and collection of SQL statements from analysis requests,
 //! supporting both file-based and inline SQL inputs.
 
+use crate::error::ParseError;
+use crate::parser::do_block::{do_block_body, parse_embedded_statement};
 use crate::parser::parse_sql_with_dialect;
-use crate::types::{issue_codes, AnalyzeRequest, Dialect, Issue, Span};
+use crate::parser::psql;
+use crate::types::{issue_codes, AnalyzeRequest, Dialect, Issue, RenderedSql, Span};
 use sqlparser::ast::Statement;
 use std::borrow::Cow;
 use std::ops::Range;
 use std::rc::Rc;
 use thiserror::Error;
+use web_time::{Duration, Instant};
 
 #[cfg(feature = "templating")]
 use crate::templater::{template_sql, TemplateMode};
@@ -118,6 +122,12 @@ pub(crate) struct StatementInput<'a> {
     /// Whether template processing was applied to produce `source_sql`.
     /// When true, `source_sql` contains the resolved/compiled SQL.
     pub(crate) templating_applied: bool,
+    /// Byte range of the enclosing `DO` block when the statement was extracted
+    /// from its procedural body.
+    pub(crate) do_block: Option<Range<usize>>,
+    /// Share of the source's parse time attributed to this statement, in microseconds
+    /// (only measured when the request's `collect_timings` option is set).
+    pub(crate) parse_micros: u64,
 }
 
 /// Collects and parses SQL statements from the analysis request.
@@ -152,14 +162,27 @@ pub(crate) struct StatementInput<'a> {
 ///
 /// # Returns
 ///
-/// A tuple of `(statements, issues)` where:
+/// A tuple of `(statements, issues, rendered)` where:
 /// - `statements`: Successfully parsed statements with source attribution
 /// - `issues`: Any validation errors or parse failures encountered
+/// - `rendered`: The SQL of each source after templating, when the request's
+///   `return_rendered_sql` option is set (empty otherwise)
 pub(crate) fn collect_statements<'a>(
     request: &'a AnalyzeRequest,
-) -> (Vec<StatementInput<'a>>, Vec<Issue>) {
+) -> (Vec<StatementInput<'a>>, Vec<Issue>, Vec<RenderedSql>) {
     let mut issues = Vec::new();
     let mut statements = Vec::new();
+    let mut rendered = Vec::new();
+    let return_rendered = request
+        .options
+        .as_ref()
+        .and_then(|o| o.return_rendered_sql)
+        .unwrap_or(false);
+    let collect_timings = request
+        .options
+        .as_ref()
+        .and_then(|o| o.collect_timings)
+        .unwrap_or(false);
 
     let has_sql = !request.sql.trim().is_empty();
     let has_files = request
@@ -173,7 +196,7 @@ pub(crate) fn collect_statements<'a>(
             issue_codes::INVALID_REQUEST,
             "Provide inline SQL or at least one file to analyze",
         ));
-        return (Vec::new(), issues);
+        return (Vec::new(), issues, rendered);
     }
 
     // Parse files first (if present)
@@ -194,13 +217,19 @@ pub(crate) fn collect_statements<'a>(
             let (source_sql, templating_applied): (Cow<'_, str>, bool) =
                 (Cow::Borrowed(file.content.as_str()), false);
 
+            if return_rendered {
+                rendered.push(RenderedSql {
+                    source_name: Some(file.name.clone()),
+                    sql: source_sql.to_string(),
+                });
+            }
             let ctx = ParseContext {
                 source_sql,
                 source_name: Some(Rc::new(file.name.clone())),
                 dialect: request.dialect,
                 templating_applied,
             };
-            let (file_stmts, file_issues) = parse_statements_individually(&ctx);
+            let (file_stmts, file_issues) = parse_source(&ctx, collect_timings);
             statements.extend(file_stmts);
             issues.extend(file_issues);
         }
@@ -217,7 +246,7 @@ pub(crate) fn collect_statements<'a>(
                     // Record error and return collected statements (same as file error handling).
                     // Inline SQL is processed last, so returning here is equivalent to continuing.
                     issues.push(template_error_issue(&e, request.source_name.as_deref()));
-                    return (statements, issues);
+                    return (statements, issues, rendered);
                 }
             }
         };
@@ -225,20 +254,50 @@ pub(crate) fn collect_statements<'a>(
         let (source_sql, templating_applied): (Cow<'_, str>, bool) =
             (Cow::Borrowed(request.sql.as_str()), false);
 
+        if return_rendered {
+            rendered.push(RenderedSql {
+                source_name: request.source_name.clone(),
+                sql: source_sql.to_string(),
+            });
+        }
         let ctx = ParseContext {
             source_sql,
             source_name: request.source_name.clone().map(Rc::new),
             dialect: request.dialect,
             templating_applied,
         };
-        let (inline_stmts, inline_issues) = parse_statements_individually(&ctx);
+        let (inline_stmts, inline_issues) = parse_source(&ctx, collect_timings);
         statements.extend(inline_stmts);
         issues.extend(inline_issues);
     }
 
+    (statements, issues, rendered)
+}
+
+/// Parses the statements of one source, timing the parse when `timed` is set.
+fn parse_source<'a>(ctx: &ParseContext<'a>, timed: bool) -> (Vec<StatementInput<'a>>, Vec<Issue>) {
+    if !timed {
+        return parse_statements_individually(ctx);
+    }
+    let started = Instant::now();
+    let (mut statements, issues) = parse_statements_individually(ctx);
+    share_parse_time(&mut statements, started.elapsed());
     (statements, issues)
 }
 
+/// Divides the time spent parsing a source among its statements by their length in bytes.
+fn share_parse_time(statements: &mut [StatementInput<'_>], elapsed: Duration) {
+    let total_micros = elapsed.as_micros();
+    let lengths: Vec<u128> = statements
+        .iter()
+        .map(|statement| statement.source_range.len().max(1) as u128)
+        .collect();
+    let total_length: u128 = lengths.iter().sum();
+    for (statement, length) in statements.iter_mut().zip(lengths) {
+        statement.parse_micros = (total_micros * length / total_length) as u64;
+    }
+}
+
 /// Parses SQL from a single buffer with best-effort error handling.
 ///
 /// The parser first tries to process the entire buffer so statements containing
@@ -249,6 +308,12 @@ fn parse_statements_individually<'a>(
     ctx: &ParseContext<'a>,
 ) -> (Vec<StatementInput<'a>>, Vec<Issue>) {
     let statement_ranges = compute_statement_ranges(&ctx.source_sql);
+    if statement_ranges
+        .iter()
+        .any(|range| psql::is_meta_command(&ctx.source_sql[range.clone()]))
+    {
+        return parse_psql_script(ctx, &statement_ranges);
+    }
 
     match parse_full_sql_buffer(ctx, &statement_ranges) {
         Ok(statements) => (statements, Vec::new()),
@@ -279,6 +344,52 @@ fn parse_statements_individually<'a>(
     }
 }
 
+/// Parses a psql script that contains client meta-commands.
+///
+/// The SQL between meta-commands is parsed with the commands blanked out, and each
+/// `\copy` becomes a statement of its own, in source order.
+fn parse_psql_script<'a>(
+    ctx: &ParseContext<'a>,
+    statement_ranges: &[Range<usize>],
+) -> (Vec<StatementInput<'a>>, Vec<Issue>) {
+    let meta_commands: Vec<Range<usize>> = statement_ranges
+        .iter()
+        .filter(|range| psql::is_meta_command(&ctx.source_sql[(*range).clone()]))
+        .cloned()
+        .collect();
+    let masked = ParseContext {
+        source_sql: Cow::Owned(psql::mask_meta_commands(&ctx.source_sql, &meta_commands)),
+        source_name: ctx.source_name.clone(),
+        dialect: ctx.dialect,
+        templating_applied: ctx.templating_applied,
+    };
+    let (mut statements, mut issues) = parse_statements_individually(&masked);
+    for statement in &mut statements {
+        statement.source_sql = ctx.source_sql.clone();
+    }
+
+    for range in meta_commands {
+        match psql::parse_meta_command(&ctx.source_sql[range.clone()], ctx.dialect) {
+            None => {}
+            Some(Ok(parsed)) => {
+                statements.extend(parsed.into_iter().map(|statement| StatementInput {
+                    statement,
+                    source_name: ctx.source_name.clone(),
+                    source_sql: ctx.source_sql.clone(),
+                    source_range: range.clone(),
+                    templating_applied: ctx.templating_applied,
+                    do_block: None,
+                    parse_micros: 0,
+                }));
+            }
+            Some(Err(e)) => issues.push(statement_parse_error(ctx, &range, &e)),
+        }
+    }
+    statements.sort_by_key(|statement| statement.source_range.start);
+
+    (statements, issues)
+}
+
 /// Attempts full SQL buffer parsing with statement range alignment.
 ///
 /// Returns:
@@ -314,13 +425,15 @@ fn parse_full_sql_buffer<'a>(
     };
 
     let mut statements = Vec::with_capacity(parsed.len());
-    for (stmt, range) in parsed.into_iter().zip(aligned_ranges.into_iter()) {
+    for (stmt, range) in parsed.into_iter().zip(aligned_ranges) {
         statements.push(StatementInput {
             statement: stmt,
             source_name: ctx.source_name.clone(),
             source_sql: ctx.source_sql.clone(),
             source_range: range,
             templating_applied: ctx.templating_applied,
+            do_block: None,
+            parse_micros: 0,
         });
     }
 
@@ -455,6 +568,14 @@ fn parse_statement_ranges_best_effort<'a>(
 
         let statement_sql = &source_sql_ref[range.clone()];
 
+        if let Some(body) = do_block_body(statement_sql, ctx.dialect) {
+            let body = range.start + body.start..range.start + body.end;
+            let leading_whitespace = statement_sql.len() - statement_sql.trim_start().len();
+            let block = range.start + leading_whitespace..range.end;
+            statements.extend(parse_do_block_body(ctx, body, block));
+            continue;
+        }
+
         match parse_sql_with_dialect(statement_sql, ctx.dialect) {
             Ok(parsed) => {
                 // Typically one statement per range, but handle multiple if present
@@ -465,22 +586,14 @@ fn parse_statement_ranges_best_effort<'a>(
                         source_sql: ctx.source_sql.clone(),
                         source_range: range.clone(),
                         templating_applied: ctx.templating_applied,
+                        do_block: None,
+                        parse_micros: 0,
                     });
                 }
             }
             Err(e) => {
                 // Record the parse error but continue with remaining statements
-                let message = match ctx.source_name.as_deref() {
-                    Some(name) => format!("Parse error in {name}: {e}"),
-                    None => format!("Parse error: {e}"),
-                };
-
-                let mut issue = Issue::error(issue_codes::PARSE_ERROR, message)
-                    .with_span(Span::new(range.start, range.end));
-                if let Some(name) = ctx.source_name.as_deref() {
-                    issue = issue.with_source_name(name);
-                }
-                issues.push(issue);
+                issues.push(statement_parse_error(ctx, &range, &e));
             }
         }
     }
@@ -488,6 +601,53 @@ fn parse_statement_ranges_best_effort<'a>(
     (statements, issues)
 }
 
+/// An error issue for a statement at `range` that failed to parse.
+fn statement_parse_error(
+    ctx: &ParseContext<'_>,
+    range: &Range<usize>,
+    error: &ParseError,
+) -> Issue {
+    let message = match ctx.source_name.as_deref() {
+        Some(name) => format!("Parse error in {name}: {error}"),
+        None => format!("Parse error: {error}"),
+    };
+
+    let mut issue = Issue::error(issue_codes::PARSE_ERROR, message)
+        .with_span(Span::new(range.start, range.end));
+    if let Some(name) = ctx.source_name.as_deref() {
+        issue = issue.with_source_name(name);
+    }
+    issue
+}
+
+/// Extracts the SQL statements embedded in a `DO` block body.
+///
+/// Pieces of the body that contain only procedural code are skipped rather than
+/// reported as parse errors.
+fn parse_do_block_body<'a>(
+    ctx: &ParseContext<'a>,
+    body: Range<usize>,
+    block: Range<usize>,
+) -> Vec<StatementInput<'a>> {
+    let body_sql = &ctx.source_sql[body.clone()];
+    compute_statement_ranges(body_sql)
+        .into_iter()
+        .filter_map(|piece| {
+            let (statement, offset) =
+                parse_embedded_statement(&body_sql[piece.clone()], ctx.dialect)?;
+            Some(StatementInput {
+                statement,
+                source_name: ctx.source_name.clone(),
+                source_sql: ctx.source_sql.clone(),
+                source_range: body.start + piece.start + offset..body.start + piece.end,
+                templating_applied: ctx.templating_applied,
+                do_block: Some(block.clone()),
+                parse_micros: 0,
+            })
+        })
+        .collect()
+}
+
 pub(crate) fn split_statement_spans(sql: &str) -> Vec<Span> {
     compute_statement_ranges(sql)
         .into_iter()
@@ -497,6 +657,10 @@ pub(crate) fn split_statement_spans(sql: &str) -> Vec<Span> {
 
 /// Split SQL text into statement ranges by finding semicolons outside of strings/comments.
 ///
+/// Segments holding only whitespace and comments produce no range, so repeated
+/// semicolons (`SELECT 1;;`) and trailing comments (`; -- done`) never become empty
+/// statements. The source text itself is kept as written.
+///
 /// # Design Decision: Character-Level State Machine
 ///
 /// This function intentionally uses a character-by-character state machine rather than
@@ -668,24 +832,20 @@ fn compute_statement_ranges(sql: &str) -> Vec<Range<usize>> {
                 i += advance;
                 continue;
             }
-            '-' => {
-                if starts_with_at(sql, i + advance, "-") {
-                    in_line_comment = true;
-                    i += advance + 1;
-                    continue;
-                }
+            '-' if starts_with_at(sql, i + advance, "-") => {
+                in_line_comment = true;
+                i += advance + 1;
+                continue;
             }
             '#' => {
                 in_line_comment = true;
                 i += advance;
                 continue;
             }
-            '/' => {
-                if starts_with_at(sql, i + advance, "*") {
-                    in_block_comment = true;
-                    i += advance + 1;
-                    continue;
-                }
+            '/' if starts_with_at(sql, i + advance, "*") => {
+                in_block_comment = true;
+                i += advance + 1;
+                continue;
             }
             '$' => {
                 if let Some((delim, end_idx)) = detect_dollar_quote(sql, i) {
@@ -698,6 +858,15 @@ fn compute_statement_ranges(sql: &str) -> Vec<Range<usize>> {
                 push_statement_range(&mut ranges, sql, start, i);
                 start = i + advance;
             }
+            // A psql meta-command (`\copy ...`) at the start of a statement runs to the
+            // end of its line
+            '\\' if trim_statement_range(sql, start, i).is_none() => {
+                let end = sql[i..].find('\n').map_or(len, |offset| i + offset);
+                push_statement_range(&mut ranges, sql, i, end);
+                start = end;
+                i = end;
+                continue;
+            }
             _ => {}
         }
 
@@ -869,7 +1038,7 @@ mod tests {
             content: "SELECT 1".to_string(),
         }]);
 
-        let (statements, issues) = collect_statements(&request);
+        let (statements, issues, _) = collect_statements(&request);
         assert!(issues.is_empty());
         assert_eq!(statements.len(), 2);
         assert_eq!(
@@ -893,7 +1062,7 @@ mod tests {
     #[test]
     fn reports_invalid_request_without_inputs() {
         let request = base_request();
-        let (_statements, issues) = collect_statements(&request);
+        let (_statements, issues, _) = collect_statements(&request);
         assert_eq!(issues.len(), 1);
         assert_eq!(issues[0].code, issue_codes::INVALID_REQUEST);
     }
@@ -917,6 +1086,82 @@ mod tests {
         assert_eq!(&sql[ranges[2].clone()], "SELECT 3");
     }
 
+    #[test]
+    fn statement_ranges_end_psql_meta_commands_at_line_end() {
+        let sql = "\\set ON_ERROR_STOP on\nSELECT 1;\n  \\copy users FROM 'u.csv'\nSELECT '\\x'; SELECT 2";
+        let ranges = compute_statement_ranges(sql);
+        let statements: Vec<&str> = ranges.iter().map(|r| &sql[r.clone()]).collect();
+        assert_eq!(
+            statements,
+            vec![
+                "\\set ON_ERROR_STOP on",
+                "SELECT 1",
+                "\\copy users FROM 'u.csv'",
+                "SELECT '\\x'",
+                "SELECT 2"
+            ]
+        );
+    }
+
+    #[test]
+    fn statement_ranges_collapse_empty_segments() {
+        let sql = "SELECT 1;; -- end\n;\n/* done */;";
+        let ranges = compute_statement_ranges(sql);
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(&sql[ranges[0].clone()], "SELECT 1");
+    }
+
+    #[test]
+    fn repeated_semicolons_and_trailing_comments_add_no_statements() {
+        let dialects = [
+            Dialect::Generic,
+            Dialect::Postgres,
+            Dialect::Mysql,
+            Dialect::Mssql,
+            Dialect::Snowflake,
+        ];
+        let scripts = [
+            "SELECT 1;; -- end",
+            "SELECT 1;;\n-- end\n;",
+            ";; SELECT 1 ;;; /* done */",
+        ];
+        for dialect in dialects {
+            for sql in scripts {
+                let mut request = base_request();
+                request.dialect = dialect;
+                request.sql = sql.to_string();
+
+                let (statements, issues, _) = collect_statements(&request);
+                assert!(
+                    issues.is_empty(),
+                    "{dialect:?} {sql:?}: unexpected issues {issues:?}"
+                );
+                assert_eq!(statements.len(), 1, "{dialect:?} {sql:?}");
+                assert_eq!(
+                    statements[0].source_sql[statements[0].source_range.clone()].trim(),
+                    "SELECT 1"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn repeated_semicolons_add_no_statements_in_best_effort_mode() {
+        let mut request = base_request();
+        request.sql = "SELECT 1;; BROKEN STATEMENT;; -- end\nSELECT 2;; -- done".to_string();
+
+        let (statements, issues, _) = collect_statements(&request);
+        assert_eq!(statements.len(), 2, "Expected 2 valid statements");
+        assert_eq!(
+            issues
+                .iter()
+                .filter(|issue| issue.code == issue_codes::PARSE_ERROR)
+                .count(),
+            1,
+            "Expected 1 parse error, got {issues:?}"
+        );
+    }
+
     #[test]
     fn statement_ranges_handle_dollar_quoting() {
         let sql = "DO $$ BEGIN RAISE NOTICE ';'; END $$; SELECT 1;";
@@ -946,7 +1191,7 @@ mod tests {
         "#
         .to_string();
 
-        let (statements, issues) = collect_statements(&request);
+        let (statements, issues, _) = collect_statements(&request);
         assert!(issues.is_empty(), "Expected no issues, got {issues:?}");
         assert_eq!(
             statements.len(),
@@ -976,7 +1221,7 @@ mod tests {
         "#
         .to_string();
 
-        let (statements, issues) = collect_statements(&request);
+        let (statements, issues, _) = collect_statements(&request);
 
         // Should have parsed 2 valid statements
         assert_eq!(statements.len(), 2, "Expected 2 valid statements");
@@ -1002,7 +1247,7 @@ mod tests {
             .to_string(),
         }]);
 
-        let (statements, issues) = collect_statements(&request);
+        let (statements, issues, _) = collect_statements(&request);
 
         assert_eq!(statements.len(), 2, "Expected 2 valid statements");
         assert_eq!(issues.len(), 1, "Expected 1 parse error");
@@ -1033,7 +1278,7 @@ mod tests {
         "#
         .to_string();
 
-        let (statements, issues) = collect_statements(&request);
+        let (statements, issues, _) = collect_statements(&request);
 
         assert_eq!(statements.len(), 3, "Expected 3 valid statements");
         assert_eq!(issues.len(), 2, "Expected 2 parse errors");
@@ -1075,7 +1320,7 @@ mod tests {
             content: "SELECT 1".to_string(),
         }]);
 
-        let (statements, issues) = collect_statements(&request);
+        let (statements, issues, _) = collect_statements(&request);
         assert!(issues.is_empty());
         assert_eq!(statements.len(), 1);
         assert_eq!(
@@ -1121,7 +1366,7 @@ mod tests {
         let mut request = base_request();
         request.sql = "SELECT '日本' AS country; SELECT 'émoji: 🚀' AS test;".to_string();
 
-        let (statements, issues) = collect_statements(&request);
+        let (statements, issues, _) = collect_statements(&request);
         assert!(issues.is_empty(), "Expected no issues, got {issues:?}");
         assert_eq!(statements.len(), 2);
 
@@ -0,0 +1,107 @@
+//! Positional column-order check for `INSERT ... SELECT`.
+//!
+//! `INSERT INTO t (a, b, c) SELECT ...` maps the SELECT list onto the target columns by
+//! position, not by name. When the SELECT outputs are named after target columns at
+//! other positions, the order is probably wrong.
+
+use sqlparser::ast::{Expr, SelectItem, SetExpr};
+
+use super::Analyzer;
+use crate::types::{issue_codes, Issue};
+
+/// Maximum number of misplaced columns spelled out in the warning.
+const MAX_LISTED_COLUMNS: usize = 3;
+
+impl<'a> Analyzer<'a> {
+    /// Warns when at least two SELECT outputs of an insert are named after a target
+    /// column other than the one they are written to.
+    ///
+    /// Needs the target columns, from the insert's column list or the target's schema.
+    pub(super) fn check_insert_column_order(
+        &mut self,
+        statement_index: usize,
+        target_name: &str,
+        target_columns: &[String],
+        body: &SetExpr,
+    ) {
+        let SetExpr::Select(select) = body else {
+            return;
+        };
+        if select.projection.len() != target_columns.len() {
+            return;
+        }
+
+        let targets: Vec<String> = target_columns
+            .iter()
+            .map(|col| self.normalize_identifier(col))
+            .collect();
+        let misplaced: Vec<(String, &String)> = select
+            .projection
+            .iter()
+            .zip(&targets)
+            .enumerate()
+            .filter_map(|(position, (item, target))| {
+                let name = self.normalize_identifier(output_name(item)?);
+                let elsewhere = |matches: fn(&str, &str) -> bool| {
+                    targets
+                        .iter()
+                        .enumerate()
+                        .any(|(other, col)| other != position && matches(&name, col))
+                };
+                // Exact names win over partial ones, so `user_id` written to `id` is
+                // misplaced when `user_id` is also a target column
+                let misplaced = if name == *target {
+                    false
+                } else if elsewhere(|a, b| a == b) {
+                    true
+                } else {
+                    !names_match(&name, target) && elsewhere(names_match)
+                };
+                misplaced.then_some((name, target))
+            })
+            .collect();
+        if misplaced.len() < 2 {
+            return;
+        }
+
+        let listed: Vec<String> = misplaced
+            .iter()
+            .take(MAX_LISTED_COLUMNS)
+            .map(|(name, target)| format!("{name} -> {target}"))
+            .collect();
+        let more = misplaced.len().saturating_sub(MAX_LISTED_COLUMNS);
+        let suffix = if more > 0 {
+            format!(" and {more} more")
+        } else {
+            String::new()
+        };
+        self.issues.push(
+            Issue::warning(
+                issue_codes::INSERT_COLUMN_ORDER_MISMATCH,
+                format!(
+                    "INSERT into {target_name} maps SELECT columns by position, but their names suggest a different order ({}{suffix}); check the column order",
+                    listed.join(", ")
+                ),
+            )
+            .with_statement(statement_index),
+        );
+    }
+}
+
+/// The name a SELECT item is known by: its alias, or the referenced column.
+fn output_name(item: &SelectItem) -> Option<&str> {
+    match item {
+        SelectItem::ExprWithAlias { alias, .. } => Some(&alias.value),
+        SelectItem::UnnamedExpr(Expr::Identifier(ident)) => Some(&ident.value),
+        SelectItem::UnnamedExpr(Expr::CompoundIdentifier(parts)) => {
+            parts.last().map(|ident| ident.value.as_str())
+        }
+        _ => None,
+    }
+}
+
+/// Whether two normalized column names refer to the same thing: equal, or one is an
+/// underscore-separated part of the other (`c_val` and `c`).
+fn names_match(a: &str, b: &str) -> bool {
+    a == b || a.split('_').any(|part| part == b) || b.split('_').any(|part| part == a)
+}
@@ -0,0 +1,137 @@
+//! Equi-join keys whose schema types cannot be compared (`ON a.id = b.code` with an
+//! integer `id` and a varchar `code`).
+//!
+//! Such joins either fail or rely on implicit casts that rarely match what was meant.
+//! Types come from schema metadata, so columns without a declared type are skipped.
+//! Types are compared by category (`int` and `bigint` are both integers), and
+//! `compatible_join_key_types` lists further categories to accept.
+
+use sqlparser::ast::{BinaryOperator, Expr};
+
+use super::helpers::are_types_comparable;
+use super::visitor::LineageVisitor;
+use crate::generated::{normalize_type_name, CanonicalType};
+use crate::types::{issue_codes, Issue};
+
+impl LineageVisitor<'_, '_> {
+    /// With `check_join_key_types`, warns about each `column = column` in a join
+    /// condition whose declared column types are incompatible.
+    pub(super) fn check_join_key_types(&mut self, expr: &Expr) {
+        let Some(options) = self.analyzer.request.options.as_ref() else {
+            return;
+        };
+        if options.check_join_key_types != Some(true) {
+            return;
+        }
+        let compatible: Vec<Vec<CanonicalType>> = options
+            .compatible_join_key_types
+            .iter()
+            .flatten()
+            .map(|group| {
+                group
+                    .iter()
+                    .filter_map(|name| canonical_type(name))
+                    .collect()
+            })
+            .collect();
+
+        let mut equalities = Vec::new();
+        collect_equalities(expr, &mut equalities);
+        for (left, right) in equalities {
+            let (Some(left), Some(right)) = (self.join_key(left), self.join_key(right)) else {
+                continue;
+            };
+            let (Some(left_type), Some(right_type)) = (
+                canonical_type(&left.data_type),
+                canonical_type(&right.data_type),
+            ) else {
+                continue;
+            };
+            let dialect = self.analyzer.request.dialect;
+            if are_types_comparable(left_type, right_type, dialect)
+                || compatible
+                    .iter()
+                    .any(|group| group.contains(&left_type) && group.contains(&right_type))
+            {
+                continue;
+            }
+
+            let message = format!(
+                "Join compares {} ({}) with {} ({}); these types are not compatible",
+                left.name, left.data_type, right.name, right.data_type
+            );
+            self.analyzer.issues.push(
+                Issue::warning(issue_codes::JOIN_KEY_TYPE_MISMATCH, message)
+                    .with_statement(self.ctx.statement_index),
+            );
+        }
+    }
+
+    /// The schema type of a column reference, resolving its qualifier (or, when
+    /// unqualified, the one table in scope that has the column).
+    fn join_key(&self, expr: &Expr) -> Option<JoinKey> {
+        let (qualifier, column) = match expr {
+            Expr::Nested(inner) => return self.join_key(inner),
+            Expr::Identifier(ident) => (None, ident),
+            Expr::CompoundIdentifier(idents) if idents.len() >= 2 => {
+                (Some(&idents[idents.len() - 2]), &idents[idents.len() - 1])
+            }
+            _ => return None,
+        };
+        let schema = &self.analyzer.schema;
+
+        let (table, data_type) = match qualifier {
+            Some(qualifier) => {
+                let table = self.resolve_table_alias(Some(&qualifier.value))?;
+                let data_type = schema.lookup_column_type(&table, &column.value)?;
+                (table, data_type)
+            }
+            None => {
+                let mut matches =
+                    self.ctx
+                        .tables_in_current_scope()
+                        .into_iter()
+                        .filter_map(|table| {
+                            let data_type = schema.lookup_column_type(&table, &column.value)?;
+                            Some((table, data_type))
+                        });
+                let found = matches.next()?;
+                if matches.next().is_some() {
+                    return None;
+                }
+                found
+            }
+        };
+        Some(JoinKey {
+            name: format!("{table}.{}", column.value),
+            data_type,
+        })
+    }
+}
+
+/// A join key column with its declared type.
+struct JoinKey {
+    name: String,
+    data_type: String,
+}
+
+/// Collects the operands of `=` comparisons joined by AND.
+fn collect_equalities<'e>(expr: &'e Expr, equalities: &mut Vec<(&'e Expr, &'e Expr)>) {
+    match expr {
+        Expr::BinaryOp { left, op, right } if *op == BinaryOperator::And => {
+            collect_equalities(left, equalities);
+            collect_equalities(right, equalities);
+        }
+        Expr::BinaryOp { left, op, right } if *op == BinaryOperator::Eq => {
+            equalities.push((left, right));
+        }
+        Expr::Nested(inner) => collect_equalities(inner, equalities),
+        _ => {}
+    }
+}
+
+/// The type category of a declared type such as `VARCHAR(20)` or `double precision`.
+fn canonical_type(declared: &str) -> Option<CanonicalType> {
+    let base = declared.split('(').next().unwrap_or(declared).trim();
+    normalize_type_name(base).or_else(|| normalize_type_name(base.split_whitespace().next()?))
+}
@@ -0,0 +1,62 @@
+//! Line length check.
+//!
+//! Reports source lines longer than `max_line_length` characters, the analogue of
+//! SQLFluff's `layout.long_lines` rule. Only lines holding part of a statement are
+//! measured, so a long comment between statements is not reported. When templating
+//! is applied, the rendered SQL is measured.
+
+use std::collections::HashSet;
+
+use super::input::StatementInput;
+use crate::types::{issue_codes, Issue, Span};
+
+/// Flag lines longer than `max_length` characters, once per line, with a span
+/// covering the overflow.
+pub(crate) fn check_line_length(statements: &[StatementInput], max_length: usize) -> Vec<Issue> {
+    let mut seen: HashSet<(Option<&str>, usize)> = HashSet::new();
+    let mut issues = Vec::new();
+
+    for (index, input) in statements.iter().enumerate() {
+        let sql = input.source_sql.as_ref();
+        let source_name = input.source_name.as_deref().map(String::as_str);
+        let mut line_start = sql[..input.source_range.start]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1);
+
+        loop {
+            let line_end = sql[line_start..]
+                .find('\n')
+                .map_or(sql.len(), |newline| line_start + newline);
+            let line = sql[line_start..line_end].trim_end_matches('\r');
+
+            if seen.insert((source_name, line_start)) {
+                let length = line.chars().count();
+                if length > max_length {
+                    let overflow = line
+                        .char_indices()
+                        .nth(max_length)
+                        .map_or(line.len(), |(offset, _)| offset);
+                    let line_number = sql[..line_start].matches('\n').count() + 1;
+                    let mut issue = Issue::info(
+                        issue_codes::LONG_LINE,
+                        format!(
+                            "Line {line_number} is {length} characters long, over the maximum of {max_length}"
+                        ),
+                    )
+                    .with_statement(index)
+                    .with_span(Span::new(line_start + overflow, line_start + line.len()));
+                    if let Some(name) = source_name {
+                        issue = issue.with_source_name(name);
+                    }
+                    issues.push(issue);
+                }
+            }
+
+            if line_end >= input.source_range.end || line_end == sql.len() {
+                break;
+            }
+            line_start = line_end + 1;
+        }
+    }
+    issues
+}
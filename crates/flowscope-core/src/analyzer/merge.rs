@@ -0,0 +1,187 @@
+//! Merging results of separate analyses (e.g. one per file, produced in parallel).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::cross_statement::CrossStatementTracker;
+use super::global::{build_global_lineage, build_summary};
+use super::graph_metrics::build_graph_metrics;
+use super::helpers::{
+    generate_column_node_id, generate_edge_id, generate_node_id, generate_output_node_id,
+};
+use crate::types::{
+    AnalyzeResult, EdgeType, NodeType, ResolvedSchemaMetadata, ResolvedSchemaTable,
+    StatementLineage, StatementTiming,
+};
+
+impl AnalyzeResult {
+    /// Combines two independent analyses into one, as if `other`'s SQL had been analyzed
+    /// after `self`'s in a single request.
+    ///
+    /// `other`'s statement indices are shifted past `self`'s, together with the IDs of
+    /// the nodes scoped to a statement (output nodes and their columns). Table and view
+    /// IDs are derived from their names, so references to the same table in both results
+    /// share a node; tables `other` reads that `self` creates as views become view nodes.
+    /// The global lineage, including cross-statement edges, and the summary are rebuilt
+    /// from the merged statements, and later schema definitions replace earlier ones.
+    ///
+    /// Analyses that depend on earlier statements (such as resolving columns of tables
+    /// created in `self`) are not redone, so the merged lineage can be less detailed
+    /// than that of a combined request.
+    pub fn merge(mut self, other: AnalyzeResult) -> AnalyzeResult {
+        let offset = self.statements.len();
+        let timings = merge_timings(self.summary.timings.take(), other.summary.timings, offset);
+        let earlier = CrossStatementTracker::from_statements(&self.statements);
+        let later = CrossStatementTracker::from_statements(&other.statements);
+
+        for mut lineage in other.statements {
+            reindex_statement(&mut lineage, offset, &earlier, &later);
+            self.statements.push(lineage);
+        }
+        self.issues
+            .extend(other.issues.into_iter().map(|mut issue| {
+                if let Some(index) = issue.statement_index.as_mut() {
+                    *index += offset;
+                }
+                issue
+            }));
+        self.resolved_schema =
+            merge_resolved_schema(self.resolved_schema, other.resolved_schema, offset);
+
+        let tracker = CrossStatementTracker::from_statements(&self.statements);
+        self.global_lineage =
+            build_global_lineage(&self.statements, tracker.build_cross_statement_edges());
+        self.summary = build_summary(&self.statements, &self.global_lineage, &self.issues);
+        self.summary.timings = timings;
+        if self.graph_metrics.is_some() || other.graph_metrics.is_some() {
+            self.graph_metrics = Some(build_graph_metrics(&self.global_lineage));
+        }
+        self
+    }
+}
+
+/// Appends `later`'s statement timings to `earlier`'s, shifting their indices by `offset`.
+fn merge_timings(
+    earlier: Option<Vec<StatementTiming>>,
+    later: Option<Vec<StatementTiming>>,
+    offset: usize,
+) -> Option<Vec<StatementTiming>> {
+    if earlier.is_none() && later.is_none() {
+        return None;
+    }
+    let mut timings = earlier.unwrap_or_default();
+    timings.extend(later.into_iter().flatten().map(|mut timing| {
+        timing.statement_index += offset;
+        timing
+    }));
+    Some(timings)
+}
+
+/// Moves a statement of a later result to index `statement_index + offset`, renaming the
+/// nodes whose IDs depend on the index or on what the earlier result created.
+fn reindex_statement(
+    lineage: &mut StatementLineage,
+    offset: usize,
+    earlier: &CrossStatementTracker,
+    later: &CrossStatementTracker,
+) {
+    let old_index = lineage.statement_index;
+    let new_index = old_index + offset;
+    lineage.statement_index = new_index;
+
+    let mut renamed: HashMap<Arc<str>, Arc<str>> = HashMap::new();
+    for node in &mut lineage.nodes {
+        let new_id = match node.node_type {
+            NodeType::Output if node.id == generate_output_node_id(old_index) => {
+                if *node.label == *output_label(old_index) {
+                    node.label = output_label(new_index).into();
+                }
+                generate_output_node_id(new_index)
+            }
+            NodeType::Table => {
+                let canonical = node.qualified_name.as_deref().unwrap_or(&node.label);
+                if !earlier.produced_views.contains(canonical) || later.was_produced(canonical) {
+                    continue;
+                }
+                node.node_type = NodeType::View;
+                generate_node_id("view", canonical)
+            }
+            _ => continue,
+        };
+        renamed.insert(std::mem::replace(&mut node.id, new_id.clone()), new_id);
+    }
+    if renamed.is_empty() {
+        return;
+    }
+
+    // Columns are identified through their owning relation
+    let mut renamed_columns = HashMap::new();
+    for edge in &lineage.edges {
+        if edge.edge_type != EdgeType::Ownership {
+            continue;
+        }
+        let Some(new_parent) = renamed.get(&edge.from) else {
+            continue;
+        };
+        if let Some(column) = lineage.nodes.iter().find(|node| node.id == edge.to) {
+            if column.id == generate_column_node_id(Some(&edge.from), &column.label) {
+                renamed_columns.insert(
+                    column.id.clone(),
+                    generate_column_node_id(Some(new_parent), &column.label),
+                );
+            }
+        }
+    }
+    renamed.extend(renamed_columns);
+    for node in &mut lineage.nodes {
+        if let Some(new_id) = renamed.get(&node.id) {
+            node.id = new_id.clone();
+        }
+    }
+
+    for edge in &mut lineage.edges {
+        let from = renamed.get(&edge.from).unwrap_or(&edge.from).clone();
+        let to = renamed.get(&edge.to).unwrap_or(&edge.to).clone();
+        if edge.id == generate_edge_id(&edge.from, &edge.to) {
+            edge.id = generate_edge_id(&from, &to);
+        }
+        edge.from = from;
+        edge.to = to;
+    }
+}
+
+/// The label of a statement's output node when no model name is known.
+fn output_label(statement_index: usize) -> String {
+    if statement_index == 0 {
+        "Output".to_string()
+    } else {
+        format!("Output ({})", statement_index + 1)
+    }
+}
+
+fn merge_resolved_schema(
+    earlier: Option<ResolvedSchemaMetadata>,
+    later: Option<ResolvedSchemaMetadata>,
+    offset: usize,
+) -> Option<ResolvedSchemaMetadata> {
+    let Some(later) = later else {
+        return earlier;
+    };
+    let mut tables: Vec<ResolvedSchemaTable> =
+        earlier.map(|schema| schema.tables).unwrap_or_default();
+    for mut table in later.tables {
+        if let Some(index) = table.source_statement_index.as_mut() {
+            *index += offset;
+        }
+        let key = (&table.catalog, &table.schema, &table.name);
+        match tables
+            .iter_mut()
+            .find(|existing| (&existing.catalog, &existing.schema, &existing.name) == key)
+        {
+            Some(existing) => *existing = table,
+            None => tables.push(table),
+        }
+    }
+    tables.sort_by(|a, b| a.name.cmp(&b.name));
+    Some(ResolvedSchemaMetadata { tables })
+}
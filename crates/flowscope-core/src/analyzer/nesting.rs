@@ -0,0 +1,121 @@
+//! Subquery nesting depth check.
+//!
+//! Walks every query in a statement and measures how many subqueries or derived tables
+//! enclose it. CTE bodies and set-operation branches sit at the level of the query that
+//! contains them, so refactoring nested subqueries into CTEs lowers the reported depth.
+
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{Query, SetExpr, Spanned, Statement, Visit, Visitor};
+use sqlparser::tokenizer::Span as SqlSpan;
+
+use super::helpers::line_col_to_offset;
+use super::Analyzer;
+use crate::types::{issue_codes, Issue, Span};
+
+impl<'a> Analyzer<'a> {
+    /// Warns when subqueries in `statement` nest deeper than `max_subquery_depth`,
+    /// pointing at the deepest one. Does nothing unless the option is set.
+    pub(super) fn check_subquery_depth(&mut self, statement: &Statement, statement_index: usize) {
+        let Some(max_depth) = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.max_subquery_depth)
+        else {
+            return;
+        };
+
+        let mut visitor = DepthVisitor::default();
+        let _ = statement.visit(&mut visitor);
+        let Some((depth, deepest)) = visitor.deepest else {
+            return;
+        };
+        if depth <= max_depth {
+            return;
+        }
+
+        let mut issue = Issue::warning(
+            issue_codes::DEEPLY_NESTED_SUBQUERY,
+            format!(
+                "Subqueries nest {depth} levels deep (limit {max_depth}); consider refactoring the inner queries into CTEs"
+            ),
+        )
+        .with_statement(statement_index);
+        if let Some(span) = self.source_span(deepest) {
+            issue = issue.with_span(span);
+        }
+        self.issues.push(issue);
+    }
+
+    /// Converts a parser span into byte offsets within the current statement's source.
+    pub(super) fn source_span(&self, span: SqlSpan) -> Option<Span> {
+        let source = self.current_statement_source.as_ref()?;
+        let start = line_col_to_offset(
+            &source.sql,
+            span.start.line as usize,
+            span.start.column as usize,
+        )?;
+        let end = line_col_to_offset(
+            &source.sql,
+            span.end.line as usize,
+            span.end.column as usize,
+        )?;
+        Some(Span::new(start, end))
+    }
+}
+
+#[derive(Default)]
+struct DepthVisitor {
+    /// Depths of the queries enclosing the one being visited
+    stack: Vec<usize>,
+    /// Queries that sit at their parent's level (CTE bodies, set-operation branches)
+    same_level: HashSet<*const Query>,
+    /// Greatest depth seen, with the span of the query at that depth
+    deepest: Option<(usize, SqlSpan)>,
+}
+
+impl Visitor for DepthVisitor {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        let depth = match self.stack.last() {
+            None => 0,
+            Some(&parent) if self.same_level.contains(&(query as *const Query)) => parent,
+            Some(&parent) => parent + 1,
+        };
+        self.stack.push(depth);
+
+        if self.deepest.is_none_or(|(deepest, _)| depth > deepest) {
+            self.deepest = Some((depth, query.span()));
+        }
+
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                self.same_level.insert(&*cte.query as *const Query);
+            }
+        }
+        collect_set_branches(&query.body, &mut self.same_level);
+        ControlFlow::Continue(())
+    }
+
+    fn post_visit_query(&mut self, _query: &Query) -> ControlFlow<Self::Break> {
+        self.stack.pop();
+        ControlFlow::Continue(())
+    }
+}
+
+/// Records parenthesized set-operation branches, which do not add a nesting level.
+fn collect_set_branches(body: &SetExpr, same_level: &mut HashSet<*const Query>) {
+    match body {
+        SetExpr::SetOperation { left, right, .. } => {
+            collect_set_branches(left, same_level);
+            collect_set_branches(right, same_level);
+        }
+        SetExpr::Query(query) => {
+            same_level.insert(&**query as *const Query);
+        }
+        _ => {}
+    }
+}
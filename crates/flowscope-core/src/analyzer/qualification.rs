@@ -0,0 +1,91 @@
+//! Project-wide consistency check for schema-qualified table names.
+//!
+//! When some models read `analytics.users` and others plain `users`, readers cannot
+//! tell whether both mean the same table, and lineage splits it into two nodes. This
+//! pass groups the tables and views of the global lineage by base name and flags each
+//! name referenced both with and without a qualifier. Unqualified names that the
+//! schema resolves (through `defaultSchema` or `searchPath`) are already qualified by
+//! then and are not reported.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::types::{issue_codes, GlobalLineage, GlobalNode, Issue, StatementLineage};
+
+/// Flag tables and views referenced both qualified and unqualified.
+pub(crate) fn check_qualification_consistency(
+    statements: &[StatementLineage],
+    global_lineage: &GlobalLineage,
+) -> Vec<Issue> {
+    let mut by_name: BTreeMap<String, Vec<&GlobalNode>> = BTreeMap::new();
+    for node in &global_lineage.nodes {
+        if node.node_type.is_table_or_view() && node.canonical_name.column.is_none() {
+            by_name
+                .entry(node.canonical_name.name.to_lowercase())
+                .or_default()
+                .push(node);
+        }
+    }
+
+    let source_of = |statement_index: usize| {
+        statements
+            .iter()
+            .find(|s| s.statement_index == statement_index)
+            .and_then(|s| s.source_name.as_deref())
+    };
+    let describe = |node: &GlobalNode| {
+        let name = node.canonical_name.to_qualified_string();
+        let files: BTreeSet<&str> = node
+            .statement_refs
+            .iter()
+            .filter_map(|r| source_of(r.statement_index))
+            .collect();
+        if files.is_empty() {
+            format!("'{name}'")
+        } else {
+            format!(
+                "'{name}' ({})",
+                files.into_iter().collect::<Vec<_>>().join(", ")
+            )
+        }
+    };
+
+    let mut issues = Vec::new();
+    for nodes in by_name.values() {
+        let (qualified, unqualified): (Vec<&GlobalNode>, Vec<&GlobalNode>) =
+            nodes.iter().partition(|node| {
+                node.canonical_name.schema.is_some() || node.canonical_name.catalog.is_some()
+            });
+        let Some(bare) = unqualified.first() else {
+            continue;
+        };
+        if qualified.is_empty() {
+            continue;
+        }
+
+        let qualified_forms: Vec<String> = qualified.iter().map(|node| describe(node)).collect();
+        let suggestion = if qualified.len() == 1 {
+            format!(
+                "use '{}' everywhere",
+                qualified[0].canonical_name.to_qualified_string()
+            )
+        } else {
+            "qualify it everywhere with the schema it means".to_string()
+        };
+        let mut issue = Issue::warning(
+            issue_codes::INCONSISTENT_QUALIFICATION,
+            format!(
+                "Table referenced as {} and as {}; {suggestion}",
+                describe(bare),
+                qualified_forms.join(", ")
+            ),
+        );
+        if let Some(first) = bare.statement_refs.iter().map(|r| r.statement_index).min() {
+            issue = issue.with_statement(first);
+            if let Some(name) = source_of(first) {
+                issue = issue.with_source_name(name);
+            }
+        }
+        issues.push(issue);
+    }
+    issues
+}
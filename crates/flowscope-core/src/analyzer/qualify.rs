@@ -0,0 +1,426 @@
+//! QUALIFY clauses in dialects that do not support them.
+//!
+//! QUALIFY filters rows on window function results (`QUALIFY ROW_NUMBER() OVER (...) = 1`).
+//! sqlparser accepts it in every dialect, but Postgres, MySQL, SQL Server, SQLite, Hive
+//! and ANSI SQL reject it at runtime. The warning carries a fix that rewrites the
+//! statement into the portable form: the window functions become extra columns of a
+//! subquery, and the outer query filters on them and projects the original columns.
+//!
+//! ```sql
+//! SELECT id, ts FROM events QUALIFY ROW_NUMBER() OVER (PARTITION BY id ORDER BY ts DESC) = 1
+//! -- becomes
+//! SELECT id, ts FROM (SELECT id, ts, ROW_NUMBER() OVER (PARTITION BY id ORDER BY ts DESC) AS _qualify_1
+//!                     FROM events) AS qualified_rows WHERE _qualify_1 = 1
+//! ```
+//!
+//! The outer query needs the name of every output column, so no fix is offered for
+//! wildcards, unnamed expressions, or conditions and ORDER BY items that refer to
+//! anything but output columns. The rewrite is rendered from the AST, which keeps no
+//! comments, so statements containing comments get no fix either.
+
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{
+    visit_expressions_mut, Distinct, Expr, GroupByExpr, Ident, OrderBy, Query, Select, SelectItem,
+    SetExpr, Spanned, Statement, TableAlias, TableFactor, TableWithJoins, Visit, VisitMut, Visitor,
+    VisitorMut,
+};
+use sqlparser::tokenizer::{Token, Tokenizer, Whitespace};
+
+use super::reserved_keywords::quote_if_reserved;
+use super::Analyzer;
+use crate::parser::parse_sql_with_dialect;
+use crate::types::{issue_codes, Issue, Span};
+use crate::Dialect;
+
+/// Alias of the subquery holding the window columns.
+const DERIVED_ALIAS: &str = "qualified_rows";
+/// Prefix of the window columns added to the subquery.
+const WINDOW_COLUMN_PREFIX: &str = "_qualify_";
+
+/// Whether `dialect` runs QUALIFY natively. Generic SQL is treated as permissive.
+fn supports_qualify(dialect: Dialect) -> bool {
+    match dialect {
+        Dialect::Generic
+        | Dialect::Bigquery
+        | Dialect::Clickhouse
+        | Dialect::Databricks
+        | Dialect::Duckdb
+        | Dialect::Redshift
+        | Dialect::Snowflake => true,
+        Dialect::Ansi | Dialect::Hive | Dialect::Mssql | Dialect::Mysql | Dialect::Postgres => {
+            false
+        }
+        Dialect::Sqlite => false,
+    }
+}
+
+impl<'a> Analyzer<'a> {
+    /// Warns about every QUALIFY clause in `statement` when the dialect lacks QUALIFY,
+    /// attaching a fix that rewrites the statement without it when possible.
+    pub(super) fn check_qualify_support(&mut self, statement: &Statement, statement_index: usize) {
+        let dialect = self.request.dialect;
+        if supports_qualify(dialect) {
+            return;
+        }
+
+        let mut collector = QualifyCollector::default();
+        let _ = statement.visit(&mut collector);
+        if collector.conditions.is_empty() {
+            return;
+        }
+
+        let mut fix = self.qualify_fix(statement);
+        for condition in collector.conditions {
+            let message = if fix.is_some() {
+                format!(
+                    "Dialect '{dialect:?}' does not support QUALIFY. This may fail at runtime; the fix filters a subquery on the window columns instead."
+                )
+            } else {
+                format!(
+                    "Dialect '{dialect:?}' does not support QUALIFY. This may fail at runtime; filter a subquery on the window columns instead."
+                )
+            };
+            let mut issue = Issue::warning(issue_codes::UNSUPPORTED_SYNTAX, message)
+                .with_statement(statement_index);
+            if let Some(span) = self.source_span(condition) {
+                issue = issue.with_span(span);
+            }
+            // The fix rewrites the whole statement, so it is attached once
+            if let Some((span, replacement)) = fix.take() {
+                issue = issue.with_fix(span, replacement);
+            }
+            self.issues.push(issue);
+        }
+    }
+
+    /// The statement's source span and its rewrite without QUALIFY, if every QUALIFY
+    /// can be rewritten and the result parses in the request's dialect.
+    fn qualify_fix(&self, statement: &Statement) -> Option<(Span, String)> {
+        // Statement ranges point into rendered templates or DO block bodies, not the source
+        #[cfg(feature = "templating")]
+        if self.request.template_config.is_some() {
+            return None;
+        }
+        let source = self.current_statement_source.as_ref()?;
+        if source.do_block.is_some()
+            || contains_comment(&source.sql[source.range.clone()], self.request.dialect)
+        {
+            return None;
+        }
+
+        let mut rewritten = statement.clone();
+        let mut rewriter = QualifyRewriter {
+            dialect: self.request.dialect,
+        };
+        if VisitMut::visit(&mut rewritten, &mut rewriter).is_break() {
+            return None;
+        }
+        let sql = rewritten.to_string();
+        match parse_sql_with_dialect(&sql, self.request.dialect) {
+            Ok(statements) if statements.len() == 1 => {
+                Some((Span::new(source.range.start, source.range.end), sql))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Whether `sql` contains a comment, or cannot be tokenized to tell.
+fn contains_comment(sql: &str, dialect: Dialect) -> bool {
+    let dialect = dialect.to_sqlparser_dialect();
+    match Tokenizer::new(dialect.as_ref(), sql).tokenize() {
+        Ok(tokens) => tokens.iter().any(|token| {
+            matches!(
+                token,
+                Token::Whitespace(
+                    Whitespace::SingleLineComment { .. } | Whitespace::MultiLineComment(_)
+                )
+            )
+        }),
+        Err(_) => true,
+    }
+}
+
+#[derive(Default)]
+struct QualifyCollector {
+    /// Span of each QUALIFY condition
+    conditions: Vec<sqlparser::tokenizer::Span>,
+}
+
+impl QualifyCollector {
+    fn collect(&mut self, body: &SetExpr) {
+        match body {
+            SetExpr::Select(select) => {
+                if let Some(condition) = &select.qualify {
+                    self.conditions.push(condition.span());
+                }
+            }
+            // Nested queries are visited on their own
+            SetExpr::SetOperation { left, right, .. } => {
+                self.collect(left);
+                self.collect(right);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visitor for QualifyCollector {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        self.collect(&query.body);
+        ControlFlow::Continue(())
+    }
+}
+
+/// Rewrites every SELECT with QUALIFY into a filter over a subquery; breaks when one
+/// cannot be rewritten.
+struct QualifyRewriter {
+    dialect: Dialect,
+}
+
+impl VisitorMut for QualifyRewriter {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &mut Query) -> ControlFlow<Self::Break> {
+        if rewrite_body(&mut query.body, query.order_by.as_ref(), self.dialect) {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    }
+}
+
+/// Rewrites the SELECTs with QUALIFY in a query body. `order_by` is the ORDER BY of
+/// the query when its body is a single SELECT.
+fn rewrite_body(body: &mut SetExpr, order_by: Option<&OrderBy>, dialect: Dialect) -> bool {
+    match body {
+        SetExpr::Select(select) if select.qualify.is_some() => {
+            match unqualify(select, order_by, dialect) {
+                Some(outer) => {
+                    **select = outer;
+                    true
+                }
+                None => false,
+            }
+        }
+        // The ORDER BY of a set operation sorts its combined output
+        SetExpr::SetOperation { left, right, .. } => {
+            rewrite_body(left, None, dialect) && rewrite_body(right, None, dialect)
+        }
+        _ => true,
+    }
+}
+
+/// The SELECT filtering a subquery of `select` the way its QUALIFY clause does.
+///
+/// Output names that are reserved keywords of `dialect` are quoted in both queries:
+/// `SELECT id AS order` is accepted, but the outer `SELECT order` would not be.
+fn unqualify(select: &Select, order_by: Option<&OrderBy>, dialect: Dialect) -> Option<Select> {
+    if matches!(select.distinct, Some(Distinct::On(_))) || select.into.is_some() {
+        return None;
+    }
+    let names = output_names(&select.projection)?;
+
+    // Window functions move into the subquery as numbered columns
+    let mut condition = select.qualify.clone()?;
+    let mut window_columns = Vec::new();
+    let _ = visit_expressions_mut(&mut condition, |expr| {
+        if matches!(expr, Expr::Function(function) if function.over.is_some()) {
+            let alias = Ident::new(format!(
+                "{WINDOW_COLUMN_PREFIX}{}",
+                window_columns.len() + 1
+            ));
+            let window = std::mem::replace(expr, Expr::Identifier(alias.clone()));
+            window_columns.push(SelectItem::ExprWithAlias {
+                expr: window,
+                alias,
+            });
+        }
+        ControlFlow::<()>::Continue(())
+    });
+
+    let mut references = ColumnReferences {
+        names: &names,
+        window_columns: window_columns.len(),
+    };
+    if condition.visit(&mut references).is_break() {
+        return None;
+    }
+    if let Some(order_by) = order_by {
+        if order_by.visit(&mut references).is_break() {
+            return None;
+        }
+    }
+
+    // QUALIFY runs before DISTINCT and TOP, which therefore stay on the outer query
+    let mut inner = select.clone();
+    inner.distinct = None;
+    inner.top = None;
+    inner.top_before_distinct = false;
+    inner.qualify = None;
+    inner.window_before_qualify = false;
+    for item in &mut inner.projection {
+        if let SelectItem::ExprWithAlias { alias, .. } = item {
+            *alias = quote_if_reserved(alias, dialect);
+        }
+    }
+    inner.projection.extend(window_columns);
+
+    let subquery = Query {
+        with: None,
+        body: Box::new(SetExpr::Select(Box::new(inner))),
+        order_by: None,
+        limit_clause: None,
+        fetch: None,
+        locks: Vec::new(),
+        for_clause: None,
+        settings: None,
+        format_clause: None,
+        pipe_operators: Vec::new(),
+    };
+
+    let mut outer = select.clone();
+    outer.projection = names
+        .into_iter()
+        .map(|name| SelectItem::UnnamedExpr(Expr::Identifier(quote_if_reserved(&name, dialect))))
+        .collect();
+    outer.exclude = None;
+    outer.from = vec![TableWithJoins {
+        relation: TableFactor::Derived {
+            lateral: false,
+            subquery: Box::new(subquery),
+            alias: Some(TableAlias {
+                name: Ident::new(DERIVED_ALIAS),
+                columns: Vec::new(),
+            }),
+        },
+        joins: Vec::new(),
+    }];
+    outer.lateral_views = Vec::new();
+    outer.prewhere = None;
+    outer.selection = Some(condition);
+    outer.group_by = GroupByExpr::Expressions(Vec::new(), Vec::new());
+    outer.cluster_by = Vec::new();
+    outer.distribute_by = Vec::new();
+    outer.sort_by = Vec::new();
+    outer.having = None;
+    outer.named_window = Vec::new();
+    outer.qualify = None;
+    outer.window_before_qualify = false;
+    outer.connect_by = None;
+    Some(outer)
+}
+
+/// Names of the output columns, or `None` if one has no name or two share one.
+fn output_names(projection: &[SelectItem]) -> Option<Vec<Ident>> {
+    let mut names: Vec<Ident> = Vec::with_capacity(projection.len());
+    for item in projection {
+        let name = match item {
+            SelectItem::ExprWithAlias { alias, .. } => alias.clone(),
+            SelectItem::UnnamedExpr(Expr::Identifier(ident)) => ident.clone(),
+            SelectItem::UnnamedExpr(Expr::CompoundIdentifier(parts)) => parts.last()?.clone(),
+            _ => return None,
+        };
+        if name.value.starts_with(WINDOW_COLUMN_PREFIX)
+            || names
+                .iter()
+                .any(|seen| seen.value.eq_ignore_ascii_case(&name.value))
+        {
+            return None;
+        }
+        names.push(name);
+    }
+    Some(names)
+}
+
+/// Breaks on anything the outer query cannot resolve: a column that is not an
+/// output column or window column, a qualified column, or a subquery.
+struct ColumnReferences<'n> {
+    names: &'n [Ident],
+    window_columns: usize,
+}
+
+impl ColumnReferences<'_> {
+    fn resolves(&self, ident: &Ident) -> bool {
+        self.names
+            .iter()
+            .any(|name| name.value.eq_ignore_ascii_case(&ident.value))
+            || (1..=self.window_columns)
+                .any(|n| ident.value == format!("{WINDOW_COLUMN_PREFIX}{n}"))
+    }
+}
+
+impl Visitor for ColumnReferences<'_> {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, _query: &Query) -> ControlFlow<Self::Break> {
+        ControlFlow::Break(())
+    }
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+        match expr {
+            Expr::Identifier(ident) if !self.resolves(ident) => ControlFlow::Break(()),
+            Expr::CompoundIdentifier(_) => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rewrite(sql: &str) -> Option<String> {
+        rewrite_in(sql, Dialect::Generic)
+    }
+
+    fn rewrite_in(sql: &str, dialect: Dialect) -> Option<String> {
+        let mut statements = parse_sql_with_dialect(sql, dialect).unwrap();
+        let mut rewriter = QualifyRewriter { dialect };
+        if VisitMut::visit(&mut statements[0], &mut rewriter).is_break() {
+            return None;
+        }
+        Some(statements[0].to_string())
+    }
+
+    #[test]
+    fn moves_distinct_and_window_columns() {
+        assert_eq!(
+            rewrite(
+                "SELECT DISTINCT t.a, SUM(b) AS total FROM t GROUP BY t.a \
+                 QUALIFY RANK() OVER (ORDER BY SUM(b) DESC) <= 3 ORDER BY total"
+            )
+            .unwrap(),
+            "SELECT DISTINCT a, total FROM (SELECT t.a, SUM(b) AS total, \
+             RANK() OVER (ORDER BY SUM(b) DESC) AS _qualify_1 FROM t GROUP BY t.a) AS qualified_rows \
+             WHERE _qualify_1 <= 3 ORDER BY total"
+        );
+    }
+
+    #[test]
+    fn refuses_unnamed_columns_and_outside_references() {
+        assert!(rewrite("SELECT * FROM t QUALIFY ROW_NUMBER() OVER (ORDER BY a) = 1").is_none());
+        assert!(rewrite("SELECT a + 1 FROM t QUALIFY ROW_NUMBER() OVER () = 1").is_none());
+        assert!(rewrite("SELECT a FROM t QUALIFY b > ROW_NUMBER() OVER ()").is_none());
+        assert!(rewrite("SELECT a FROM t QUALIFY ROW_NUMBER() OVER () = 1 ORDER BY t.b").is_none());
+        // Numbered ORDER BY positions still point at the same output columns
+        assert!(rewrite("SELECT a FROM t QUALIFY ROW_NUMBER() OVER () = 1 ORDER BY 1").is_some());
+    }
+
+    #[test]
+    fn quotes_reserved_keyword_output_names() {
+        let sql = "SELECT id AS order, ts FROM events \
+                   QUALIFY ROW_NUMBER() OVER (PARTITION BY id ORDER BY ts DESC) = 1";
+        let rewritten = rewrite_in(sql, Dialect::Postgres).unwrap();
+        assert_eq!(
+            rewritten,
+            "SELECT \"order\", ts FROM (SELECT id AS \"order\", ts, \
+             ROW_NUMBER() OVER (PARTITION BY id ORDER BY ts DESC) AS _qualify_1 FROM events) AS qualified_rows \
+             WHERE _qualify_1 = 1"
+        );
+        assert!(parse_sql_with_dialect(&rewritten, Dialect::Postgres).is_ok());
+    }
+}
@@ -4,7 +4,7 @@
 //! FROM clauses, JOINs, WHERE/HAVING filters, and wildcard expansion. It builds the
 //! column-level lineage graph by tracking data flow from source columns to output columns.
 
-use super::context::{ColumnRef, OutputColumn, PendingWildcard, StatementContext};
+use super::context::{root_column, ColumnRef, OutputColumn, PendingWildcard, StatementContext};
 use super::helpers::{generate_column_node_id, generate_edge_id, normalize_schema_type};
 use super::visitor::{LineageVisitor, Visitor};
 use super::Analyzer;
@@ -43,6 +43,7 @@ pub(super) struct OutputColumnParams {
     pub sources: Vec<ColumnRef>,
     pub expression: Option<String>,
     pub data_type: Option<String>,
+    pub nullable: Option<bool>,
     pub target_node: Option<String>,
     pub approximate: bool,
     pub aggregation: Option<AggregationInfo>,
@@ -159,7 +160,16 @@ impl<'a> Analyzer<'a> {
 
         // Create node if not already present
         if !ctx.node_ids.contains(&id) {
-            self.create_table_node(ctx, &canonical, &id, node_type, is_known, resolution_source);
+            let label = display_label(table_name, &canonical);
+            self.create_table_node(
+                ctx,
+                &canonical,
+                &label,
+                &id,
+                node_type,
+                is_known,
+                resolution_source,
+            );
         }
 
         self.tracker
@@ -200,10 +210,12 @@ impl<'a> Analyzer<'a> {
     }
 
     /// Creates a table node and adds it to the context.
+    #[allow(clippy::too_many_arguments)]
     fn create_table_node(
         &mut self,
         ctx: &mut StatementContext,
         canonical: &str,
+        label: &str,
         id: &std::sync::Arc<str>,
         node_type: NodeType,
         is_known: bool,
@@ -232,7 +244,7 @@ impl<'a> Analyzer<'a> {
         ctx.add_node(Node {
             id: id.clone(),
             node_type,
-            label: crate::analyzer::helpers::extract_simple_name(canonical).into(),
+            label: label.into(),
             qualified_name: Some(canonical.to_string().into()),
             expression: None,
             span: None,
@@ -249,7 +261,8 @@ impl<'a> Analyzer<'a> {
         });
     }
 
-    /// Creates a data flow edge from source to target.
+    /// Creates a data flow edge from source to target, or a recursive self-reference
+    /// edge when a CTE reads itself.
     fn create_source_edge(
         &mut self,
         ctx: &mut StatementContext,
@@ -263,11 +276,17 @@ impl<'a> Analyzer<'a> {
             return;
         }
 
+        let edge_type = if &**source_id == target && ctx.cte_node_to_name.contains_key(source_id) {
+            EdgeType::RecursiveSelfReference
+        } else {
+            EdgeType::DataFlow
+        };
+
         ctx.add_edge(Edge {
             id: edge_id,
             from: source_id.clone(),
             to: target.to_string().into(),
-            edge_type: EdgeType::DataFlow,
+            edge_type,
             expression: None,
             operation: ctx.last_operation.as_deref().map(Into::into),
             join_type: ctx.current_join_info.join_type,
@@ -287,6 +306,9 @@ impl<'a> Analyzer<'a> {
         table_canonical: &str,
         table_node_id: &str,
     ) {
+        if !ctx.column_lineage {
+            return;
+        }
         if let Some(schema_entry) = self.schema.get(table_canonical) {
             // We must clone columns to avoid borrowing self while iterating
             let columns = schema_entry.table.columns.clone();
@@ -335,7 +357,11 @@ impl<'a> Analyzer<'a> {
         ctx: &mut StatementContext,
         table_qualifier: Option<&str>,
         target_node: Option<&str>,
+        options: &ast::WildcardAdditionalOptions,
     ) {
+        let wildcard_span = options.wildcard_token.0.span;
+        let excluded = self.wildcard_exclusions(options);
+
         // Resolve table qualifier to canonical name
         let tables_to_expand: Vec<String> = if let Some(qualifier) = table_qualifier {
             let resolved = self.resolve_table_alias(ctx, Some(qualifier));
@@ -396,6 +422,9 @@ impl<'a> Analyzer<'a> {
             if let Some(columns) = columns_to_add {
                 // Expand from schema - NOT approximate
                 for col_info in columns {
+                    if excluded.contains(&self.normalize_identifier(&col_info.name)) {
+                        continue;
+                    }
                     let sources = vec![ColumnRef {
                         table: Some(col_info.table_canonical),
                         column: col_info.name.clone(),
@@ -422,6 +451,7 @@ impl<'a> Analyzer<'a> {
                     issue = issue.with_span(span);
                 }
                 self.issues.push(issue);
+                self.report_unknown_wildcard_table(ctx, &table_canonical, wildcard_span);
 
                 // If there's a target node, create an approximate edge from source table to target
                 // and record the pending wildcard for backward inference
@@ -463,6 +493,61 @@ impl<'a> Analyzer<'a> {
         }
     }
 
+    /// Normalized names of the columns dropped from a wildcard by `* EXCLUDE (...)`
+    /// (DuckDB, Snowflake) or `* EXCEPT (...)` (BigQuery, ClickHouse).
+    fn wildcard_exclusions(&self, options: &ast::WildcardAdditionalOptions) -> HashSet<String> {
+        let excluded: Vec<&ast::Ident> = match (&options.opt_exclude, &options.opt_except) {
+            (Some(ast::ExcludeSelectItem::Single(ident)), _) => vec![ident],
+            (Some(ast::ExcludeSelectItem::Multiple(idents)), _) => idents.iter().collect(),
+            (None, Some(except)) => std::iter::once(&except.first_element)
+                .chain(&except.additional_elements)
+                .collect(),
+            (None, None) => Vec::new(),
+        };
+        excluded
+            .into_iter()
+            .map(|ident| self.normalize_identifier(&ident.value))
+            .collect()
+    }
+
+    /// Warns that `SELECT *` over `table_canonical` could not be expanded because the
+    /// table is missing from the provided schema, unless `warn_unknown_wildcard_tables`
+    /// turns it off. CTEs, derived tables and tables created earlier in the request
+    /// are left to their own diagnostics.
+    fn report_unknown_wildcard_table(
+        &mut self,
+        ctx: &StatementContext,
+        table_canonical: &str,
+        wildcard_span: sqlparser::tokenizer::Span,
+    ) {
+        let enabled = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.warn_unknown_wildcard_tables)
+            .unwrap_or(true);
+        if !enabled
+            || self.request.schema.is_none()
+            || self.schema.is_known(table_canonical)
+            || ctx.cte_definitions.contains_key(table_canonical)
+            || ctx.aliased_subquery_columns.contains_key(table_canonical)
+        {
+            return;
+        }
+
+        let mut issue = Issue::warning(
+            issue_codes::SCHEMA_UNKNOWN_TABLE,
+            format!(
+                "SELECT * from '{table_canonical}' was not expanded: the table is not in the provided schema"
+            ),
+        )
+        .with_statement(ctx.statement_index);
+        if let Some(span) = self.source_span(wildcard_span) {
+            issue = issue.with_span(span);
+        }
+        self.issues.push(issue);
+    }
+
     pub(super) fn resolve_table_alias(
         &self,
         ctx: &StatementContext,
@@ -487,14 +572,57 @@ impl<'a> Analyzer<'a> {
                     // Subquery alias - no canonical name
                     None
                 } else {
-                    // Treat as table name
-                    Some(self.canonicalize_table_reference(q).canonical)
+                    // Treat as table name. Qualifiers arrive unquoted, so a quoted
+                    // mixed-case name (`"Order Items".qty`) is matched against the tables in
+                    // scope as written before it is case-folded like an unquoted name.
+                    let in_scope = ctx
+                        .scope_stack
+                        .iter()
+                        .rev()
+                        .flat_map(|scope| scope.tables.keys())
+                        .find(|table| {
+                            table.as_str() == q
+                                || table
+                                    .strip_suffix(q)
+                                    .is_some_and(|rest| rest.ends_with('.'))
+                        });
+                    Some(match in_scope {
+                        Some(table) => table.clone(),
+                        None => self.canonicalize_table_reference(q).canonical,
+                    })
                 }
             }
             None => None,
         }
     }
 
+    /// Returns true if `qualifier` names a table, alias, CTE, or derived table visible
+    /// in the statement, as opposed to a struct column whose fields are accessed.
+    pub(super) fn is_relation_qualifier(&self, ctx: &StatementContext, qualifier: &str) -> bool {
+        if ctx.scope_stack.iter().any(|scope| {
+            scope.aliases.contains_key(qualifier) || scope.subquery_aliases.contains(qualifier)
+        }) || ctx.table_aliases.contains_key(qualifier)
+            || ctx.cte_definitions.contains_key(qualifier)
+            || ctx.subquery_aliases.contains(qualifier)
+        {
+            return true;
+        }
+
+        // Unaliased tables may be qualified by their full or bare name
+        let canonical = self.canonicalize_table_reference(qualifier).canonical;
+        let bare = self.normalize_identifier(qualifier);
+        ctx.scope_stack
+            .iter()
+            .flat_map(|scope| scope.tables.keys())
+            .any(|table| {
+                *table == canonical
+                    || table
+                        .rsplit('.')
+                        .next()
+                        .is_some_and(|name| self.normalize_identifier(name) == bare)
+            })
+    }
+
     pub(crate) fn resolve_column_table(
         &mut self,
         ctx: &StatementContext,
@@ -528,7 +656,7 @@ impl<'a> Analyzer<'a> {
             return Some(tables_in_scope[0].clone());
         }
 
-        let normalized_col = self.normalize_identifier(column);
+        let normalized_col = self.normalize_identifier(root_column(column));
 
         // Collect candidates using CTE output columns and schema metadata
         // Only consider tables that are actually in the current scope
@@ -563,6 +691,20 @@ impl<'a> Analyzer<'a> {
                 if tables_in_scope.len() == 1 {
                     return Some(tables_in_scope[0].clone());
                 }
+                // Relations with a known column list cannot own it, so when a single
+                // table of unknown shape remains (e.g. beside an UNNEST), assume it is that one
+                let unknown_shape: Vec<&String> = tables_in_scope
+                    .iter()
+                    .filter(|table| {
+                        ctx.aliased_subquery_columns
+                            .get(*table)
+                            .is_none_or(Vec::is_empty)
+                            && self.schema.get(table).is_none()
+                    })
+                    .collect();
+                if let [table] = unknown_shape.as_slice() {
+                    return Some((*table).clone());
+                }
                 // Multiple tables but column not found in any - ambiguous
                 let mut sorted_tables = tables_in_scope.clone();
                 sorted_tables.sort();
@@ -621,6 +763,7 @@ impl<'a> Analyzer<'a> {
                 sources,
                 expression,
                 data_type,
+                nullable: None,
                 target_node: target_node.map(|s| s.to_string()),
                 approximate,
                 aggregation: None,
@@ -634,7 +777,11 @@ impl<'a> Analyzer<'a> {
         ctx: &mut StatementContext,
         params: OutputColumnParams,
     ) {
-        let normalized_name = self.normalize_identifier(&params.name);
+        // INSERT targets with a known column list take their names by position
+        let name = ctx
+            .next_insert_column(params.target_node.as_deref())
+            .unwrap_or_else(|| params.name.clone());
+        let normalized_name = self.normalize_identifier(&name);
         let node_id = generate_column_node_id(params.target_node.as_deref(), &normalized_name);
 
         // Create column node
@@ -645,11 +792,16 @@ impl<'a> Analyzer<'a> {
             qualified_name: None, // Will be set if we have target table
             expression: params.expression.as_deref().map(Into::into),
             span: None,
-            metadata: params.data_type.as_ref().map(|dt| {
+            metadata: {
                 let mut m = HashMap::new();
-                m.insert("data_type".to_string(), json!(dt));
-                m
-            }),
+                if let Some(dt) = &params.data_type {
+                    m.insert("data_type".to_string(), json!(dt));
+                }
+                if let Some(nullable) = params.nullable {
+                    m.insert("nullable".to_string(), json!(nullable));
+                }
+                (!m.is_empty()).then_some(m)
+            },
             resolution_source: None,
             filters: Vec::new(),
             join_type: None,
@@ -773,6 +925,7 @@ impl<'a> Analyzer<'a> {
         ctx.output_columns.push(OutputColumn {
             name: normalized_name,
             data_type: params.data_type,
+            nullable: params.nullable,
             node_id,
         });
     }
@@ -1153,3 +1306,15 @@ impl<'a> Analyzer<'a> {
         Some(col_node_id)
     }
 }
+
+/// The label of a table node: the name as written when it only differs from the
+/// canonical name by the dialect's case folding, otherwise the canonical name.
+fn display_label(written: &str, canonical: &str) -> String {
+    let folded = super::helpers::extract_simple_name(canonical);
+    let written = super::helpers::unquote_identifier(&super::helpers::extract_simple_name(written));
+    if written != folded && written.eq_ignore_ascii_case(&folded) {
+        written
+    } else {
+        folded
+    }
+}
@@ -0,0 +1,170 @@
+//! Recursive CTEs whose recursive branch has no condition on the recursive relation.
+//!
+//! A recursive CTE stops once its recursive branch produces no new rows. When that
+//! branch filters nothing based on the rows of the previous iteration (a depth
+//! counter, a bound on a generated value, a visited check), termination rests on
+//! the data alone, and a cycle in a hierarchy or an ever-growing counter makes the
+//! query run away. This is a heuristic: a WHERE that mentions the recursive relation
+//! at all is taken as narrowing it, and unqualified columns count as mentioning it.
+
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{
+    Expr, ObjectNamePart, Query, SetExpr, Statement, TableFactor, Visit, Visitor,
+};
+
+use super::Analyzer;
+use crate::types::{issue_codes, Issue};
+
+impl<'a> Analyzer<'a> {
+    /// With `check_recursive_termination`, warns about every recursive CTE in
+    /// `statement` whose recursive branch lacks a WHERE condition on the CTE.
+    pub(super) fn check_recursive_termination(
+        &mut self,
+        statement: &Statement,
+        statement_index: usize,
+    ) {
+        let enabled = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.check_recursive_termination)
+            .unwrap_or(false);
+        if !enabled {
+            return;
+        }
+
+        let mut collector = UnboundedRecursionCollector::default();
+        let _ = statement.visit(&mut collector);
+
+        for name in collector.ctes {
+            let message = format!(
+                "Recursive CTE '{name}' has no WHERE condition on '{name}' in its recursive branch, so nothing but the data stops the recursion; bound it, e.g. with a depth column and `WHERE {name}.depth < 100`"
+            );
+            let mut issue = Issue::warning(issue_codes::UNBOUNDED_RECURSION, message)
+                .with_statement(statement_index);
+            if let Some(span) = self.find_span(&name) {
+                issue = issue.with_span(span);
+            }
+            self.issues.push(issue);
+        }
+    }
+}
+
+#[derive(Default)]
+struct UnboundedRecursionCollector {
+    /// Names of the recursive CTEs lacking a narrowing condition, in query order
+    ctes: Vec<String>,
+}
+
+impl Visitor for UnboundedRecursionCollector {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        let Some(with) = query.with.as_ref().filter(|with| with.recursive) else {
+            return ControlFlow::Continue(());
+        };
+        for cte in &with.cte_tables {
+            let name = &cte.alias.name.value;
+            let mut branches = Vec::new();
+            collect_branches(&cte.query.body, &mut branches);
+
+            let mut recursive = branches
+                .into_iter()
+                .filter_map(|branch| recursive_branch(branch, name))
+                .peekable();
+            // A CTE declared RECURSIVE without referring to itself is not checked
+            if recursive.peek().is_none() {
+                continue;
+            }
+            if recursive.any(|(selection, references)| {
+                !selection.is_some_and(|expr| mentions_relation(expr, &references))
+            }) {
+                self.ctes.push(name.clone());
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// The SELECTs combined by set operations in `body`.
+fn collect_branches<'q>(body: &'q SetExpr, branches: &mut Vec<&'q SetExpr>) {
+    match body {
+        SetExpr::SetOperation { left, right, .. } => {
+            collect_branches(left, branches);
+            collect_branches(right, branches);
+        }
+        SetExpr::Query(query) => collect_branches(&query.body, branches),
+        _ => branches.push(body),
+    }
+}
+
+/// For a branch reading the CTE `name`, its WHERE condition and the names the CTE
+/// goes by in it.
+fn recursive_branch<'q>(
+    branch: &'q SetExpr,
+    name: &str,
+) -> Option<(Option<&'q Expr>, Vec<String>)> {
+    let SetExpr::Select(select) = branch else {
+        return None;
+    };
+    let mut references = Vec::new();
+    let relations = select.from.iter().flat_map(|table| {
+        std::iter::once(&table.relation).chain(table.joins.iter().map(|j| &j.relation))
+    });
+    for relation in relations {
+        let TableFactor::Table {
+            name: table, alias, ..
+        } = relation
+        else {
+            continue;
+        };
+        let is_cte = table.0.len() == 1
+            && matches!(&table.0[0], ObjectNamePart::Identifier(ident) if ident.value.eq_ignore_ascii_case(name));
+        if is_cte {
+            references.push(
+                alias
+                    .as_ref()
+                    .map_or_else(|| name.to_string(), |alias| alias.name.value.clone()),
+            );
+        }
+    }
+    if references.is_empty() {
+        return None;
+    }
+    Some((select.selection.as_ref(), references))
+}
+
+/// Whether `expr` refers to a column of one of `relations`, counting unqualified
+/// columns as such.
+fn mentions_relation(expr: &Expr, relations: &[String]) -> bool {
+    expr.visit(&mut RelationMentionVisitor { relations })
+        .is_break()
+}
+
+/// Breaks at the first column reference to one of `relations`.
+struct RelationMentionVisitor<'r> {
+    relations: &'r [String],
+}
+
+impl Visitor for RelationMentionVisitor<'_> {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+        let mentions = match expr {
+            Expr::Identifier(_) => true,
+            Expr::CompoundIdentifier(parts) if parts.len() >= 2 => {
+                let qualifier = &parts[parts.len() - 2].value;
+                self.relations
+                    .iter()
+                    .any(|relation| relation.eq_ignore_ascii_case(qualifier))
+            }
+            _ => false,
+        };
+        if mentions {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+}
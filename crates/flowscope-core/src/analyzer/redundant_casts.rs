@@ -0,0 +1,144 @@
+//! Nested casts that convert a value back to a type it already had
+//! (`x::int::text::int`, `CAST(CAST(x AS VARCHAR) AS INT)` on an integer `x`).
+//!
+//! A chain of casts is reduced to the canonical type after each step, starting with
+//! the operand's own type when it can be inferred (literals, and casts). When a type
+//! comes up again further along the chain, the casts in between are a round trip:
+//! they cost a conversion and may lose precision or formatting without changing the
+//! result type. `TRY_CAST`/`SAFE_CAST` and casts with a `FORMAT` are skipped, since
+//! they are used to validate or reformat values.
+
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{CastKind, DataType, Expr, Statement, Visit, Visitor};
+
+use super::helpers::{canonical_type_from_data_type, infer_expr_type};
+use super::Analyzer;
+use crate::generated::CanonicalType;
+use crate::types::{issue_codes, Issue};
+
+impl<'a> Analyzer<'a> {
+    /// With `check_redundant_casts`, warns about every chain of nested casts in
+    /// `statement` that converts back to a type it already had.
+    pub(super) fn check_redundant_casts(&mut self, statement: &Statement, statement_index: usize) {
+        let enabled = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.check_redundant_casts)
+            .unwrap_or(false);
+        if !enabled {
+            return;
+        }
+
+        let mut collector = RedundantCastCollector::default();
+        let _ = statement.visit(&mut collector);
+
+        for message in collector.messages {
+            self.issues.push(
+                Issue::warning(issue_codes::REDUNDANT_CAST, message)
+                    .with_statement(statement_index),
+            );
+        }
+    }
+}
+
+/// One step of a cast chain.
+struct CastStep<'e> {
+    data_type: &'e DataType,
+    canonical: Option<CanonicalType>,
+}
+
+#[derive(Default)]
+struct RedundantCastCollector {
+    messages: Vec<String>,
+    /// Casts already covered as part of an enclosing chain
+    inner_casts: HashSet<*const Expr>,
+}
+
+impl Visitor for RedundantCastCollector {
+    type Break = ();
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+        if !matches!(expr, Expr::Cast { .. }) || self.inner_casts.contains(&(expr as *const Expr)) {
+            return ControlFlow::Continue(());
+        }
+
+        // Walk from the outermost cast down to the operand
+        let mut steps = Vec::new();
+        let mut plain = true;
+        let mut current = expr;
+        loop {
+            match current {
+                Expr::Cast {
+                    kind,
+                    expr: inner,
+                    data_type,
+                    format,
+                } => {
+                    if !std::ptr::eq(current, expr) {
+                        self.inner_casts.insert(current as *const Expr);
+                    }
+                    plain &=
+                        matches!(kind, CastKind::Cast | CastKind::DoubleColon) && format.is_none();
+                    steps.push(CastStep {
+                        data_type,
+                        canonical: canonical_type_from_data_type(data_type),
+                    });
+                    current = inner;
+                }
+                Expr::Nested(inner) => current = inner,
+                _ => break,
+            }
+        }
+        if steps.len() < 2 || !plain {
+            return ControlFlow::Continue(());
+        }
+        steps.reverse();
+
+        if let Some(problem) = redundancy(infer_expr_type(current), &steps) {
+            self.messages.push(format!("`{expr}` {problem}"));
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Describes the first redundancy in a chain of casts applied in order to an operand
+/// of type `operand`: two consecutive casts to the same type, or a return to a type
+/// the value had before, through known types only.
+fn redundancy(operand: Option<CanonicalType>, steps: &[CastStep]) -> Option<String> {
+    for pair in steps.windows(2) {
+        if pair[0].data_type == pair[1].data_type {
+            return Some(format!(
+                "casts to {} twice; the inner cast is redundant",
+                pair[1].data_type
+            ));
+        }
+    }
+
+    let types: Vec<Option<CanonicalType>> = std::iter::once(operand)
+        .chain(steps.iter().map(|step| step.canonical))
+        .collect();
+    for end in 2..types.len() {
+        let Some(last) = types[end] else {
+            continue;
+        };
+        let Some(start) = (0..end - 1).rev().find(|&start| types[start] == Some(last)) else {
+            continue;
+        };
+        let between = &types[start + 1..end];
+        if between.iter().all(|t| t.is_some_and(|t| t != last)) {
+            let path: Vec<&str> = types[start..=end]
+                .iter()
+                .flatten()
+                .map(CanonicalType::as_uppercase_str)
+                .collect();
+            return Some(format!(
+                "converts {} and back; the round trip is redundant",
+                path.join(" -> ")
+            ));
+        }
+    }
+    None
+}
@@ -0,0 +1,112 @@
+//! Row limits without ORDER BY (`LIMIT`, `OFFSET`, `FETCH`, SQL Server `TOP`).
+//!
+//! Without an ordering the database may return any qualifying rows, so the result
+//! changes between runs. `WITH TIES` is meaningless without ORDER BY and rejected by
+//! SQL Server outright.
+
+use std::ops::ControlFlow;
+
+use sqlparser::ast::{LimitClause, Query, SetExpr, Statement, Visit, Visitor};
+
+use super::Analyzer;
+use crate::types::{issue_codes, Issue};
+
+impl<'a> Analyzer<'a> {
+    /// With `check_unordered_limits`, warns about every query in `statement` that
+    /// limits or skips rows without an ORDER BY.
+    pub(super) fn check_unordered_row_limits(
+        &mut self,
+        statement: &Statement,
+        statement_index: usize,
+    ) {
+        let enabled = self
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.check_unordered_limits)
+            .unwrap_or(false);
+        if !enabled {
+            return;
+        }
+
+        let mut collector = UnorderedLimitCollector::default();
+        let _ = statement.visit(&mut collector);
+
+        for limit in collector.limits {
+            let message = if limit.with_ties {
+                format!(
+                    "{} WITH TIES requires an ORDER BY to decide which rows tie",
+                    limit.clause
+                )
+            } else {
+                format!(
+                    "{} without ORDER BY returns an arbitrary set of rows; add an ORDER BY",
+                    limit.clause
+                )
+            };
+            self.issues.push(
+                Issue::warning(issue_codes::UNORDERED_ROW_LIMIT, message)
+                    .with_statement(statement_index),
+            );
+        }
+    }
+}
+
+/// A row-limiting clause found on a query without ORDER BY.
+struct UnorderedLimit {
+    /// The clause as written: `LIMIT`, `OFFSET`, `FETCH`, `TOP` or `TOP ... PERCENT`
+    clause: &'static str,
+    with_ties: bool,
+}
+
+#[derive(Default)]
+struct UnorderedLimitCollector {
+    limits: Vec<UnorderedLimit>,
+}
+
+impl Visitor for UnorderedLimitCollector {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        if query.order_by.is_some() {
+            return ControlFlow::Continue(());
+        }
+
+        // TOP belongs to the SELECT, but ORDER BY to the query around it
+        if let SetExpr::Select(select) = query.body.as_ref() {
+            if let Some(top) = &select.top {
+                self.limits.push(UnorderedLimit {
+                    clause: if top.percent {
+                        "TOP ... PERCENT"
+                    } else {
+                        "TOP"
+                    },
+                    with_ties: top.with_ties,
+                });
+            }
+        }
+        match &query.limit_clause {
+            Some(LimitClause::LimitOffset { limit: Some(_), .. })
+            | Some(LimitClause::OffsetCommaLimit { .. }) => self.limits.push(UnorderedLimit {
+                clause: "LIMIT",
+                with_ties: false,
+            }),
+            Some(LimitClause::LimitOffset {
+                limit: None,
+                offset: Some(_),
+                ..
+            }) => self.limits.push(UnorderedLimit {
+                clause: "OFFSET",
+                with_ties: false,
+            }),
+            _ => {}
+        }
+        if let Some(fetch) = &query.fetch {
+            self.limits.push(UnorderedLimit {
+                clause: "FETCH",
+                with_ties: fetch.with_ties,
+            });
+        }
+        ControlFlow::Continue(())
+    }
+}
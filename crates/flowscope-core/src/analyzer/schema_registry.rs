@@ -403,6 +403,17 @@ impl SchemaRegistry {
             return None;
         }
 
+        // Repeated creates of one table (`IF NOT EXISTS`, `OR REPLACE`) coalesce to the
+        // most complete definition instead of the last one seen.
+        if self.ddl_seeded_tables.contains(canonical)
+            && self
+                .schema_tables
+                .get(canonical)
+                .is_some_and(|existing| existing.table.columns.len() > columns.len())
+        {
+            return None;
+        }
+
         // Parse canonical name into parts.
         let parts = split_qualified_identifiers(canonical);
         let (catalog, schema, table_name) = match parts.as_slice() {
@@ -748,6 +759,25 @@ impl SchemaRegistry {
             .and_then(|c| c.data_type.clone())
     }
 
+    /// Looks up whether a column may hold NULL values.
+    ///
+    /// Primary key columns are never nullable. Returns `None` when the table or
+    /// column is unknown or its nullability was not declared.
+    pub(crate) fn lookup_column_nullable(&self, canonical: &str, column: &str) -> Option<bool> {
+        let entry = self.schema_tables.get(canonical)?;
+        let normalized_col = self.normalize_identifier(column);
+
+        let column = entry
+            .table
+            .columns
+            .iter()
+            .find(|c| self.normalize_identifier(&c.name) == normalized_col)?;
+        if column.is_primary_key == Some(true) {
+            return Some(false);
+        }
+        column.nullable
+    }
+
     /// Validates that a column exists in the schema for a given table.
     ///
     /// Returns an optional warning issue if the column is not found.
@@ -992,12 +1022,14 @@ mod tests {
                 data_type: Some("integer".to_string()),
                 is_primary_key: None,
                 foreign_key: None,
+                nullable: None,
             },
             ColumnSchema {
                 name: "name".to_string(),
                 data_type: Some("text".to_string()),
                 is_primary_key: None,
                 foreign_key: None,
+                nullable: None,
             },
         ];
 
@@ -1023,6 +1055,7 @@ mod tests {
                     data_type: Some("integer".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
             default_catalog: None,
@@ -1041,12 +1074,14 @@ mod tests {
                 data_type: Some("integer".to_string()),
                 is_primary_key: None,
                 foreign_key: None,
+                nullable: None,
             },
             ColumnSchema {
                 name: "email".to_string(),
                 data_type: Some("text".to_string()),
                 is_primary_key: None,
                 foreign_key: None,
+                nullable: None,
             },
         ];
 
@@ -1065,6 +1100,7 @@ mod tests {
             data_type: Some("integer".to_string()),
             is_primary_key: None,
             foreign_key: None,
+            nullable: None,
         }];
 
         registry.register_implied("public.temp", columns, false, "CREATE TABLE", 0);
@@ -1111,12 +1147,14 @@ mod tests {
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "email".to_string(),
                         data_type: Some("text".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             }],
@@ -1152,6 +1190,7 @@ mod tests {
                     data_type: Some("text".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
             default_catalog: None,
@@ -1200,6 +1239,7 @@ mod tests {
             data_type: Some("integer".to_string()),
             is_primary_key: None,
             foreign_key: None,
+            nullable: None,
         }];
 
         // Should still mark as known but not store schema details
@@ -1275,6 +1315,7 @@ mod tests {
                         data_type: None,
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     }],
                 },
                 SchemaTable {
@@ -1286,6 +1327,7 @@ mod tests {
                         data_type: None,
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     }],
                 },
             ],
@@ -1424,6 +1466,7 @@ mod tests {
                     data_type: Some("integer".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
             default_catalog: None,
@@ -1441,6 +1484,7 @@ mod tests {
             data_type: Some("integer".to_string()),
             is_primary_key: None,
             foreign_key: None,
+            nullable: None,
         }];
 
         let issue = registry.register_implied("public.users", columns, false, "CREATE TABLE", 0);
@@ -1483,6 +1527,7 @@ mod tests {
                     data_type: Some("integer".to_string()),
                     is_primary_key: Some(true),
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
             default_catalog: None,
@@ -1514,6 +1559,7 @@ mod tests {
                         table: "public.users".to_string(),
                         column: "id".to_string(),
                     }),
+                    nullable: None,
                 }],
             }],
             default_catalog: None,
@@ -1542,6 +1588,7 @@ mod tests {
                 data_type: Some("integer".to_string()),
                 is_primary_key: Some(true),
                 foreign_key: None,
+                nullable: None,
             },
             ColumnSchema {
                 name: "order_id".to_string(),
@@ -1551,6 +1598,7 @@ mod tests {
                     table: "orders".to_string(),
                     column: "id".to_string(),
                 }),
+                nullable: None,
             },
         ];
 
@@ -1594,18 +1642,21 @@ mod tests {
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "email".to_string(),
                         data_type: Some("varchar".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "created_at".to_string(),
                         data_type: Some("timestamp".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             }],
@@ -1645,6 +1696,7 @@ mod tests {
                     data_type: Some("integer".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
             default_catalog: None,
@@ -1681,6 +1733,7 @@ mod tests {
                     data_type: Some("text".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
             default_catalog: None,
@@ -1715,6 +1768,7 @@ mod tests {
                     data_type: None, // No type defined
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
             default_catalog: None,
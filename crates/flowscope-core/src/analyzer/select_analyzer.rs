@@ -1,13 +1,14 @@
 use super::context::{ColumnRef, StatementContext};
 use super::expression::ExpressionAnalyzer;
+use super::expression::MAX_RECURSION_DEPTH;
 use super::helpers::{
     alias_visibility_warning, infer_expr_type, is_simple_column_ref, lateral_alias_warning,
-    normalize_schema_type,
+    normalize_schema_type, resolve_named_windows,
 };
 use super::query::OutputColumnParams;
 use super::Analyzer;
-use crate::types::FilterClauseType;
-use sqlparser::ast::{self, Select, SelectItem};
+use crate::types::{issue_codes, FilterClauseType, Issue};
+use sqlparser::ast::{self, Expr, OrderByExpr, Select, SelectItem, TableFactor};
 use std::collections::{HashMap, HashSet};
 
 /// Analyzes SELECT statements to extract column lineage.
@@ -15,6 +16,8 @@ pub(crate) struct SelectAnalyzer<'a, 'b> {
     analyzer: &'a mut Analyzer<'b>,
     ctx: &'a mut StatementContext,
     target_node: Option<String>,
+    /// Relations on the optional side of an outer join in the current SELECT
+    outer_joined: HashSet<String>,
 }
 
 impl<'a, 'b> SelectAnalyzer<'a, 'b> {
@@ -27,6 +30,7 @@ impl<'a, 'b> SelectAnalyzer<'a, 'b> {
             analyzer,
             ctx,
             target_node,
+            outer_joined: HashSet::new(),
         }
     }
 
@@ -38,11 +42,26 @@ impl<'a, 'b> SelectAnalyzer<'a, 'b> {
     /// - Aggregation info
     pub(crate) fn analyze(&mut self, select: &Select) {
         self.ctx.clear_grouping();
+        // Restored on exit so nested subqueries don't leak the flag to their parent
+        let outer_hierarchical = std::mem::replace(
+            &mut self.ctx.in_hierarchical_query,
+            select.connect_by.is_some(),
+        );
 
         self.analyze_group_by(&select.group_by);
-        self.analyze_projection(&select.projection);
+        self.ctx.reset_insert_position(self.target_node.as_deref());
+        self.outer_joined = self.outer_joined_relations(select);
+
+        self.check_unqualified_wildcard(select);
+        // Inline `WINDOW w AS (...)` definitions so `OVER w` contributes its columns.
+        let resolved_projection = resolve_named_windows(&select.projection, &select.named_window);
+        self.analyze_projection(resolved_projection.as_deref().unwrap_or(&select.projection));
         self.analyze_selection(&select.selection);
+        self.analyze_connect_by(select.connect_by.as_ref());
         self.analyze_having(&select.having);
+        self.analyze_qualify(&select.qualify);
+
+        self.ctx.in_hierarchical_query = outer_hierarchical;
     }
 
     /// Analyzes GROUP BY expressions to track grouping columns.
@@ -148,6 +167,7 @@ impl<'a, 'b> SelectAnalyzer<'a, 'b> {
                     let data_type = infer_expr_type(expr)
                         .map(|t| t.to_string())
                         .or_else(|| self.lookup_source_column_type(&sources));
+                    let nullable = self.infer_nullability(expr, 0);
 
                     // Record source columns for implied schema. For simple column references,
                     // we can safely propagate the type. For transformed expressions (CAST,
@@ -163,6 +183,7 @@ impl<'a, 'b> SelectAnalyzer<'a, 'b> {
                             sources,
                             expression: expr_text,
                             data_type,
+                            nullable,
                             target_node: self.target_node.clone(),
                             approximate: false,
                             aggregation,
@@ -200,6 +221,7 @@ impl<'a, 'b> SelectAnalyzer<'a, 'b> {
                     let data_type = infer_expr_type(expr)
                         .map(|t| t.to_string())
                         .or_else(|| self.lookup_source_column_type(&sources));
+                    let nullable = self.infer_nullability(expr, 0);
 
                     // Record source columns for implied schema. For simple column references,
                     // we can safely propagate the type. For transformed expressions (CAST,
@@ -224,23 +246,29 @@ impl<'a, 'b> SelectAnalyzer<'a, 'b> {
                             sources,
                             expression: expr_text,
                             data_type,
+                            nullable,
                             target_node: self.target_node.clone(),
                             approximate: false,
                             aggregation,
                         },
                     );
                 }
-                SelectItem::QualifiedWildcard(name, _) => {
+                SelectItem::QualifiedWildcard(name, options) => {
                     let table_name = name.to_string();
                     self.analyzer.expand_wildcard(
                         self.ctx,
                         Some(&table_name),
                         self.target_node.as_deref(),
+                        options,
                     );
                 }
-                SelectItem::Wildcard(_) => {
-                    self.analyzer
-                        .expand_wildcard(self.ctx, None, self.target_node.as_deref());
+                SelectItem::Wildcard(options) => {
+                    self.analyzer.expand_wildcard(
+                        self.ctx,
+                        None,
+                        self.target_node.as_deref(),
+                        options,
+                    );
                 }
             }
         }
@@ -295,6 +323,334 @@ impl<'a, 'b> SelectAnalyzer<'a, 'b> {
         sources
     }
 
+    /// With `check_unqualified_wildcards`, warns about a bare `*` selecting from more
+    /// than one FROM source, whose output columns silently follow every joined table.
+    fn check_unqualified_wildcard(&mut self, select: &Select) {
+        let enabled = self
+            .analyzer
+            .request
+            .options
+            .as_ref()
+            .and_then(|o| o.check_unqualified_wildcards)
+            .unwrap_or(false);
+        if !enabled
+            || !select
+                .projection
+                .iter()
+                .any(|item| matches!(item, SelectItem::Wildcard(_)))
+        {
+            return;
+        }
+
+        let sources: Vec<Option<String>> = select
+            .from
+            .iter()
+            .flat_map(|from| {
+                std::iter::once(&from.relation).chain(from.joins.iter().map(|join| &join.relation))
+            })
+            .map(|relation| match relation {
+                TableFactor::Table { name, alias, .. } => Some(
+                    alias
+                        .as_ref()
+                        .map_or_else(|| name.to_string(), |alias| alias.name.to_string()),
+                ),
+                TableFactor::Derived {
+                    alias: Some(alias), ..
+                } => Some(alias.name.to_string()),
+                _ => None,
+            })
+            .collect();
+        if sources.len() < 2 {
+            return;
+        }
+
+        let named: Vec<&str> = sources.iter().flatten().map(String::as_str).collect();
+        let suggestion = if named.len() == sources.len() {
+            let qualified: Vec<String> = named.iter().map(|name| format!("{name}.*")).collect();
+            format!("select {} or list the columns", qualified.join(", "))
+        } else {
+            "list the columns or qualify the wildcard per source".to_string()
+        };
+        self.analyzer.issues.push(
+            Issue::warning(
+                issue_codes::UNQUALIFIED_WILDCARD,
+                format!(
+                    "Unqualified * selects from {} sources ({}); {suggestion}",
+                    sources.len(),
+                    named.join(", ")
+                ),
+            )
+            .with_statement(self.ctx.statement_index),
+        );
+    }
+
+    /// Warns about ORDER BY names that could sort by more than one thing: a name the
+    /// SELECT list outputs more than once, or an alias that also names a different
+    /// column of a FROM relation (dialects disagree on which of the two wins).
+    pub(crate) fn check_ambiguous_order_by(&mut self, select: &Select, order_by: &[OrderByExpr]) {
+        let mut outputs: HashMap<String, Vec<&Expr>> = HashMap::new();
+        for item in &select.projection {
+            let (name, expr) = match item {
+                SelectItem::ExprWithAlias { expr, alias } => (&alias.value, expr),
+                SelectItem::UnnamedExpr(expr @ Expr::Identifier(ident)) => (&ident.value, expr),
+                SelectItem::UnnamedExpr(expr @ Expr::CompoundIdentifier(parts)) => {
+                    match parts.last() {
+                        Some(ident) => (&ident.value, expr),
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+            outputs
+                .entry(self.analyzer.normalize_identifier(name))
+                .or_default()
+                .push(expr);
+        }
+
+        for order in order_by {
+            let Expr::Identifier(ident) = &order.expr else {
+                continue;
+            };
+            let name = self.analyzer.normalize_identifier(&ident.value);
+            let Some(exprs) = outputs.get(&name) else {
+                continue;
+            };
+
+            let message = if exprs.len() > 1 {
+                format!(
+                    "ORDER BY {ident} is ambiguous: the SELECT list outputs {} columns named {ident}",
+                    exprs.len()
+                )
+            } else if let Some(relation) = (!self.is_column_named(exprs[0], &name))
+                .then(|| self.relation_with_column(select, &name))
+                .flatten()
+            {
+                format!(
+                    "ORDER BY {ident} is ambiguous: {ident} is both a SELECT alias and a column of {relation}"
+                )
+            } else {
+                continue;
+            };
+            self.analyzer.issues.push(
+                Issue::warning(issue_codes::AMBIGUOUS_ORDER_BY, message)
+                    .with_statement(self.ctx.statement_index),
+            );
+        }
+    }
+
+    /// Whether `expr` is a plain reference to a column named `name` (normalized).
+    fn is_column_named(&self, expr: &Expr, name: &str) -> bool {
+        let ident = match expr {
+            Expr::Identifier(ident) => ident,
+            Expr::CompoundIdentifier(parts) => match parts.last() {
+                Some(ident) => ident,
+                None => return false,
+            },
+            _ => return false,
+        };
+        self.analyzer.normalize_identifier(&ident.value) == name
+    }
+
+    /// First FROM relation known to have a column named `column`.
+    fn relation_with_column(&self, select: &Select, column: &str) -> Option<String> {
+        select
+            .from
+            .iter()
+            .flat_map(|from| {
+                std::iter::once(&from.relation).chain(from.joins.iter().map(|join| &join.relation))
+            })
+            .filter_map(|relation| self.relation_canonical(relation))
+            .find(|canonical| self.known_column_nullability(canonical, column).is_some())
+    }
+
+    /// Collects the relations whose rows an outer join may replace with NULLs.
+    ///
+    /// `LEFT JOIN` makes the joined relation optional, `RIGHT JOIN` everything joined
+    /// before it, and `FULL JOIN` both sides.
+    fn outer_joined_relations(&self, select: &Select) -> HashSet<String> {
+        let mut outer_joined = HashSet::new();
+        for from in &select.from {
+            let mut preceding = vec![&from.relation];
+            for join in &from.joins {
+                let (left_optional, right_optional) = match &join.join_operator {
+                    ast::JoinOperator::Left(_) | ast::JoinOperator::LeftOuter(_) => (false, true),
+                    ast::JoinOperator::Right(_) | ast::JoinOperator::RightOuter(_) => (true, false),
+                    ast::JoinOperator::FullOuter(_) => (true, true),
+                    _ => (false, false),
+                };
+                if left_optional {
+                    outer_joined
+                        .extend(preceding.iter().filter_map(|r| self.relation_canonical(r)));
+                }
+                if right_optional {
+                    outer_joined.extend(self.relation_canonical(&join.relation));
+                }
+                preceding.push(&join.relation);
+            }
+        }
+        outer_joined
+    }
+
+    /// Canonical name under which a FROM relation's columns are resolved.
+    fn relation_canonical(&self, relation: &TableFactor) -> Option<String> {
+        let visible = match relation {
+            TableFactor::Table {
+                alias: Some(alias), ..
+            }
+            | TableFactor::Derived {
+                alias: Some(alias), ..
+            } => alias.name.value.clone(),
+            TableFactor::Table { name, .. } => name.to_string(),
+            _ => return None,
+        };
+        Some(
+            self.analyzer
+                .resolve_table_alias(self.ctx, Some(&visible))
+                .unwrap_or(visible),
+        )
+    }
+
+    /// Infers whether an output expression may be NULL (`None` when unknown).
+    ///
+    /// Columns take their nullability from CTE/derived-table outputs or the schema and
+    /// become nullable on the optional side of an outer join. Operators propagate NULLs,
+    /// `COALESCE` is non-null if any argument is, and `CASE` without `ELSE` is nullable.
+    fn infer_nullability(&self, expr: &Expr, depth: usize) -> Option<bool> {
+        if depth > MAX_RECURSION_DEPTH {
+            return None;
+        }
+        let next_depth = depth + 1;
+
+        match expr {
+            Expr::Identifier(ident) => self.lookup_column_nullability(&ColumnRef {
+                table: None,
+                column: ident.value.clone(),
+            }),
+            Expr::CompoundIdentifier(parts) if parts.len() >= 2 => {
+                let table = parts[..parts.len() - 1]
+                    .iter()
+                    .map(|i| i.value.as_str())
+                    .collect::<Vec<_>>()
+                    .join(".");
+                self.lookup_column_nullability(&ColumnRef {
+                    table: Some(table),
+                    column: parts[parts.len() - 1].value.clone(),
+                })
+            }
+            Expr::Value(value) => Some(matches!(value.value, ast::Value::Null)),
+            Expr::Nested(inner) | Expr::UnaryOp { expr: inner, .. } => {
+                self.infer_nullability(inner, next_depth)
+            }
+            Expr::Cast { kind, expr, .. } => match kind {
+                ast::CastKind::TryCast | ast::CastKind::SafeCast => Some(true),
+                _ => self.infer_nullability(expr, next_depth),
+            },
+            Expr::BinaryOp { left, right, .. } => any_nullable([
+                self.infer_nullability(left, next_depth),
+                self.infer_nullability(right, next_depth),
+            ]),
+            Expr::IsNull(_)
+            | Expr::IsNotNull(_)
+            | Expr::IsTrue(_)
+            | Expr::IsNotTrue(_)
+            | Expr::IsFalse(_)
+            | Expr::IsNotFalse(_)
+            | Expr::Exists { .. } => Some(false),
+            Expr::Case {
+                conditions,
+                else_result,
+                ..
+            } => {
+                let Some(else_result) = else_result else {
+                    return Some(true);
+                };
+                any_nullable(
+                    conditions
+                        .iter()
+                        .map(|case_when| &case_when.result)
+                        .chain(std::iter::once(else_result.as_ref()))
+                        .map(|result| self.infer_nullability(result, next_depth)),
+                )
+            }
+            Expr::Function(func) => {
+                let name = func.name.to_string().to_uppercase();
+                let args: Vec<&Expr> = match &func.args {
+                    ast::FunctionArguments::List(list) => list
+                        .args
+                        .iter()
+                        .filter_map(|arg| match arg {
+                            ast::FunctionArg::Unnamed(ast::FunctionArgExpr::Expr(e)) => Some(e),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                match name.as_str() {
+                    "COUNT" | "ZEROIFNULL" => Some(false),
+                    "NULLIF" => Some(true),
+                    // MySQL's one-argument ISNULL(x) is a boolean test
+                    "ISNULL" if args.len() == 1 => Some(false),
+                    "COALESCE" | "IFNULL" | "NVL" | "ISNULL" => all_nullable(
+                        args.iter()
+                            .map(|arg| self.infer_nullability(arg, next_depth)),
+                    ),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Looks up whether a referenced column may be NULL.
+    ///
+    /// Columns of outer-joined relations are always nullable; otherwise the answer
+    /// comes from CTE/derived-table outputs or the schema registry.
+    fn lookup_column_nullability(&self, source: &ColumnRef) -> Option<bool> {
+        let canonical = match source.table.as_ref() {
+            Some(table) => self.analyzer.resolve_table_alias(self.ctx, Some(table))?,
+            None => {
+                let tables = self.ctx.tables_in_current_scope();
+                if tables.len() == 1 {
+                    tables.into_iter().next()?
+                } else {
+                    tables.into_iter().find(|table| {
+                        self.known_column_nullability(table, &source.column)
+                            .is_some()
+                    })?
+                }
+            }
+        };
+
+        if self.outer_joined.contains(&canonical) {
+            return Some(true);
+        }
+        self.known_column_nullability(&canonical, &source.column)
+            .flatten()
+    }
+
+    /// Nullability of a column known to belong to `canonical`, or `None` if the
+    /// relation has no such column on record.
+    fn known_column_nullability(&self, canonical: &str, column: &str) -> Option<Option<bool>> {
+        let normalized_col = self.analyzer.normalize_identifier(column);
+        if let Some(cte_cols) = self.ctx.aliased_subquery_columns.get(canonical) {
+            if let Some(col) = cte_cols.iter().find(|c| c.name == normalized_col) {
+                return Some(col.nullable);
+            }
+        }
+
+        let entry = self.analyzer.schema.get(canonical)?;
+        entry
+            .table
+            .columns
+            .iter()
+            .any(|c| self.analyzer.normalize_identifier(&c.name) == normalized_col)
+            .then(|| {
+                self.analyzer
+                    .schema
+                    .lookup_column_nullable(canonical, column)
+            })
+    }
+
     /// Emits a warning for unsupported alias usage in a clause.
     fn emit_alias_warning(&mut self, clause_name: &str, alias_name: &str) {
         let dialect = self.analyzer.request.dialect;
@@ -340,6 +696,30 @@ impl<'a, 'b> SelectAnalyzer<'a, 'b> {
         }
     }
 
+    /// Analyzes `START WITH ... CONNECT BY ...` hierarchical query clauses.
+    ///
+    /// `PRIOR col` refers to the parent row of the same table, so both sides of the
+    /// relationship are recorded as columns of the base table.
+    fn analyze_connect_by(&mut self, connect_by: Option<&ast::ConnectBy>) {
+        let Some(connect_by) = connect_by else {
+            return;
+        };
+        for expr in std::iter::once(&connect_by.condition).chain(&connect_by.relationships) {
+            let mut ea = ExpressionAnalyzer::new(self.analyzer, self.ctx);
+            ea.analyze(expr);
+            let column_refs = ea.extract_column_refs_with_warning(expr);
+            for col_ref in column_refs {
+                if let Some(table) = self.analyzer.resolve_column_table(
+                    self.ctx,
+                    col_ref.table.as_deref(),
+                    &col_ref.column,
+                ) {
+                    self.ctx.record_source_column(&table, &col_ref.column, None);
+                }
+            }
+        }
+    }
+
     fn analyze_having(&mut self, having: &Option<sqlparser::ast::Expr>) {
         if let Some(ref having_expr) = having {
             let dialect = self.analyzer.request.dialect;
@@ -355,6 +735,18 @@ impl<'a, 'b> SelectAnalyzer<'a, 'b> {
         }
     }
 
+    /// Analyzes the QUALIFY clause, which filters rows on window function results.
+    ///
+    /// Columns read by the predicate, including those inside `OVER (...)`, are recorded
+    /// like WHERE columns and the predicate is attached to the tables it reads.
+    fn analyze_qualify(&mut self, qualify: &Option<sqlparser::ast::Expr>) {
+        if let Some(ref qualify_expr) = qualify {
+            let mut ea = ExpressionAnalyzer::new(self.analyzer, self.ctx);
+            ea.analyze(qualify_expr);
+            ea.capture_filter_predicates(qualify_expr, FilterClauseType::Qualify);
+        }
+    }
+
     /// Checks if an expression references any output column aliases and emits a warning.
     ///
     /// Used by HAVING and can be extended to other clauses that need alias checking.
@@ -464,3 +856,29 @@ impl<'a, 'b> SelectAnalyzer<'a, 'b> {
         None
     }
 }
+
+/// Nullability of an expression that is NULL whenever any operand is.
+fn any_nullable(values: impl IntoIterator<Item = Option<bool>>) -> Option<bool> {
+    let mut result = Some(false);
+    for value in values {
+        match value {
+            Some(true) => return Some(true),
+            None => result = None,
+            Some(false) => {}
+        }
+    }
+    result
+}
+
+/// Nullability of an expression that is NULL only when every operand is (`COALESCE`).
+fn all_nullable(values: impl IntoIterator<Item = Option<bool>>) -> Option<bool> {
+    let mut result = Some(true);
+    for value in values {
+        match value {
+            Some(false) => return Some(false),
+            None => result = None,
+            Some(true) => {}
+        }
+    }
+    result
+}
@@ -5,24 +5,29 @@
 //! and query analysis while managing the overall statement context and lineage graph.
 
 use super::complexity;
-use super::context::StatementContext;
+use super::context::{ColumnRef, InsertTarget, StatementContext};
 use super::expression::ExpressionAnalyzer;
 use super::helpers::{
     classify_query_type, extract_simple_name, generate_edge_id, generate_node_id,
-    split_qualified_identifiers,
+    is_simple_column_ref, split_qualified_identifiers,
 };
+use super::query::OutputColumnParams;
 use super::visitor::{LineageVisitor, Visitor};
 use super::Analyzer;
 use crate::error::ParseError;
+use crate::parser::multi_insert::{is_multi_table_insert, multi_table_insert_targets};
 use crate::types::{
-    issue_codes, Edge, EdgeType, Issue, JoinType, Node, NodeType, Span, StatementLineage,
+    issue_codes, Dialect, Edge, EdgeType, Issue, JoinType, Node, NodeType, Span, StatementLineage,
 };
 use sqlparser::ast::{
-    self, AlterTableOperation, Assignment, CopyIntoSnowflakeKind, CopySource, CopyTarget, Expr,
-    FromTable, MergeAction, MergeClause, MergeInsertKind, ObjectName, RenameTableNameKind,
-    Statement, TableFactor, TableWithJoins, UpdateTableFromKind,
+    self, visit_expressions_mut, AlterTableOperation, Assignment, AssignmentTarget, ColumnDef,
+    CopyIntoSnowflakeKind, CopySource, CopyTarget, CreateTable, CreateTableLikeKind, DataType,
+    Expr, FromTable, Ident, MergeAction, MergeClause, MergeInsertKind, ObjectName, Query,
+    RenameTableNameKind, SelectItem, SetExpr, Statement, TableFactor, TableWithJoins,
+    UpdateTableFromKind,
 };
 use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
 use std::ops::Range;
 use std::sync::Arc;
 #[cfg(feature = "tracing")]
@@ -52,8 +57,28 @@ impl<'a> Analyzer<'a> {
         resolved_sql: Option<String>,
     ) -> Result<StatementLineage, ParseError> {
         let mut ctx = StatementContext::new(index);
+        ctx.column_lineage = self.column_lineage_enabled;
+        self.check_subquery_depth(statement, index);
+        self.check_reserved_keyword_identifiers(statement, index);
+        self.check_identifier_names(statement, index);
+        self.check_implicit_table_aliases(statement, index);
+        self.check_unordered_row_limits(statement, index);
+        self.check_duplicate_projections(statement, index);
+        self.check_constant_predicates(statement, index);
+        self.check_aggregate_case_else(statement, index);
+        self.check_qualify_support(statement, index);
+        self.check_recursive_termination(statement, index);
+        self.check_redundant_casts(statement, index);
 
         let statement_type = match statement {
+            Statement::Query(query) if self.is_refresh_materialized_view() => {
+                self.analyze_refresh_materialized_view(&mut ctx, query);
+                "REFRESH_MATERIALIZED_VIEW".to_string()
+            }
+            Statement::Query(query) if self.is_multi_table_insert() => {
+                self.analyze_multi_table_insert(&mut ctx, query);
+                "INSERT".to_string()
+            }
             Statement::Query(query) => {
                 // In dbt mode, a bare SELECT represents a model that should be registered
                 // with the model name derived from the source file path.
@@ -81,9 +106,25 @@ impl<'a> Analyzer<'a> {
                 "INSERT".to_string()
             }
             Statement::CreateTable(create) => {
+                let storage = self.table_storage_metadata(create);
                 if let Some(query) = &create.query {
-                    self.analyze_create_table_as(&mut ctx, &create.name, query, create.temporary);
+                    self.analyze_create_table_as(
+                        &mut ctx,
+                        &create.name,
+                        query,
+                        create.temporary,
+                        storage,
+                    );
                     "CREATE_TABLE_AS".to_string()
+                } else if let Some((source, operation)) = table_copy_source(create) {
+                    self.analyze_create_table_copy(
+                        &mut ctx,
+                        &create.name,
+                        source,
+                        operation,
+                        create.temporary,
+                    );
+                    "CREATE_TABLE".to_string()
                 } else {
                     self.analyze_create_table(
                         &mut ctx,
@@ -91,6 +132,7 @@ impl<'a> Analyzer<'a> {
                         &create.columns,
                         &create.constraints,
                         create.temporary,
+                        storage,
                     );
                     "CREATE_TABLE".to_string()
                 }
@@ -99,9 +141,18 @@ impl<'a> Analyzer<'a> {
                 name,
                 query,
                 temporary,
+                materialized,
+                options,
+                secure,
                 ..
             } => {
-                self.analyze_create_view(&mut ctx, name, query, *temporary);
+                let metadata = self.view_metadata(options, *secure);
+                self.analyze_create_view(&mut ctx, name, query, *temporary, metadata);
+                if *materialized {
+                    let canonical = self.normalize_table_name(&name.to_string());
+                    self.materialized_view_definitions
+                        .insert(canonical, (**query).clone());
+                }
                 "CREATE_VIEW".to_string()
             }
             Statement::Update {
@@ -214,6 +265,8 @@ impl<'a> Analyzer<'a> {
             }
         };
 
+        self.add_do_block_node(&mut ctx, source_name.as_deref());
+
         // Apply pending filter predicates to table nodes before finalizing
         self.apply_pending_filters(&mut ctx);
 
@@ -222,6 +275,13 @@ impl<'a> Analyzer<'a> {
 
         self.add_join_dependency_edges(&mut ctx);
 
+        // Edges recorded before their column node was skipped
+        if !ctx.skipped_column_ids.is_empty() {
+            let skipped = &ctx.skipped_column_ids;
+            ctx.edges
+                .retain(|edge| !skipped.contains(&edge.from) && !skipped.contains(&edge.to));
+        }
+
         // Register implied schema for source tables referenced in the query
         self.register_source_tables_schema(&ctx);
 
@@ -242,6 +302,39 @@ impl<'a> Analyzer<'a> {
         })
     }
 
+    /// Adds the synthetic node of the `DO` block the current statement was extracted from.
+    ///
+    /// Statements from the same block share the node, so the global lineage lists them
+    /// all under it.
+    fn add_do_block_node(&self, ctx: &mut StatementContext, source_name: Option<&str>) {
+        let Some(block) = self
+            .current_statement_source
+            .as_ref()
+            .and_then(|source| source.do_block.clone())
+        else {
+            return;
+        };
+
+        let key = format!("{}:{}", source_name.unwrap_or_default(), block.start);
+        ctx.add_node(Node {
+            id: generate_node_id("block", &key),
+            node_type: NodeType::Output,
+            label: "DO block".into(),
+            qualified_name: None,
+            expression: None,
+            span: Some(Span::new(block.start, block.end)),
+            metadata: Some(HashMap::from([(
+                "block".to_string(),
+                serde_json::Value::from("do"),
+            )])),
+            resolution_source: None,
+            filters: Vec::new(),
+            join_type: None,
+            join_condition: None,
+            aggregation: None,
+        });
+    }
+
     fn add_join_dependency_edges(&self, ctx: &mut StatementContext) {
         let output_node_id = match ctx.output_node_id.as_ref() {
             Some(node_id) => node_id.clone(),
@@ -322,13 +415,77 @@ impl<'a> Analyzer<'a> {
 
     pub(super) fn analyze_insert(&mut self, ctx: &mut StatementContext, insert: &ast::Insert) {
         let target_name = insert.table.to_string();
-        let canonical = self.normalize_table_name(&target_name);
+        let target_id = self.add_insert_target(ctx, &target_name, &insert.columns);
+        if let Some(partitions) = insert.partitioned.as_deref() {
+            if matches!(self.request.dialect, Dialect::Hive | Dialect::Databricks) {
+                apply_partition_spec(ctx, &target_id, &partition_spec(partitions), insert);
+            }
+        }
+
+        // Analyze source - check the body of the insert
+        if let Some(ref source_body) = insert.source {
+            if let Some(target) = &ctx.insert_target {
+                let columns = target.columns.clone();
+                self.check_insert_column_order(
+                    ctx.statement_index,
+                    &target_name,
+                    &columns,
+                    &source_body.body,
+                );
+            }
+            self.analyze_query_body(ctx, &source_body.body, Some(&target_id));
+        }
+        ctx.insert_target = None;
+    }
+
+    /// Analyzes a multi-table `INSERT ALL` / `INSERT FIRST`, which reaches the analyzer
+    /// as its shared source query. Each `INTO` target receives the source columns named
+    /// by its `VALUES` list, or all of them in order when the list is omitted.
+    pub(super) fn analyze_multi_table_insert(&mut self, ctx: &mut StatementContext, query: &Query) {
+        let targets = match &self.current_statement_source {
+            Some(source) => {
+                multi_table_insert_targets(&source.sql[source.range.clone()], self.request.dialect)
+            }
+            None => return,
+        };
+
+        for target in targets {
+            let target_id = self.add_insert_target(ctx, &target.table.to_string(), &target.columns);
+            match &target.values {
+                Some(values) => {
+                    self.analyze_query(ctx, &project_insert_values(query, values), Some(&target_id))
+                }
+                None => self.analyze_query(ctx, query, Some(&target_id)),
+            }
+            ctx.insert_target = None;
+        }
+    }
+
+    /// Returns true when the current statement is a multi-table insert.
+    pub(super) fn is_multi_table_insert(&self) -> bool {
+        self.current_statement_source
+            .as_ref()
+            .is_some_and(|source| {
+                is_multi_table_insert(&source.sql[source.range.clone()], self.request.dialect)
+            })
+    }
+
+    /// Adds the node of an insert target and prepares positional mapping of the
+    /// projected columns onto it. Callers clear `ctx.insert_target` once the source
+    /// has been analyzed.
+    fn add_insert_target(
+        &mut self,
+        ctx: &mut StatementContext,
+        target_name: &str,
+        columns: &[Ident],
+    ) -> Arc<str> {
+        let canonical = self.normalize_table_name(target_name);
 
         // Create target table node
         let target_id = ctx.add_node(Node {
             id: generate_node_id("table", &canonical),
             node_type: NodeType::Table,
-            label: extract_simple_name(&target_name).into(),
+            label: extract_simple_name(target_name).into(),
             qualified_name: Some(canonical.clone().into()),
             expression: None,
             span: None,
@@ -343,10 +500,31 @@ impl<'a> Analyzer<'a> {
         self.tracker
             .record_produced(&canonical, ctx.statement_index);
 
-        // Analyze source - check the body of the insert
-        if let Some(ref source_body) = insert.source {
-            self.analyze_query_body(ctx, &source_body.body, Some(&target_id));
+        // Map projected columns (including expanded `*`) onto the target by position,
+        // using the explicit column list or, failing that, the target's schema.
+        let target_columns: Vec<String> = if columns.is_empty() {
+            self.schema
+                .get(&canonical)
+                .map(|entry| {
+                    entry
+                        .table
+                        .columns
+                        .iter()
+                        .map(|col| col.name.clone())
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            columns.iter().map(|col| col.to_string()).collect()
+        };
+        if !target_columns.is_empty() {
+            ctx.insert_target = Some(InsertTarget {
+                node_id: target_id.clone(),
+                columns: target_columns,
+                position: 0,
+            });
         }
+        target_id
     }
 
     pub(super) fn analyze_update(
@@ -357,11 +535,38 @@ impl<'a> Analyzer<'a> {
         from: &Option<UpdateTableFromKind>,
         selection: &Option<Expr>,
     ) {
-        let target_node_id = {
+        let from_tables = from.as_ref().map(|from_kind| match from_kind {
+            UpdateTableFromKind::BeforeSet(tables) | UpdateTableFromKind::AfterSet(tables) => {
+                tables.as_slice()
+            }
+        });
+
+        // Target and FROM tables form the scope that SET and WHERE columns resolve in
+        ctx.push_scope();
+        let (target_node_id, target_is_aliased) = {
             let mut visitor = LineageVisitor::new(self, ctx, None);
 
-            // 1. Analyze the target table
-            visitor.analyze_dml_target_from_table_with_joins(table)
+            // 1. Analyze the target table. SQL Server names the target by an alias
+            // declared in FROM: `UPDATE t SET ... FROM target t JOIN ...`
+            for t in from_tables.unwrap_or_default() {
+                visitor.register_aliases_in_table_with_joins(t);
+            }
+            let aliased_target = match &table.relation {
+                TableFactor::Table {
+                    name, alias: None, ..
+                } if table.joins.is_empty() => {
+                    visitor.ctx.table_aliases.get(&name.to_string()).cloned()
+                }
+                _ => None,
+            };
+            let target_is_aliased = aliased_target.is_some();
+            let node_id = match aliased_target {
+                Some(canonical) => visitor
+                    .analyze_dml_target(&canonical, None)
+                    .map(|(_, node_id)| node_id),
+                None => visitor.analyze_dml_target_from_table_with_joins(table),
+            };
+            (node_id, target_is_aliased)
         };
 
         // 2. Analyze FROM clause (Postgres style) and joins in target table structure
@@ -369,19 +574,8 @@ impl<'a> Analyzer<'a> {
             let target = LineageVisitor::target_from_arc(target_node_id.as_ref());
             let mut visitor = LineageVisitor::new(self, ctx, target);
 
-            if let Some(from_kind) = from {
-                match from_kind {
-                    UpdateTableFromKind::BeforeSet(tables) => {
-                        for t in tables {
-                            visitor.visit_table_with_joins(t);
-                        }
-                    }
-                    UpdateTableFromKind::AfterSet(tables) => {
-                        for t in tables {
-                            visitor.visit_table_with_joins(t);
-                        }
-                    }
-                }
+            for t in from_tables.unwrap_or_default() {
+                visitor.visit_table_with_joins(t);
             }
 
             for join in &table.joins {
@@ -389,17 +583,85 @@ impl<'a> Analyzer<'a> {
                 visitor.visit_table_factor(&join.relation);
             }
         }
+        // Visiting the FROM entry that declares the target links the target to itself
+        if let (true, Some(target)) = (target_is_aliased, &target_node_id) {
+            let self_edge = generate_edge_id(target, target);
+            if ctx.edge_ids.remove(&self_edge) {
+                ctx.edges.retain(|edge| edge.id != self_edge);
+            }
+        }
 
-        // 3. Analyze assignments (SET clause)
-        let mut expr_analyzer = ExpressionAnalyzer::new(self, ctx);
+        // 3. Analyze assignments (SET clause), tracing each assigned column to the
+        // columns its value reads
         for assignment in assignments {
-            expr_analyzer.analyze(&assignment.value);
+            let sources = {
+                let mut expr_analyzer = ExpressionAnalyzer::new(self, ctx);
+                expr_analyzer.analyze(&assignment.value);
+                expr_analyzer.extract_column_refs_with_warning(&assignment.value)
+            };
+            if let Some(target) = &target_node_id {
+                self.add_assignment_lineage(ctx, target, assignment, sources);
+            }
         }
 
         // 4. Analyze selection (WHERE clause)
         if let Some(expr) = selection {
-            expr_analyzer.analyze(expr);
+            ExpressionAnalyzer::new(self, ctx).analyze(expr);
         }
+        ctx.pop_scope();
+    }
+
+    /// Adds the column written by a `SET col = expr` assignment to `target` and links
+    /// it to the source columns read by `expr`. Tuple assignments are not traced.
+    fn add_assignment_lineage(
+        &mut self,
+        ctx: &mut StatementContext,
+        target: &Arc<str>,
+        assignment: &Assignment,
+        sources: Vec<ColumnRef>,
+    ) {
+        let AssignmentTarget::ColumnName(column) = &assignment.target else {
+            return;
+        };
+        // `SET t.x = ...` qualifies the column with the target table or alias
+        let Some(name) = column.0.last().and_then(|part| part.as_ident()) else {
+            return;
+        };
+        let normalized = self.normalize_identifier(&name.value);
+
+        // Reading the assigned column itself (`SET x = x + 1`) is not a new source;
+        // unqualified, the name refers to the target's column
+        let sources = sources
+            .into_iter()
+            .filter(|source| {
+                if self.normalize_identifier(&source.column) != normalized {
+                    return true;
+                }
+                let Some(qualifier) = source.table.as_deref() else {
+                    return false;
+                };
+                let table_node = self
+                    .resolve_table_alias(ctx, Some(qualifier))
+                    .and_then(|canonical| ctx.table_node_ids.get(&canonical).cloned());
+                table_node.as_ref() != Some(target)
+            })
+            .collect();
+
+        let expression =
+            (!is_simple_column_ref(&assignment.value)).then(|| assignment.value.to_string());
+        self.add_output_column_with_aggregation(
+            ctx,
+            OutputColumnParams {
+                name: name.value.clone(),
+                sources,
+                expression,
+                data_type: None,
+                nullable: None,
+                target_node: Some(target.to_string()),
+                approximate: false,
+                aggregation: None,
+            },
+        );
     }
 
     pub(super) fn analyze_delete(
@@ -892,3 +1154,183 @@ fn extract_model_name(path: &str) -> &str {
         .or_else(|| filename.strip_suffix(".sql.jinja"))
         .unwrap_or(filename)
 }
+
+/// One column of a Hive/Spark `PARTITION (...)` clause.
+struct PartitionColumn {
+    name: String,
+    /// The static value, or `None` for a dynamic partition column
+    value: Option<String>,
+}
+
+/// Reads `PARTITION (dt = '2024-01-01', region)` into its columns. Entries that are
+/// neither `column = value` nor a bare column are skipped.
+fn partition_spec(partitions: &[Expr]) -> Vec<PartitionColumn> {
+    partitions
+        .iter()
+        .filter_map(|expr| match expr {
+            Expr::Identifier(ident) => Some(PartitionColumn {
+                name: ident.value.clone(),
+                value: None,
+            }),
+            Expr::BinaryOp {
+                left,
+                op: ast::BinaryOperator::Eq,
+                right,
+            } => {
+                let Expr::Identifier(ident) = left.as_ref() else {
+                    return None;
+                };
+                let value = match right.as_ref() {
+                    Expr::Value(value) => match &value.value {
+                        ast::Value::SingleQuotedString(s) | ast::Value::DoubleQuotedString(s) => {
+                            s.clone()
+                        }
+                        other => other.to_string(),
+                    },
+                    other => other.to_string(),
+                };
+                Some(PartitionColumn {
+                    name: ident.value.clone(),
+                    value: Some(value),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Records the partition spec of a partitioned insert on its target node, and maps the
+/// projection the way Hive does without a column list: static partition columns take
+/// no select item, and dynamic ones take the last items in `PARTITION` order.
+fn apply_partition_spec(
+    ctx: &mut StatementContext,
+    target_id: &Arc<str>,
+    spec: &[PartitionColumn],
+    insert: &ast::Insert,
+) {
+    if spec.is_empty() {
+        return;
+    }
+
+    if let Some(node) = ctx.nodes.iter_mut().find(|node| &node.id == target_id) {
+        let partition = spec
+            .iter()
+            .map(|column| {
+                let mut entry = serde_json::Map::new();
+                entry.insert("column".to_string(), column.name.clone().into());
+                if let Some(value) = &column.value {
+                    entry.insert("value".to_string(), value.clone().into());
+                }
+                serde_json::Value::Object(entry)
+            })
+            .collect::<Vec<_>>();
+        let metadata = node.metadata.get_or_insert_with(HashMap::new);
+        metadata.insert("partition".to_string(), partition.into());
+        if insert.overwrite {
+            metadata.insert("overwrite".to_string(), true.into());
+        }
+    }
+
+    if !insert.columns.is_empty() {
+        return;
+    }
+    if let Some(target) = ctx.insert_target.as_mut() {
+        target.columns.retain(|column| {
+            !spec
+                .iter()
+                .any(|partition| partition.name.eq_ignore_ascii_case(column))
+        });
+        target.columns.extend(
+            spec.iter()
+                .filter(|partition| partition.value.is_none())
+                .map(|partition| partition.name.clone()),
+        );
+    }
+}
+
+/// The table a `CREATE TABLE ... LIKE` / `CLONE` copies, with the operation name.
+fn table_copy_source(create: &CreateTable) -> Option<(&ObjectName, &'static str)> {
+    match (&create.like, &create.clone) {
+        (Some(CreateTableLikeKind::Plain(like) | CreateTableLikeKind::Parenthesized(like)), _) => {
+            Some((&like.name, "LIKE"))
+        }
+        (None, Some(clone)) => Some((clone, "CLONE")),
+        (None, None) => match create.columns.as_slice() {
+            // Dialects without `(LIKE t)` support read it as a column `LIKE` of type `t`
+            [ColumnDef {
+                name,
+                data_type: DataType::Custom(source, modifiers),
+                options,
+            }] if name.quote_style.is_none()
+                && name.value.eq_ignore_ascii_case("LIKE")
+                && modifiers.is_empty()
+                && options.is_empty() =>
+            {
+                Some((source, "LIKE"))
+            }
+            _ => None,
+        },
+    }
+}
+
+/// Rewrites the projection of a multi-table insert's source `query` into the `VALUES`
+/// of one `INTO` clause. Values name the source query's output columns, so each
+/// reference is replaced by the select item that produces it; anything else is kept
+/// and resolves against the source tables.
+///
+/// Set operations are left as they are and map onto the target by position.
+fn project_insert_values(query: &Query, values: &[Expr]) -> Query {
+    let mut projected = query.clone();
+    let SetExpr::Select(select) = projected.body.as_mut() else {
+        return projected;
+    };
+
+    let outputs: Vec<(String, &SelectItem)> = select
+        .projection
+        .iter()
+        .filter_map(|item| {
+            let name = match item {
+                SelectItem::ExprWithAlias { alias, .. } => alias.value.clone(),
+                SelectItem::UnnamedExpr(Expr::Identifier(ident)) => ident.value.clone(),
+                SelectItem::UnnamedExpr(Expr::CompoundIdentifier(parts)) => {
+                    parts.last()?.value.clone()
+                }
+                _ => return None,
+            };
+            Some((name, item))
+        })
+        .collect();
+    let output = |ident: &Ident| {
+        outputs
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&ident.value))
+            .map(|(_, item)| *item)
+    };
+
+    let projection = values
+        .iter()
+        .map(|value| {
+            if let Expr::Identifier(ident) = value {
+                if let Some(item) = output(ident) {
+                    return item.clone();
+                }
+            }
+            let mut value = value.clone();
+            let _ = visit_expressions_mut(&mut value, |expr| {
+                if let Expr::Identifier(ident) = expr {
+                    if let Some(
+                        SelectItem::ExprWithAlias { expr: source, .. }
+                        | SelectItem::UnnamedExpr(source),
+                    ) = output(ident)
+                    {
+                        *expr = source.clone();
+                    }
+                }
+                ControlFlow::<()>::Continue(())
+            });
+            SelectItem::UnnamedExpr(value)
+        })
+        .collect();
+    select.projection = projection;
+    projected
+}
@@ -1,6 +1,6 @@
 use super::*;
 use crate::test_utils::{load_schema_fixture, load_sql_fixture};
-use crate::types::AnalysisOptions;
+use crate::types::{AnalysisOptions, ImplicitAliasContext};
 use std::collections::{BTreeSet, HashMap, HashSet};
 
 fn make_request(sql: &str) -> AnalyzeRequest {
@@ -46,6 +46,30 @@ fn schema_with_known_table() -> SchemaMetadata {
     }
 }
 
+/// Analyzes `sql` with `options` and returns the issues reported with `code`.
+fn issues_with_code(sql: &str, options: AnalysisOptions, code: &str) -> Vec<Issue> {
+    request_issues_with_code(
+        &AnalyzeRequest {
+            options: Some(options),
+            ..make_request(sql)
+        },
+        code,
+    )
+}
+
+/// Like [`issues_with_code`], for requests that set a dialect, schema or files.
+fn request_issues_with_code(request: &AnalyzeRequest, code: &str) -> Vec<Issue> {
+    analyze(request)
+        .issues
+        .into_iter()
+        .filter(|i| i.code == code)
+        .collect()
+}
+
+fn messages(issues: Vec<Issue>) -> Vec<String> {
+    issues.into_iter().map(|i| i.message).collect()
+}
+
 #[test]
 fn test_simple_select() {
     let request = make_request("SELECT * FROM users");
@@ -285,6 +309,20 @@ fn file_statements_produce_spans() {
     assert_eq!(&file_sql[span.start..span.end], "missing_table");
 }
 
+#[test]
+fn trailing_semicolons_and_comments_produce_no_empty_statements() {
+    let result = analyze(&make_request("SELECT 1;; -- end"));
+
+    assert!(result.issues.is_empty(), "{:?}", result.issues);
+    assert_eq!(result.statements.len(), 1);
+    assert_eq!(result.summary.statement_count, 1);
+    let statement = &result.statements[0];
+    assert_eq!(statement.statement_type, "SELECT");
+    assert!(!statement.nodes.is_empty());
+    let span = statement.span.expect("statement span");
+    assert_eq!(&"SELECT 1;; -- end"[span.start..span.end], "SELECT 1");
+}
+
 #[test]
 fn depth_limit_warning_emitted_once_per_statement() {
     let request = make_request("SELECT 1");
@@ -335,18 +373,1282 @@ fn hide_ctes_option_filters_statement_and_global_lineage() {
         "global lineage should exclude CTE nodes when hidden"
     );
 
-    assert!(
-        stmt_with.nodes.len() < stmt_without.nodes.len(),
-        "hiding CTEs should reduce statement node count"
+    assert!(
+        stmt_with.nodes.len() < stmt_without.nodes.len(),
+        "hiding CTEs should reduce statement node count"
+    );
+    assert!(
+        with_hiding.summary.table_count < without_hiding.summary.table_count,
+        "summary table_count should decrease when CTE nodes are hidden"
+    );
+    assert!(
+        with_hiding.summary.complexity_score < without_hiding.summary.complexity_score,
+        "complexity score should decrease when CTE nodes are hidden"
+    );
+}
+
+#[test]
+fn inline_ctes_collapses_chained_ctes_and_keeps_filters() {
+    let sql = r#"
+        WITH paid AS (
+            SELECT order_id, customer_id, amount FROM orders WHERE status = 'paid'
+        ),
+        large AS (
+            SELECT order_id, customer_id, amount FROM paid WHERE amount > 100
+        ),
+        by_customer AS (
+            SELECT customer_id, SUM(amount) AS total FROM large GROUP BY customer_id
+        )
+        SELECT customer_id, total FROM by_customer WHERE total > 1000
+    "#;
+
+    let expanded = analyze(&make_request_with_options(sql, false, true));
+    let inlined = analyze(&AnalyzeRequest {
+        options: Some(AnalysisOptions {
+            inline_ctes: Some(true),
+            ..Default::default()
+        }),
+        ..make_request(sql)
+    });
+    let expanded_stmt = &expanded.statements[0];
+    let inlined_stmt = &inlined.statements[0];
+
+    assert!(
+        inlined_stmt.edges.len() < expanded_stmt.edges.len(),
+        "inlining should drop the edges through CTEs: {} vs {}",
+        inlined_stmt.edges.len(),
+        expanded_stmt.edges.len()
+    );
+    assert!(inlined_stmt
+        .nodes
+        .iter()
+        .all(|n| n.node_type != NodeType::Cte));
+
+    // `total` traces straight to orders.amount through all three CTEs
+    let node = |id: &str| inlined_stmt.nodes.iter().find(|n| *n.id == *id).unwrap();
+    let total_sources: Vec<&str> = inlined_stmt
+        .edges
+        .iter()
+        .filter(|e| e.edge_type != EdgeType::Ownership && node(&e.to).label.as_ref() == "total")
+        .map(|e| node(&e.from).label.as_ref())
+        .collect();
+    assert!(!total_sources.is_empty());
+    assert!(total_sources.iter().all(|label| *label == "amount"));
+
+    // Predicates on the CTEs' rows move to the table they read from when it has
+    // the columns they name
+    let orders = inlined_stmt
+        .nodes
+        .iter()
+        .find(|n| n.node_type == NodeType::Table && &*n.label == "orders")
+        .expect("orders table");
+    let filters: Vec<&str> = orders
+        .filters
+        .iter()
+        .map(|f| f.expression.as_str())
+        .collect();
+    assert_eq!(filters, ["status = 'paid'", "amount > 100"]);
+
+    // `total` is computed by by_customer, so its predicate stays on the output
+    let kept: Vec<(NodeType, &str)> = inlined_stmt
+        .nodes
+        .iter()
+        .filter(|n| n.filters.iter().any(|f| f.expression == "total > 1000"))
+        .map(|n| (n.node_type, n.label.as_ref()))
+        .collect();
+    assert_eq!(kept, [(NodeType::Output, "Output")]);
+
+    // hide_ctes collapses the same CTEs but drops their predicates; orders keeps
+    // only the one written against it
+    let hidden = analyze(&make_request_with_options(sql, true, true));
+    let hidden_stmt = &hidden.statements[0];
+    assert_eq!(hidden_stmt.edges.len(), inlined_stmt.edges.len());
+    let hidden_filters: Vec<&str> = hidden_stmt
+        .nodes
+        .iter()
+        .flat_map(|n| n.filters.iter().map(|f| f.expression.as_str()))
+        .collect();
+    assert_eq!(hidden_filters, ["status = 'paid'"]);
+}
+
+#[test]
+fn ignore_tables_option_removes_matching_tables_and_edges() {
+    let sql = "INSERT INTO etl_log SELECT id FROM orders; \
+               INSERT INTO report SELECT o.id FROM orders o JOIN etl_log l ON o.id = l.id";
+
+    let mut request = make_request(sql);
+    request.options = Some(AnalysisOptions {
+        ignore_tables: Some(vec!["etl_*".to_string()]),
+        ..Default::default()
+    });
+    let result = analyze(&request);
+
+    let global = &result.global_lineage;
+    assert!(
+        global.nodes.iter().all(|n| !n.label.contains("etl_log")),
+        "ignored table should be removed from global lineage"
+    );
+    for table in ["orders", "report"] {
+        assert!(
+            global
+                .nodes
+                .iter()
+                .any(|n| n.node_type == NodeType::Table && &*n.label == table),
+            "{table} should remain in the graph"
+        );
+    }
+
+    let node_ids: HashSet<_> = global.nodes.iter().map(|n| n.id.clone()).collect();
+    assert!(
+        global
+            .edges
+            .iter()
+            .all(|e| node_ids.contains(&e.from) && node_ids.contains(&e.to)),
+        "no edge should dangle after removing ignored tables"
+    );
+    assert!(
+        global
+            .edges
+            .iter()
+            .any(|e| e.edge_type == EdgeType::DataFlow),
+        "lineage between the remaining tables should be kept"
+    );
+
+    assert!(result
+        .issues
+        .iter()
+        .any(|i| i.code == issue_codes::IGNORED_TABLES && i.message.contains("etl_log")));
+}
+
+fn dead_column_messages(terminal_tables: Option<Vec<String>>) -> Vec<(String, Option<String>)> {
+    let mut request = make_request("");
+    request.files = Some(vec![
+        FileSource {
+            name: "staging/orders.sql".to_string(),
+            content: "CREATE TABLE staging.orders AS \
+                      SELECT id, customer_id, amount, legacy_flag, status FROM raw.orders"
+                .to_string(),
+        },
+        FileSource {
+            name: "marts/revenue.sql".to_string(),
+            content: "CREATE TABLE mart.revenue AS \
+                      SELECT id, amount * 2 AS total FROM staging.orders \
+                      WHERE status = 'paid'"
+                .to_string(),
+        },
+        FileSource {
+            name: "marts/customers.sql".to_string(),
+            content: "CREATE TABLE mart.customers AS SELECT customer_id FROM staging.orders"
+                .to_string(),
+        },
+    ]);
+    request.options = Some(AnalysisOptions {
+        report_dead_columns: Some(true),
+        terminal_tables,
+        ..Default::default()
+    });
+
+    request_issues_with_code(&request, issue_codes::DEAD_COLUMN)
+        .into_iter()
+        .map(|i| (i.message, i.source_name))
+        .collect()
+}
+
+#[test]
+fn report_dead_columns_flags_unused_intermediate_columns() {
+    let dead = dead_column_messages(None);
+
+    assert_eq!(
+        dead,
+        vec![(
+            "Column 'staging.orders.legacy_flag' is never used downstream".to_string(),
+            Some("staging/orders.sql".to_string())
+        )],
+        "only legacy_flag is unused; status is read by a WHERE clause and mart columns are terminal"
+    );
+}
+
+#[test]
+fn report_dead_columns_skips_terminal_table_patterns() {
+    assert!(dead_column_messages(Some(vec!["staging.*".to_string()])).is_empty());
+}
+
+#[test]
+fn dead_columns_are_not_reported_by_default() {
+    let result = analyze(&make_request(
+        "CREATE TABLE stg AS SELECT a, b FROM src; CREATE TABLE mart AS SELECT a FROM stg",
+    ));
+    assert!(result
+        .issues
+        .iter()
+        .all(|i| i.code != issue_codes::DEAD_COLUMN));
+}
+
+fn datetime_style_request(check: bool) -> AnalyzeRequest {
+    let mut request = make_request("");
+    request.files = Some(vec![
+        FileSource {
+            name: "orders.sql".to_string(),
+            content: "SELECT id FROM orders WHERE created_at >= CURRENT_DATE - 7; \
+                      SELECT CURRENT_TIMESTAMP AS loaded_at FROM orders"
+                .to_string(),
+        },
+        FileSource {
+            name: "events.sql".to_string(),
+            content: "SELECT id FROM events WHERE ts < NOW()::date".to_string(),
+        },
+    ]);
+    request.options = Some(AnalysisOptions {
+        check_datetime_consistency: Some(check),
+        ..Default::default()
+    });
+    request
+}
+
+#[test]
+fn datetime_consistency_flags_minority_style_file() {
+    let flagged = request_issues_with_code(
+        &datetime_style_request(true),
+        issue_codes::INCONSISTENT_DATETIME_STYLE,
+    );
+
+    assert_eq!(flagged.len(), 1, "only the NOW() file should be flagged");
+    let issue = &flagged[0];
+    assert_eq!(issue.source_name.as_deref(), Some("events.sql"));
+    assert_eq!(issue.statement_index, Some(2));
+    assert!(issue.message.contains("NOW()"), "{}", issue.message);
+    assert!(
+        issue.message.contains("CURRENT_DATE/CURRENT_TIMESTAMP"),
+        "message should name the dominant style: {}",
+        issue.message
+    );
+}
+
+#[test]
+fn datetime_consistency_is_opt_in() {
+    let result = analyze(&datetime_style_request(false));
+    assert!(result
+        .issues
+        .iter()
+        .all(|i| i.code != issue_codes::INCONSISTENT_DATETIME_STYLE));
+}
+
+fn qualification_issues(files: &[(&str, &str)], check: bool) -> Vec<Issue> {
+    let mut request = make_request("");
+    request.files = Some(
+        files
+            .iter()
+            .map(|(name, content)| FileSource {
+                name: name.to_string(),
+                content: content.to_string(),
+            })
+            .collect(),
+    );
+    request.options = Some(AnalysisOptions {
+        check_qualification_consistency: Some(check),
+        ..Default::default()
+    });
+    request_issues_with_code(&request, issue_codes::INCONSISTENT_QUALIFICATION)
+}
+
+#[test]
+fn qualification_consistency_flags_mixed_references_across_files() {
+    let files = [
+        (
+            "active_users.sql",
+            "CREATE TABLE mart.active_users AS SELECT id FROM analytics.users WHERE active",
+        ),
+        (
+            "signups.sql",
+            "SELECT id, created_at FROM users JOIN analytics.events ON users.id = events.user_id",
+        ),
+        ("events.sql", "SELECT user_id FROM analytics.events"),
+    ];
+    let issues = qualification_issues(&files, true);
+
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert_eq!(issues[0].source_name.as_deref(), Some("signups.sql"));
+    assert_eq!(issues[0].statement_index, Some(1));
+    assert_eq!(
+        issues[0].message,
+        "Table referenced as 'users' (signups.sql) and as 'analytics.users' (active_users.sql); \
+         use 'analytics.users' everywhere"
+    );
+    assert!(qualification_issues(&files, false).is_empty());
+}
+
+#[test]
+fn unqualified_wildcard_flags_multi_source_star() {
+    let issues = issues_with_code(
+        "SELECT * FROM users u JOIN orders o ON o.user_id = u.id; SELECT * FROM a, b",
+        AnalysisOptions {
+            check_unqualified_wildcards: Some(true),
+            ..Default::default()
+        },
+        issue_codes::UNQUALIFIED_WILDCARD,
+    );
+
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].statement_index, Some(0));
+    assert!(
+        issues[0].message.contains("u.*, o.*"),
+        "{}",
+        issues[0].message
+    );
+    assert_eq!(issues[1].statement_index, Some(1));
+}
+
+#[test]
+fn unqualified_wildcard_allows_single_source_and_qualified_star() {
+    assert!(issues_with_code(
+        "SELECT * FROM users; \
+         SELECT u.*, o.id FROM users u JOIN orders o ON o.user_id = u.id; \
+         SELECT * FROM (SELECT a.id FROM a JOIN b ON a.id = b.id) AS t",
+        AnalysisOptions {
+            check_unqualified_wildcards: Some(true),
+            ..Default::default()
+        },
+        issue_codes::UNQUALIFIED_WILDCARD,
+    )
+    .is_empty());
+}
+
+#[test]
+fn unqualified_wildcard_check_is_opt_in() {
+    let result = analyze(&make_request(
+        "SELECT * FROM users u JOIN orders o ON o.user_id = u.id",
+    ));
+    assert!(result
+        .issues
+        .iter()
+        .all(|i| i.code != issue_codes::UNQUALIFIED_WILDCARD));
+}
+
+#[test]
+fn ambiguous_order_by_flags_duplicate_output_name() {
+    let issues = issues_with_code(
+        "SELECT a + b AS total, c AS total FROM t ORDER BY total; \
+         SELECT u.id, o.id FROM users u JOIN orders o ON o.user_id = u.id ORDER BY id",
+        AnalysisOptions::default(),
+        issue_codes::AMBIGUOUS_ORDER_BY,
+    );
+
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert_eq!(issues[0].statement_index, Some(0));
+    assert!(
+        issues[0].message.contains("2 columns named total"),
+        "{}",
+        issues[0].message
+    );
+    assert_eq!(issues[1].statement_index, Some(1));
+}
+
+#[test]
+fn ambiguous_order_by_flags_alias_shadowing_column() {
+    let issues = issues_with_code(
+        "CREATE TABLE t (a INT, b INT, total INT); \
+         SELECT a + b AS total FROM t ORDER BY total",
+        AnalysisOptions::default(),
+        issue_codes::AMBIGUOUS_ORDER_BY,
+    );
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].statement_index, Some(1));
+    assert!(
+        issues[0].message.contains("column of t"),
+        "{}",
+        issues[0].message
+    );
+}
+
+#[test]
+fn ambiguous_order_by_allows_unique_and_self_named_aliases() {
+    assert!(issues_with_code(
+        "CREATE TABLE t (a INT, b INT, total INT); \
+         SELECT a + b AS sum_ab FROM t ORDER BY sum_ab; \
+         SELECT t.total AS total, a FROM t ORDER BY total, a; \
+         SELECT a + b AS total FROM t ORDER BY t.total",
+        AnalysisOptions::default(),
+        issue_codes::AMBIGUOUS_ORDER_BY,
+    )
+    .is_empty());
+}
+
+const FOUR_LEVEL_QUERY: &str = "SELECT * FROM (\
+     SELECT * FROM (\
+     SELECT * FROM (\
+     SELECT id FROM (SELECT id FROM t) d1) d2) d3) d4";
+
+#[test]
+fn deeply_nested_subquery_flags_deepest_point() {
+    let issues = issues_with_code(
+        FOUR_LEVEL_QUERY,
+        AnalysisOptions {
+            max_subquery_depth: Some(3),
+            ..Default::default()
+        },
+        issue_codes::DEEPLY_NESTED_SUBQUERY,
+    );
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert!(
+        issues[0].message.contains("4 levels deep (limit 3)"),
+        "{}",
+        issues[0].message
+    );
+    let span = issues[0].span.expect("span of deepest subquery");
+    assert_eq!(&FOUR_LEVEL_QUERY[span.start..span.end], "SELECT id FROM t");
+}
+
+#[test]
+fn deeply_nested_subquery_allows_shallow_queries_and_ctes() {
+    let issues = issues_with_code(
+        "SELECT * FROM (SELECT id FROM (SELECT id FROM t) d1) d2 \
+         WHERE id IN (SELECT id FROM u); \
+         WITH a AS (SELECT id FROM (SELECT id FROM t) x), \
+              b AS (SELECT id FROM (SELECT id FROM a) y) \
+         SELECT * FROM (SELECT id FROM b UNION (SELECT id FROM a)) z",
+        AnalysisOptions {
+            max_subquery_depth: Some(3),
+            ..Default::default()
+        },
+        issue_codes::DEEPLY_NESTED_SUBQUERY,
+    );
+    assert!(issues.is_empty(), "{issues:?}");
+}
+
+#[test]
+fn deeply_nested_subquery_check_is_opt_in() {
+    assert!(issues_with_code(
+        FOUR_LEVEL_QUERY,
+        AnalysisOptions::default(),
+        issue_codes::DEEPLY_NESTED_SUBQUERY,
+    )
+    .is_empty());
+}
+
+#[test]
+fn deeply_nested_subquery_threshold_is_configurable() {
+    let issues_at_depth = |depth| {
+        issues_with_code(
+            FOUR_LEVEL_QUERY,
+            AnalysisOptions {
+                max_subquery_depth: Some(depth),
+                ..Default::default()
+            },
+            issue_codes::DEEPLY_NESTED_SUBQUERY,
+        )
+    };
+    assert!(issues_at_depth(4).is_empty());
+    assert_eq!(issues_at_depth(1).len(), 1);
+}
+
+#[test]
+fn insert_column_order_flags_misaligned_names() {
+    let issues = issues_with_code(
+        "INSERT INTO t (a, b, c) SELECT c_val, a_val, b_val FROM s; \
+         CREATE TABLE accounts (id INT, user_id INT); \
+         INSERT INTO accounts SELECT user_id, id FROM staging",
+        AnalysisOptions::default(),
+        issue_codes::INSERT_COLUMN_ORDER_MISMATCH,
+    );
+
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert_eq!(issues[0].statement_index, Some(0));
+    assert!(
+        issues[0].message.contains("c_val -> a"),
+        "{}",
+        issues[0].message
+    );
+    // The target schema supplies the columns when the insert has no column list
+    assert_eq!(issues[1].statement_index, Some(2));
+}
+
+#[test]
+fn insert_column_order_allows_aligned_or_unknown_names() {
+    assert!(issues_with_code(
+        "INSERT INTO t (a, b, c) SELECT a_val, b AS b, s.c FROM s; \
+         INSERT INTO t (id, parent_id) SELECT id, id FROM s; \
+         INSERT INTO t (a, b) SELECT x + y, z FROM s; \
+         INSERT INTO unknown_target SELECT b, a FROM s",
+        AnalysisOptions::default(),
+        issue_codes::INSERT_COLUMN_ORDER_MISMATCH,
+    )
+    .is_empty());
+}
+
+/// Keyword identifier issues followed by keyword column alias issues.
+fn reserved_keyword_issues(sql: &str, dialect: Dialect) -> Vec<Issue> {
+    let request = AnalyzeRequest {
+        dialect,
+        options: Some(AnalysisOptions {
+            check_reserved_keywords: Some(true),
+            ..Default::default()
+        }),
+        ..make_request(sql)
+    };
+    let mut issues = request_issues_with_code(&request, issue_codes::RESERVED_KEYWORD_IDENTIFIER);
+    issues.extend(request_issues_with_code(
+        &request,
+        issue_codes::RESERVED_KEYWORD_COLUMN_ALIAS,
+    ));
+    issues
+}
+
+#[test]
+fn reserved_keyword_flags_postgres_column_named_order() {
+    let sql = "CREATE TABLE sales (id INT, order INT); \
+               CREATE TABLE quoted_sales (\"order\" INT, amount INT)";
+    let issues = reserved_keyword_issues(sql, Dialect::Postgres);
+
+    assert_eq!(issues.len(), 2, "{issues:?}");
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert_eq!(issues[0].statement_index, Some(0));
+    assert!(
+        issues[0].message.contains("Column order"),
+        "{}",
+        issues[0].message
+    );
+    let span = issues[0].span.expect("span");
+    assert_eq!(&sql[span.start..span.end], "order");
+
+    // Quoting makes the name usable, so it is only informational
+    assert_eq!(issues[1].severity, Severity::Info);
+    assert_eq!(issues[1].statement_index, Some(1));
+}
+
+#[test]
+fn reserved_keyword_depends_on_dialect_and_covers_aliases() {
+    let sql = "WITH limit AS (SELECT id AS offset FROM t) SELECT offset FROM limit";
+    assert_eq!(reserved_keyword_issues(sql, Dialect::Postgres).len(), 2);
+    // LIMIT and OFFSET are not reserved by the SQL standard
+    assert!(reserved_keyword_issues(sql, Dialect::Generic).is_empty());
+}
+
+#[test]
+fn keyword_column_alias_is_flagged_with_quoting_fix() {
+    let sql = "SELECT id AS Order, amount AS total FROM sales";
+    let issues = reserved_keyword_issues(sql, Dialect::Postgres);
+
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].code, issue_codes::RESERVED_KEYWORD_COLUMN_ALIAS);
+    assert_eq!(issues[0].severity, Severity::Warning);
+    let span = issues[0].span.expect("span");
+    assert_eq!(&sql[span.start..span.end], "Order");
+    // Postgres folds the unquoted alias to lower case, so the fix keeps that name
+    assert_eq!(
+        crate::apply_fixes(sql, &issues).0,
+        "SELECT id AS \"order\", amount AS total FROM sales"
+    );
+
+    let mysql = reserved_keyword_issues("SELECT id AS `order` FROM sales", Dialect::Mysql);
+    assert!(mysql
+        .iter()
+        .all(|i| i.code != issue_codes::RESERVED_KEYWORD_COLUMN_ALIAS));
+    let fixed = reserved_keyword_issues("SELECT id AS order FROM sales", Dialect::Mysql);
+    assert_eq!(
+        fixed[0].fix.as_ref().map(|fix| fix.replacement.as_str()),
+        Some("`order`")
+    );
+}
+
+#[test]
+fn quoted_keyword_column_alias_is_not_flagged_for_quoting() {
+    let issues = reserved_keyword_issues(
+        "SELECT id AS \"order\" FROM sales UNION ALL SELECT id AS \"order\" FROM returns",
+        Dialect::Postgres,
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].code, issue_codes::RESERVED_KEYWORD_IDENTIFIER);
+    assert_eq!(issues[0].severity, Severity::Info);
+    assert!(issues[0].fix.is_none());
+}
+
+#[test]
+fn reserved_keyword_check_is_opt_in() {
+    let mut request = make_request("CREATE TABLE sales (id INT, order INT)");
+    request.dialect = Dialect::Postgres;
+    assert!(analyze(&request)
+        .issues
+        .iter()
+        .all(|i| i.code != issue_codes::RESERVED_KEYWORD_IDENTIFIER));
+}
+
+fn implicit_alias_names(allow: Option<ImplicitAliasContext>) -> Vec<String> {
+    let sql = "SELECT o.id, d.total FROM orders o \
+               JOIN (SELECT order_id, SUM(amount) AS total FROM items GROUP BY order_id) d \
+               ON d.order_id = o.id \
+               JOIN customers AS c ON c.id = o.customer_id \
+               JOIN (SELECT 1 AS one) AS x ON TRUE";
+    let options = AnalysisOptions {
+        check_implicit_aliases: Some(true),
+        allow_implicit_aliases_for: allow,
+        ..Default::default()
+    };
+    issues_with_code(sql, options, issue_codes::IMPLICIT_TABLE_ALIAS)
+        .into_iter()
+        .map(|i| {
+            let span = i.span.expect("span");
+            sql[span.start..span.end].to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn implicit_aliases_flagged_in_every_context_by_default() {
+    assert_eq!(implicit_alias_names(None), ["o", "d"]);
+    assert_eq!(
+        implicit_alias_names(Some(ImplicitAliasContext::None)),
+        ["o", "d"]
+    );
+}
+
+#[test]
+fn implicit_aliases_allowed_per_context() {
+    assert_eq!(
+        implicit_alias_names(Some(ImplicitAliasContext::Derived)),
+        ["o"]
+    );
+    assert_eq!(
+        implicit_alias_names(Some(ImplicitAliasContext::Base)),
+        ["d"]
+    );
+}
+
+fn unordered_limit_request(sql: &str, dialect: Dialect) -> AnalyzeRequest {
+    AnalyzeRequest {
+        dialect,
+        options: Some(AnalysisOptions {
+            check_unordered_limits: Some(true),
+            ..Default::default()
+        }),
+        ..make_request(sql)
+    }
+}
+
+#[test]
+fn top_without_order_by_is_flagged() {
+    let issues = request_issues_with_code(
+        &unordered_limit_request("SELECT TOP 10 id FROM orders", Dialect::Mssql),
+        issue_codes::UNORDERED_ROW_LIMIT,
+    );
+    assert_eq!(issues.len(), 1);
+    assert!(
+        issues[0].message.starts_with("TOP without ORDER BY"),
+        "{issues:?}"
+    );
+
+    let issues = request_issues_with_code(
+        &unordered_limit_request(
+            "SELECT o.id FROM orders o \
+             WHERE o.id IN (SELECT TOP 5 PERCENT order_id FROM items)",
+            Dialect::Mssql,
+        ),
+        issue_codes::UNORDERED_ROW_LIMIT,
+    );
+    assert_eq!(issues.len(), 1);
+    assert!(
+        issues[0]
+            .message
+            .starts_with("TOP ... PERCENT without ORDER BY"),
+        "{issues:?}"
+    );
+
+    assert!(request_issues_with_code(
+        &unordered_limit_request(
+            "SELECT TOP 10 PERCENT WITH TIES id FROM orders ORDER BY amount DESC; \
+             SELECT id FROM orders ORDER BY id OFFSET 10 ROWS FETCH NEXT 5 ROWS ONLY",
+            Dialect::Mssql,
+        ),
+        issue_codes::UNORDERED_ROW_LIMIT,
+    )
+    .is_empty());
+}
+
+#[test]
+fn with_ties_without_order_by_is_flagged() {
+    let issues = request_issues_with_code(
+        &unordered_limit_request("SELECT TOP 10 WITH TIES id FROM orders", Dialect::Mssql),
+        issue_codes::UNORDERED_ROW_LIMIT,
+    );
+    assert_eq!(issues.len(), 1);
+    assert!(
+        issues[0].message.contains("WITH TIES requires an ORDER BY"),
+        "{issues:?}"
+    );
+}
+
+#[test]
+fn offset_without_order_by_is_flagged() {
+    let issues = request_issues_with_code(
+        &unordered_limit_request("SELECT id FROM orders OFFSET 10", Dialect::Postgres),
+        issue_codes::UNORDERED_ROW_LIMIT,
+    );
+    assert_eq!(issues.len(), 1);
+    assert!(
+        issues[0].message.starts_with("OFFSET without ORDER BY"),
+        "{issues:?}"
+    );
+}
+
+#[test]
+fn limit_is_not_ordered_by_a_subquery_order_by() {
+    let issues = request_issues_with_code(
+        &unordered_limit_request(
+            "SELECT id FROM (SELECT id FROM orders ORDER BY amount DESC) AS big LIMIT 5",
+            Dialect::Postgres,
+        ),
+        issue_codes::UNORDERED_ROW_LIMIT,
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert!(
+        issues[0].message.starts_with("LIMIT without ORDER BY"),
+        "{issues:?}"
+    );
+}
+
+#[test]
+fn ordered_limit_is_not_flagged() {
+    assert!(request_issues_with_code(
+        &unordered_limit_request(
+            "SELECT id FROM orders ORDER BY id LIMIT 5 OFFSET 10; \
+             SELECT id FROM (SELECT id, amount FROM orders) AS o ORDER BY amount DESC LIMIT 5",
+            Dialect::Postgres,
+        ),
+        issue_codes::UNORDERED_ROW_LIMIT,
+    )
+    .is_empty());
+}
+
+#[test]
+fn top_with_ties_keeps_column_lineage() {
+    let mut request = make_request(
+        "INSERT INTO top_orders (id) SELECT TOP 10 PERCENT WITH TIES id FROM orders ORDER BY amount DESC",
+    );
+    request.dialect = Dialect::Mssql;
+    let result = analyze(&request);
+    assert!(!result.summary.has_errors, "{:?}", result.issues);
+    let statement = &result.statements[0];
+    let source = statement
+        .nodes
+        .iter()
+        .find(|node| {
+            node.node_type == NodeType::Column
+                && node.qualified_name.as_deref() == Some("orders.id")
+        })
+        .expect("orders.id column");
+    // INSERT target columns carry no qualified name
+    assert!(statement.edges.iter().any(|edge| {
+        edge.from == source.id
+            && statement.nodes.iter().any(|node| {
+                node.id == edge.to
+                    && node.node_type == NodeType::Column
+                    && node.label.as_ref() == "id"
+                    && node.qualified_name.is_none()
+            })
+    }));
+}
+
+fn join_key_type_request(sql: &str, compatible: Option<Vec<Vec<String>>>) -> AnalyzeRequest {
+    let column = |name: &str, data_type: &str| ColumnSchema {
+        name: name.to_string(),
+        data_type: Some(data_type.to_string()),
+        is_primary_key: None,
+        foreign_key: None,
+        nullable: None,
+    };
+    let table = |name: &str, columns: Vec<ColumnSchema>| SchemaTable {
+        catalog: None,
+        schema: None,
+        name: name.to_string(),
+        columns,
+    };
+    let mut request = make_request(sql);
+    request.schema = Some(SchemaMetadata {
+        default_catalog: None,
+        default_schema: None,
+        search_path: None,
+        case_sensitivity: None,
+        tables: vec![
+            table(
+                "users",
+                vec![column("id", "INT"), column("account_id", "bigint")],
+            ),
+            table(
+                "orders",
+                vec![
+                    column("user_code", "VARCHAR(20)"),
+                    column("user_id", "BIGINT"),
+                    column("placed_at", "date"),
+                ],
+            ),
+            table("accounts", vec![column("id", "integer")]),
+        ],
+        allow_implied: true,
+    });
+    request.options = Some(AnalysisOptions {
+        check_join_key_types: Some(true),
+        compatible_join_key_types: compatible,
+        ..Default::default()
+    });
+    request
+}
+
+#[test]
+fn join_keys_with_incompatible_types_are_flagged() {
+    let messages = messages(request_issues_with_code(
+        &join_key_type_request(
+            "SELECT u.id FROM users u \
+         JOIN orders o ON u.id = o.user_code AND o.placed_at = u.account_id",
+            None,
+        ),
+        issue_codes::JOIN_KEY_TYPE_MISMATCH,
+    ));
+    assert_eq!(
+        messages,
+        [
+            "Join compares users.id (INT) with orders.user_code (VARCHAR(20)); these types are not compatible",
+            "Join compares orders.placed_at (date) with users.account_id (bigint); these types are not compatible",
+        ]
+    );
+}
+
+#[test]
+fn join_keys_with_compatible_types_are_not_flagged() {
+    // int, bigint and integer are all integers
+    assert!(messages(request_issues_with_code(
+        &join_key_type_request(
+            "SELECT u.id FROM users u \
+         JOIN orders o ON o.user_id = u.id \
+         JOIN accounts ON accounts.id = u.account_id",
+            None
+        ),
+        issue_codes::JOIN_KEY_TYPE_MISMATCH
+    ))
+    .is_empty());
+
+    let sql = "SELECT u.id FROM users u JOIN orders o ON u.id = o.user_code";
+    let allowed = messages(request_issues_with_code(
+        &join_key_type_request(
+            sql,
+            Some(vec![vec!["int".to_string(), "varchar".to_string()]]),
+        ),
+        issue_codes::JOIN_KEY_TYPE_MISMATCH,
+    ));
+    assert!(allowed.is_empty(), "{allowed:?}");
+}
+
+#[test]
+fn join_key_types_need_schema_types_and_the_option() {
+    // Unknown columns carry no type to compare
+    assert!(messages(request_issues_with_code(
+        &join_key_type_request(
+            "SELECT * FROM users u JOIN events e ON u.id = e.user_name",
+            None
+        ),
+        issue_codes::JOIN_KEY_TYPE_MISMATCH
+    ))
+    .is_empty());
+
+    let result = analyze(&make_request(
+        "SELECT u.id FROM users u JOIN orders o ON u.id = o.user_code",
+    ));
+    assert!(!result
+        .issues
+        .iter()
+        .any(|i| i.code == issue_codes::JOIN_KEY_TYPE_MISMATCH));
+}
+
+#[test]
+fn repeated_select_columns_are_flagged() {
+    assert_eq!(
+        messages(issues_with_code(
+            "SELECT id, name, id FROM users",
+            AnalysisOptions {
+                check_duplicate_projections: Some(true),
+                ..Default::default()
+            },
+            issue_codes::DUPLICATE_PROJECTION
+        )),
+        vec!["SELECT projects id 2 times; remove the repeated column"]
+    );
+    // Layout does not matter, and subqueries and set operations are checked too
+    assert_eq!(
+        messages(issues_with_code(
+            "SELECT * FROM (SELECT amount * 2, amount*2 FROM orders) d \
+             UNION ALL SELECT a, b FROM t",
+            AnalysisOptions {
+                check_duplicate_projections: Some(true),
+                ..Default::default()
+            },
+            issue_codes::DUPLICATE_PROJECTION
+        )),
+        vec!["SELECT projects amount * 2 2 times; remove the repeated column"]
+    );
+}
+
+#[test]
+fn repeated_select_columns_with_different_aliases_are_configurable() {
+    let sql = "SELECT amount AS gross, amount AS net, id, id AS user_id FROM orders";
+    assert!(messages(issues_with_code(
+        sql,
+        AnalysisOptions {
+            check_duplicate_projections: Some(true),
+            ..Default::default()
+        },
+        issue_codes::DUPLICATE_PROJECTION
+    ))
+    .is_empty());
+    assert_eq!(
+        messages(issues_with_code(
+            sql,
+            AnalysisOptions {
+                check_duplicate_projections: Some(true),
+                include_aliased_duplicate_projections: Some(true),
+                ..Default::default()
+            },
+            issue_codes::DUPLICATE_PROJECTION
+        )),
+        vec![
+            "SELECT projects amount 2 times (as gross, net)",
+            "SELECT projects id 2 times (as id, user_id)",
+        ]
+    );
+}
+
+#[test]
+fn distinct_select_columns_are_not_flagged() {
+    let sql = "SELECT u.id, o.id, name, UPPER(name), COUNT(*) FROM users u \
+               JOIN orders o ON o.user_id = u.id GROUP BY 1, 2, 3";
+    assert!(messages(issues_with_code(
+        sql,
+        AnalysisOptions {
+            check_duplicate_projections: Some(true),
+            ..Default::default()
+        },
+        issue_codes::DUPLICATE_PROJECTION
+    ))
+    .is_empty());
+    let result = analyze(&make_request("SELECT id, id FROM users"));
+    assert!(!result
+        .issues
+        .iter()
+        .any(|i| i.code == issue_codes::DUPLICATE_PROJECTION));
+}
+
+fn analyze_with_graph_metrics(sql: &str) -> AnalyzeResult {
+    let mut request = make_request(sql);
+    request.options = Some(AnalysisOptions {
+        graph_metrics: Some(true),
+        ..Default::default()
+    });
+    analyze(&request)
+}
+
+#[test]
+fn graph_metrics_rank_shared_tables_by_fan_in() {
+    let result = analyze_with_graph_metrics(
+        "CREATE TABLE stg_orders AS SELECT id, customer_id, amount FROM raw_orders;
+         CREATE TABLE revenue AS SELECT SUM(amount) AS total FROM stg_orders;
+         CREATE TABLE order_counts AS SELECT customer_id, COUNT(*) AS n FROM stg_orders GROUP BY customer_id;
+         CREATE VIEW big_orders AS SELECT o.id, c.name FROM stg_orders o JOIN customers c ON c.id = o.customer_id;",
+    );
+    let metrics = result.graph_metrics.expect("graph metrics requested");
+    let by_name: HashMap<&str, &crate::TableGraphMetrics> = metrics
+        .tables
+        .iter()
+        .map(|t| (t.name.as_str(), t))
+        .collect();
+
+    // Read by three models, so it comes first
+    assert_eq!(metrics.tables[0].name, "stg_orders");
+    assert_eq!(metrics.tables[0].fan_in, 3);
+    assert_eq!(metrics.tables[0].fan_out, 1);
+    assert!(metrics.tables[1..].iter().all(|t| t.fan_in < 3));
+
+    // Sources read nothing; final models are read by nothing
+    assert_eq!(by_name["raw_orders"].fan_out, 0);
+    assert_eq!(by_name["customers"].fan_out, 0);
+    assert_eq!(by_name["revenue"].fan_in, 0);
+    assert_eq!(by_name["big_orders"].fan_out, 2);
+    assert_eq!(by_name["big_orders"].node_type, NodeType::View);
+
+    // stg_orders is the only relation between others, and everything depends on raw_orders
+    let top_betweenness = metrics
+        .tables
+        .iter()
+        .max_by(|a, b| a.betweenness.total_cmp(&b.betweenness))
+        .unwrap();
+    assert_eq!(top_betweenness.name, "stg_orders");
+    assert_eq!(by_name["raw_orders"].betweenness, 0.0);
+    let top_rank = metrics
+        .tables
+        .iter()
+        .max_by(|a, b| a.page_rank.total_cmp(&b.page_rank))
+        .unwrap();
+    assert_eq!(top_rank.name, "raw_orders");
+}
+
+#[test]
+fn graph_metrics_are_opt_in() {
+    let result = analyze(&make_request(
+        "CREATE TABLE b AS SELECT id FROM a; CREATE TABLE c AS SELECT id FROM b;",
+    ));
+    assert!(result.graph_metrics.is_none());
+    assert!(!serde_json::to_string(&result)
+        .unwrap()
+        .contains("graphMetrics"));
+}
+
+#[test]
+fn aggregate_case_without_else_is_flagged() {
+    let sql = "SELECT sum(CASE WHEN paid THEN 1 END), \
+               AVG((CASE status WHEN 'ok' THEN amount END)) FROM orders";
+    assert_eq!(
+        messages(issues_with_code(sql, AnalysisOptions {
+check_aggregate_case_else: Some(true),
+..Default::default()
+}, issue_codes::AGGREGATE_CASE_WITHOUT_ELSE)),
+        vec![
+            "SUM(CASE ...) has no ELSE, so rows matching no WHEN are NULL and skipped by SUM; add ELSE 0 if they should count as zero",
+            "AVG(CASE ...) has no ELSE, so rows matching no WHEN are NULL and skipped by AVG; add ELSE 0 if they should count as zero",
+        ]
+    );
+}
+
+#[test]
+fn aggregate_case_with_else_or_counting_is_not_flagged() {
+    assert!(messages(issues_with_code(
+        "SELECT SUM(CASE WHEN paid THEN 1 ELSE 0 END), COUNT(CASE WHEN paid THEN 1 END), \
+         MAX(CASE WHEN kind = 'a' THEN created_at END) FROM orders",
+        AnalysisOptions {
+            check_aggregate_case_else: Some(true),
+            ..Default::default()
+        },
+        issue_codes::AGGREGATE_CASE_WITHOUT_ELSE
+    ))
+    .is_empty());
+    let result = analyze(&make_request(
+        "SELECT SUM(CASE WHEN paid THEN 1 END) FROM orders",
+    ));
+    assert!(!result
+        .issues
+        .iter()
+        .any(|i| i.code == issue_codes::AGGREGATE_CASE_WITHOUT_ELSE));
+}
+
+#[test]
+fn recursive_cte_without_narrowing_predicate_is_flagged() {
+    let sql = "WITH RECURSIVE tree AS (\
+                 SELECT id, parent_id FROM nodes WHERE parent_id IS NULL \
+                 UNION ALL \
+                 SELECT n.id, n.parent_id FROM nodes n JOIN tree t ON n.parent_id = t.id \
+                 WHERE n.active\
+               ) SELECT id FROM tree";
+    let issues = issues_with_code(
+        sql,
+        AnalysisOptions {
+            check_recursive_termination: Some(true),
+            ..Default::default()
+        },
+        issue_codes::UNBOUNDED_RECURSION,
+    );
+
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert!(
+        issues[0].message.starts_with(
+            "Recursive CTE 'tree' has no WHERE condition on 'tree' in its recursive branch"
+        ),
+        "{}",
+        issues[0].message
+    );
+    let span = issues[0].span.expect("span");
+    assert_eq!(&sql[span.start..span.end], "tree");
+    assert_eq!(span.start, sql.find("tree").unwrap());
+}
+
+#[test]
+fn recursive_cte_with_narrowing_predicate_is_not_flagged() {
+    assert!(issues_with_code(
+        "WITH RECURSIVE tree AS (\
+           SELECT id, 1 AS depth FROM nodes WHERE parent_id IS NULL \
+           UNION ALL \
+           SELECT n.id, t.depth + 1 FROM nodes n JOIN tree t ON n.parent_id = t.id \
+           WHERE t.depth < 10\
+         ), counter(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM counter WHERE n < 100) \
+         SELECT id FROM tree",
+        AnalysisOptions {
+            check_recursive_termination: Some(true),
+            ..Default::default()
+        },
+        issue_codes::UNBOUNDED_RECURSION
+    )
+    .is_empty());
+    // Off by default, and non-recursive CTEs are never checked
+    let result = analyze(&make_request(
+        "WITH RECURSIVE c(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM c) SELECT n FROM c",
+    ));
+    assert!(!result
+        .issues
+        .iter()
+        .any(|i| i.code == issue_codes::UNBOUNDED_RECURSION));
+    assert!(issues_with_code(
+        "WITH RECURSIVE base AS (SELECT id FROM nodes) SELECT id FROM base",
+        AnalysisOptions {
+            check_recursive_termination: Some(true),
+            ..Default::default()
+        },
+        issue_codes::UNBOUNDED_RECURSION
+    )
+    .is_empty());
+    assert_eq!(
+        issues_with_code(
+            "WITH RECURSIVE c(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM c) SELECT n FROM c",
+            AnalysisOptions {
+                check_recursive_termination: Some(true),
+                ..Default::default()
+            },
+            issue_codes::UNBOUNDED_RECURSION
+        )
+        .len(),
+        1
+    );
+}
+
+#[test]
+fn literal_only_where_conditions_are_flagged() {
+    assert_eq!(
+        messages(issues_with_code(
+            "SELECT id FROM users WHERE 5 > 3",
+            AnalysisOptions {
+                check_constant_predicates: Some(true),
+                ..Default::default()
+            },
+            issue_codes::CONSTANT_PREDICATE
+        )),
+        vec!["WHERE condition `5 > 3` is always true and filters nothing"]
+    );
+    assert_eq!(
+        messages(issues_with_code(
+            "SELECT id FROM users WHERE 1 = 2",
+            AnalysisOptions {
+                check_constant_predicates: Some(true),
+                ..Default::default()
+            },
+            issue_codes::CONSTANT_PREDICATE
+        )),
+        vec!["WHERE condition `1 = 2` is always false; the query returns no rows"]
+    );
+    let sql = "SELECT status, COUNT(*) FROM users WHERE 'a' = 'a' AND NOT (-1 >= 0) \
+               GROUP BY status HAVING status = 'x' AND (2 < 1 OR FALSE)";
+    assert_eq!(
+        messages(issues_with_code(sql, AnalysisOptions {
+check_constant_predicates: Some(true),
+..Default::default()
+}, issue_codes::CONSTANT_PREDICATE)),
+        vec![
+            "WHERE condition `'a' = 'a' AND NOT (-1 >= 0)` is always true and filters nothing",
+            "HAVING condition `status = 'x' AND (2 < 1 OR false)` is always false; the query returns no rows",
+        ]
+    );
+}
+
+#[test]
+fn conditions_depending_on_columns_are_not_flagged() {
+    for sql in [
+        "SELECT id FROM users WHERE id > 3",
+        "SELECT id FROM users WHERE 1 = 1 AND id > 3",
+        "SELECT id FROM users WHERE id > 3 OR 1 = 2",
+        // Distinct strings may be equal under a case-insensitive collation
+        "SELECT id FROM users WHERE 'a' = 'A'",
+        "SELECT id FROM users WHERE 1 = '1'",
+    ] {
+        assert!(
+            messages(issues_with_code(
+                sql,
+                AnalysisOptions {
+                    check_constant_predicates: Some(true),
+                    ..Default::default()
+                },
+                issue_codes::CONSTANT_PREDICATE
+            ))
+            .is_empty(),
+            "{sql}"
+        );
+    }
+    let result = analyze(&make_request("SELECT id FROM users WHERE 1 = 2"));
+    assert!(!result
+        .issues
+        .iter()
+        .any(|i| i.code == issue_codes::CONSTANT_PREDICATE));
+}
+
+#[test]
+fn not_in_subquery_flags_nullable_column() {
+    let issues = issues_with_code(
+        "CREATE TABLE blocked (user_id INT NULL); \
+         SELECT id FROM users WHERE id NOT IN (SELECT b.user_id FROM blocked b)",
+        AnalysisOptions::default(),
+        issue_codes::NOT_IN_NULLABLE_SUBQUERY,
     );
+
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert_eq!(issues[0].statement_index, Some(1));
     assert!(
-        with_hiding.summary.table_count < without_hiding.summary.table_count,
-        "summary table_count should decrease when CTE nodes are hidden"
+        issues[0].message.contains("NOT EXISTS"),
+        "{}",
+        issues[0].message
     );
-    assert!(
-        with_hiding.summary.complexity_score < without_hiding.summary.complexity_score,
-        "complexity score should decrease when CTE nodes are hidden"
+}
+
+#[test]
+fn not_in_subquery_allows_not_null_column() {
+    assert!(issues_with_code(
+        "CREATE TABLE blocked (user_id INT NOT NULL); \
+         SELECT id FROM users WHERE id NOT IN (SELECT user_id FROM blocked)",
+        AnalysisOptions::default(),
+        issue_codes::NOT_IN_NULLABLE_SUBQUERY
+    )
+    .is_empty());
+    assert!(issues_with_code(
+        "CREATE TABLE blocked (user_id INT PRIMARY KEY); \
+         SELECT id FROM users WHERE id NOT IN (SELECT user_id FROM blocked)",
+        AnalysisOptions::default(),
+        issue_codes::NOT_IN_NULLABLE_SUBQUERY
+    )
+    .is_empty());
+}
+
+#[test]
+fn not_in_subquery_without_schema_warns_unless_filtered() {
+    assert_eq!(
+        issues_with_code(
+            "SELECT id FROM users WHERE id NOT IN (SELECT user_id FROM blocked)",
+            AnalysisOptions::default(),
+            issue_codes::NOT_IN_NULLABLE_SUBQUERY
+        )
+        .len(),
+        1
     );
+    assert!(issues_with_code(
+        "SELECT id FROM users \
+         WHERE id NOT IN (SELECT user_id FROM blocked WHERE user_id IS NOT NULL)",
+        AnalysisOptions::default(),
+        issue_codes::NOT_IN_NULLABLE_SUBQUERY
+    )
+    .is_empty());
+    assert!(issues_with_code(
+        "SELECT id FROM users WHERE id IN (SELECT user_id FROM blocked)",
+        AnalysisOptions::default(),
+        issue_codes::NOT_IN_NULLABLE_SUBQUERY
+    )
+    .is_empty());
 }
 
 #[test]
@@ -506,6 +1808,7 @@ fn hide_ctes_customer_360_preserves_relationships() {
             EdgeType::Derivation => "derivation",
             EdgeType::JoinDependency => "join_dependency",
             EdgeType::CrossStatement => "cross_statement",
+            EdgeType::RecursiveSelfReference => "recursive_self_reference",
         }
     };
 
@@ -916,3 +2219,734 @@ fn test_type_mismatch_has_statement_index() {
         "type mismatch warning should reference the second statement"
     );
 }
+
+#[test]
+fn table_granularity_produces_table_edges_without_columns() {
+    let sql = "CREATE TABLE orders (id INT, customer_id INT, amount INT);
+        CREATE TABLE customers (id INT, name TEXT);
+        CREATE VIEW order_totals AS
+            SELECT c.name, SUM(o.amount) AS total
+            FROM orders o JOIN customers c ON c.id = o.customer_id
+            GROUP BY c.name;
+        INSERT INTO archive SELECT * FROM orders;
+        MERGE INTO customers t USING staging s ON t.id = s.id
+            WHEN MATCHED THEN UPDATE SET name = s.name;";
+    let columns = analyze(&make_request_with_options(sql, false, true));
+    let tables = analyze(&make_request_with_options(sql, false, false));
+
+    let table_edges = |result: &AnalyzeResult| -> BTreeSet<(String, String)> {
+        let label = |statement: &StatementLineage, id: &str| {
+            statement
+                .nodes
+                .iter()
+                .find(|node| &*node.id == id)
+                .filter(|node| node.node_type.is_table_like())
+                .map(|node| node.label.to_string())
+        };
+        result
+            .statements
+            .iter()
+            .flat_map(|statement| {
+                statement.edges.iter().filter_map(move |edge| {
+                    Some((label(statement, &edge.from)?, label(statement, &edge.to)?))
+                })
+            })
+            .collect()
+    };
+    for statement in &tables.statements {
+        assert!(
+            statement
+                .nodes
+                .iter()
+                .all(|node| node.node_type != NodeType::Column),
+            "column nodes in {}: {:?}",
+            statement.statement_type,
+            statement.nodes
+        );
+        assert!(statement
+            .edges
+            .iter()
+            .all(|edge| edge.edge_type != EdgeType::Ownership));
+    }
+    assert!(!table_edges(&tables).is_empty());
+    assert_eq!(table_edges(&tables), table_edges(&columns));
+}
+
+fn global_table_labels(result: &AnalyzeResult) -> Vec<(String, String)> {
+    result
+        .global_lineage
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Table)
+        .map(|n| (n.id.to_string(), n.label.to_string()))
+        .collect()
+}
+
+#[test]
+fn case_variant_table_references_merge_under_case_insensitive_dialect() {
+    let mut request =
+        make_request("CREATE TABLE Users (id INT); SELECT id FROM users; SELECT u.id FROM USERS u");
+    request.dialect = Dialect::Postgres;
+    let result = analyze(&request);
+
+    let tables = global_table_labels(&result);
+    assert_eq!(tables.len(), 1, "case variants should merge: {tables:?}");
+    // The merged node keeps the label of its first occurrence
+    assert_eq!(tables[0].1, "Users");
+
+    let statement_ids: HashSet<String> = result
+        .statements
+        .iter()
+        .flat_map(|s| s.nodes.iter())
+        .filter(|n| n.node_type == NodeType::Table)
+        .map(|n| n.id.to_string())
+        .collect();
+    assert_eq!(statement_ids, HashSet::from([tables[0].0.clone()]));
+
+    let labels: Vec<String> = result
+        .statements
+        .iter()
+        .flat_map(|s| s.nodes.iter())
+        .filter(|n| n.node_type == NodeType::Table)
+        .map(|n| n.label.to_string())
+        .collect();
+    // Each occurrence keeps the spelling it was written with
+    assert_eq!(labels, vec!["Users", "users", "USERS"]);
+}
+
+#[test]
+fn case_variant_table_references_stay_separate_when_case_sensitive() {
+    let sql = "CREATE TABLE Users (id INT); SELECT id FROM users; SELECT u.id FROM USERS u";
+
+    let mut request = make_request(sql);
+    request.dialect = Dialect::Mysql;
+    let mut labels: Vec<String> = global_table_labels(&analyze(&request))
+        .into_iter()
+        .map(|(_, label)| label)
+        .collect();
+    labels.sort();
+    assert_eq!(labels, vec!["USERS", "Users", "users"]);
+
+    // An explicit case sensitivity overrides the dialect's folding
+    let mut request = make_request(sql);
+    request.dialect = Dialect::Postgres;
+    request.schema = Some(SchemaMetadata {
+        case_sensitivity: Some(CaseSensitivity::Exact),
+        tables: Vec::new(),
+        ..schema_with_known_table()
+    });
+    assert_eq!(global_table_labels(&analyze(&request)).len(), 3);
+}
+
+#[test]
+fn psql_copy_meta_command_is_analyzed_like_copy() {
+    let mut request = make_request(
+        "\\set ON_ERROR_STOP on\n\
+         \\copy users FROM 'u.csv' CSV HEADER\n\
+         INSERT INTO active SELECT id FROM users WHERE active;\n\
+         \\COPY active TO active.csv\n",
+    );
+    request.dialect = Dialect::Postgres;
+    let result = analyze(&request);
+
+    assert!(
+        !result
+            .issues
+            .iter()
+            .any(|i| i.code == issue_codes::PARSE_ERROR),
+        "{:?}",
+        result.issues
+    );
+    let types: Vec<&str> = result
+        .statements
+        .iter()
+        .map(|s| s.statement_type.as_str())
+        .collect();
+    assert_eq!(types, vec!["COPY", "INSERT", "COPY"]);
+    assert!(result.statements[0]
+        .nodes
+        .iter()
+        .any(|n| n.node_type == NodeType::Table && &*n.label == "users"));
+
+    let mut tables: Vec<&str> = result
+        .global_lineage
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Table)
+        .map(|n| &*n.label)
+        .collect();
+    tables.sort();
+    assert_eq!(tables, vec!["active", "users"]);
+}
+
+#[test]
+fn unresolved_as_errors_promotes_schema_misses() {
+    let severities = |unresolved_as_errors: Option<bool>, schema: Option<SchemaMetadata>| {
+        let mut request = make_request("SELECT id, nmae FROM existing; SELECT id FROM missing");
+        request.schema = schema;
+        request.options = Some(AnalysisOptions {
+            unresolved_as_errors,
+            ..Default::default()
+        });
+        let result = analyze(&request);
+        let severities: Vec<(String, Severity)> = result
+            .issues
+            .iter()
+            .filter(|i| {
+                i.code == issue_codes::UNKNOWN_COLUMN || i.code == issue_codes::UNRESOLVED_REFERENCE
+            })
+            .map(|i| (i.code.clone(), i.severity))
+            .collect();
+        (severities, result.summary.has_errors)
+    };
+    let mut schema = schema_with_known_table();
+    schema.tables[0].columns = vec![ColumnSchema {
+        name: "id".to_string(),
+        data_type: Some("integer".to_string()),
+        is_primary_key: None,
+        foreign_key: None,
+        nullable: None,
+    }];
+
+    let (issues, has_errors) = severities(None, Some(schema.clone()));
+    assert_eq!(
+        issues,
+        vec![
+            (issue_codes::UNKNOWN_COLUMN.to_string(), Severity::Warning),
+            (
+                issue_codes::UNRESOLVED_REFERENCE.to_string(),
+                Severity::Warning
+            ),
+        ]
+    );
+    assert!(!has_errors);
+
+    let (issues, has_errors) = severities(Some(true), Some(schema));
+    assert!(issues
+        .iter()
+        .all(|(_, severity)| *severity == Severity::Error));
+    assert_eq!(issues.len(), 2);
+    assert!(has_errors);
+
+    // Without a schema there is nothing to check against
+    let (_, has_errors) = severities(Some(true), None);
+    assert!(!has_errors);
+}
+
+fn qualify_issues(sql: &str, dialect: Dialect) -> Vec<Issue> {
+    let request = AnalyzeRequest {
+        dialect,
+        ..make_request(sql)
+    };
+    request_issues_with_code(&request, issue_codes::UNSUPPORTED_SYNTAX)
+        .into_iter()
+        .filter(|i| i.message.contains("QUALIFY"))
+        .collect()
+}
+
+#[test]
+fn qualify_is_rewritten_for_dialects_without_it() {
+    let sql = "-- latest event per user\n\
+               SELECT user_id, ts FROM events \
+               QUALIFY ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY ts DESC) = 1;\n\
+               SELECT 1;";
+    let issues = qualify_issues(sql, Dialect::Postgres);
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].severity, Severity::Warning);
+    let span = issues[0].span.expect("span");
+    assert_eq!(
+        &sql[span.start..span.end],
+        "ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY ts DESC) = 1"
+    );
+
+    let (fixed, _) = crate::apply_fixes(sql, &issues);
+    assert_eq!(
+        fixed,
+        "-- latest event per user\n\
+         SELECT user_id, ts FROM (SELECT user_id, ts, \
+         ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY ts DESC) AS _qualify_1 FROM events) \
+         AS qualified_rows WHERE _qualify_1 = 1;\n\
+         SELECT 1;"
+    );
+    assert!(qualify_issues(&fixed, Dialect::Postgres).is_empty());
+    assert!(qualify_issues(sql, Dialect::Snowflake).is_empty());
+}
+
+#[test]
+fn qualify_over_wildcard_is_flagged_without_fix() {
+    let issues = qualify_issues(
+        "SELECT * FROM events QUALIFY ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY ts) = 1",
+        Dialect::Mysql,
+    );
+    assert_eq!(issues.len(), 1);
+    assert!(issues[0].fix.is_none());
+}
+
+#[test]
+fn qualify_with_comments_inside_the_statement_is_flagged_without_fix() {
+    // Rendering the rewrite from the AST would drop both comments
+    let sql = "SELECT user_id, -- the key
+               ts /* keep me */ FROM events \
+               QUALIFY ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY ts DESC) = 1";
+    let issues = qualify_issues(sql, Dialect::Postgres);
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert!(issues[0].fix.is_none());
+    assert!(
+        issues[0]
+            .message
+            .ends_with("filter a subquery on the window columns instead."),
+        "{}",
+        issues[0].message
+    );
+}
+
+#[test]
+fn quoted_multi_word_table_names_resolve_qualifiers_and_spans() {
+    let sql = "SELECT [Order Items].qty, oi.price FROM [dbo].[Order Items] \
+               JOIN [dbo].[Order Items] AS [oi] ON oi.id = [Order Items].id";
+    let mut request = make_request(sql);
+    request.dialect = Dialect::Mssql;
+    request.schema = Some(SchemaMetadata {
+        default_catalog: None,
+        default_schema: Some("dbo".to_string()),
+        search_path: None,
+        case_sensitivity: None,
+        tables: vec![SchemaTable {
+            catalog: None,
+            schema: Some("dbo".to_string()),
+            name: "orders".to_string(),
+            columns: vec![],
+        }],
+        allow_implied: false,
+    });
+    let result = analyze(&request);
+    let lineage = &result.statements[0];
+
+    // Both the table-name and the alias qualifier resolve to the one table node
+    let tables: Vec<_> = lineage
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Table)
+        .collect();
+    assert_eq!(tables.len(), 1, "{tables:?}");
+    assert_eq!(&*tables[0].label, "Order Items");
+    let node_ids: HashSet<_> = lineage.nodes.iter().map(|n| n.id.clone()).collect();
+    assert!(lineage
+        .edges
+        .iter()
+        .all(|e| node_ids.contains(&e.from) && node_ids.contains(&e.to)));
+
+    let unresolved: Vec<_> = result
+        .issues
+        .iter()
+        .filter(|i| i.code == issue_codes::UNRESOLVED_REFERENCE)
+        .collect();
+    assert!(!unresolved.is_empty());
+    let span = unresolved[0].span.expect("span");
+    assert_eq!(&sql[span.start..span.end], "[dbo].[Order Items]");
+}
+
+#[test]
+fn line_length_limit_is_configurable() {
+    let long_line = format!("SELECT id, {} FROM users;", "x".repeat(77));
+    assert_eq!(long_line.chars().count(), 100);
+    let sql = format!("SELECT 1;\n{long_line}\nSELECT 2");
+
+    let issues = issues_with_code(
+        &sql,
+        AnalysisOptions {
+            max_line_length: Some(80),
+            ..Default::default()
+        },
+        issue_codes::LONG_LINE,
+    );
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].statement_index, Some(1));
+    assert!(issues[0]
+        .message
+        .starts_with("Line 2 is 100 characters long"));
+    let span = issues[0].span.expect("span");
+    assert_eq!(&sql[span.start..span.end], &long_line[80..]);
+
+    assert!(issues_with_code(
+        &sql,
+        AnalysisOptions {
+            max_line_length: Some(120),
+            ..Default::default()
+        },
+        issue_codes::LONG_LINE
+    )
+    .is_empty());
+    assert!(issues_with_code(
+        &sql,
+        AnalysisOptions {
+            max_line_length: None,
+            ..Default::default()
+        },
+        issue_codes::LONG_LINE
+    )
+    .is_empty());
+}
+
+#[test]
+fn long_line_shared_by_statements_is_reported_once() {
+    let sql = format!("SELECT a FROM t; SELECT {} FROM u", "b".repeat(80));
+    let issues = issues_with_code(
+        &sql,
+        AnalysisOptions {
+            max_line_length: Some(80),
+            ..Default::default()
+        },
+        issue_codes::LONG_LINE,
+    );
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].statement_index, Some(0));
+}
+
+#[test]
+fn cast_round_trips_are_flagged() {
+    let issues = issues_with_code(
+        "SELECT x::int::text::int AS a, CAST(CAST(y AS INT) AS VARCHAR)::int AS b FROM t",
+        AnalysisOptions {
+            check_redundant_casts: Some(true),
+            ..Default::default()
+        },
+        issue_codes::REDUNDANT_CAST,
+    );
+
+    assert_eq!(issues.len(), 2, "{issues:?}");
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert_eq!(issues[0].statement_index, Some(0));
+    assert!(
+        issues[0].message.contains("INTEGER -> TEXT -> INTEGER"),
+        "{}",
+        issues[0].message
+    );
+
+    // The operand's own type counts as the start of the chain
+    let issues = issues_with_code(
+        "SELECT '42'::int::text FROM t",
+        AnalysisOptions {
+            check_redundant_casts: Some(true),
+            ..Default::default()
+        },
+        issue_codes::REDUNDANT_CAST,
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+
+    let issues = issues_with_code(
+        "SELECT x::int::int FROM t",
+        AnalysisOptions {
+            check_redundant_casts: Some(true),
+            ..Default::default()
+        },
+        issue_codes::REDUNDANT_CAST,
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert!(issues[0].message.contains("twice"), "{}", issues[0].message);
+}
+
+#[test]
+fn single_and_converting_casts_are_not_flagged() {
+    assert!(issues_with_code(
+        "SELECT CAST(x AS INT), x::text::date, TRY_CAST(TRY_CAST(y AS INT) AS TEXT) FROM t",
+        AnalysisOptions {
+            check_redundant_casts: Some(true),
+            ..Default::default()
+        },
+        issue_codes::REDUNDANT_CAST
+    )
+    .is_empty());
+
+    let request = make_request("SELECT x::int::text::int FROM t");
+    assert!(!analyze(&request)
+        .issues
+        .iter()
+        .any(|i| i.code == issue_codes::REDUNDANT_CAST));
+}
+
+#[test]
+fn timings_are_collected_per_statement_on_request() {
+    let mut request = make_request("SELECT a FROM t; SELEC oops; INSERT INTO u SELECT a FROM t");
+    assert!(analyze(&request).summary.timings.is_none());
+
+    request.options = Some(AnalysisOptions {
+        collect_timings: Some(true),
+        ..Default::default()
+    });
+    let result = analyze(&request);
+    let timings = result.summary.timings.as_ref().expect("timings");
+    let indices: Vec<usize> = timings.iter().map(|t| t.statement_index).collect();
+    assert_eq!(indices, [0, 1]);
+
+    let json = serde_json::to_value(&result.summary).unwrap();
+    assert!(json["timings"][0].get("parseMicros").is_some(), "{json}");
+    assert!(json["timings"][0].get("analysisMicros").is_some(), "{json}");
+}
+
+fn identifier_length_request(sql: &str, dialect: Dialect, max: Option<usize>) -> AnalyzeRequest {
+    AnalyzeRequest {
+        dialect,
+        options: Some(AnalysisOptions {
+            check_identifier_length: Some(true),
+            max_identifier_length: max,
+            ..Default::default()
+        }),
+        ..make_request(sql)
+    }
+}
+
+#[test]
+fn over_length_column_is_flagged_against_postgres_limit() {
+    let long = "c".repeat(64);
+    let sql = format!(
+        "CREATE TABLE events (id INT, {long} INT, {} INT)",
+        "d".repeat(63)
+    );
+    let issues = request_issues_with_code(
+        &identifier_length_request(&sql, Dialect::Postgres, None),
+        issue_codes::IDENTIFIER_TOO_LONG,
+    );
+
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].code, issue_codes::IDENTIFIER_TOO_LONG);
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert!(
+        issues[0].message.contains("64 bytes long") && issues[0].message.contains("of 63"),
+        "{}",
+        issues[0].message
+    );
+    let span = issues[0].span.expect("span");
+    assert_eq!(&sql[span.start..span.end], long);
+
+    // Snowflake allows the same column, and the limit is configurable
+    assert!(request_issues_with_code(
+        &identifier_length_request(&sql, Dialect::Snowflake, None),
+        issue_codes::IDENTIFIER_TOO_LONG
+    )
+    .is_empty());
+    assert_eq!(
+        request_issues_with_code(
+            &identifier_length_request(&sql, Dialect::Snowflake, Some(63)),
+            issue_codes::IDENTIFIER_TOO_LONG
+        )
+        .len(),
+        1
+    );
+}
+
+#[test]
+fn over_length_names_are_flagged_against_snowflake_limit() {
+    let long = "c".repeat(256);
+    let sql = format!("SELECT id AS {long}, {}(id) FROM events", "f".repeat(260));
+    let issues = request_issues_with_code(
+        &identifier_length_request(&sql, Dialect::Snowflake, None),
+        issue_codes::IDENTIFIER_TOO_LONG,
+    );
+
+    assert_eq!(issues.len(), 2, "{issues:?}");
+    assert!(
+        issues[0].message.contains("256 characters long") && issues[0].message.contains("of 255"),
+        "{}",
+        issues[0].message
+    );
+    // The column generated for an unaliased call is named after the function
+    assert!(
+        issues[1].message.starts_with("Generated column name"),
+        "{}",
+        issues[1].message
+    );
+    assert!(request_issues_with_code(
+        &identifier_length_request(&sql, Dialect::Snowflake, Some(300)),
+        issue_codes::IDENTIFIER_TOO_LONG
+    )
+    .is_empty());
+}
+
+#[test]
+fn reserved_identifier_prefixes_are_flagged() {
+    let sql = "CREATE TABLE pg_stats_copy (id INT, SYS_flag INT, display_name TEXT)";
+    let request = AnalyzeRequest {
+        dialect: Dialect::Postgres,
+        options: Some(AnalysisOptions {
+            reserved_identifier_prefixes: Some(vec!["pg_".into(), "sys_".into()]),
+            ..Default::default()
+        }),
+        ..make_request(sql)
+    };
+    let issues = request_issues_with_code(&request, issue_codes::RESERVED_IDENTIFIER_PREFIX);
+
+    assert_eq!(issues.len(), 2, "{issues:?}");
+    assert!(issues[0].message.contains("Table pg_stats_copy"));
+    assert!(issues[1].message.contains("Column SYS_flag"));
+}
+
+#[test]
+fn identifier_name_checks_are_opt_in() {
+    let sql = format!("CREATE TABLE pg_events ({} INT)", "c".repeat(70));
+    let request = AnalyzeRequest {
+        dialect: Dialect::Postgres,
+        ..make_request(&sql)
+    };
+    for code in [
+        issue_codes::IDENTIFIER_TOO_LONG,
+        issue_codes::RESERVED_IDENTIFIER_PREFIX,
+    ] {
+        assert!(request_issues_with_code(&request, code).is_empty());
+    }
+}
+
+fn known_table_request(sql: &str, options: Option<AnalysisOptions>) -> AnalyzeRequest {
+    AnalyzeRequest {
+        schema: Some(schema_with_known_table()),
+        options,
+        ..make_request(sql)
+    }
+}
+
+#[test]
+fn wildcard_over_table_missing_from_schema_is_flagged_at_the_star() {
+    let sql = "SELECT * FROM orders; SELECT e.id, o.* FROM existing e JOIN orders o ON o.id = e.id";
+    let issues = request_issues_with_code(
+        &known_table_request(sql, None),
+        issue_codes::SCHEMA_UNKNOWN_TABLE,
+    );
+
+    assert_eq!(issues.len(), 2, "{issues:?}");
+    assert_eq!(issues[0].severity, Severity::Warning);
+    assert!(
+        issues[0].message.contains("'orders'"),
+        "{}",
+        issues[0].message
+    );
+    let star = sql.find('*').unwrap();
+    assert_eq!(issues[0].span, Some(Span::new(star, star + 1)));
+    assert_eq!(issues[1].statement_index, Some(1));
+    let qualified_star = sql.rfind('*').unwrap();
+    assert_eq!(
+        issues[1].span,
+        Some(Span::new(qualified_star, qualified_star + 1))
+    );
+}
+
+#[test]
+fn wildcard_over_known_tables_and_ctes_is_not_flagged() {
+    let sql = "WITH recent AS (SELECT * FROM existing) SELECT * FROM recent; \
+               CREATE TABLE staged AS SELECT 1 AS id; SELECT * FROM staged";
+    assert!(request_issues_with_code(
+        &known_table_request(sql, None),
+        issue_codes::SCHEMA_UNKNOWN_TABLE
+    )
+    .is_empty());
+
+    // Without a schema nothing was expected to expand
+    let request = make_request("SELECT * FROM orders");
+    assert!(analyze(&request)
+        .issues
+        .iter()
+        .all(|i| i.code != issue_codes::SCHEMA_UNKNOWN_TABLE));
+}
+
+#[test]
+fn unknown_wildcard_table_warning_can_be_turned_off() {
+    let options = AnalysisOptions {
+        warn_unknown_wildcard_tables: Some(false),
+        ..Default::default()
+    };
+    assert!(request_issues_with_code(
+        &known_table_request("SELECT * FROM orders", Some(options)),
+        issue_codes::SCHEMA_UNKNOWN_TABLE
+    )
+    .is_empty());
+}
+
+fn with_trailing_newline_check(request: &AnalyzeRequest) -> AnalyzeRequest {
+    AnalyzeRequest {
+        options: Some(AnalysisOptions {
+            check_trailing_newline: Some(true),
+            ..Default::default()
+        }),
+        ..request.clone()
+    }
+}
+
+#[test]
+fn missing_trailing_newline_is_flagged_with_a_fix() {
+    let mut request = make_request("");
+    request.files = Some(vec![
+        FileSource {
+            name: "a.sql".to_string(),
+            content: "SELECT 1;\n-- done".to_string(),
+        },
+        FileSource {
+            name: "b.sql".to_string(),
+            content: "SELECT 2;\n".to_string(),
+        },
+        FileSource {
+            name: "empty.sql".to_string(),
+            content: String::new(),
+        },
+    ]);
+    let issues = request_issues_with_code(
+        &with_trailing_newline_check(&request),
+        issue_codes::MISSING_TRAILING_NEWLINE,
+    );
+
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].severity, Severity::Info);
+    assert_eq!(issues[0].source_name.as_deref(), Some("a.sql"));
+    let (fixed, _) = crate::apply_fixes("SELECT 1;\n-- done", &issues);
+    assert_eq!(fixed, "SELECT 1;\n-- done\n");
+
+    // The fixed source passes, and the check is opt-in
+    request.files.as_mut().unwrap()[0].content = fixed.into_owned();
+    assert!(request_issues_with_code(
+        &with_trailing_newline_check(&request),
+        issue_codes::MISSING_TRAILING_NEWLINE
+    )
+    .is_empty());
+    let request = make_request("SELECT 1");
+    assert!(analyze(&request)
+        .issues
+        .iter()
+        .all(|i| i.code != issue_codes::MISSING_TRAILING_NEWLINE));
+    assert_eq!(
+        request_issues_with_code(
+            &with_trailing_newline_check(&request),
+            issue_codes::MISSING_TRAILING_NEWLINE
+        )
+        .len(),
+        1
+    );
+}
+
+#[test]
+fn missing_trailing_newline_fix_matches_crlf_line_endings() {
+    let sql = "SELECT 1;\r\nSELECT 2;";
+    let issues = request_issues_with_code(
+        &with_trailing_newline_check(&make_request(sql)),
+        issue_codes::MISSING_TRAILING_NEWLINE,
+    );
+    let (fixed, applied) = crate::apply_fixes(sql, &issues);
+    assert_eq!(applied, 1);
+    assert_eq!(fixed, "SELECT 1;\r\nSELECT 2;\r\n");
+
+    // A source ending in a lone CR only needs the LF
+    let sql = "SELECT 1;\r\nSELECT 2;\r";
+    let issues = request_issues_with_code(
+        &with_trailing_newline_check(&make_request(sql)),
+        issue_codes::MISSING_TRAILING_NEWLINE,
+    );
+    assert_eq!(
+        crate::apply_fixes(sql, &issues).0,
+        "SELECT 1;\r\nSELECT 2;\r\n"
+    );
+
+    // Mixed endings get the dominant one
+    let sql = "SELECT 1;\nSELECT 2;\r\nSELECT 3;\nSELECT 4;";
+    let issues = request_issues_with_code(
+        &with_trailing_newline_check(&make_request(sql)),
+        issue_codes::MISSING_TRAILING_NEWLINE,
+    );
+    assert_eq!(crate::apply_fixes(sql, &issues).0, format!("{sql}\n"));
+}
@@ -0,0 +1,56 @@
+//! End-of-file newline check.
+//!
+//! Reports sources whose last line is not terminated by a newline, the analogue of
+//! SQLFluff's `layout.end_of_file` rule. Each issue carries a fix inserting the
+//! newline, so `apply_fixes` makes the source pass. The source as written is checked,
+//! not the rendered SQL, since that is the text a fix is applied to. The fix uses the
+//! source's dominant line ending, so a CRLF file gets `\r\n` rather than a bare `\n`.
+
+use crate::types::{issue_codes, AnalyzeRequest, Issue, Span};
+
+/// Flag each non-blank source of `request` that does not end with a newline.
+pub(crate) fn check_trailing_newline(request: &AnalyzeRequest) -> Vec<Issue> {
+    let files = request.files.iter().flatten();
+    let sources = files
+        .map(|file| (Some(file.name.as_str()), file.content.as_str()))
+        .chain(std::iter::once((
+            request.source_name.as_deref(),
+            request.sql.as_str(),
+        )));
+
+    let mut issues = Vec::new();
+    for (source_name, sql) in sources {
+        if sql.trim().is_empty() || sql.ends_with('\n') {
+            continue;
+        }
+        let line_ending = if sql.ends_with('\r') {
+            // Completes a final CRLF cut short
+            "\n"
+        } else {
+            dominant_line_ending(sql)
+        };
+        let end = Span::new(sql.len(), sql.len());
+        let mut issue = Issue::info(
+            issue_codes::MISSING_TRAILING_NEWLINE,
+            "File does not end with a newline",
+        )
+        .with_span(end)
+        .with_fix(end, line_ending);
+        if let Some(name) = source_name {
+            issue = issue.with_source_name(name);
+        }
+        issues.push(issue);
+    }
+    issues
+}
+
+/// `\r\n` when most lines of `sql` end with it, otherwise `\n`; ties go to `\n`.
+fn dominant_line_ending(sql: &str) -> &'static str {
+    let crlf = sql.matches("\r\n").count();
+    let lf = sql.matches('\n').count() - crlf;
+    if crlf > lf {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
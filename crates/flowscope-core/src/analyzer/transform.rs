@@ -3,8 +3,178 @@
 //! This module provides functions to transform lineage graphs after analysis,
 //! such as filtering out certain node types while preserving connectivity.
 
-use crate::types::{Edge, EdgeType, NodeType, StatementLineage};
+use crate::parser::redact_literals;
+use crate::types::{Dialect, Edge, EdgeType, NodeType, StatementLineage};
+use regex::{Regex, RegexBuilder};
+use sqlparser::ast::{visit_expressions, Expr};
+use sqlparser::parser::Parser;
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+/// Compiled table-name patterns for excluding tables from lineage output.
+///
+/// Patterns are globs (`*` and `?` wildcards) unless prefixed with `re:`, in which
+/// case the remainder is a regular expression. Matching is case-insensitive and a
+/// pattern may match either the qualified name or the bare table name.
+///
+/// Matching runs in time linear in the table name (the `regex` crate does not
+/// backtrack), so hostile patterns can only cost compile time and memory, which
+/// [`PATTERN_SIZE_LIMIT`] bounds.
+pub(crate) struct TableFilter {
+    patterns: Vec<Regex>,
+}
+
+/// Upper bound in bytes on a compiled pattern, so a request cannot make the server
+/// build a huge automaton (`re:\w{1000}{1000}`). Patterns over it count as invalid.
+const PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+impl TableFilter {
+    /// Compile `patterns`, returning the filter and any patterns that failed to compile.
+    pub(crate) fn new(patterns: &[String]) -> (Self, Vec<String>) {
+        let mut compiled = Vec::new();
+        let mut invalid = Vec::new();
+        for pattern in patterns {
+            let source = match pattern.strip_prefix("re:") {
+                Some(regex) => regex.to_string(),
+                None => glob_to_regex(pattern),
+            };
+            let regex = RegexBuilder::new(&source)
+                .case_insensitive(true)
+                .size_limit(PATTERN_SIZE_LIMIT)
+                .dfa_size_limit(PATTERN_SIZE_LIMIT)
+                .build();
+            match regex {
+                Ok(regex) => compiled.push(regex),
+                Err(_) => invalid.push(pattern.clone()),
+            }
+        }
+        (Self { patterns: compiled }, invalid)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Returns true if the qualified table name, or its last segment, matches a pattern.
+    pub(crate) fn matches(&self, qualified_name: &str) -> bool {
+        let bare_name = qualified_name.rsplit('.').next().unwrap_or(qualified_name);
+        self.patterns
+            .iter()
+            .any(|re| re.is_match(qualified_name) || re.is_match(bare_name))
+    }
+}
+
+/// Translate a `*`/`?` glob into an anchored regular expression.
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    for ch in glob.chars() {
+        match ch {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            _ => regex.push_str(&regex::escape(&ch.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+/// Remove tables matching `filter` (and the columns they own) from lineage.
+///
+/// Edges touching a removed node are dropped rather than bypassed, since ignored
+/// tables are noise rather than intermediate steps. Returns the qualified names of
+/// the removed tables.
+pub(crate) fn filter_ignored_tables(
+    lineage: &mut StatementLineage,
+    filter: &TableFilter,
+) -> Vec<String> {
+    let mut removed_tables = Vec::new();
+    let mut removable_ids: HashSet<String> = HashSet::new();
+    for node in &lineage.nodes {
+        if !node.node_type.is_table_or_view() {
+            continue;
+        }
+        let name = node.qualified_name.as_ref().unwrap_or(&node.label);
+        if filter.matches(name) {
+            removed_tables.push(name.to_string());
+            removable_ids.insert(node.id.to_string());
+        }
+    }
+
+    if removable_ids.is_empty() {
+        return removed_tables;
+    }
+
+    for edge in &lineage.edges {
+        if edge.edge_type == EdgeType::Ownership && removable_ids.contains(edge.from.as_ref()) {
+            removable_ids.insert(edge.to.to_string());
+        }
+    }
+
+    lineage
+        .nodes
+        .retain(|n| !removable_ids.contains(n.id.as_ref()));
+    lineage.edges.retain(|e| {
+        !removable_ids.contains(e.from.as_ref()) && !removable_ids.contains(e.to.as_ref())
+    });
+    removed_tables
+}
+
+/// Replace string and number literals in the SQL text a statement's lineage carries:
+/// node and edge expressions, filter predicates, join conditions and resolved SQL.
+pub(crate) fn redact_statement_literals(lineage: &mut StatementLineage, dialect: Dialect) {
+    let redact_arc = |text: &mut Option<Arc<str>>| {
+        if let Some(value) = text {
+            if let Cow::Owned(redacted) = redact_literals(value, dialect) {
+                *value = redacted.into();
+            }
+        }
+    };
+
+    for node in &mut lineage.nodes {
+        redact_arc(&mut node.expression);
+        redact_arc(&mut node.join_condition);
+        for filter in &mut node.filters {
+            if let Cow::Owned(redacted) = redact_literals(&filter.expression, dialect) {
+                filter.expression = redacted;
+            }
+        }
+    }
+    for edge in &mut lineage.edges {
+        redact_arc(&mut edge.expression);
+        redact_arc(&mut edge.join_condition);
+    }
+    if let Some(sql) = &mut lineage.resolved_sql {
+        if let Cow::Owned(redacted) = redact_literals(sql, dialect) {
+            *sql = redacted;
+        }
+    }
+}
+
+/// Lowercased names of the columns a filter predicate reads, or `None` if it does
+/// not parse in `dialect`.
+fn predicate_columns(expression: &str, dialect: Dialect) -> Option<HashSet<String>> {
+    let sqlparser_dialect = dialect.to_sqlparser_dialect();
+    let expr = Parser::new(sqlparser_dialect.as_ref())
+        .try_with_sql(expression)
+        .ok()?
+        .parse_expr()
+        .ok()?;
+    let mut columns = HashSet::new();
+    let _ = visit_expressions(&expr, |expr| {
+        let column = match expr {
+            Expr::Identifier(ident) => Some(ident),
+            Expr::CompoundIdentifier(parts) => parts.last(),
+            _ => None,
+        };
+        if let Some(column) = column {
+            columns.insert(column.value.to_lowercase());
+        }
+        ControlFlow::<()>::Continue(())
+    });
+    Some(columns)
+}
 
 /// Remove CTE nodes (and their columns) from lineage and create bypass edges.
 ///
@@ -17,7 +187,27 @@ use std::collections::{HashMap, HashSet};
 /// Column-level lineage for CTE-owned columns is also bypassed by removing
 /// column nodes owned by CTEs and reconnecting their incoming and outgoing
 /// edges to preserve dataflow.
+///
+/// Filter predicates attached to a removed CTE are dropped with it; see
+/// [`inline_cte_nodes`] to keep them.
 pub fn filter_cte_nodes(lineage: &mut StatementLineage) {
+    collapse_cte_nodes(lineage, None);
+}
+
+/// Remove CTE nodes like [`filter_cte_nodes`], keeping their filter predicates.
+///
+/// A predicate moves to each table or view the CTE reads from that owns every
+/// column it reads, so `FROM cte WHERE status = 'paid'` shows up on the underlying
+/// source. A predicate the sources cannot hold, such as one on an aggregate the CTE
+/// computes, one naming no column, or one that does not parse in `dialect`, stays
+/// with the nodes the CTE feeds instead.
+pub(crate) fn inline_cte_nodes(lineage: &mut StatementLineage, dialect: Dialect) {
+    collapse_cte_nodes(lineage, Some(dialect));
+}
+
+/// Removes CTE nodes, moving their filters when `keep_filters` gives the dialect to
+/// parse them in.
+fn collapse_cte_nodes(lineage: &mut StatementLineage, keep_filters: Option<Dialect>) {
     // 1. Identify CTE node IDs (owned strings for simplicity)
     let mut removable_ids: HashSet<String> = lineage
         .nodes
@@ -42,7 +232,11 @@ pub fn filter_cte_nodes(lineage: &mut StatementLineage) {
     let mut outgoing: HashMap<String, Vec<String>> = HashMap::new();
 
     for edge in &lineage.edges {
-        if edge.edge_type == EdgeType::Ownership {
+        // A recursive CTE's loop onto itself leads nowhere else
+        if matches!(
+            edge.edge_type,
+            EdgeType::Ownership | EdgeType::RecursiveSelfReference
+        ) {
             continue;
         }
         incoming
@@ -133,6 +327,7 @@ pub fn filter_cte_nodes(lineage: &mut StatementLineage) {
                 EdgeType::Derivation => 2u8.hash(state),
                 EdgeType::JoinDependency => 3u8.hash(state),
                 EdgeType::CrossStatement => 4u8.hash(state),
+                EdgeType::RecursiveSelfReference => 5u8.hash(state),
             }
             self.expression.hash(state);
             self.operation.hash(state);
@@ -163,7 +358,10 @@ pub fn filter_cte_nodes(lineage: &mut StatementLineage) {
     let mut outgoing_edges: HashMap<String, Vec<&Edge>> = HashMap::new();
 
     for edge in &lineage.edges {
-        if edge.edge_type == EdgeType::Ownership {
+        if matches!(
+            edge.edge_type,
+            EdgeType::Ownership | EdgeType::RecursiveSelfReference
+        ) {
             continue;
         }
         incoming_edges
@@ -295,7 +493,113 @@ pub fn filter_cte_nodes(lineage: &mut StatementLineage) {
     // 7. Create the final edge list
     let new_edges: Vec<Edge> = bypass_edges.into_values().collect();
 
-    // 8. Remove CTE and CTE-owned column nodes and update edges
+    // 8. When inlining, move the filters of removed CTEs to the tables they read
+    // from, through chained CTEs, so predicates on a CTE's rows survive the collapse.
+    // A predicate only moves to a table that owns every column it reads; the others
+    // (e.g. on an aggregate the CTE computes) stay with the nodes the CTE feeds.
+    if let Some(dialect) = keep_filters {
+        let mut owned_columns: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut owners: HashMap<&str, &str> = HashMap::new();
+        for edge in &lineage.edges {
+            if edge.edge_type == EdgeType::Ownership {
+                owned_columns
+                    .entry(edge.from.as_ref())
+                    .or_default()
+                    .push(edge.to.as_ref());
+                owners.insert(edge.to.as_ref(), edge.from.as_ref());
+            }
+        }
+        let node_types: HashMap<&str, NodeType> = lineage
+            .nodes
+            .iter()
+            .map(|n| (n.id.as_ref(), n.node_type))
+            .collect();
+        let column_labels: HashMap<&str, String> = lineage
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == NodeType::Column)
+            .map(|n| (n.id.as_ref(), n.label.to_lowercase()))
+            .collect();
+        let columns_of = |table: &str| -> HashSet<&str> {
+            owned_columns
+                .get(table)
+                .into_iter()
+                .flatten()
+                .filter_map(|column| column_labels.get(column).map(String::as_str))
+                .collect()
+        };
+        let mut moved_filters = Vec::new();
+        for cte in &lineage.nodes {
+            if cte.node_type != NodeType::Cte || cte.filters.is_empty() {
+                continue;
+            }
+            let mut upstream =
+                find_sources(&cte.id, &removable_ids, &incoming, &mut HashSet::new());
+            for column in owned_columns.get(cte.id.as_ref()).into_iter().flatten() {
+                for source in find_sources(column, &removable_ids, &incoming, &mut HashSet::new()) {
+                    if let Some(owner) = owners.get(source.as_str()) {
+                        upstream.push(owner.to_string());
+                    }
+                }
+            }
+            let mut seen = HashSet::new();
+            let tables: Vec<String> = upstream
+                .into_iter()
+                .filter(|table| {
+                    node_types
+                        .get(table.as_str())
+                        .is_some_and(|node_type| node_type.is_table_or_view())
+                })
+                .filter(|table| seen.insert(table.clone()))
+                .collect();
+            let mut downstream =
+                find_targets(&cte.id, &removable_ids, &outgoing, &mut HashSet::new());
+            for column in owned_columns.get(cte.id.as_ref()).into_iter().flatten() {
+                for target in find_targets(column, &removable_ids, &outgoing, &mut HashSet::new()) {
+                    if let Some(owner) = owners.get(target.as_str()) {
+                        downstream.push(owner.to_string());
+                    }
+                }
+            }
+            let mut seen = HashSet::new();
+            downstream.retain(|node| seen.insert(node.clone()));
+
+            for filter in &cte.filters {
+                let referenced = predicate_columns(&filter.expression, dialect).unwrap_or_default();
+                let mut moved = false;
+                for table in &tables {
+                    let columns = columns_of(table);
+                    if !referenced.is_empty()
+                        && referenced
+                            .iter()
+                            .all(|column| columns.contains(column.as_str()))
+                    {
+                        moved_filters.push((table.clone(), filter.clone()));
+                        moved = true;
+                    }
+                }
+                if !moved {
+                    for target in &downstream {
+                        moved_filters.push((target.clone(), filter.clone()));
+                    }
+                }
+            }
+        }
+        for (id, filter) in moved_filters {
+            let Some(node) = lineage.nodes.iter_mut().find(|n| *n.id == *id) else {
+                continue;
+            };
+            let duplicate = node.filters.iter().any(|existing| {
+                existing.expression == filter.expression
+                    && existing.clause_type == filter.clause_type
+            });
+            if !duplicate {
+                node.filters.push(filter);
+            }
+        }
+    }
+
+    // 9. Remove CTE and CTE-owned column nodes and update edges
     lineage
         .nodes
         .retain(|n| !removable_ids.contains(n.id.as_ref()));
@@ -305,7 +609,7 @@ pub fn filter_cte_nodes(lineage: &mut StatementLineage) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::Node;
+    use crate::types::{FilterClauseType, FilterPredicate, Node};
 
     fn make_table(id: &str) -> Node {
         Node::table(id, id.rsplit(':').next().unwrap_or(id))
@@ -323,6 +627,37 @@ mod tests {
         Edge::data_flow(format!("edge_{}_{}", from, to), from, to)
     }
 
+    #[test]
+    fn test_table_filter_matches_globs_and_regex() {
+        let (filter, invalid) = TableFilter::new(&[
+            "etl_*".to_string(),
+            "re:^audit\\.".to_string(),
+            "re:(".to_string(),
+        ]);
+        assert_eq!(invalid, vec!["re:(".to_string()]);
+        assert!(filter.matches("etl_log"));
+        assert!(filter.matches("staging.ETL_LOG"));
+        assert!(filter.matches("audit.events"));
+        assert!(!filter.matches("orders"));
+        assert!(!filter.matches("my_etl_log"));
+    }
+
+    #[test]
+    fn test_table_filter_bounds_pathological_patterns() {
+        let (filter, invalid) = TableFilter::new(&[
+            "re:^(a+)+$".to_string(),
+            "re:(?s)^(.*a){12}$".to_string(),
+            "re:\\w{1000}{1000}".to_string(),
+        ]);
+        assert_eq!(invalid, vec!["re:\\w{1000}{1000}".to_string()]);
+
+        // Inputs that make backtracking engines take exponential time
+        let name = format!("{}!", "a".repeat(50_000));
+        let started = std::time::Instant::now();
+        assert!(!filter.matches(&name));
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+
     #[test]
     fn test_single_cte_bypass() {
         // A → CTE → B should become A → B
@@ -387,6 +722,131 @@ mod tests {
         assert_eq!(lineage.edges[0].to.as_ref(), "table:b");
     }
 
+    fn where_filter(expression: &str) -> FilterPredicate {
+        FilterPredicate {
+            expression: expression.to_string(),
+            clause_type: FilterClauseType::Where,
+        }
+    }
+
+    fn filters_of(lineage: &StatementLineage, id: &str) -> Vec<String> {
+        let node = lineage.nodes.iter().find(|n| &*n.id == id).unwrap();
+        node.filters.iter().map(|f| f.expression.clone()).collect()
+    }
+
+    #[test]
+    fn test_chained_cte_filters_move_to_source_table() {
+        // A.amount → CTE1.amount → CTE2.amount → B.amount with a filter on CTE2
+        let mut filtered_cte = make_cte("cte:temp2");
+        filtered_cte.filters.push(where_filter("amount > 10"));
+        let mut lineage = StatementLineage {
+            statement_index: 0,
+            statement_type: "SELECT".to_string(),
+            source_name: None,
+            nodes: vec![
+                make_table("table:a"),
+                make_column("column:a_amount", "amount"),
+                make_cte("cte:temp1"),
+                make_column("column:temp1_amount", "amount"),
+                filtered_cte,
+                make_column("column:temp2_amount", "amount"),
+                make_table("table:b"),
+                make_column("column:b_amount", "amount"),
+            ],
+            edges: vec![
+                Edge::ownership("own_a", "table:a", "column:a_amount"),
+                Edge::ownership("own_temp1", "cte:temp1", "column:temp1_amount"),
+                Edge::ownership("own_temp2", "cte:temp2", "column:temp2_amount"),
+                Edge::ownership("own_b", "table:b", "column:b_amount"),
+                make_edge("table:a", "cte:temp1"),
+                make_edge("cte:temp1", "cte:temp2"),
+                make_edge("cte:temp2", "table:b"),
+                make_edge("column:a_amount", "column:temp1_amount"),
+                make_edge("column:temp1_amount", "column:temp2_amount"),
+                make_edge("column:temp2_amount", "column:b_amount"),
+            ],
+            span: None,
+            join_count: 0,
+            complexity_score: 1,
+            resolved_sql: None,
+        };
+
+        inline_cte_nodes(&mut lineage, Dialect::Generic);
+
+        assert_eq!(filters_of(&lineage, "table:a"), ["amount > 10"]);
+        assert!(filters_of(&lineage, "table:b").is_empty());
+    }
+
+    /// A.amount → CTE.amount, CTE.total → B.total, with `filters` on the CTE.
+    fn sums_lineage(filters: &[&str]) -> StatementLineage {
+        let mut cte = make_cte("cte:sums");
+        cte.filters = filters.iter().map(|f| where_filter(f)).collect();
+        StatementLineage {
+            statement_index: 0,
+            statement_type: "SELECT".to_string(),
+            source_name: None,
+            nodes: vec![
+                make_table("table:a"),
+                make_column("column:a_amount", "amount"),
+                cte,
+                make_column("column:cte_amount", "amount"),
+                make_column("column:cte_total", "total"),
+                make_table("table:b"),
+                make_column("column:b_total", "total"),
+            ],
+            edges: vec![
+                Edge::ownership("own_a", "table:a", "column:a_amount"),
+                Edge::ownership("own_cte_amount", "cte:sums", "column:cte_amount"),
+                Edge::ownership("own_cte_total", "cte:sums", "column:cte_total"),
+                Edge::ownership("own_b", "table:b", "column:b_total"),
+                make_edge("column:a_amount", "column:cte_amount"),
+                make_edge("column:a_amount", "column:cte_total"),
+                make_edge("column:cte_total", "column:b_total"),
+            ],
+            span: None,
+            join_count: 0,
+            complexity_score: 1,
+            resolved_sql: None,
+        }
+    }
+
+    #[test]
+    fn test_cte_filter_on_computed_column_stays_downstream() {
+        // `total` is not a column of A
+        let mut lineage = sums_lineage(&["amount > 0", "s.total > 10"]);
+        inline_cte_nodes(&mut lineage, Dialect::Generic);
+
+        assert_eq!(filters_of(&lineage, "table:a"), ["amount > 0"]);
+        assert_eq!(filters_of(&lineage, "table:b"), ["s.total > 10"]);
+    }
+
+    #[test]
+    fn test_cte_filter_literal_is_not_a_column() {
+        // 'amount' is a string, and A has no `kind` column
+        let mut lineage = sums_lineage(&["kind = 'amount'"]);
+        inline_cte_nodes(&mut lineage, Dialect::Generic);
+
+        assert!(filters_of(&lineage, "table:a").is_empty());
+        assert_eq!(filters_of(&lineage, "table:b"), ["kind = 'amount'"]);
+    }
+
+    #[test]
+    fn test_cte_filter_naming_no_known_column_stays_downstream() {
+        let mut lineage = sums_lineage(&["1 = 1", "missing > 0"]);
+        inline_cte_nodes(&mut lineage, Dialect::Generic);
+
+        assert!(filters_of(&lineage, "table:a").is_empty());
+        assert_eq!(filters_of(&lineage, "table:b"), ["1 = 1", "missing > 0"]);
+    }
+
+    #[test]
+    fn test_hiding_ctes_drops_their_filters() {
+        let mut lineage = sums_lineage(&["amount > 0", "total > 10"]);
+        filter_cte_nodes(&mut lineage);
+
+        assert!(lineage.nodes.iter().all(|n| n.filters.is_empty()));
+    }
+
     #[test]
     fn test_fan_in_fan_out() {
         // A → CTE, B → CTE, CTE → C, CTE → D
@@ -4,12 +4,13 @@
 //! and building lineage graphs. It separates traversal logic (the `Visitor` trait)
 //! from analysis logic (the `LineageVisitor` implementation).
 
-use super::context::StatementContext;
+use super::context::{ColumnRef, StatementContext};
 use super::expression::ExpressionAnalyzer;
 use super::helpers::{
     alias_visibility_warning, find_cte_definition_span, find_derived_table_alias_span,
     generate_node_id,
 };
+use super::query::OutputColumnParams;
 use super::select_analyzer::SelectAnalyzer;
 use super::Analyzer;
 use crate::types::{issue_codes, Issue, Node, NodeType, Span};
@@ -184,6 +185,118 @@ impl<'a, 'b> LineageVisitor<'a, 'b> {
         self.locate_span(identifier, find_derived_table_alias_span)
     }
 
+    /// Models an aliased `UNNEST` like a derived table whose columns are derived from
+    /// the unnested array expressions.
+    ///
+    /// `UNNEST(a, b) AS t(x, y)` names one column per array; with a bare alias
+    /// (`UNNEST(tags) AS tag`) the single column takes the alias name. `WITH OFFSET`
+    /// and `WITH ORDINALITY` add a position column that has no upstream column.
+    fn visit_aliased_unnest(
+        &mut self,
+        array_exprs: &[Expr],
+        alias: &TableAlias,
+        with_offset: Option<Option<&Ident>>,
+        with_ordinality: bool,
+    ) {
+        let name = alias.name.to_string();
+        let span = self.locate_derived_alias_span(&name);
+        let node_id = self.ctx.add_node(Node {
+            id: generate_node_id("derived", &name),
+            node_type: NodeType::Cte,
+            label: name.clone().into(),
+            qualified_name: Some(name.clone().into()),
+            expression: None,
+            span,
+            metadata: None,
+            resolution_source: None,
+            filters: Vec::new(),
+            join_type: None,
+            join_condition: None,
+            aggregation: None,
+        });
+        self.ctx
+            .cte_node_to_name
+            .insert(node_id.clone(), name.clone());
+
+        let mut column_names = alias.columns.iter().map(|column| column.name.value.clone());
+        let value_names: Vec<String> = if alias.columns.is_empty() && array_exprs.len() == 1 {
+            vec![alias.name.value.clone()]
+        } else {
+            column_names.by_ref().take(array_exprs.len()).collect()
+        };
+
+        let projection_checkpoint = self.ctx.projection_checkpoint();
+        for (expr, column_name) in array_exprs.iter().zip(value_names) {
+            for table_canonical in self.unnest_source_tables(expr) {
+                self.analyzer
+                    .add_source_table(self.ctx, &table_canonical, Some(&node_id));
+            }
+            let sources = ExpressionAnalyzer::new(self.analyzer, self.ctx)
+                .extract_column_refs_with_warning(expr);
+            self.add_unnest_column(
+                &node_id,
+                column_name,
+                sources,
+                Some(format!("UNNEST({expr})")),
+            );
+        }
+        if with_ordinality {
+            let column_name = column_names
+                .next()
+                .unwrap_or_else(|| "ordinality".to_string());
+            self.add_unnest_column(&node_id, column_name, Vec::new(), None);
+        }
+        if let Some(offset_alias) = with_offset {
+            let column_name =
+                offset_alias.map_or_else(|| "offset".to_string(), |a| a.value.clone());
+            self.add_unnest_column(&node_id, column_name, Vec::new(), None);
+        }
+        let columns = self.ctx.take_output_columns_since(projection_checkpoint);
+
+        self.ctx.register_table_in_scope(name.clone(), node_id);
+        self.ctx.register_alias_in_scope(name.clone(), name.clone());
+        self.ctx.aliased_subquery_columns.insert(name, columns);
+    }
+
+    /// Adds a column produced by an `UNNEST` to its node.
+    fn add_unnest_column(
+        &mut self,
+        node_id: &str,
+        name: String,
+        sources: Vec<ColumnRef>,
+        expression: Option<String>,
+    ) {
+        self.analyzer.add_output_column_with_aggregation(
+            self.ctx,
+            OutputColumnParams {
+                name,
+                sources,
+                expression,
+                data_type: None,
+                nullable: None,
+                target_node: Some(node_id.to_string()),
+                approximate: false,
+                aggregation: None,
+            },
+        );
+    }
+
+    /// Returns the tables owning the columns an `UNNEST` array expression reads.
+    fn unnest_source_tables(&mut self, expr: &Expr) -> Vec<String> {
+        let column_refs =
+            ExpressionAnalyzer::new(self.analyzer, self.ctx).extract_column_refs_with_warning(expr);
+        column_refs
+            .iter()
+            .filter_map(|col_ref| {
+                self.analyzer.resolve_column_table(
+                    self.ctx,
+                    col_ref.table.as_deref(),
+                    &col_ref.column,
+                )
+            })
+            .collect()
+    }
+
     /// Extract the expression from a JoinOperator's constraint, if any.
     fn extract_join_constraint_expr(op: &ast::JoinOperator) -> Option<&Expr> {
         let constraint = match op {
@@ -487,6 +600,25 @@ impl<'a, 'b> LineageVisitor<'a, 'b> {
         ));
     }
 
+    /// Visits a SELECT, checking the names in the ORDER BY of its enclosing query
+    /// (if any) for ambiguity.
+    fn visit_select_ordered(&mut self, select: &Select, order_by: Option<&[ast::OrderByExpr]>) {
+        self.ctx.push_scope();
+        for table_with_joins in &select.from {
+            self.visit_table_with_joins(table_with_joins);
+        }
+        if self.analyzer.column_lineage_enabled {
+            let output_node = self.ctx.output_node_id().map(|node_id| node_id.to_string());
+            let target_node = self.target_node.clone().or(output_node);
+            let mut select_analyzer = SelectAnalyzer::new(self.analyzer, self.ctx, target_node);
+            select_analyzer.analyze(select);
+            if let Some(order_by) = order_by {
+                select_analyzer.check_ambiguous_order_by(select, order_by);
+            }
+        }
+        self.ctx.pop_scope();
+    }
+
     /// Analyzes ORDER BY clause for alias visibility warnings.
     ///
     /// Checks if aliases from the SELECT list are used in ORDER BY expressions
@@ -569,7 +701,20 @@ impl<'a, 'b> Visitor for LineageVisitor<'a, 'b> {
                     .insert(cte.alias.name.to_string(), columns);
             }
         }
-        self.visit_set_expr(&query.body);
+        let order_exprs = query
+            .order_by
+            .as_ref()
+            .and_then(|order_by| match &order_by.kind {
+                ast::OrderByKind::Expressions(exprs) => Some(exprs.as_slice()),
+                ast::OrderByKind::All(_) => None,
+            });
+        match (query.body.as_ref(), order_exprs) {
+            // ORDER BY names are checked against the SELECT while its FROM scope is live
+            (SetExpr::Select(select), Some(order_exprs)) => {
+                self.visit_select_ordered(select, Some(order_exprs))
+            }
+            _ => self.visit_set_expr(&query.body),
+        }
 
         // Analyze ORDER BY for alias visibility warnings
         if let Some(order_by) = &query.order_by {
@@ -619,17 +764,7 @@ impl<'a, 'b> Visitor for LineageVisitor<'a, 'b> {
     }
 
     fn visit_select(&mut self, select: &Select) {
-        self.ctx.push_scope();
-        for table_with_joins in &select.from {
-            self.visit_table_with_joins(table_with_joins);
-        }
-        if self.analyzer.column_lineage_enabled {
-            let output_node = self.ctx.output_node_id().map(|node_id| node_id.to_string());
-            let target_node = self.target_node.clone().or(output_node);
-            let mut select_analyzer = SelectAnalyzer::new(self.analyzer, self.ctx, target_node);
-            select_analyzer.analyze(select);
-        }
-        self.ctx.pop_scope();
+        self.visit_select_ordered(select, None);
     }
 
     fn visit_table_with_joins(&mut self, table_with_joins: &TableWithJoins) {
@@ -648,6 +783,7 @@ impl<'a, 'b> Visitor for LineageVisitor<'a, 'b> {
 
                 // Extract implied FK relationships from equality conditions
                 self.record_join_fk_relationships(expr);
+                self.check_join_key_types(expr);
             }
 
             self.ctx.current_join_info.join_type = None;
@@ -802,28 +938,27 @@ impl<'a, 'b> Visitor for LineageVisitor<'a, 'b> {
                 );
             }
             TableFactor::UNNEST {
-                array_exprs, alias, ..
+                array_exprs,
+                alias: Some(alias),
+                with_offset,
+                with_offset_alias,
+                with_ordinality,
             } => {
-                // UNNEST expands array columns into rows. Extract column references
-                // from the array expressions and resolve them to their source tables.
+                self.visit_aliased_unnest(
+                    array_exprs,
+                    alias,
+                    with_offset.then_some(with_offset_alias.as_ref()),
+                    *with_ordinality,
+                );
+            }
+            TableFactor::UNNEST { array_exprs, .. } => {
+                // UNNEST expands array columns into rows. Without an alias its columns
+                // cannot be referenced, so only the source tables are recorded.
                 for expr in array_exprs {
-                    let mut ea = ExpressionAnalyzer::new(self.analyzer, self.ctx);
-                    let column_refs = ea.extract_column_refs_with_warning(expr);
-                    for col_ref in &column_refs {
-                        // Resolve the column to its source table and add it as a data source
-                        if let Some(table_canonical) = self.analyzer.resolve_column_table(
-                            self.ctx,
-                            col_ref.table.as_deref(),
-                            &col_ref.column,
-                        ) {
-                            self.add_source_table(&table_canonical);
-                        }
+                    for table_canonical in self.unnest_source_tables(expr) {
+                        self.add_source_table(&table_canonical);
                     }
                 }
-                if let Some(a) = alias {
-                    self.ctx
-                        .register_subquery_alias_in_scope(a.name.to_string());
-                }
             }
             _ => {}
         }
@@ -1,7 +1,7 @@
 use sqlparser::keywords::Keyword;
 use sqlparser::tokenizer::{Token, TokenWithSpan, Tokenizer, Word};
 
-use crate::analyzer::helpers::line_col_to_offset;
+use crate::analyzer::helpers::{identifier_quote_chars, line_col_to_offset};
 use crate::analyzer::schema_registry::SchemaRegistry;
 use crate::types::{
     AstContext, CompletionClause, CompletionColumn, CompletionContext, CompletionItem,
@@ -1207,22 +1207,25 @@ fn is_identifier_char(ch: char) -> bool {
 
 /// Extracts the last identifier from a SQL fragment.
 ///
-/// Handles both quoted identifiers (e.g., `"My Table"`) and unquoted identifiers.
+/// Handles both identifiers quoted the way `dialect` quotes them (e.g., `"My Table"`,
+/// `` `My Table` `` on MySQL, `[My Table]` on SQL Server) and unquoted identifiers.
 /// Returns `None` if the source is empty or contains only non-identifier characters.
 ///
 /// # Examples
 /// - `"SELECT users"` → `Some("users")`
 /// - `"\"My Table\""` → `Some("My Table")`
 /// - `"schema.table"` → `Some("table")`
-fn extract_last_identifier(source: &str) -> Option<String> {
+fn extract_last_identifier(source: &str, dialect: Dialect) -> Option<String> {
     let trimmed = source.trim_end();
     if trimmed.is_empty() {
         return None;
     }
 
-    if let Some(stripped) = trimmed.strip_suffix('"') {
-        if let Some(start) = stripped.rfind('"') {
-            return Some(stripped[start + 1..].to_string());
+    for (open, close) in identifier_quote_chars(dialect) {
+        if let Some(stripped) = trimmed.strip_suffix(close) {
+            if let Some(start) = stripped.rfind(open) {
+                return Some(stripped[start + open.len_utf8()..].to_string());
+            }
         }
     }
 
@@ -1255,7 +1258,7 @@ fn extract_last_identifier(source: &str) -> Option<String> {
 ///
 /// # Safety
 /// Returns `None` if `cursor_offset` is out of bounds or not on a valid UTF-8 boundary.
-fn extract_qualifier(sql: &str, cursor_offset: usize) -> Option<String> {
+fn extract_qualifier(sql: &str, cursor_offset: usize, dialect: Dialect) -> Option<String> {
     if cursor_offset == 0 || cursor_offset > sql.len() {
         return None;
     }
@@ -1272,7 +1275,7 @@ fn extract_qualifier(sql: &str, cursor_offset: usize) -> Option<String> {
 
     if let Some(stripped) = trimmed.strip_suffix('.') {
         let before_dot = stripped.trim_end();
-        return extract_last_identifier(before_dot);
+        return extract_last_identifier(before_dot, dialect);
     }
 
     if let Some(dot_idx) = trimmed.rfind('.') {
@@ -1280,7 +1283,7 @@ fn extract_qualifier(sql: &str, cursor_offset: usize) -> Option<String> {
         let dot_after_space = whitespace_idx.is_none_or(|space| dot_idx > space);
         if dot_after_space {
             let before_dot = trimmed[..dot_idx].trim_end();
-            return extract_last_identifier(before_dot);
+            return extract_last_identifier(before_dot, dialect);
         }
     }
 
@@ -1789,7 +1792,7 @@ pub fn completion_items(request: &CompletionRequest) -> CompletionItemsResult {
     // SchemaRegistry::new returns (registry, issues). Issues are intentionally discarded
     // because completion should work even with schema validation warnings.
     let (registry, _schema_issues) = SchemaRegistry::new(request.schema.as_ref(), request.dialect);
-    let qualifier = extract_qualifier(&request.sql, request.cursor_offset);
+    let qualifier = extract_qualifier(&request.sql, request.cursor_offset, request.dialect);
     let qualifier_resolution = qualifier.as_ref().and_then(|value| {
         resolve_qualifier(
             value,
@@ -2231,12 +2234,14 @@ mod tests {
                             data_type: Some("integer".to_string()),
                             is_primary_key: None,
                             foreign_key: None,
+                            nullable: None,
                         },
                         ColumnSchema {
                             name: "name".to_string(),
                             data_type: Some("varchar".to_string()),
                             is_primary_key: None,
                             foreign_key: None,
+                            nullable: None,
                         },
                     ],
                 },
@@ -2250,12 +2255,14 @@ mod tests {
                             data_type: Some("integer".to_string()),
                             is_primary_key: None,
                             foreign_key: None,
+                            nullable: None,
                         },
                         ColumnSchema {
                             name: "user_id".to_string(),
                             data_type: Some("integer".to_string()),
                             is_primary_key: None,
                             foreign_key: None,
+                            nullable: None,
                         },
                     ],
                 },
@@ -2307,12 +2314,14 @@ mod tests {
                             data_type: Some("integer".to_string()),
                             is_primary_key: None,
                             foreign_key: None,
+                            nullable: None,
                         },
                         ColumnSchema {
                             name: "name".to_string(),
                             data_type: Some("varchar".to_string()),
                             is_primary_key: None,
                             foreign_key: None,
+                            nullable: None,
                         },
                     ],
                 },
@@ -2325,6 +2334,7 @@ mod tests {
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     }],
                 },
             ],
@@ -2367,6 +2377,7 @@ mod tests {
                     data_type: Some("varchar".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
         };
@@ -2401,13 +2412,16 @@ mod tests {
 
     #[test]
     fn test_extract_last_identifier_simple() {
-        assert_eq!(extract_last_identifier("users"), Some("users".to_string()));
         assert_eq!(
-            extract_last_identifier("foo_bar"),
+            extract_last_identifier("users", Dialect::Generic),
+            Some("users".to_string())
+        );
+        assert_eq!(
+            extract_last_identifier("foo_bar", Dialect::Generic),
             Some("foo_bar".to_string())
         );
         assert_eq!(
-            extract_last_identifier("table123"),
+            extract_last_identifier("table123", Dialect::Generic),
             Some("table123".to_string())
         );
     }
@@ -2415,12 +2429,15 @@ mod tests {
     #[test]
     fn test_extract_last_identifier_with_spaces() {
         assert_eq!(
-            extract_last_identifier("SELECT users"),
+            extract_last_identifier("SELECT users", Dialect::Generic),
+            Some("users".to_string())
+        );
+        assert_eq!(
+            extract_last_identifier("users ", Dialect::Generic),
             Some("users".to_string())
         );
-        assert_eq!(extract_last_identifier("users "), Some("users".to_string()));
         assert_eq!(
-            extract_last_identifier("  users  "),
+            extract_last_identifier("  users  ", Dialect::Generic),
             Some("users".to_string())
         );
     }
@@ -2428,39 +2445,45 @@ mod tests {
     #[test]
     fn test_extract_last_identifier_quoted() {
         assert_eq!(
-            extract_last_identifier("\"MyTable\""),
+            extract_last_identifier("\"MyTable\"", Dialect::Generic),
             Some("MyTable".to_string())
         );
         assert_eq!(
-            extract_last_identifier("SELECT \"My Table\""),
+            extract_last_identifier("SELECT \"My Table\"", Dialect::Generic),
             Some("My Table".to_string())
         );
         assert_eq!(
-            extract_last_identifier("\"schema\".\"table\""),
+            extract_last_identifier("\"schema\".\"table\"", Dialect::Generic),
             Some("table".to_string())
         );
     }
 
     #[test]
     fn test_extract_last_identifier_empty() {
-        assert_eq!(extract_last_identifier(""), None);
-        assert_eq!(extract_last_identifier("   "), None);
+        assert_eq!(extract_last_identifier("", Dialect::Generic), None);
+        assert_eq!(extract_last_identifier("   ", Dialect::Generic), None);
         // Note: "SELECT " extracts "SELECT" because the function doesn't distinguish keywords
         assert_eq!(
-            extract_last_identifier("SELECT "),
+            extract_last_identifier("SELECT ", Dialect::Generic),
             Some("SELECT".to_string())
         );
         // Only punctuation/operators return None
-        assert_eq!(extract_last_identifier("("), None);
-        assert_eq!(extract_last_identifier(", "), None);
+        assert_eq!(extract_last_identifier("(", Dialect::Generic), None);
+        assert_eq!(extract_last_identifier(", ", Dialect::Generic), None);
     }
 
     #[test]
     fn test_extract_qualifier_with_trailing_dot() {
-        assert_eq!(extract_qualifier("users.", 6), Some("users".to_string()));
-        assert_eq!(extract_qualifier("SELECT u.", 9), Some("u".to_string()));
         assert_eq!(
-            extract_qualifier("schema.table.", 13),
+            extract_qualifier("users.", 6, Dialect::Generic),
+            Some("users".to_string())
+        );
+        assert_eq!(
+            extract_qualifier("SELECT u.", 9, Dialect::Generic),
+            Some("u".to_string())
+        );
+        assert_eq!(
+            extract_qualifier("schema.table.", 13, Dialect::Generic),
             Some("table".to_string())
         );
     }
@@ -2468,27 +2491,30 @@ mod tests {
     #[test]
     fn test_extract_qualifier_mid_token() {
         assert_eq!(
-            extract_qualifier("users.name", 10),
+            extract_qualifier("users.name", 10, Dialect::Generic),
             Some("users".to_string())
         );
-        assert_eq!(extract_qualifier("SELECT u.id", 11), Some("u".to_string()));
+        assert_eq!(
+            extract_qualifier("SELECT u.id", 11, Dialect::Generic),
+            Some("u".to_string())
+        );
     }
 
     #[test]
     fn test_extract_qualifier_no_qualifier() {
-        assert_eq!(extract_qualifier("SELECT", 6), None);
-        assert_eq!(extract_qualifier("users", 5), None);
-        assert_eq!(extract_qualifier("", 0), None);
+        assert_eq!(extract_qualifier("SELECT", 6, Dialect::Generic), None);
+        assert_eq!(extract_qualifier("users", 5, Dialect::Generic), None);
+        assert_eq!(extract_qualifier("", 0, Dialect::Generic), None);
     }
 
     #[test]
     fn test_extract_qualifier_cursor_at_start() {
-        assert_eq!(extract_qualifier("users.name", 0), None);
+        assert_eq!(extract_qualifier("users.name", 0, Dialect::Generic), None);
     }
 
     #[test]
     fn test_extract_qualifier_cursor_out_of_bounds() {
-        assert_eq!(extract_qualifier("users", 100), None);
+        assert_eq!(extract_qualifier("users", 100, Dialect::Generic), None);
     }
 
     #[test]
@@ -2496,19 +2522,36 @@ mod tests {
         // Multi-byte UTF-8 character (emoji is 4 bytes)
         let sql = "SELECT 🎉.";
         // Cursor in middle of emoji (invalid boundary) should return None
-        assert_eq!(extract_qualifier(sql, 8), None); // Middle of emoji
-                                                     // Cursor after emoji + dot should work
-        assert_eq!(extract_qualifier(sql, sql.len()), None); // 🎉 is not identifier char
+        assert_eq!(extract_qualifier(sql, 8, Dialect::Generic), None); // Middle of emoji
+                                                                       // Cursor after emoji + dot should work
+        assert_eq!(extract_qualifier(sql, sql.len(), Dialect::Generic), None); // 🎉 is not identifier char
     }
 
     #[test]
     fn test_extract_qualifier_quoted_identifier() {
         assert_eq!(
-            extract_qualifier("\"My Schema\".", 12),
+            extract_qualifier("\"My Schema\".", 12, Dialect::Generic),
             Some("My Schema".to_string())
         );
     }
 
+    #[test]
+    fn test_extract_qualifier_uses_dialect_quotes() {
+        assert_eq!(
+            extract_qualifier("SELECT `order items`.", 21, Dialect::Mysql),
+            Some("order items".to_string())
+        );
+        assert_eq!(
+            extract_qualifier("SELECT [Order Items].qty", 24, Dialect::Mssql),
+            Some("Order Items".to_string())
+        );
+        // Backticks do not quote identifiers in Postgres
+        assert_eq!(
+            extract_qualifier("SELECT `order items`.", 21, Dialect::Postgres),
+            None
+        );
+    }
+
     // Unit tests for resolve_qualifier
 
     #[test]
@@ -2605,6 +2648,7 @@ mod tests {
                     data_type: Some("integer".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
         };
@@ -2857,12 +2901,14 @@ mod tests {
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "name".to_string(),
                         data_type: Some("varchar".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             }],
@@ -2926,24 +2972,28 @@ mod tests {
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "created_at".to_string(),
                         data_type: Some("timestamp".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "name".to_string(),
                         data_type: Some("varchar".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "score".to_string(),
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             }],
@@ -3019,6 +3069,7 @@ mod tests {
                     data_type: Some("integer".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
         };
@@ -3064,6 +3115,7 @@ mod tests {
                     data_type: Some("integer".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
         };
@@ -3109,6 +3161,7 @@ mod tests {
                     data_type: Some("integer".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
         };
@@ -3153,6 +3206,7 @@ mod tests {
                     data_type: Some("integer".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
         };
@@ -3414,6 +3468,7 @@ mod tests {
                     data_type: Some("integer".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
         };
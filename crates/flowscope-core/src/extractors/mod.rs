@@ -1,4 +1,4 @@
-use sqlparser::ast::{Statement, TableFactor};
+use sqlparser::ast::{CreateTableLikeKind, Statement, TableFactor};
 
 pub fn extract_tables(statements: &[Statement]) -> Vec<String> {
     let mut tables = Vec::new();
@@ -64,6 +64,22 @@ pub fn extract_tables(statements: &[Statement]) -> Vec<String> {
                 extract_tables_from_table_factor(table, &mut tables);
                 extract_tables_from_table_factor(source, &mut tables);
             }
+            Statement::CreateTable(create) => {
+                tables.push(create.name.to_string());
+                match &create.like {
+                    Some(CreateTableLikeKind::Plain(like))
+                    | Some(CreateTableLikeKind::Parenthesized(like)) => {
+                        tables.push(like.name.to_string());
+                    }
+                    None => {}
+                }
+                if let Some(clone) = &create.clone {
+                    tables.push(clone.to_string());
+                }
+                if let Some(query) = &create.query {
+                    extract_tables_from_query_body(&query.body, &mut tables);
+                }
+            }
             _ => {}
         }
     }
@@ -251,4 +267,13 @@ mod tests {
         assert!(tables.contains(&"target".to_string()));
         assert!(tables.contains(&"source".to_string()));
     }
+
+    #[test]
+    fn test_extract_create_table_like_and_clone() {
+        let sql = "CREATE TABLE copy LIKE original; CREATE TABLE backup CLONE orders";
+        let statements =
+            crate::parser::parse_sql_with_dialect(sql, crate::types::Dialect::Snowflake).unwrap();
+        let tables = extract_tables(&statements);
+        assert_eq!(tables, ["copy", "original", "backup", "orders"]);
+    }
 }
@@ -0,0 +1,67 @@
+//! Applying the edits attached to issues ([`crate::IssueFix`]) to source SQL.
+
+use std::borrow::Cow;
+
+use crate::types::{Issue, IssueFix};
+
+/// Applies the fixes of `issues` to `sql`, the text they were reported against.
+///
+/// Issues from other sources (a different `source_name` in a multi-file request) must be
+/// filtered out first. Fixes are applied in source order; one that overlaps an earlier
+/// fix or does not fall on character boundaries of `sql` is skipped. Returns the fixed
+/// SQL, borrowing the input when there is nothing to apply, and the number of fixes
+/// applied.
+pub fn apply_fixes<'a>(sql: &'a str, issues: &[Issue]) -> (Cow<'a, str>, usize) {
+    let mut fixes: Vec<&IssueFix> = issues
+        .iter()
+        .filter_map(|issue| issue.fix.as_ref())
+        .filter(|fix| {
+            fix.span.start <= fix.span.end
+                && sql.is_char_boundary(fix.span.start)
+                && sql.is_char_boundary(fix.span.end)
+        })
+        .collect();
+    if fixes.is_empty() {
+        return (Cow::Borrowed(sql), 0);
+    }
+    fixes.sort_by_key(|fix| (fix.span.start, fix.span.end));
+
+    let mut fixed = String::with_capacity(sql.len());
+    let mut last = 0;
+    let mut applied = 0;
+    for fix in fixes {
+        if fix.span.start < last {
+            continue;
+        }
+        fixed.push_str(&sql[last..fix.span.start]);
+        fixed.push_str(&fix.replacement);
+        last = fix.span.end;
+        applied += 1;
+    }
+    fixed.push_str(&sql[last..]);
+    (Cow::Owned(fixed), applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Span;
+
+    #[test]
+    fn applies_fixes_in_source_order_skipping_overlaps() {
+        let sql = "SELECT a AS order, b AS select FROM t";
+        let issues = vec![
+            Issue::warning("X", "second").with_fix(Span::new(24, 30), "\"select\""),
+            Issue::warning("X", "first").with_fix(Span::new(12, 17), "\"order\""),
+            Issue::warning("X", "overlaps").with_fix(Span::new(14, 20), "?"),
+            Issue::warning("X", "no fix"),
+        ];
+        let (fixed, applied) = apply_fixes(sql, &issues);
+        assert_eq!(fixed, "SELECT a AS \"order\", b AS \"select\" FROM t");
+        assert_eq!(applied, 2);
+        assert!(matches!(
+            apply_fixes(sql, &issues[3..]),
+            (Cow::Borrowed(_), 0)
+        ));
+    }
+}
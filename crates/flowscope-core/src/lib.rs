@@ -2,6 +2,7 @@ pub mod analyzer;
 pub mod completion;
 pub mod error;
 pub mod extractors;
+pub mod fix;
 pub mod generated;
 pub mod parser;
 #[cfg(feature = "templating")]
@@ -13,16 +14,21 @@ pub use analyzer::{analyze, split_statements};
 pub use completion::{completion_context, completion_items};
 pub use error::ParseError;
 pub use extractors::extract_tables;
-pub use parser::{parse_sql, parse_sql_with_dialect};
+pub use fix::apply_fixes;
+pub use parser::{parse_sql, parse_sql_with_dialect, redact_literals};
 
 // Re-export types explicitly
 pub use types::{
+    // Introspection
+    issue_catalog,
     // Issue codes
     issue_codes,
+    supported_dialects,
     // Request types
     AggregationInfo,
     AnalysisOptions,
     AnalyzeRequest,
+    AnalyzeRequestBuilder,
     // Response types
     AnalyzeResult,
     CanonicalName,
@@ -46,13 +52,18 @@ pub use types::{
     GlobalEdge,
     GlobalLineage,
     GlobalNode,
+    GraphMetrics,
+    ImplicitAliasContext,
     Issue,
+    IssueCodeInfo,
     IssueCount,
+    IssueFix,
     JoinType,
     // Legacy
     LineageResult,
     Node,
     NodeType,
+    RenderedSql,
     ResolutionSource,
     ResolvedColumnSchema,
     ResolvedSchemaMetadata,
@@ -67,8 +78,10 @@ pub use types::{
     StatementRef,
     StatementSplitRequest,
     StatementSplitResult,
+    StatementTiming,
     Summary,
     TableConstraintInfo,
+    TableGraphMetrics,
 };
 
 // Re-export template types when the templating feature is enabled
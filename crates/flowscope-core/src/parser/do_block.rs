@@ -0,0 +1,150 @@
+//! PostgreSQL anonymous `DO $$ ... $$` block support.
+//!
+//! sqlparser cannot parse `DO` statements or the PL/pgSQL inside them. Best-effort
+//! parsing instead locates the dollar-quoted body and extracts the SQL statements
+//! embedded in it, skipping procedural control flow (`DECLARE`, `IF ... THEN`,
+//! `LOOP`, `RAISE`, ...) that does not parse as SQL.
+
+use std::ops::Range;
+
+use sqlparser::ast::{SetExpr, Statement};
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Token, TokenWithSpan, Tokenizer};
+
+use crate::analyzer::helpers::line_col_to_offset;
+use crate::types::Dialect;
+
+use super::parse_sql_with_dialect;
+
+/// Keywords that can start an embedded SQL statement inside a procedural body.
+const STATEMENT_KEYWORDS: &[Keyword] = &[
+    Keyword::SELECT,
+    Keyword::WITH,
+    Keyword::INSERT,
+    Keyword::UPDATE,
+    Keyword::DELETE,
+    Keyword::MERGE,
+    Keyword::CREATE,
+    Keyword::TRUNCATE,
+];
+
+/// Returns the byte range of the dollar-quoted body when `statement_sql` is a
+/// `DO [LANGUAGE lang] $tag$ ... $tag$ [LANGUAGE lang]` statement.
+pub(crate) fn do_block_body(statement_sql: &str, dialect: Dialect) -> Option<Range<usize>> {
+    let tokens = tokenize(statement_sql, dialect)?;
+    let mut tokens = tokens.iter();
+    if !tokens.next().is_some_and(|t| is_keyword(t, Keyword::DO)) {
+        return None;
+    }
+
+    let mut next = tokens.next()?;
+    if is_keyword(next, Keyword::LANGUAGE) {
+        tokens.next()?;
+        next = tokens.next()?;
+    }
+    let Token::DollarQuotedString(body) = &next.token else {
+        return None;
+    };
+
+    let literal = token_range(statement_sql, next)?;
+    // `$tag$` delimiters on both sides
+    let delimiter_len = body.tag.as_ref().map_or(0, String::len) + 2;
+    let start = literal.start + delimiter_len;
+    let end = literal.end.checked_sub(delimiter_len)?;
+    (start <= end).then_some(start..end)
+}
+
+/// Parses the SQL statement embedded in one semicolon-delimited piece of a procedural
+/// body, returning it with its byte offset within `piece`.
+///
+/// Procedural prefixes such as `BEGIN IF cond THEN` are skipped by retrying the parse
+/// from each statement keyword in turn. `SELECT ... INTO variable` assignments are not
+/// table writes and are ignored.
+pub(crate) fn parse_embedded_statement(
+    piece: &str,
+    dialect: Dialect,
+) -> Option<(Statement, usize)> {
+    let tokens = tokenize(piece, dialect)?;
+    tokens
+        .iter()
+        .filter(|t| STATEMENT_KEYWORDS.iter().any(|&k| is_keyword(t, k)))
+        .filter_map(|t| token_range(piece, t))
+        .find_map(|range| {
+            let mut parsed = parse_sql_with_dialect(&piece[range.start..], dialect).ok()?;
+            if parsed.len() != 1 || is_variable_assignment(&parsed[0]) {
+                return None;
+            }
+            Some((parsed.remove(0), range.start))
+        })
+}
+
+fn is_variable_assignment(statement: &Statement) -> bool {
+    matches!(
+        statement,
+        Statement::Query(query)
+            if matches!(query.body.as_ref(), SetExpr::Select(select) if select.into.is_some())
+    )
+}
+
+fn tokenize(sql: &str, dialect: Dialect) -> Option<Vec<TokenWithSpan>> {
+    let sqlparser_dialect = dialect.to_sqlparser_dialect();
+    let tokens = Tokenizer::new(sqlparser_dialect.as_ref(), sql)
+        .tokenize_with_location()
+        .ok()?;
+    Some(
+        tokens
+            .into_iter()
+            .filter(|t| !matches!(t.token, Token::Whitespace(_)))
+            .collect(),
+    )
+}
+
+fn is_keyword(token: &TokenWithSpan, keyword: Keyword) -> bool {
+    matches!(&token.token, Token::Word(word) if word.keyword == keyword && word.quote_style.is_none())
+}
+
+fn token_range(sql: &str, token: &TokenWithSpan) -> Option<Range<usize>> {
+    let start = line_col_to_offset(
+        sql,
+        token.span.start.line as usize,
+        token.span.start.column as usize,
+    )?;
+    let end = line_col_to_offset(
+        sql,
+        token.span.end.line as usize,
+        token.span.end.column as usize,
+    )?;
+    Some(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_dollar_quoted_body() {
+        let sql = "DO LANGUAGE plpgsql $body$ BEGIN NULL; END $body$";
+        let body = do_block_body(sql, Dialect::Postgres).expect("body");
+        assert_eq!(&sql[body], " BEGIN NULL; END ");
+        assert_eq!(
+            do_block_body("DO $$$$", Dialect::Postgres).map(|r| r.len()),
+            Some(0)
+        );
+        assert!(do_block_body("SELECT '$$'", Dialect::Postgres).is_none());
+    }
+
+    #[test]
+    fn skips_procedural_prefixes_and_assignments() {
+        let piece = "\n  BEGIN\n  IF NOT EXISTS (SELECT 1 FROM log) THEN\n    DELETE FROM log";
+        let (statement, offset) =
+            parse_embedded_statement(piece, Dialect::Postgres).expect("statement");
+        assert!(matches!(statement, Statement::Delete(_)));
+        assert!(piece[offset..].starts_with("DELETE"));
+
+        assert!(
+            parse_embedded_statement("SELECT count(*) INTO n FROM log", Dialect::Postgres)
+                .is_none()
+        );
+        assert!(parse_embedded_statement("RAISE NOTICE 'done'", Dialect::Postgres).is_none());
+    }
+}
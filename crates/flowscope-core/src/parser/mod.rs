@@ -1,12 +1,41 @@
+use std::borrow::Cow;
+
 use crate::error::ParseError;
 use crate::types::Dialect;
 use sqlparser::ast::Statement;
 use sqlparser::parser::Parser;
 
+pub(crate) mod do_block;
+pub(crate) mod multi_insert;
+pub(crate) mod psql;
+pub mod redact;
+pub(crate) mod refresh;
+pub(crate) mod spark;
+pub(crate) mod view_options;
+
+pub use redact::redact_literals;
+
 /// Parse SQL using the specified dialect
 pub fn parse_sql_with_dialect(sql: &str, dialect: Dialect) -> Result<Vec<Statement>, ParseError> {
     let sqlparser_dialect = dialect.to_sqlparser_dialect();
-    let statements = Parser::parse_sql(sqlparser_dialect.as_ref(), sql)?;
+    let sql = if spark::has_spark_table_clauses(dialect) {
+        spark::mask_spark_table_clauses(sql, dialect)
+    } else {
+        sql.into()
+    };
+    let sql = match refresh::mask_refresh_materialized_views(&sql, dialect) {
+        Cow::Owned(masked) => Cow::Owned(masked),
+        Cow::Borrowed(_) => sql,
+    };
+    let sql = match multi_insert::mask_multi_table_inserts(&sql, dialect) {
+        Cow::Owned(masked) => Cow::Owned(masked),
+        Cow::Borrowed(_) => sql,
+    };
+    let sql = match view_options::mask_view_options(&sql, dialect) {
+        Cow::Owned(masked) => Cow::Owned(masked),
+        Cow::Borrowed(_) => sql,
+    };
+    let statements = Parser::parse_sql(sqlparser_dialect.as_ref(), &sql)?;
     Ok(statements)
 }
 
@@ -18,6 +47,7 @@ pub fn parse_sql(sql: &str) -> Result<Vec<Statement>, ParseError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sqlparser::ast::{ExcludeSelectItem, SelectItem, SetExpr};
 
     #[test]
     fn test_parse_valid_select() {
@@ -65,6 +95,33 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_parse_with_duckdb_exclude_list() {
+        let sql = "SELECT * EXCLUDE (password, ssn) FROM users";
+        let statements = parse_sql_with_dialect(sql, Dialect::Duckdb).unwrap();
+        let Statement::Query(query) = &statements[0] else {
+            panic!("expected a query");
+        };
+        let SetExpr::Select(select) = query.body.as_ref() else {
+            panic!("expected a SELECT");
+        };
+        let SelectItem::Wildcard(options) = &select.projection[0] else {
+            panic!("expected a wildcard");
+        };
+        let Some(ExcludeSelectItem::Multiple(excluded)) = &options.opt_exclude else {
+            panic!("expected an EXCLUDE list");
+        };
+        let names: Vec<&str> = excluded.iter().map(|ident| ident.value.as_str()).collect();
+        assert_eq!(names, ["password", "ssn"]);
+    }
+
+    #[test]
+    fn test_parse_with_duckdb_qualify() {
+        let sql = "SELECT * FROM events QUALIFY ROW_NUMBER() OVER (PARTITION BY user_id) = 1";
+        let result = parse_sql_with_dialect(sql, Dialect::Duckdb);
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_parse_cte() {
         let sql = r#"
@@ -0,0 +1,286 @@
+//! Multi-table `INSERT ALL` / `INSERT FIRST` support (Oracle, Snowflake).
+//!
+//! sqlparser cannot parse multi-table inserts, so before parsing everything ahead of the
+//! shared source query is blanked with spaces, leaving a plain `SELECT` at the same byte
+//! offsets. The analyzer recognizes the original text, recovers each `INTO` clause with
+//! [`multi_table_insert_targets`], and maps the source query onto every target.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use sqlparser::ast::{Expr, Ident, ObjectName, SetExpr, Statement, TableObject};
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Token, TokenWithSpan, Tokenizer};
+
+use crate::analyzer::helpers::line_col_to_offset;
+use crate::types::Dialect;
+
+use super::parse_sql_with_dialect;
+
+/// One `INTO target [(columns)] [VALUES (exprs)]` clause of a multi-table insert.
+#[derive(Debug, Clone)]
+pub(crate) struct MultiInsertTarget {
+    pub table: ObjectName,
+    pub columns: Vec<Ident>,
+    /// Values written to `columns`, in terms of the source query's output columns.
+    /// `None` inserts the source query's columns as they are.
+    pub values: Option<Vec<Expr>>,
+}
+
+/// Rewrite every multi-table insert in `sql` into its source query by blanking the
+/// `INSERT ALL|FIRST [WHEN ... THEN] INTO ...` prefix. Returns the input unchanged when
+/// there is nothing to rewrite.
+pub(crate) fn mask_multi_table_inserts(sql: &str, dialect: Dialect) -> Cow<'_, str> {
+    // Avoid tokenizing buffers that cannot contain the statement
+    if !sql
+        .as_bytes()
+        .windows(6)
+        .any(|w| w.eq_ignore_ascii_case(b"INSERT"))
+    {
+        return Cow::Borrowed(sql);
+    }
+    let Some(tokens) = tokenize(sql, dialect) else {
+        return Cow::Borrowed(sql);
+    };
+
+    let mut masks: Vec<Range<usize>> = Vec::new();
+    let mut at_statement_start = true;
+    let mut i = 0;
+    while i < tokens.len() {
+        if matches!(tokens[i].token, Token::SemiColon) {
+            at_statement_start = true;
+            i += 1;
+            continue;
+        }
+        if !at_statement_start || !starts_multi_table_insert(&tokens[i..]) {
+            at_statement_start = false;
+            i += 1;
+            continue;
+        }
+        at_statement_start = false;
+
+        let statement = &tokens[i..];
+        let Some(source) = source_query_start(statement) else {
+            i += 1;
+            continue;
+        };
+        if let (Some(first), Some(last)) = (
+            token_range(sql, &statement[0]),
+            token_range(sql, &statement[source - 1]),
+        ) {
+            masks.push(first.start..last.end);
+        }
+        i += source;
+    }
+
+    if masks.is_empty() {
+        return Cow::Borrowed(sql);
+    }
+
+    let mut bytes = sql.as_bytes().to_vec();
+    for range in masks {
+        // Keep line breaks so line/column spans after the prefix stay put
+        for byte in &mut bytes[range] {
+            if *byte != b'\n' && *byte != b'\r' {
+                *byte = b' ';
+            }
+        }
+    }
+    // Masked ranges start and end on token boundaries and every byte inside them is
+    // replaced, so no partial characters remain
+    Cow::Owned(String::from_utf8(bytes).expect("masking preserves UTF-8"))
+}
+
+/// Returns true when `statement_sql` is a multi-table `INSERT ALL` / `INSERT FIRST`.
+pub(crate) fn is_multi_table_insert(statement_sql: &str, dialect: Dialect) -> bool {
+    tokenize(statement_sql, dialect).is_some_and(|tokens| starts_multi_table_insert(&tokens))
+}
+
+/// The `INTO` clauses of the multi-table insert in `statement_sql`, in source order.
+///
+/// `WHEN` conditions are not evaluated: every target is assumed to receive rows.
+pub(crate) fn multi_table_insert_targets(
+    statement_sql: &str,
+    dialect: Dialect,
+) -> Vec<MultiInsertTarget> {
+    let Some(tokens) = tokenize(statement_sql, dialect) else {
+        return Vec::new();
+    };
+    if !starts_multi_table_insert(&tokens) {
+        return Vec::new();
+    }
+    let Some(source) = source_query_start(&tokens) else {
+        return Vec::new();
+    };
+
+    let prefix = &tokens[..source];
+    let mut targets = Vec::new();
+    let mut depth = 0usize;
+    let mut clause_start = None;
+    for (i, token) in prefix.iter().enumerate() {
+        let ends_clause = depth == 0
+            && (is_keyword(token, Keyword::INTO)
+                || is_keyword(token, Keyword::WHEN)
+                || is_keyword(token, Keyword::ELSE));
+        if ends_clause {
+            if let Some(start) = clause_start.take() {
+                targets.extend(parse_into_clause(statement_sql, &prefix[start..i], dialect));
+            }
+            if is_keyword(token, Keyword::INTO) {
+                clause_start = Some(i);
+            }
+        }
+        match token.token {
+            Token::LParen => depth += 1,
+            Token::RParen => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    if let Some(start) = clause_start {
+        targets.extend(parse_into_clause(statement_sql, &prefix[start..], dialect));
+    }
+    targets
+}
+
+/// Parses `INTO t [(cols)] [VALUES (...)]` by reading it back as a regular insert.
+fn parse_into_clause(
+    sql: &str,
+    clause: &[TokenWithSpan],
+    dialect: Dialect,
+) -> Option<MultiInsertTarget> {
+    let start = token_range(sql, clause.first()?)?.start;
+    let end = token_range(sql, clause.last()?)?.end;
+    let text = &sql[start..end];
+    let has_values = clause.iter().any(|t| is_keyword(t, Keyword::VALUES));
+    let insert_sql = if has_values {
+        format!("INSERT {text}")
+    } else {
+        format!("INSERT {text} SELECT 1")
+    };
+
+    let mut statements = parse_sql_with_dialect(&insert_sql, dialect).ok()?;
+    let Some(Statement::Insert(insert)) = statements.pop() else {
+        return None;
+    };
+    let TableObject::TableName(table) = insert.table else {
+        return None;
+    };
+    let values = if has_values {
+        match insert.source.as_deref().map(|query| query.body.as_ref()) {
+            Some(SetExpr::Values(values)) if values.rows.len() == 1 => Some(values.rows[0].clone()),
+            _ => return None,
+        }
+    } else {
+        None
+    };
+
+    Some(MultiInsertTarget {
+        table,
+        columns: insert.columns,
+        values,
+    })
+}
+
+/// Index of the token starting the shared source query: the first `SELECT` or `WITH`
+/// outside parentheses following an `INTO` clause.
+fn source_query_start(tokens: &[TokenWithSpan]) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut seen_into = false;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.token {
+            Token::LParen => depth += 1,
+            Token::RParen => depth = depth.saturating_sub(1),
+            Token::SemiColon => return None,
+            _ if depth > 0 => {}
+            _ if is_keyword(token, Keyword::INTO) => seen_into = true,
+            _ if seen_into
+                && (is_keyword(token, Keyword::SELECT) || is_keyword(token, Keyword::WITH)) =>
+            {
+                return Some(i);
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn starts_multi_table_insert(tokens: &[TokenWithSpan]) -> bool {
+    tokens
+        .first()
+        .is_some_and(|t| is_keyword(t, Keyword::INSERT))
+        && tokens
+            .get(1)
+            .is_some_and(|t| is_keyword(t, Keyword::ALL) || is_keyword(t, Keyword::FIRST))
+}
+
+fn tokenize(sql: &str, dialect: Dialect) -> Option<Vec<TokenWithSpan>> {
+    let sqlparser_dialect = dialect.to_sqlparser_dialect();
+    let tokens = Tokenizer::new(sqlparser_dialect.as_ref(), sql)
+        .tokenize_with_location()
+        .ok()?;
+    Some(
+        tokens
+            .into_iter()
+            .filter(|t| !matches!(t.token, Token::Whitespace(_)))
+            .collect(),
+    )
+}
+
+fn is_keyword(token: &TokenWithSpan, keyword: Keyword) -> bool {
+    matches!(&token.token, Token::Word(word) if word.keyword == keyword && word.quote_style.is_none())
+}
+
+fn token_range(sql: &str, token: &TokenWithSpan) -> Option<Range<usize>> {
+    let start = line_col_to_offset(
+        sql,
+        token.span.start.line as usize,
+        token.span.start.column as usize,
+    )?;
+    let end = line_col_to_offset(
+        sql,
+        token.span.end.line as usize,
+        token.span.end.column as usize,
+    )?;
+    Some(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQL: &str = "INSERT ALL\n  INTO a (id, total) VALUES (id, amount)\n  INTO b\nSELECT id, amount FROM src;\nSELECT 1";
+
+    #[test]
+    fn masks_prefix_preserving_offsets() {
+        let masked = mask_multi_table_inserts(SQL, Dialect::Snowflake);
+        assert_eq!(masked.len(), SQL.len());
+        assert_eq!(masked.lines().count(), SQL.lines().count());
+        assert_eq!(
+            masked.split_whitespace().collect::<Vec<_>>(),
+            vec!["SELECT", "id,", "amount", "FROM", "src;", "SELECT", "1"]
+        );
+        assert!(matches!(
+            mask_multi_table_inserts("INSERT INTO a SELECT 1", Dialect::Snowflake),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn recovers_into_clauses() {
+        assert!(is_multi_table_insert(SQL, Dialect::Snowflake));
+        let targets = multi_table_insert_targets(SQL, Dialect::Snowflake);
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0].table.to_string(), "a");
+        assert_eq!(targets[0].columns.len(), 2);
+        assert_eq!(targets[0].values.as_ref().map(Vec::len), Some(2));
+        assert_eq!(targets[1].table.to_string(), "b");
+        assert!(targets[1].values.is_none());
+
+        let conditional = "INSERT FIRST WHEN amount > (SELECT 10) THEN INTO big ELSE INTO small SELECT amount FROM src";
+        let names: Vec<String> = multi_table_insert_targets(conditional, Dialect::Snowflake)
+            .iter()
+            .map(|t| t.table.to_string())
+            .collect();
+        assert_eq!(names, ["big", "small"]);
+    }
+}
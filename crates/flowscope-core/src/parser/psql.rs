@@ -0,0 +1,150 @@
+//! psql client meta-commands (`\copy`, `\set`, `\i`, ...).
+//!
+//! psql scripts mix SQL with backslash commands that psql runs itself. A meta-command
+//! ends at the end of its line rather than at a semicolon, and sqlparser rejects it.
+//! Statement splitting gives each meta-command its own range, the SQL around them is
+//! parsed with the commands blanked out, and `\copy` is parsed as the server-side
+//! `COPY` it mirrors. Other meta-commands have no lineage and are skipped.
+
+use std::ops::Range;
+
+use sqlparser::ast::Statement;
+
+use super::parse_sql_with_dialect;
+use crate::error::ParseError;
+use crate::types::Dialect;
+
+/// Whether a (trimmed) statement range holds a meta-command.
+pub(crate) fn is_meta_command(statement_sql: &str) -> bool {
+    statement_sql.starts_with('\\')
+}
+
+/// Replace the meta-commands at `ranges` with spaces, keeping byte offsets stable.
+pub(crate) fn mask_meta_commands(sql: &str, ranges: &[Range<usize>]) -> String {
+    let mut bytes = sql.as_bytes().to_vec();
+    for range in ranges {
+        for byte in &mut bytes[range.clone()] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    }
+    // Ranges span whole lines, so only complete characters were replaced
+    String::from_utf8(bytes).expect("masking preserves UTF-8")
+}
+
+/// Parse a `\copy` meta-command as the equivalent `COPY` statement.
+///
+/// Returns `None` for other meta-commands.
+pub(crate) fn parse_meta_command(
+    command: &str,
+    dialect: Dialect,
+) -> Option<Result<Vec<Statement>, ParseError>> {
+    let body = command.strip_prefix('\\')?;
+    let name_end = body.find(char::is_whitespace).unwrap_or(body.len());
+    if !body[..name_end].eq_ignore_ascii_case("copy") {
+        return None;
+    }
+    let args = body[name_end..].trim().trim_end_matches(';');
+    Some(parse_sql_with_dialect(
+        &format!("COPY {}", server_side_copy_args(args)),
+        dialect,
+    ))
+}
+
+/// Rewrite the file argument of `\copy` for `COPY`: psql accepts unquoted file names
+/// and the `pstdin`/`pstdout` streams, which the server-side syntax does not.
+fn server_side_copy_args(args: &str) -> String {
+    let words = top_level_words(args);
+    let Some(position) = words.iter().position(|word| {
+        let word = &args[word.clone()];
+        word.eq_ignore_ascii_case("from") || word.eq_ignore_ascii_case("to")
+    }) else {
+        return args.to_string();
+    };
+    let Some(file) = words.get(position + 1) else {
+        return args.to_string();
+    };
+
+    let text = &args[file.clone()];
+    let replacement = if text.eq_ignore_ascii_case("pstdin") {
+        "STDIN".to_string()
+    } else if text.eq_ignore_ascii_case("pstdout") {
+        "STDOUT".to_string()
+    } else if text.starts_with('\'')
+        || ["stdin", "stdout", "program"]
+            .iter()
+            .any(|keyword| text.eq_ignore_ascii_case(keyword))
+    {
+        return args.to_string();
+    } else {
+        format!("'{}'", text.replace('\'', "''"))
+    };
+    format!("{}{replacement}{}", &args[..file.start], &args[file.end..])
+}
+
+/// Byte ranges of the whitespace-separated words of `args` outside parentheses and
+/// quotes, so a `\copy (SELECT ... FROM t) TO ...` query is skipped.
+fn top_level_words(args: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut depth = 0usize;
+    let mut in_quote = false;
+    for (i, ch) in args.char_indices() {
+        let top_level = if in_quote {
+            in_quote = ch != '\'';
+            depth == 0
+        } else {
+            match ch {
+                '\'' => in_quote = true,
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            depth == 0 && !ch.is_whitespace() && ch != ')'
+        };
+        if top_level {
+            start.get_or_insert(i);
+        } else if let Some(word_start) = start.take() {
+            words.push(word_start..i);
+        }
+    }
+    if let Some(word_start) = start {
+        words.push(word_start..args.len());
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_psql_file_arguments() {
+        assert_eq!(
+            server_side_copy_args("users FROM 'u.csv' CSV HEADER"),
+            "users FROM 'u.csv' CSV HEADER"
+        );
+        assert_eq!(
+            server_side_copy_args("users (id, name) from data/u.csv with csv"),
+            "users (id, name) from 'data/u.csv' with csv"
+        );
+        assert_eq!(
+            server_side_copy_args("(SELECT id FROM users) TO pstdout"),
+            "(SELECT id FROM users) TO STDOUT"
+        );
+        assert_eq!(
+            server_side_copy_args("users FROM stdin"),
+            "users FROM stdin"
+        );
+    }
+
+    #[test]
+    fn skips_other_meta_commands() {
+        assert!(parse_meta_command("\\set ON_ERROR_STOP on", Dialect::Postgres).is_none());
+        let parsed = parse_meta_command("\\COPY users FROM 'u.csv';", Dialect::Postgres)
+            .expect("copy is recognized")
+            .expect("copy parses");
+        assert!(matches!(parsed.as_slice(), [Statement::Copy { .. }]));
+    }
+}
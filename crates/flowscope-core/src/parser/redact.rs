@@ -0,0 +1,135 @@
+//! Literal redaction for sharing analysis output without the data it embeds.
+//!
+//! String and number literals are replaced in place, like the parser's pre-masking:
+//! every literal keeps its byte length, so spans and offsets into the text stay valid
+//! and the SQL around it is untouched.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use sqlparser::tokenizer::{Token, TokenWithSpan, Tokenizer};
+
+use crate::analyzer::helpers::line_col_to_offset;
+use crate::types::Dialect;
+
+/// Placeholder for string literals (including byte, raw, national and dollar-quoted ones).
+pub const STRING_PLACEHOLDER: &str = "'<str>'";
+/// Placeholder for numeric literals.
+pub const NUMBER_PLACEHOLDER: &str = "<num>";
+
+/// Replace the string and number literals in `sql` with [`STRING_PLACEHOLDER`] and
+/// [`NUMBER_PLACEHOLDER`], padded with trailing spaces to the literal's length.
+///
+/// Literals shorter than their placeholder are filled with `?` instead (`'?'`, `?`).
+/// Returns the input unchanged when it has no literals or cannot be tokenized.
+pub fn redact_literals(sql: &str, dialect: Dialect) -> Cow<'_, str> {
+    let sqlparser_dialect = dialect.to_sqlparser_dialect();
+    let Ok(tokens) = Tokenizer::new(sqlparser_dialect.as_ref(), sql).tokenize_with_location()
+    else {
+        return Cow::Borrowed(sql);
+    };
+
+    let literals: Vec<(Range<usize>, LiteralKind)> = tokens
+        .iter()
+        .filter_map(|token| Some((token_range(sql, token)?, literal_kind(&token.token)?)))
+        .collect();
+    if literals.is_empty() {
+        return Cow::Borrowed(sql);
+    }
+
+    let mut redacted = String::with_capacity(sql.len());
+    let mut last = 0;
+    for (range, kind) in literals {
+        redacted.push_str(&sql[last..range.start]);
+        redacted.push_str(&placeholder(kind, range.len()));
+        last = range.end;
+    }
+    redacted.push_str(&sql[last..]);
+    Cow::Owned(redacted)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum LiteralKind {
+    String,
+    Number,
+}
+
+fn literal_kind(token: &Token) -> Option<LiteralKind> {
+    match token {
+        Token::Number(..) => Some(LiteralKind::Number),
+        Token::SingleQuotedString(_)
+        | Token::DoubleQuotedString(_)
+        | Token::TripleSingleQuotedString(_)
+        | Token::TripleDoubleQuotedString(_)
+        | Token::DollarQuotedString(_)
+        | Token::SingleQuotedByteStringLiteral(_)
+        | Token::DoubleQuotedByteStringLiteral(_)
+        | Token::TripleSingleQuotedByteStringLiteral(_)
+        | Token::TripleDoubleQuotedByteStringLiteral(_)
+        | Token::SingleQuotedRawStringLiteral(_)
+        | Token::DoubleQuotedRawStringLiteral(_)
+        | Token::TripleSingleQuotedRawStringLiteral(_)
+        | Token::TripleDoubleQuotedRawStringLiteral(_)
+        | Token::NationalStringLiteral(_)
+        | Token::EscapedStringLiteral(_)
+        | Token::UnicodeStringLiteral(_)
+        | Token::HexStringLiteral(_) => Some(LiteralKind::String),
+        _ => None,
+    }
+}
+
+/// The ASCII replacement for a literal spanning `len` bytes.
+fn placeholder(kind: LiteralKind, len: usize) -> String {
+    let full = match kind {
+        LiteralKind::String => STRING_PLACEHOLDER,
+        LiteralKind::Number => NUMBER_PLACEHOLDER,
+    };
+    if len >= full.len() {
+        return format!("{full:<len$}");
+    }
+    match kind {
+        LiteralKind::String if len >= 2 => format!("'{}'", "?".repeat(len - 2)),
+        _ => "?".repeat(len),
+    }
+}
+
+fn token_range(sql: &str, token: &TokenWithSpan) -> Option<Range<usize>> {
+    let start = line_col_to_offset(
+        sql,
+        token.span.start.line as usize,
+        token.span.start.column as usize,
+    )?;
+    let end = line_col_to_offset(
+        sql,
+        token.span.end.line as usize,
+        token.span.end.column as usize,
+    )?;
+    Some(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_literals_keeping_offsets() {
+        let sql =
+            "SELECT id FROM users WHERE email = 'alice@example.com' AND id = 123456 AND x = 'é'";
+        let redacted = redact_literals(sql, Dialect::Postgres);
+        assert_eq!(redacted.len(), sql.len());
+        assert_eq!(
+            redacted,
+            "SELECT id FROM users WHERE email = '<str>'             AND id = <num>  AND x = '??'"
+        );
+        assert_eq!(redacted.find("AND id"), sql.find("AND id"));
+    }
+
+    #[test]
+    fn leaves_sql_without_literals_untouched() {
+        let sql = "SELECT \"a\", b FROM t -- 'comment'";
+        assert!(matches!(
+            redact_literals(sql, Dialect::Postgres),
+            Cow::Borrowed(_)
+        ));
+    }
+}
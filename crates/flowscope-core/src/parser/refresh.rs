@@ -0,0 +1,168 @@
+//! `REFRESH MATERIALIZED VIEW` support.
+//!
+//! sqlparser has no statement for refreshing a materialized view, so before parsing the
+//! statement is rewritten in place into `SELECT * FROM <view>`: each keyword is
+//! overwritten and padded with spaces, keeping byte offsets stable for statement ranges
+//! and spans. The analyzer recognizes the original text and records the refresh.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Token, TokenWithSpan, Tokenizer};
+
+use crate::analyzer::helpers::line_col_to_offset;
+use crate::types::Dialect;
+
+/// Rewrite every `REFRESH MATERIALIZED VIEW [CONCURRENTLY] v [WITH [NO] DATA]` in `sql`
+/// into `SELECT * FROM v`. Returns the input unchanged when there is nothing to rewrite.
+pub(crate) fn mask_refresh_materialized_views(sql: &str, dialect: Dialect) -> Cow<'_, str> {
+    // Avoid tokenizing buffers that cannot contain the statement
+    if !sql
+        .as_bytes()
+        .windows(7)
+        .any(|w| w.eq_ignore_ascii_case(b"REFRESH"))
+    {
+        return Cow::Borrowed(sql);
+    }
+    let Some(tokens) = tokenize(sql, dialect) else {
+        return Cow::Borrowed(sql);
+    };
+
+    let mut rewrites: Vec<(Range<usize>, &str)> = Vec::new();
+    let mut at_statement_start = true;
+    let mut i = 0;
+    while i < tokens.len() {
+        if matches!(tokens[i].token, Token::SemiColon) {
+            at_statement_start = true;
+            i += 1;
+            continue;
+        }
+        if !at_statement_start || !starts_refresh(&tokens[i..]) {
+            at_statement_start = false;
+            i += 1;
+            continue;
+        }
+        at_statement_start = false;
+
+        let mut push = |token: &TokenWithSpan, replacement| {
+            if let Some(range) = token_range(sql, token) {
+                rewrites.push((range, replacement));
+            }
+        };
+        push(&tokens[i], "SELECT");
+        push(&tokens[i + 1], "* FROM");
+        push(&tokens[i + 2], "");
+        i += 3;
+        if tokens
+            .get(i)
+            .is_some_and(|t| is_keyword(t, Keyword::CONCURRENTLY))
+        {
+            push(&tokens[i], "");
+            i += 1;
+        }
+
+        // Blank the trailing `WITH [NO] DATA` after the view name
+        while i < tokens.len() && !matches!(tokens[i].token, Token::SemiColon) {
+            if is_keyword(&tokens[i], Keyword::WITH) {
+                while i < tokens.len() && !matches!(tokens[i].token, Token::SemiColon) {
+                    push(&tokens[i], "");
+                    i += 1;
+                }
+                break;
+            }
+            i += 1;
+        }
+    }
+
+    if rewrites.is_empty() {
+        return Cow::Borrowed(sql);
+    }
+
+    let mut bytes = sql.as_bytes().to_vec();
+    for (range, replacement) in rewrites {
+        let target = &mut bytes[range];
+        target.fill(b' ');
+        // Every keyword is at least as long as its replacement
+        target[..replacement.len()].copy_from_slice(replacement.as_bytes());
+    }
+    // Ranges cover whole ASCII keyword tokens, so only complete characters were replaced
+    Cow::Owned(String::from_utf8(bytes).expect("masking preserves UTF-8"))
+}
+
+/// Returns true when `statement_sql` is a `REFRESH MATERIALIZED VIEW` statement.
+pub(crate) fn is_refresh_materialized_view(statement_sql: &str, dialect: Dialect) -> bool {
+    tokenize(statement_sql, dialect).is_some_and(|tokens| starts_refresh(&tokens))
+}
+
+fn tokenize(sql: &str, dialect: Dialect) -> Option<Vec<TokenWithSpan>> {
+    let sqlparser_dialect = dialect.to_sqlparser_dialect();
+    let tokens = Tokenizer::new(sqlparser_dialect.as_ref(), sql)
+        .tokenize_with_location()
+        .ok()?;
+    Some(
+        tokens
+            .into_iter()
+            .filter(|t| !matches!(t.token, Token::Whitespace(_)))
+            .collect(),
+    )
+}
+
+fn starts_refresh(tokens: &[TokenWithSpan]) -> bool {
+    // sqlparser does not know REFRESH as a keyword
+    matches!(
+        tokens.first().map(|t| &t.token),
+        Some(Token::Word(word)) if word.value.eq_ignore_ascii_case("REFRESH")
+    ) && tokens
+        .get(1)
+        .is_some_and(|t| is_keyword(t, Keyword::MATERIALIZED))
+        && tokens.get(2).is_some_and(|t| is_keyword(t, Keyword::VIEW))
+}
+
+fn is_keyword(token: &TokenWithSpan, keyword: Keyword) -> bool {
+    matches!(&token.token, Token::Word(word) if word.keyword == keyword && word.quote_style.is_none())
+}
+
+fn token_range(sql: &str, token: &TokenWithSpan) -> Option<Range<usize>> {
+    let start = line_col_to_offset(
+        sql,
+        token.span.start.line as usize,
+        token.span.start.column as usize,
+    )?;
+    let end = line_col_to_offset(
+        sql,
+        token.span.end.line as usize,
+        token.span.end.column as usize,
+    )?;
+    Some(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_refresh_into_select_preserving_length() {
+        let sql = "REFRESH MATERIALIZED VIEW CONCURRENTLY mv WITH NO DATA;\nSELECT 1";
+        let masked = mask_refresh_materialized_views(sql, Dialect::Postgres);
+        assert_eq!(masked.len(), sql.len());
+        assert_eq!(
+            masked.split_whitespace().collect::<Vec<_>>(),
+            vec!["SELECT", "*", "FROM", "mv", ";", "SELECT", "1"]
+        );
+    }
+
+    #[test]
+    fn ignores_refresh_outside_statement_start() {
+        let sql = "SELECT refresh FROM t";
+        assert!(matches!(
+            mask_refresh_materialized_views(sql, Dialect::Postgres),
+            Cow::Borrowed(_)
+        ));
+        assert!(!is_refresh_materialized_view(sql, Dialect::Postgres));
+        assert!(is_refresh_materialized_view(
+            "-- nightly\nrefresh materialized view mv",
+            Dialect::Postgres
+        ));
+    }
+}
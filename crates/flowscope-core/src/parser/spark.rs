@@ -0,0 +1,235 @@
+//! Spark/Databricks `CREATE TABLE ... USING <format> OPTIONS (...)` support.
+//!
+//! sqlparser does not accept the data source clauses of Spark DDL. They only describe
+//! storage, so before parsing they are blanked out with spaces (keeping byte offsets
+//! stable for statement ranges and spans), and the analyzer reads them back from the
+//! original text to annotate the created table.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Token, TokenWithSpan, Tokenizer};
+
+use crate::analyzer::helpers::line_col_to_offset;
+use crate::types::Dialect;
+
+/// A data source clause of a Spark `CREATE TABLE`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SparkTableClause {
+    /// `USING <format>`
+    Using(String),
+    /// `OPTIONS (key = value, ...)`
+    Options(Vec<(String, String)>),
+}
+
+/// Returns true for dialects whose `CREATE TABLE` may carry Spark data source clauses.
+pub(crate) fn has_spark_table_clauses(dialect: Dialect) -> bool {
+    matches!(dialect, Dialect::Databricks | Dialect::Hive)
+}
+
+/// Blank out `USING`/`OPTIONS` clauses of `CREATE TABLE` statements so sqlparser can
+/// parse the rest. Returns the input unchanged when there is nothing to mask.
+pub(crate) fn mask_spark_table_clauses(sql: &str, dialect: Dialect) -> Cow<'_, str> {
+    let clauses = find_spark_table_clauses(sql, dialect);
+    if clauses.is_empty() {
+        return Cow::Borrowed(sql);
+    }
+
+    let mut bytes = sql.as_bytes().to_vec();
+    for (range, _) in clauses {
+        for byte in &mut bytes[range] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    }
+    // Ranges cover whole tokens, so only complete characters were replaced
+    Cow::Owned(String::from_utf8(bytes).expect("masking preserves UTF-8"))
+}
+
+/// Find the `USING`/`OPTIONS` clauses of every `CREATE TABLE` in `sql`, with their byte ranges.
+pub(crate) fn find_spark_table_clauses(
+    sql: &str,
+    dialect: Dialect,
+) -> Vec<(Range<usize>, SparkTableClause)> {
+    let sqlparser_dialect = dialect.to_sqlparser_dialect();
+    let Ok(tokens) = Tokenizer::new(sqlparser_dialect.as_ref(), sql).tokenize_with_location()
+    else {
+        return Vec::new();
+    };
+    let tokens: Vec<TokenWithSpan> = tokens
+        .into_iter()
+        .filter(|t| !matches!(t.token, Token::Whitespace(_)))
+        .collect();
+
+    let mut clauses = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if !is_keyword(&tokens[i], Keyword::CREATE) {
+            i += 1;
+            continue;
+        }
+        i += 1;
+        while i < tokens.len()
+            && [
+                Keyword::OR,
+                Keyword::REPLACE,
+                Keyword::EXTERNAL,
+                Keyword::TEMPORARY,
+            ]
+            .iter()
+            .any(|k| is_keyword(&tokens[i], *k))
+        {
+            i += 1;
+        }
+        if i >= tokens.len() || !is_keyword(&tokens[i], Keyword::TABLE) {
+            continue;
+        }
+
+        // Scan the table definition up to its query or the end of the statement
+        let mut depth = 0usize;
+        while i < tokens.len() {
+            match &tokens[i].token {
+                Token::LParen => depth += 1,
+                Token::RParen => depth = depth.saturating_sub(1),
+                Token::SemiColon => break,
+                _ if depth > 0 => {}
+                _ if is_keyword(&tokens[i], Keyword::AS) => break,
+                _ if is_keyword(&tokens[i], Keyword::USING) => {
+                    if let Some(format) = tokens.get(i + 1).and_then(token_text) {
+                        if let Some(range) = byte_range(sql, &tokens[i], &tokens[i + 1]) {
+                            clauses.push((range, SparkTableClause::Using(format)));
+                        }
+                        i += 1;
+                    }
+                }
+                _ if is_keyword(&tokens[i], Keyword::OPTIONS)
+                    && matches!(tokens.get(i + 1).map(|t| &t.token), Some(Token::LParen)) =>
+                {
+                    let start = i;
+                    let (options, end) = parse_options(&tokens, i + 2);
+                    if let Some(range) =
+                        byte_range(sql, &tokens[start], &tokens[end.min(tokens.len() - 1)])
+                    {
+                        clauses.push((range, SparkTableClause::Options(options)));
+                    }
+                    i = end;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+    clauses
+}
+
+/// Parse `key = value` pairs up to the closing parenthesis starting at `i`.
+///
+/// Returns the pairs and the index of the closing parenthesis.
+fn parse_options(tokens: &[TokenWithSpan], mut i: usize) -> (Vec<(String, String)>, usize) {
+    let mut options = Vec::new();
+    let mut key = String::new();
+    let mut value: Option<String> = None;
+    let mut depth = 0usize;
+
+    while i < tokens.len() {
+        match &tokens[i].token {
+            Token::LParen => depth += 1,
+            Token::RParen if depth == 0 => break,
+            Token::RParen => depth -= 1,
+            Token::Comma if depth == 0 => {
+                if let Some(value) = value.take() {
+                    options.push((std::mem::take(&mut key), value));
+                }
+                key.clear();
+            }
+            Token::Eq if depth == 0 => value = Some(String::new()),
+            token => {
+                let text = token_text(&tokens[i]).unwrap_or_else(|| token.to_string());
+                match &mut value {
+                    Some(value) => value.push_str(&text),
+                    None => key.push_str(&text),
+                }
+            }
+        }
+        i += 1;
+    }
+    if let Some(value) = value {
+        options.push((key, value));
+    }
+    (options, i)
+}
+
+fn is_keyword(token: &TokenWithSpan, keyword: Keyword) -> bool {
+    matches!(&token.token, Token::Word(word) if word.keyword == keyword)
+}
+
+/// Word or string literal text, without quotes.
+fn token_text(token: &TokenWithSpan) -> Option<String> {
+    match &token.token {
+        Token::Word(word) => Some(word.value.clone()),
+        Token::SingleQuotedString(s) | Token::DoubleQuotedString(s) => Some(s.clone()),
+        Token::Number(n, _) => Some(n.clone()),
+        Token::Period => Some(".".to_string()),
+        _ => None,
+    }
+}
+
+fn byte_range(sql: &str, first: &TokenWithSpan, last: &TokenWithSpan) -> Option<Range<usize>> {
+    let start = line_col_to_offset(
+        sql,
+        first.span.start.line as usize,
+        first.span.start.column as usize,
+    )?;
+    let end = line_col_to_offset(
+        sql,
+        last.span.end.line as usize,
+        last.span.end.column as usize,
+    )?;
+    Some(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_using_and_options_preserving_length() {
+        let sql = "CREATE TABLE t USING delta OPTIONS ('a' = '1', b.c = true) AS SELECT 1";
+        let masked = mask_spark_table_clauses(sql, Dialect::Databricks);
+        assert_eq!(masked.len(), sql.len());
+        assert_eq!(
+            masked.split_whitespace().collect::<Vec<_>>(),
+            vec!["CREATE", "TABLE", "t", "AS", "SELECT", "1"]
+        );
+    }
+
+    #[test]
+    fn extracts_format_and_options() {
+        let sql = "CREATE TABLE t USING delta OPTIONS ('a' = '1', b.c = true) AS SELECT 1";
+        let clauses: Vec<SparkTableClause> = find_spark_table_clauses(sql, Dialect::Databricks)
+            .into_iter()
+            .map(|(_, clause)| clause)
+            .collect();
+        assert_eq!(
+            clauses,
+            vec![
+                SparkTableClause::Using("delta".to_string()),
+                SparkTableClause::Options(vec![
+                    ("a".to_string(), "1".to_string()),
+                    ("b.c".to_string(), "true".to_string()),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_using_outside_create_table() {
+        let sql = "SELECT * FROM a JOIN b USING (id)";
+        assert!(matches!(
+            mask_spark_table_clauses(sql, Dialect::Databricks),
+            Cow::Borrowed(_)
+        ));
+    }
+}
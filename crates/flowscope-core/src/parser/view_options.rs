@@ -0,0 +1,261 @@
+//! `CREATE VIEW ... WITH (option [= value], ...)` and `WITH [CASCADED | LOCAL] CHECK OPTION`
+//! support.
+//!
+//! sqlparser only accepts `key = value` view options and rejects both Postgres' bare
+//! boolean options (`WITH (security_barrier)`) and the trailing check option. Neither
+//! affects lineage, so before parsing they are blanked out with spaces (keeping byte
+//! offsets stable for statement ranges and spans), and the analyzer reads them back
+//! from the original text to annotate the view.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Token, TokenWithSpan, Tokenizer};
+
+use crate::analyzer::helpers::line_col_to_offset;
+use crate::types::Dialect;
+
+/// An option clause of a `CREATE VIEW`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ViewClause {
+    /// `WITH (key [= value], ...)`; a bare key has the value `true`
+    Options(Vec<(String, String)>),
+    /// `WITH [CASCADED | LOCAL] CHECK OPTION`, lowercased (`cascaded` when omitted)
+    CheckOption(String),
+}
+
+/// Blank out the option clauses of `CREATE VIEW` statements so sqlparser can parse the
+/// rest. Returns the input unchanged when there is nothing to mask.
+pub(crate) fn mask_view_options(sql: &str, dialect: Dialect) -> Cow<'_, str> {
+    let clauses = find_view_clauses(sql, dialect);
+    if clauses.is_empty() {
+        return Cow::Borrowed(sql);
+    }
+
+    let mut bytes = sql.as_bytes().to_vec();
+    for (range, _) in clauses {
+        for byte in &mut bytes[range] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    }
+    // Ranges cover whole tokens, so only complete characters were replaced
+    Cow::Owned(String::from_utf8(bytes).expect("masking preserves UTF-8"))
+}
+
+/// Find the option clauses of every `CREATE VIEW` in `sql`, with their byte ranges.
+pub(crate) fn find_view_clauses(sql: &str, dialect: Dialect) -> Vec<(Range<usize>, ViewClause)> {
+    // Avoid tokenizing buffers that cannot contain the statement
+    if !sql
+        .as_bytes()
+        .windows(4)
+        .any(|w| w.eq_ignore_ascii_case(b"VIEW"))
+    {
+        return Vec::new();
+    }
+    let sqlparser_dialect = dialect.to_sqlparser_dialect();
+    let Ok(tokens) = Tokenizer::new(sqlparser_dialect.as_ref(), sql).tokenize_with_location()
+    else {
+        return Vec::new();
+    };
+    let tokens: Vec<TokenWithSpan> = tokens
+        .into_iter()
+        .filter(|t| !matches!(t.token, Token::Whitespace(_)))
+        .collect();
+
+    let mut clauses = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if !is_keyword(&tokens[i], Keyword::CREATE) {
+            i += 1;
+            continue;
+        }
+        i += 1;
+        while i < tokens.len()
+            && [
+                Keyword::OR,
+                Keyword::REPLACE,
+                Keyword::ALTER,
+                Keyword::TEMP,
+                Keyword::TEMPORARY,
+                Keyword::RECURSIVE,
+                Keyword::MATERIALIZED,
+                Keyword::SECURE,
+            ]
+            .iter()
+            .any(|k| is_keyword(&tokens[i], *k))
+        {
+            i += 1;
+        }
+        if i >= tokens.len() || !is_keyword(&tokens[i], Keyword::VIEW) {
+            continue;
+        }
+
+        // Options come between the view name and `AS`, the check option after the query
+        let mut in_query = false;
+        let mut depth = 0usize;
+        while i < tokens.len() {
+            match &tokens[i].token {
+                Token::LParen => depth += 1,
+                Token::RParen => depth = depth.saturating_sub(1),
+                Token::SemiColon => break,
+                _ if depth > 0 => {}
+                _ if !in_query && is_keyword(&tokens[i], Keyword::AS) => in_query = true,
+                _ if !in_query
+                    && is_keyword(&tokens[i], Keyword::WITH)
+                    && matches!(tokens.get(i + 1).map(|t| &t.token), Some(Token::LParen)) =>
+                {
+                    let start = i;
+                    let (options, end) = parse_options(&tokens, i + 2);
+                    if let Some(range) =
+                        byte_range(sql, &tokens[start], &tokens[end.min(tokens.len() - 1)])
+                    {
+                        clauses.push((range, ViewClause::Options(options)));
+                    }
+                    i = end;
+                }
+                _ if in_query && is_keyword(&tokens[i], Keyword::WITH) => {
+                    if let Some((end, mode)) = check_option_end(&tokens, i + 1) {
+                        if let Some(range) = byte_range(sql, &tokens[i], &tokens[end]) {
+                            clauses.push((range, ViewClause::CheckOption(mode)));
+                        }
+                        i = end;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+    clauses
+}
+
+/// Match `[CASCADED | LOCAL] CHECK OPTION` starting at `i`, returning the index of
+/// `OPTION` and the check mode.
+fn check_option_end(tokens: &[TokenWithSpan], mut i: usize) -> Option<(usize, String)> {
+    let mut mode = "cascaded".to_string();
+    if let Some(Token::Word(word)) = tokens.get(i).map(|t| &t.token) {
+        if word.keyword == Keyword::CASCADED || word.keyword == Keyword::LOCAL {
+            mode = word.value.to_lowercase();
+            i += 1;
+        }
+    }
+    let is_check = tokens.get(i).is_some_and(|t| is_keyword(t, Keyword::CHECK))
+        && tokens
+            .get(i + 1)
+            .is_some_and(|t| is_keyword(t, Keyword::OPTION));
+    is_check.then_some((i + 1, mode))
+}
+
+/// Parse `key [= value]` entries up to the closing parenthesis starting at `i`.
+///
+/// Returns the entries and the index of the closing parenthesis.
+fn parse_options(tokens: &[TokenWithSpan], mut i: usize) -> (Vec<(String, String)>, usize) {
+    let mut options = Vec::new();
+    let mut key = String::new();
+    let mut value: Option<String> = None;
+    let mut depth = 0usize;
+
+    let mut finish = |key: &mut String, value: &mut Option<String>| {
+        if !key.is_empty() {
+            let value = value.take().unwrap_or_else(|| "true".to_string());
+            options.push((std::mem::take(key), value));
+        }
+        value.take();
+    };
+    while i < tokens.len() {
+        match &tokens[i].token {
+            Token::LParen => depth += 1,
+            Token::RParen if depth == 0 => break,
+            Token::RParen => depth -= 1,
+            Token::Comma if depth == 0 => finish(&mut key, &mut value),
+            Token::Eq if depth == 0 => value = Some(String::new()),
+            token => {
+                let text = token_text(&tokens[i]).unwrap_or_else(|| token.to_string());
+                match &mut value {
+                    Some(value) => value.push_str(&text),
+                    None => key.push_str(&text),
+                }
+            }
+        }
+        i += 1;
+    }
+    finish(&mut key, &mut value);
+    (options, i)
+}
+
+fn is_keyword(token: &TokenWithSpan, keyword: Keyword) -> bool {
+    matches!(&token.token, Token::Word(word) if word.keyword == keyword && word.quote_style.is_none())
+}
+
+/// Word or string literal text, without quotes.
+fn token_text(token: &TokenWithSpan) -> Option<String> {
+    match &token.token {
+        Token::Word(word) => Some(word.value.clone()),
+        Token::SingleQuotedString(s) | Token::DoubleQuotedString(s) => Some(s.clone()),
+        Token::Number(n, _) => Some(n.clone()),
+        Token::Period => Some(".".to_string()),
+        _ => None,
+    }
+}
+
+fn byte_range(sql: &str, first: &TokenWithSpan, last: &TokenWithSpan) -> Option<Range<usize>> {
+    let start = line_col_to_offset(
+        sql,
+        first.span.start.line as usize,
+        first.span.start.column as usize,
+    )?;
+    let end = line_col_to_offset(
+        sql,
+        last.span.end.line as usize,
+        last.span.end.column as usize,
+    )?;
+    Some(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_options_and_check_option_preserving_length() {
+        let sql = "CREATE VIEW v (a) WITH (security_barrier, check_option = local) \
+                   AS WITH x AS (SELECT 1 AS a) SELECT a FROM x WITH LOCAL CHECK OPTION;\n\
+                   SELECT 1";
+        let masked = mask_view_options(sql, Dialect::Postgres);
+        assert_eq!(masked.len(), sql.len());
+        assert_eq!(
+            masked.split_whitespace().collect::<Vec<_>>(),
+            vec![
+                "CREATE", "VIEW", "v", "(a)", "AS", "WITH", "x", "AS", "(SELECT", "1", "AS", "a)",
+                "SELECT", "a", "FROM", "x", ";", "SELECT", "1"
+            ]
+        );
+
+        let clauses: Vec<ViewClause> = find_view_clauses(sql, Dialect::Postgres)
+            .into_iter()
+            .map(|(_, clause)| clause)
+            .collect();
+        assert_eq!(
+            clauses,
+            vec![
+                ViewClause::Options(vec![
+                    ("security_barrier".to_string(), "true".to_string()),
+                    ("check_option".to_string(), "local".to_string()),
+                ]),
+                ViewClause::CheckOption("local".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_with_outside_create_view() {
+        let sql = "WITH v AS (SELECT 1) SELECT * FROM v; CREATE TABLE view_log (id INT)";
+        assert!(matches!(
+            mask_view_options(sql, Dialect::Postgres),
+            Cow::Borrowed(_)
+        ));
+    }
+}
@@ -12,8 +12,9 @@
 //!   attribute access like `.schema`, `.identifier`. Doesn't resolve package.yml dependencies
 //!   or handle versioned models (`v=N` parameter is ignored)
 //! - **`source()`**: Returns a `RelationEmulator` with `schema.table` format
-//! - **`var()`**: Falls back to the variable name if undefined (1-arg form) rather than
-//!   erroring like real dbt would
+//! - **`var()`**: Uses the default (second or `default=` argument) when the variable is not
+//!   in `vars`; without one, falls back to the variable name rather than erroring like
+//!   real dbt would
 //! - **`is_incremental()`**: Always returns `false`
 //! - **`this`**: Returns a `RelationEmulator` when `model_name` is provided in context
 //! - **`execute`**: Always `false` for static analysis
@@ -22,6 +23,7 @@
 //! For accurate lineage of complex dbt projects, consider using dbt's native `compile`
 //! command and analyzing the rendered SQL.
 
+use minijinja::value::{from_args, Kwargs};
 use minijinja::{Environment, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -216,31 +218,23 @@ pub(crate) fn register_dbt_builtins(
     // config(...) -> returns empty string (configuration macro, no SQL output)
     env.add_function("config", |_args: &[Value]| -> Value { Value::from("") });
 
-    // var('name') or var('name', 'default') -> returns variable value or default
+    // var('name'), var('name', 'default') or var('name', default='default') -> returns
+    // variable value or default
     let vars_clone = vars.clone();
     env.add_function(
         "var",
         move |args: &[Value]| -> Result<Value, minijinja::Error> {
-            match args.len() {
-                1 => {
-                    let name = args[0].as_str().unwrap_or("");
-                    match vars_clone.get(name) {
-                        Some(v) => Ok(v.clone()),
-                        None => Ok(Value::from(name)), // Return the var name as fallback
-                    }
-                }
-                2 => {
-                    let name = args[0].as_str().unwrap_or("");
-                    let default = &args[1];
-                    match vars_clone.get(name) {
-                        Some(v) => Ok(v.clone()),
-                        None => Ok(default.clone()),
-                    }
-                }
-                _ => Err(minijinja::Error::new(
-                    minijinja::ErrorKind::InvalidOperation,
-                    "var() expects 1 or 2 arguments",
-                )),
+            let (name, default, kwargs): (Value, Option<Value>, Kwargs) = from_args(args)?;
+            let default = match default {
+                Some(default) => Some(default),
+                None => kwargs.get::<Option<Value>>("default")?,
+            };
+            kwargs.assert_all_used()?;
+
+            let name = name.as_str().unwrap_or("");
+            match vars_clone.get(name) {
+                Some(v) => Ok(v.clone()),
+                None => Ok(default.unwrap_or_else(|| Value::from(name))), // Return the var name as fallback
             }
         },
     );
@@ -630,6 +624,18 @@ mod tests {
         assert_eq!(result, "SELECT * FROM public.users");
     }
 
+    #[test]
+    fn var_with_keyword_default() {
+        let ctx = HashMap::new();
+        let result = render_dbt(
+            "SELECT * FROM {{ var('schema', default='analytics') }}.users",
+            &ctx,
+        )
+        .unwrap();
+        assert_eq!(result, "SELECT * FROM analytics.users");
+        assert!(render_dbt("{{ var('schema', fallback='analytics') }}", &ctx).is_err());
+    }
+
     #[test]
     fn var_from_context() {
         let mut ctx = HashMap::new();
@@ -58,6 +58,21 @@ pub struct Issue {
     /// Optional: source file name where the issue occurred
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source_name: Option<String>,
+
+    /// Optional: an edit to the source SQL that resolves the issue
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<IssueFix>,
+}
+
+/// A machine-applicable edit resolving an issue: `span` of the source SQL is replaced
+/// with `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueFix {
+    /// Byte range of the source SQL to replace
+    pub span: Span,
+    /// Text to put in its place
+    pub replacement: String,
 }
 
 impl Issue {
@@ -69,6 +84,7 @@ impl Issue {
             span: None,
             statement_index: None,
             source_name: None,
+            fix: None,
         }
     }
 
@@ -80,6 +96,7 @@ impl Issue {
             span: None,
             statement_index: None,
             source_name: None,
+            fix: None,
         }
     }
 
@@ -91,6 +108,7 @@ impl Issue {
             span: None,
             statement_index: None,
             source_name: None,
+            fix: None,
         }
     }
 
@@ -108,6 +126,14 @@ impl Issue {
         self.source_name = Some(name.into());
         self
     }
+
+    pub fn with_fix(mut self, span: Span, replacement: impl Into<String>) -> Self {
+        self.fix = Some(IssueFix {
+            span,
+            replacement: replacement.into(),
+        });
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -158,6 +184,23 @@ pub struct Summary {
 
     /// Quick check: true if any errors were encountered
     pub has_errors: bool,
+
+    /// Per-statement parse and analysis durations, when `collect_timings` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Vec<StatementTiming>>,
+}
+
+/// How long one statement took to process.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementTiming {
+    /// Index of the statement
+    pub statement_index: usize,
+    /// Time spent parsing, in microseconds. A source is parsed as a whole, so its parse
+    /// time is shared among its statements in proportion to their length.
+    pub parse_micros: u64,
+    /// Time spent analyzing, in microseconds
+    pub analysis_micros: u64,
 }
 
 /// Counts of issues by severity level.
@@ -189,6 +232,30 @@ pub mod issue_codes {
     pub const SCHEMA_CONFLICT: &str = "SCHEMA_CONFLICT";
     pub const TEMPLATE_ERROR: &str = "TEMPLATE_ERROR";
     pub const TYPE_MISMATCH: &str = "TYPE_MISMATCH";
+    pub const IGNORED_TABLES: &str = "IGNORED_TABLES";
+    pub const DEAD_COLUMN: &str = "DEAD_COLUMN";
+    pub const INCONSISTENT_DATETIME_STYLE: &str = "INCONSISTENT_DATETIME_STYLE";
+    pub const NOT_IN_NULLABLE_SUBQUERY: &str = "NOT_IN_NULLABLE_SUBQUERY";
+    pub const UNQUALIFIED_WILDCARD: &str = "UNQUALIFIED_WILDCARD";
+    pub const AMBIGUOUS_ORDER_BY: &str = "AMBIGUOUS_ORDER_BY";
+    pub const DEEPLY_NESTED_SUBQUERY: &str = "DEEPLY_NESTED_SUBQUERY";
+    pub const INSERT_COLUMN_ORDER_MISMATCH: &str = "INSERT_COLUMN_ORDER_MISMATCH";
+    pub const RESERVED_KEYWORD_IDENTIFIER: &str = "RESERVED_KEYWORD_IDENTIFIER";
+    pub const IMPLICIT_TABLE_ALIAS: &str = "IMPLICIT_TABLE_ALIAS";
+    pub const RESERVED_KEYWORD_COLUMN_ALIAS: &str = "RESERVED_KEYWORD_COLUMN_ALIAS";
+    pub const UNORDERED_ROW_LIMIT: &str = "UNORDERED_ROW_LIMIT";
+    pub const JOIN_KEY_TYPE_MISMATCH: &str = "JOIN_KEY_TYPE_MISMATCH";
+    pub const DUPLICATE_PROJECTION: &str = "DUPLICATE_PROJECTION";
+    pub const CONSTANT_PREDICATE: &str = "CONSTANT_PREDICATE";
+    pub const AGGREGATE_CASE_WITHOUT_ELSE: &str = "AGGREGATE_CASE_WITHOUT_ELSE";
+    pub const INCONSISTENT_QUALIFICATION: &str = "INCONSISTENT_QUALIFICATION";
+    pub const UNBOUNDED_RECURSION: &str = "UNBOUNDED_RECURSION";
+    pub const LONG_LINE: &str = "LONG_LINE";
+    pub const REDUNDANT_CAST: &str = "REDUNDANT_CAST";
+    pub const IDENTIFIER_TOO_LONG: &str = "IDENTIFIER_TOO_LONG";
+    pub const RESERVED_IDENTIFIER_PREFIX: &str = "RESERVED_IDENTIFIER_PREFIX";
+    pub const SCHEMA_UNKNOWN_TABLE: &str = "SCHEMA_UNKNOWN_TABLE";
+    pub const MISSING_TRAILING_NEWLINE: &str = "MISSING_TRAILING_NEWLINE";
 }
 
 #[cfg(test)]
@@ -0,0 +1,328 @@
+//! Read-only catalog of the issue codes the analyzer emits.
+
+use serde::Serialize;
+
+use super::common::{issue_codes, Severity};
+use Severity::{Error, Info, Warning};
+
+/// Describes one issue code for tooling such as editors and docs generators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueCodeInfo {
+    /// The code carried by `Issue.code`
+    pub code: &'static str,
+    /// Severity the code is usually reported with; some checks downgrade or
+    /// escalate individual issues (see docs/error-codes.md)
+    pub default_severity: Severity,
+    /// The `AnalysisOptions` field (camelCase) that enables or tunes the check;
+    /// `None` when the code is always reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub option: Option<&'static str>,
+    /// The closest SQLFluff rule, when there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sqlfluff_rule: Option<&'static str>,
+    /// True for codes that are no longer emitted
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// One-line summary of what the code reports
+    pub description: &'static str,
+}
+
+impl IssueCodeInfo {
+    const fn new(
+        code: &'static str,
+        default_severity: Severity,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            code,
+            default_severity,
+            option: None,
+            sqlfluff_rule: None,
+            deprecated: false,
+            description,
+        }
+    }
+
+    const fn option(mut self, option: &'static str) -> Self {
+        self.option = Some(option);
+        self
+    }
+
+    const fn sqlfluff_rule(mut self, rule: &'static str) -> Self {
+        self.sqlfluff_rule = Some(rule);
+        self
+    }
+
+    const fn deprecated(mut self) -> Self {
+        self.deprecated = true;
+        self
+    }
+}
+
+const ISSUE_CATALOG: &[IssueCodeInfo] = &[
+    IssueCodeInfo::new(
+        issue_codes::PARSE_ERROR,
+        Error,
+        "SQL syntax error; statement lineage skipped",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::INVALID_REQUEST,
+        Error,
+        "Request payload invalid or missing required fields",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::DIALECT_FALLBACK,
+        Info,
+        "Reserved for dialect fallback behavior",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::UNSUPPORTED_SYNTAX,
+        Warning,
+        "Statement parsed but not fully supported",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::UNSUPPORTED_RECURSIVE_CTE,
+        Warning,
+        "Recursive CTE not supported; recursive CTEs are now analyzed",
+    )
+    .deprecated(),
+    IssueCodeInfo::new(
+        issue_codes::APPROXIMATE_LINEAGE,
+        Info,
+        "Lineage is approximate due to missing schema",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::UNKNOWN_COLUMN,
+        Warning,
+        "Column reference not resolved from schema",
+    )
+    .option("unresolvedAsErrors"),
+    IssueCodeInfo::new(
+        issue_codes::UNKNOWN_TABLE,
+        Warning,
+        "Table reference not resolved from schema",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::UNRESOLVED_REFERENCE,
+        Warning,
+        "Cross-statement reference not produced earlier",
+    )
+    .option("unresolvedAsErrors"),
+    IssueCodeInfo::new(
+        issue_codes::CANCELLED,
+        Info,
+        "Reserved for host-initiated cancellation",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::PAYLOAD_SIZE_WARNING,
+        Warning,
+        "Reserved for large payload warnings",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::MEMORY_LIMIT_EXCEEDED,
+        Error,
+        "Reserved for host/runtime memory limits",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::SCHEMA_CONFLICT,
+        Warning,
+        "Imported schema conflicts with implied schema",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::TEMPLATE_ERROR,
+        Error,
+        "Template rendering failed",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::TYPE_MISMATCH,
+        Warning,
+        "Type incompatibility detected in an expression",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::IGNORED_TABLES,
+        Info,
+        "Tables matching ignore patterns were removed from the lineage graph",
+    )
+    .option("ignoreTables"),
+    IssueCodeInfo::new(
+        issue_codes::DEAD_COLUMN,
+        Info,
+        "Intermediate model column not used by any downstream statement",
+    )
+    .option("reportDeadColumns"),
+    IssueCodeInfo::new(
+        issue_codes::INCONSISTENT_DATETIME_STYLE,
+        Warning,
+        "File uses a current date/time function style other than the project's dominant one",
+    )
+    .option("checkDatetimeConsistency"),
+    IssueCodeInfo::new(
+        issue_codes::NOT_IN_NULLABLE_SUBQUERY,
+        Warning,
+        "NOT IN (subquery) projects a column that may be NULL",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::UNQUALIFIED_WILDCARD,
+        Warning,
+        "Bare SELECT * reads from more than one FROM source",
+    )
+    .option("checkUnqualifiedWildcards"),
+    IssueCodeInfo::new(
+        issue_codes::AMBIGUOUS_ORDER_BY,
+        Warning,
+        "ORDER BY name matches more than one column",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::DEEPLY_NESTED_SUBQUERY,
+        Warning,
+        "Subqueries nest deeper than the configured depth",
+    )
+    .option("maxSubqueryDepth"),
+    IssueCodeInfo::new(
+        issue_codes::INSERT_COLUMN_ORDER_MISMATCH,
+        Warning,
+        "INSERT ... SELECT columns appear to be written to the wrong target columns",
+    ),
+    IssueCodeInfo::new(
+        issue_codes::RESERVED_KEYWORD_IDENTIFIER,
+        Warning,
+        "Table, column or alias named after a reserved keyword",
+    )
+    .option("checkReservedKeywords")
+    .sqlfluff_rule("references.keywords"),
+    IssueCodeInfo::new(
+        issue_codes::IMPLICIT_TABLE_ALIAS,
+        Warning,
+        "Table alias written without AS",
+    )
+    .option("checkImplicitAliases")
+    .sqlfluff_rule("aliasing.table"),
+    IssueCodeInfo::new(
+        issue_codes::RESERVED_KEYWORD_COLUMN_ALIAS,
+        Warning,
+        "Output column aliased to an unquoted reserved keyword",
+    )
+    .option("checkReservedKeywords"),
+    IssueCodeInfo::new(
+        issue_codes::UNORDERED_ROW_LIMIT,
+        Warning,
+        "LIMIT, OFFSET, FETCH or TOP without ORDER BY",
+    )
+    .option("checkUnorderedLimits"),
+    IssueCodeInfo::new(
+        issue_codes::JOIN_KEY_TYPE_MISMATCH,
+        Warning,
+        "Equi-join compares columns of incompatible schema types",
+    )
+    .option("checkJoinKeyTypes"),
+    IssueCodeInfo::new(
+        issue_codes::DUPLICATE_PROJECTION,
+        Warning,
+        "SELECT list projects the same expression more than once",
+    )
+    .option("checkDuplicateProjections"),
+    IssueCodeInfo::new(
+        issue_codes::CONSTANT_PREDICATE,
+        Warning,
+        "WHERE or HAVING condition is always true or always false",
+    )
+    .option("checkConstantPredicates"),
+    IssueCodeInfo::new(
+        issue_codes::AGGREGATE_CASE_WITHOUT_ELSE,
+        Warning,
+        "SUM or AVG over a CASE without ELSE",
+    )
+    .option("checkAggregateCaseElse"),
+    IssueCodeInfo::new(
+        issue_codes::INCONSISTENT_QUALIFICATION,
+        Warning,
+        "Table referenced both schema-qualified and unqualified",
+    )
+    .option("checkQualificationConsistency"),
+    IssueCodeInfo::new(
+        issue_codes::UNBOUNDED_RECURSION,
+        Warning,
+        "Recursive CTE has no WHERE condition bounding the recursion",
+    )
+    .option("checkRecursiveTermination"),
+    IssueCodeInfo::new(
+        issue_codes::LONG_LINE,
+        Info,
+        "Line longer than the configured maximum",
+    )
+    .option("maxLineLength")
+    .sqlfluff_rule("layout.long_lines"),
+    IssueCodeInfo::new(
+        issue_codes::REDUNDANT_CAST,
+        Warning,
+        "Nested casts convert a value back to a type it already had",
+    )
+    .option("checkRedundantCasts"),
+    IssueCodeInfo::new(
+        issue_codes::IDENTIFIER_TOO_LONG,
+        Warning,
+        "Identifier longer than the dialect's limit",
+    )
+    .option("checkIdentifierLength"),
+    IssueCodeInfo::new(
+        issue_codes::RESERVED_IDENTIFIER_PREFIX,
+        Warning,
+        "Identifier starts with a prefix reserved by the platform",
+    )
+    .option("reservedIdentifierPrefixes"),
+    IssueCodeInfo::new(
+        issue_codes::SCHEMA_UNKNOWN_TABLE,
+        Warning,
+        "SELECT * reads a table missing from the provided schema",
+    )
+    .option("warnUnknownWildcardTables"),
+    IssueCodeInfo::new(
+        issue_codes::MISSING_TRAILING_NEWLINE,
+        Info,
+        "Source does not end with a newline",
+    )
+    .option("checkTrailingNewline")
+    .sqlfluff_rule("layout.end_of_file"),
+];
+
+/// Every issue code the analyzer can emit, in the order of [`issue_codes`].
+///
+/// The catalog is static, so tooling can list the codes and the options that
+/// enable them without running an analysis.
+pub fn issue_catalog() -> &'static [IssueCodeInfo] {
+    ISSUE_CATALOG
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_catalog_lists_every_issue_code_once() {
+        let declared: Vec<&str> = include_str!("common.rs")
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("pub const "))
+            .filter_map(|rest| rest.split(':').next())
+            .collect();
+        let listed: Vec<&str> = issue_catalog().iter().map(|info| info.code).collect();
+
+        assert_eq!(listed, declared);
+        let unique: HashSet<&str> = listed.iter().copied().collect();
+        assert_eq!(unique.len(), listed.len());
+    }
+
+    #[test]
+    fn test_catalog_serializes_camel_case() {
+        let info = issue_catalog()
+            .iter()
+            .find(|info| info.code == issue_codes::LONG_LINE)
+            .unwrap();
+        let json = serde_json::to_value(info).unwrap();
+        assert_eq!(json["defaultSeverity"], "info");
+        assert_eq!(json["option"], "maxLineLength");
+        assert_eq!(json["sqlfluffRule"], "layout.long_lines");
+        assert!(json.get("deprecated").is_none());
+    }
+}
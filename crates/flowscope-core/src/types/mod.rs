@@ -6,18 +6,23 @@
 
 mod common;
 mod completion;
+mod issue_catalog;
 mod legacy;
 mod request;
 mod response;
 pub mod serde_utils;
 
 // Re-export all public types
-pub use common::{issue_codes, CaseSensitivity, Issue, IssueCount, Severity, Span, Summary};
+pub use common::{
+    issue_codes, CaseSensitivity, Issue, IssueCount, IssueFix, Severity, Span, StatementTiming,
+    Summary,
+};
 pub use completion::{
     CompletionClause, CompletionColumn, CompletionContext, CompletionItem, CompletionItemCategory,
     CompletionItemKind, CompletionItemsResult, CompletionKeywordHints, CompletionKeywordSet,
     CompletionTable, CompletionToken, CompletionTokenKind,
 };
+pub use issue_catalog::{issue_catalog, IssueCodeInfo};
 
 // Re-export internal AST types for crate-internal use only
 pub(crate) use completion::{
@@ -25,14 +30,16 @@ pub(crate) use completion::{
 };
 pub use legacy::LineageResult;
 pub use request::{
-    AnalysisOptions, AnalyzeRequest, ColumnSchema, CompletionRequest, Dialect, FileSource,
-    ForeignKeyRef, SchemaMetadata, SchemaNamespaceHint, SchemaTable, StatementSplitRequest,
+    supported_dialects, AnalysisOptions, AnalyzeRequest, AnalyzeRequestBuilder, ColumnSchema,
+    CompletionRequest, Dialect, FileSource, ForeignKeyRef, ImplicitAliasContext, SchemaMetadata,
+    SchemaNamespaceHint, SchemaTable, StatementSplitRequest,
 };
 #[cfg(feature = "templating")]
 pub use request::{TemplateConfig, TemplateError, TemplateMode};
 pub use response::{
     AggregationInfo, AnalyzeResult, CanonicalName, ConstraintType, Edge, EdgeType,
-    FilterClauseType, FilterPredicate, GlobalEdge, GlobalLineage, GlobalNode, JoinType, Node,
-    NodeType, ResolutionSource, ResolvedColumnSchema, ResolvedSchemaMetadata, ResolvedSchemaTable,
-    SchemaOrigin, StatementLineage, StatementRef, StatementSplitResult, TableConstraintInfo,
+    FilterClauseType, FilterPredicate, GlobalEdge, GlobalLineage, GlobalNode, GraphMetrics,
+    JoinType, Node, NodeType, RenderedSql, ResolutionSource, ResolvedColumnSchema,
+    ResolvedSchemaMetadata, ResolvedSchemaTable, SchemaOrigin, StatementLineage, StatementRef,
+    StatementSplitResult, TableConstraintInfo, TableGraphMetrics,
 };
@@ -44,6 +44,124 @@ pub struct AnalyzeRequest {
     pub template_config: Option<TemplateConfig>,
 }
 
+impl AnalyzeRequest {
+    /// Start building a request. See [`AnalyzeRequestBuilder`].
+    pub fn builder() -> AnalyzeRequestBuilder {
+        AnalyzeRequestBuilder::default()
+    }
+
+    /// Start building a request over a set of source files.
+    ///
+    /// Equivalent to `AnalyzeRequest::builder().files(files)`.
+    pub fn from_sql_files(files: impl IntoIterator<Item = FileSource>) -> AnalyzeRequestBuilder {
+        Self::builder().files(files)
+    }
+}
+
+/// Builder for [`AnalyzeRequest`].
+///
+/// Handles the single-file vs. multi-file shape and the feature-gated template
+/// field so callers don't have to. A lone file with no inline SQL is sent as
+/// `sql` + `source_name`, matching how spans are reported for inline SQL.
+///
+/// ```
+/// use flowscope_core::{AnalyzeRequest, Dialect, FileSource};
+///
+/// let request = AnalyzeRequest::from_sql_files([FileSource {
+///     name: "orders.sql".to_string(),
+///     content: "SELECT id FROM orders".to_string(),
+/// }])
+/// .dialect(Dialect::Postgres)
+/// .build();
+///
+/// assert_eq!(request.source_name.as_deref(), Some("orders.sql"));
+/// assert!(request.files.is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AnalyzeRequestBuilder {
+    sql: String,
+    source_name: Option<String>,
+    files: Vec<FileSource>,
+    dialect: Dialect,
+    options: Option<AnalysisOptions>,
+    schema: Option<SchemaMetadata>,
+    #[cfg(feature = "templating")]
+    template_config: Option<TemplateConfig>,
+}
+
+impl AnalyzeRequestBuilder {
+    /// Inline SQL to analyze.
+    pub fn sql(mut self, sql: impl Into<String>) -> Self {
+        self.sql = sql.into();
+        self
+    }
+
+    /// Source name reported for the inline SQL.
+    pub fn source_name(mut self, name: impl Into<String>) -> Self {
+        self.source_name = Some(name.into());
+        self
+    }
+
+    /// Add one source file.
+    pub fn file(mut self, name: impl Into<String>, content: impl Into<String>) -> Self {
+        self.files.push(FileSource {
+            name: name.into(),
+            content: content.into(),
+        });
+        self
+    }
+
+    /// Add source files.
+    pub fn files(mut self, files: impl IntoIterator<Item = FileSource>) -> Self {
+        self.files.extend(files);
+        self
+    }
+
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    pub fn options(mut self, options: AnalysisOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Schema metadata; `None` leaves the request without schema.
+    pub fn schema(mut self, schema: impl Into<Option<SchemaMetadata>>) -> Self {
+        self.schema = schema.into();
+        self
+    }
+
+    /// Template preprocessing; `None` disables templating.
+    #[cfg(feature = "templating")]
+    pub fn template_config(mut self, config: impl Into<Option<TemplateConfig>>) -> Self {
+        self.template_config = config.into();
+        self
+    }
+
+    pub fn build(self) -> AnalyzeRequest {
+        let (sql, source_name, files) = if self.sql.is_empty() && self.files.len() == 1 {
+            let file = self.files.into_iter().next().expect("one file");
+            (file.content, Some(file.name), None)
+        } else {
+            let files = (!self.files.is_empty()).then_some(self.files);
+            (self.sql, self.source_name, files)
+        };
+
+        AnalyzeRequest {
+            sql,
+            files,
+            dialect: self.dialect,
+            source_name,
+            options: self.options,
+            schema: self.schema,
+            #[cfg(feature = "templating")]
+            template_config: self.template_config,
+        }
+    }
+}
+
 /// A request to compute completion context at a cursor position.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
@@ -106,6 +224,28 @@ pub enum Dialect {
     Sqlite,
 }
 
+/// Every dialect this build can parse, in declaration order.
+///
+/// All dialects are always compiled in; embedders can list these instead of
+/// hardcoding the names, which are the lowercase serde names (`"postgres"`).
+pub fn supported_dialects() -> &'static [Dialect] {
+    &[
+        Dialect::Generic,
+        Dialect::Ansi,
+        Dialect::Bigquery,
+        Dialect::Clickhouse,
+        Dialect::Databricks,
+        Dialect::Duckdb,
+        Dialect::Hive,
+        Dialect::Mssql,
+        Dialect::Mysql,
+        Dialect::Postgres,
+        Dialect::Redshift,
+        Dialect::Snowflake,
+        Dialect::Sqlite,
+    ]
+}
+
 impl Dialect {
     pub fn to_sqlparser_dialect(&self) -> Box<dyn sqlparser::dialect::Dialect> {
         use sqlparser::dialect::{
@@ -160,11 +300,24 @@ pub enum GraphDetailLevel {
     Column,
 }
 
+/// Where implicit table aliases (written without `AS`) are accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ImplicitAliasContext {
+    /// Flag every implicit table alias (default)
+    #[default]
+    None,
+    /// Accept implicit aliases on derived tables (`FROM (SELECT ...) d`)
+    Derived,
+    /// Accept implicit aliases on base tables (`FROM orders o`)
+    Base,
+}
+
 /// Options controlling the analysis behavior.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct AnalysisOptions {
-    /// Enable column-level lineage (Phase 2+, default true when implemented)
+    /// Build column-level lineage (default true); when false, only table-level nodes and edges are produced
     #[serde(default)]
     pub enable_column_lineage: Option<bool>,
 
@@ -175,6 +328,130 @@ pub struct AnalysisOptions {
     /// Hide CTEs from output, creating bypass edges (A→CTE→B becomes A→B)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub hide_ctes: Option<bool>,
+
+    /// Collapse CTEs like `hide_ctes`, but keep their filter predicates on the sources or outputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inline_ctes: Option<bool>,
+
+    /// Table name patterns to exclude from lineage output (globs, or `re:` regexes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_tables: Option<Vec<String>>,
+
+    /// Report columns of intermediate models that no downstream statement uses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_dead_columns: Option<bool>,
+
+    /// Table name patterns treated as terminal models, exempt from dead-column reports
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal_tables: Option<Vec<String>>,
+
+    /// Flag files whose current date/time functions differ from the project's dominant style
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_datetime_consistency: Option<bool>,
+
+    /// Flag unqualified `SELECT *` over more than one FROM source
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_unqualified_wildcards: Option<bool>,
+
+    /// Maximum subquery/derived table nesting depth before a warning (off when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_subquery_depth: Option<usize>,
+
+    /// Flag tables, columns and aliases named after a reserved keyword of the dialect
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_reserved_keywords: Option<bool>,
+
+    /// Replace string and number literals in echoed SQL (expressions, filters, resolved SQL) with placeholders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redact_literals: Option<bool>,
+
+    /// Flag table and derived-table aliases written without `AS`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_implicit_aliases: Option<bool>,
+
+    /// Alias context in which `check_implicit_aliases` accepts implicit aliases
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_implicit_aliases_for: Option<ImplicitAliasContext>,
+
+    /// Flag LIMIT, OFFSET, FETCH and TOP (including `WITH TIES`) on queries without ORDER BY
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_unordered_limits: Option<bool>,
+
+    /// Flag equi-join conditions whose columns have incompatible types in the schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_join_key_types: Option<bool>,
+
+    /// Groups of type names (e.g. `["integer", "text"]`) that `check_join_key_types` accepts joining with one another
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compatible_join_key_types: Option<Vec<Vec<String>>>,
+
+    /// Flag SELECT lists that project the same expression more than once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_duplicate_projections: Option<bool>,
+
+    /// Also flag repeated projections that carry different aliases (`a AS x, a AS y`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_aliased_duplicate_projections: Option<bool>,
+
+    /// Flag WHERE and HAVING conditions that literals alone make always true or always false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_constant_predicates: Option<bool>,
+
+    /// Return each source's SQL after template rendering in `AnalyzeResult.rendered_sql`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_rendered_sql: Option<bool>,
+
+    /// Report tables and columns missing from the provided schema (`UNRESOLVED_REFERENCE`, `UNKNOWN_COLUMN`) as errors instead of warnings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unresolved_as_errors: Option<bool>,
+
+    /// Flag SUM and AVG calls whose argument is a CASE without ELSE (`SUM(CASE WHEN c THEN 1 END)`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_aggregate_case_else: Option<bool>,
+
+    /// Compute fan-in, fan-out, and centrality of each table and view in `AnalyzeResult.graphMetrics`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph_metrics: Option<bool>,
+
+    /// Flag tables and views referenced both schema-qualified and unqualified across the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_qualification_consistency: Option<bool>,
+
+    /// Flag recursive CTEs whose recursive branch has no WHERE condition on the CTE itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_recursive_termination: Option<bool>,
+
+    /// Report lines longer than this many characters as `LONG_LINE` issues (unset disables the check)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_line_length: Option<usize>,
+
+    /// Flag nested casts that convert a value back to a type it already had
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_redundant_casts: Option<bool>,
+
+    /// Record how long each statement took to parse and analyze in `summary.timings`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collect_timings: Option<bool>,
+
+    /// Flag tables, columns and aliases (including generated column names) longer than the dialect's identifier limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_identifier_length: Option<bool>,
+
+    /// Identifier length limit for `check_identifier_length`, overriding the dialect's (e.g. 63 for Postgres, 255 for Snowflake)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_identifier_length: Option<usize>,
+
+    /// Name prefixes (e.g. `pg_`, `sys_`) reserved by the platform; tables, columns and aliases starting with one are flagged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reserved_identifier_prefixes: Option<Vec<String>>,
+
+    /// Warn (`SCHEMA_UNKNOWN_TABLE`) when `SELECT *` reads a table missing from the provided schema (default true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warn_unknown_wildcard_tables: Option<bool>,
+
+    /// Report sources that do not end with a newline as `MISSING_TRAILING_NEWLINE` issues, with a fix adding it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_trailing_newline: Option<bool>,
 }
 
 /// Schema metadata for accurate column and table resolution.
@@ -250,6 +527,9 @@ pub struct ColumnSchema {
     /// Foreign key reference if this column references another table
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub foreign_key: Option<ForeignKeyRef>,
+    /// False if this column is declared NOT NULL (unknown when absent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
 }
 
 /// A foreign key reference to another table's column.
@@ -285,6 +565,80 @@ mod tests {
         assert_eq!(deserialized.dialect, Dialect::Postgres);
     }
 
+    #[test]
+    fn test_supported_dialects_round_trip_through_serde() {
+        let dialects = supported_dialects();
+        assert_eq!(dialects.first(), Some(&Dialect::Generic));
+        for dialect in dialects {
+            let json = serde_json::to_string(dialect).unwrap();
+            let parsed: Dialect = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, *dialect);
+        }
+        let unique: std::collections::HashSet<_> = dialects.iter().collect();
+        assert_eq!(unique.len(), dialects.len());
+    }
+
+    fn file(name: &str, content: &str) -> FileSource {
+        FileSource {
+            name: name.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_builder_single_file_uses_inline_sql() {
+        let request = AnalyzeRequest::from_sql_files([file("a.sql", "SELECT 1")])
+            .dialect(Dialect::Snowflake)
+            .build();
+
+        assert_eq!(request.sql, "SELECT 1");
+        assert_eq!(request.source_name.as_deref(), Some("a.sql"));
+        assert!(request.files.is_none());
+        assert_eq!(request.dialect, Dialect::Snowflake);
+        assert!(request.schema.is_none());
+        assert!(request.options.is_none());
+    }
+
+    #[test]
+    fn test_builder_multiple_files() {
+        let request = AnalyzeRequest::builder()
+            .file("a.sql", "SELECT 1")
+            .files([file("b.sql", "SELECT 2")])
+            .build();
+
+        assert!(request.sql.is_empty());
+        assert!(request.source_name.is_none());
+        let names: Vec<_> = request
+            .files
+            .expect("files")
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        assert_eq!(names, vec!["a.sql", "b.sql"]);
+        assert_eq!(request.dialect, Dialect::Generic);
+    }
+
+    #[test]
+    fn test_builder_with_schema_and_inline_sql() {
+        let schema = SchemaMetadata {
+            default_schema: Some("public".to_string()),
+            ..Default::default()
+        };
+        let request = AnalyzeRequest::builder()
+            .sql("SELECT * FROM users")
+            .source_name("inline.sql")
+            .schema(schema)
+            .build();
+
+        assert_eq!(request.sql, "SELECT * FROM users");
+        assert_eq!(request.source_name.as_deref(), Some("inline.sql"));
+        assert!(request.files.is_none());
+        assert_eq!(
+            request.schema.and_then(|s| s.default_schema).as_deref(),
+            Some("public")
+        );
+    }
+
     #[test]
     fn test_dialect_case_sensitivity() {
         // Postgres folds to lowercase
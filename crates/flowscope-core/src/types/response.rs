@@ -30,6 +30,60 @@ pub struct AnalyzeResult {
     /// Effective schema used during analysis (imported + implied)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub resolved_schema: Option<ResolvedSchemaMetadata>,
+
+    /// SQL of each input source after template rendering (with `return_rendered_sql`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rendered_sql: Vec<RenderedSql>,
+
+    /// Fan-in, fan-out, and centrality of each table and view (with `graph_metrics`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph_metrics: Option<GraphMetrics>,
+}
+
+/// The SQL an input source was analyzed as, after template rendering.
+///
+/// Statement spans of the source point into this text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedSql {
+    /// File name, or the request's source name for inline SQL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_name: Option<String>,
+    /// Rendered SQL text (the original text when no templating was applied)
+    pub sql: String,
+}
+
+/// Dependency metrics of the tables and views in the global lineage.
+///
+/// The metrics treat lineage as a dependency graph: a relation depends on every
+/// relation it reads from, so heavily read hub tables have a high fan-in.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphMetrics {
+    /// One entry per table or view, by descending fan-in
+    pub tables: Vec<TableGraphMetrics>,
+}
+
+/// Graph metrics of one table or view.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TableGraphMetrics {
+    /// ID of the global lineage node
+    pub id: String,
+    /// Qualified name of the relation
+    pub name: String,
+    /// Table or view
+    #[serde(rename = "type")]
+    pub node_type: NodeType,
+    /// Number of relations reading from this one
+    pub fan_in: usize,
+    /// Number of relations this one reads from
+    pub fan_out: usize,
+    /// Share of the shortest dependency paths between other relations that pass
+    /// through this one (0 to 1)
+    pub betweenness: f64,
+    /// PageRank score over the dependency edges; scores of all relations sum to 1
+    pub page_rank: f64,
 }
 
 /// The result of splitting SQL into statement spans.
@@ -72,8 +126,11 @@ impl AnalyzeResult {
                     infos: 0,
                 },
                 has_errors: true,
+                timings: None,
             },
             resolved_schema: None,
+            rendered_sql: Vec::new(),
+            graph_metrics: None,
         }
     }
 }
@@ -421,7 +478,7 @@ impl Edge {
     }
 }
 
-/// A filter predicate from a WHERE, HAVING, or JOIN ON clause.
+/// A filter predicate from a WHERE, HAVING, QUALIFY, or JOIN ON clause.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FilterPredicate {
@@ -442,6 +499,8 @@ pub enum FilterClauseType {
     Having,
     /// JOIN ... ON clause
     JoinOn,
+    /// QUALIFY clause (filters window function results)
+    Qualify,
 }
 
 /// Information about aggregation applied to a column.
@@ -548,6 +607,8 @@ pub enum EdgeType {
     JoinDependency,
     /// Cross-statement dependency
     CrossStatement,
+    /// Recursive CTE reading its own rows (a back-edge from the CTE to itself)
+    RecursiveSelfReference,
 }
 
 /// Global lineage graph spanning all statements in the analyzed SQL.
@@ -659,6 +720,13 @@ pub struct GlobalEdge {
     pub producer_statement: Option<StatementRef>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub consumer_statement: Option<StatementRef>,
+    /// Index of the statement that created this edge; for cross-statement edges,
+    /// the statement reading the table
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_statement: Option<usize>,
+    /// Source file of the statement that created this edge (when known)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
@@ -751,6 +819,10 @@ pub struct ResolvedColumnSchema {
     /// Foreign key reference if this column references another table
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub foreign_key: Option<ForeignKeyRef>,
+
+    /// False if this column is declared NOT NULL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
 }
 
 /// The origin of schema information.
@@ -810,6 +882,8 @@ mod tests {
             issues: vec![],
             summary: Summary::default(),
             resolved_schema: None,
+            rendered_sql: Vec::new(),
+            graph_metrics: None,
         };
 
         let json = serde_json::to_string_pretty(&result).unwrap();
@@ -21,12 +21,14 @@ fn sample_schema() -> SchemaMetadata {
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "email".to_string(),
                         data_type: Some("varchar".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             },
@@ -40,12 +42,14 @@ fn sample_schema() -> SchemaMetadata {
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "total".to_string(),
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             },
@@ -91,12 +95,14 @@ fn schema_with_catalog() -> SchemaMetadata {
                     data_type: Some("integer".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 },
                 ColumnSchema {
                     name: "name".to_string(),
                     data_type: Some("varchar".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 },
             ],
         }],
@@ -121,24 +127,28 @@ fn schema_prefix_matching() -> SchemaMetadata {
                     data_type: Some("integer".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 },
                 ColumnSchema {
                     name: "power_user".to_string(),
                     data_type: Some("boolean".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 },
                 ColumnSchema {
                     name: "email".to_string(),
                     data_type: Some("varchar".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 },
                 ColumnSchema {
                     name: "email_verified".to_string(),
                     data_type: Some("boolean".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 },
             ],
         }],
@@ -297,12 +307,14 @@ fn completion_items_prefers_exact_column_match() {
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "node_details".to_string(),
                         data_type: Some("varchar".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             },
@@ -315,6 +327,7 @@ fn completion_items_prefers_exact_column_match() {
                     data_type: Some("integer".to_string()),
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             },
         ],
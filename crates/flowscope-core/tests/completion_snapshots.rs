@@ -47,18 +47,21 @@ fn sample_schema() -> SchemaMetadata {
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "email".to_string(),
                         data_type: Some("varchar".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "name".to_string(),
                         data_type: Some("varchar".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             },
@@ -72,18 +75,21 @@ fn sample_schema() -> SchemaMetadata {
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "total".to_string(),
                         data_type: Some("decimal".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "user_id".to_string(),
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             },
@@ -97,18 +103,21 @@ fn sample_schema() -> SchemaMetadata {
                         data_type: Some("integer".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "name".to_string(),
                         data_type: Some("varchar".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "price".to_string(),
                         data_type: Some("decimal".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             },
@@ -135,12 +135,14 @@ fn golden_resolved_schema_with_imported_and_implied() {
                         data_type: Some("INTEGER".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "username".to_string(),
                         data_type: Some("TEXT".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             }],
@@ -180,12 +182,14 @@ fn golden_imported_precedence_over_create_table() {
                         data_type: Some("BIGINT".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "imported_col".to_string(),
                         data_type: None,
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             }],
@@ -227,12 +231,14 @@ fn imported_schema_preserves_qualified_names() {
                         data_type: Some("INT".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "email".to_string(),
                         data_type: Some("TEXT".to_string()),
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             }],
@@ -272,12 +278,14 @@ fn imported_schema_resolves_via_default_schema() {
                         data_type: None,
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                     ColumnSchema {
                         name: "email".to_string(),
                         data_type: None,
                         is_primary_key: None,
                         foreign_key: None,
+                        nullable: None,
                     },
                 ],
             }],
@@ -321,6 +329,7 @@ fn imported_schema_resolves_via_search_path_with_catalog() {
                     data_type: None,
                     is_primary_key: None,
                     foreign_key: None,
+                    nullable: None,
                 }],
             }],
         }),
@@ -1,6 +1,6 @@
 use flowscope_core::{
     analyze, issue_codes, AnalyzeRequest, AnalyzeResult, ColumnSchema, ConstraintType, Dialect,
-    Edge, EdgeType, FilterClauseType, JoinType, Node, NodeType, SchemaMetadata,
+    Edge, EdgeType, FileSource, FilterClauseType, JoinType, Node, NodeType, SchemaMetadata,
     SchemaNamespaceHint, SchemaTable, Severity, StatementLineage,
 };
 use rstest::rstest;
@@ -87,6 +87,7 @@ fn column(name: &str) -> ColumnSchema {
         data_type: None,
         is_primary_key: None,
         foreign_key: None,
+        nullable: None,
     }
 }
 
@@ -98,6 +99,7 @@ fn column_typed(name: &str, data_type: &str) -> ColumnSchema {
         data_type: Some(data_type.to_string()),
         is_primary_key: None,
         foreign_key: None,
+        nullable: None,
     }
 }
 
@@ -109,6 +111,7 @@ fn column_pk(name: &str, data_type: &str) -> ColumnSchema {
         data_type: Some(data_type.to_string()),
         is_primary_key: Some(true),
         foreign_key: None,
+        nullable: None,
     }
 }
 
@@ -124,6 +127,7 @@ fn column_fk(name: &str, data_type: &str, ref_table: &str, ref_column: &str) ->
             table: ref_table.to_string(),
             column: ref_column.to_string(),
         }),
+        nullable: None,
     }
 }
 
@@ -372,10 +376,11 @@ fn recursive_ctes_produce_lineage_without_warnings() {
         .find(|n| n.node_type == NodeType::Table && &*n.label == "employees")
         .expect("base table should be present");
 
-    let has_self_edge = stmt
-        .edges
-        .iter()
-        .any(|e| e.from == cte_node.id && e.to == cte_node.id);
+    let has_self_edge = stmt.edges.iter().any(|e| {
+        e.from == cte_node.id
+            && e.to == cte_node.id
+            && e.edge_type == EdgeType::RecursiveSelfReference
+    });
     assert!(
         has_self_edge,
         "recursive CTE should have a self-referential edge to represent recursion"
@@ -391,6 +396,37 @@ fn recursive_ctes_produce_lineage_without_warnings() {
     );
 }
 
+#[test]
+fn recursive_cte_self_reference_is_a_distinct_edge() {
+    let sql = "WITH RECURSIVE nums(n) AS (\
+                 SELECT 1 UNION ALL SELECT n + 1 FROM nums WHERE n < 10\
+               ) SELECT n FROM nums";
+    let result = run_analysis(sql, Dialect::Postgres, None);
+    let stmt = first_statement(&result);
+
+    let cte_node = stmt
+        .nodes
+        .iter()
+        .find(|n| n.node_type == NodeType::Cte && &*n.label == "nums")
+        .expect("cte node should be present");
+    let self_edges: Vec<_> = stmt.edges.iter().filter(|e| e.from == e.to).collect();
+
+    assert_eq!(self_edges.len(), 1, "{self_edges:?}");
+    assert_eq!(self_edges[0].from, cte_node.id);
+    assert_eq!(self_edges[0].edge_type, EdgeType::RecursiveSelfReference);
+
+    // Non-recursive CTEs and plain reads stay data flow
+    let result = run_analysis(
+        "WITH t AS (SELECT id FROM users) SELECT id FROM t",
+        Dialect::Postgres,
+        None,
+    );
+    assert!(first_statement(&result)
+        .edges
+        .iter()
+        .all(|e| e.edge_type != EdgeType::RecursiveSelfReference));
+}
+
 #[test]
 fn derived_tables_and_exists_predicates_produce_complete_lineage() {
     let sql = r#"
@@ -822,6 +858,61 @@ fn ansi_star_with_schema_expands_columns() {
     );
 }
 
+fn archive_schema() -> SchemaMetadata {
+    SchemaMetadata {
+        allow_implied: true,
+        default_catalog: None,
+        default_schema: None,
+        search_path: None,
+        case_sensitivity: None,
+        tables: vec![
+            schema_table(None, None, "users", &["id", "email"]),
+            schema_table(None, None, "archive", &["archived_id", "archived_email"]),
+        ],
+    }
+}
+
+#[test]
+fn ansi_insert_select_star_maps_schema_columns_by_position() {
+    let sql = "INSERT INTO archive SELECT * FROM users;";
+
+    let result = run_analysis(sql, Dialect::Generic, Some(archive_schema()));
+    let lineage = first_statement(&result);
+
+    for (source, target) in [("id", "archived_id"), ("email", "archived_email")] {
+        assert!(
+            has_edge(lineage, source, target, EdgeType::DataFlow),
+            "expected users.{source} -> archive.{target}, nodes: {:?}",
+            column_labels(lineage)
+        );
+    }
+    assert!(
+        !column_labels(lineage).iter().any(|label| label == "*"),
+        "star should be expanded, not left opaque"
+    );
+}
+
+#[test]
+fn ansi_insert_select_star_maps_column_list_by_position() {
+    let sql = "INSERT INTO archive (archived_email, archived_id) SELECT * FROM users;";
+
+    let result = run_analysis(sql, Dialect::Generic, Some(archive_schema()));
+    let lineage = first_statement(&result);
+
+    assert!(has_edge(
+        lineage,
+        "id",
+        "archived_email",
+        EdgeType::DataFlow
+    ));
+    assert!(has_edge(
+        lineage,
+        "email",
+        "archived_id",
+        EdgeType::DataFlow
+    ));
+}
+
 #[test]
 fn ansi_window_functions_produce_derivation_edges() {
     let sql = r#"
@@ -846,6 +937,32 @@ fn ansi_window_functions_produce_derivation_edges() {
     );
 }
 
+#[test]
+fn ansi_named_window_contributes_partition_columns() {
+    let sql = r#"
+        SELECT
+            SUM(amount) OVER w AS running_total,
+            AVG(amount) OVER w AS running_avg
+        FROM orders
+        WINDOW w AS (PARTITION BY user_id ORDER BY created_at);
+    "#;
+
+    let result = run_analysis(sql, Dialect::Postgres, None);
+    let stmt = first_statement(&result);
+    let user_id = find_column_node(stmt, "user_id").expect("partition column node");
+
+    for output in ["running_total", "running_avg"] {
+        let output_node = find_column_node(stmt, output).expect("window output column");
+        assert!(
+            stmt.edges
+                .iter()
+                .any(|edge| edge.from == user_id.id && edge.to == output_node.id),
+            "partition column should be a source of {output}: {:?}",
+            stmt.edges
+        );
+    }
+}
+
 #[test]
 fn ansi_values_clause_requires_no_tables() {
     let sql = r#"
@@ -896,6 +1013,95 @@ fn ansi_unnest_clause_keeps_base_table_lineage() {
     );
 }
 
+/// The column named `column` owned by the node `owner_id`.
+fn owned_column<'a>(lineage: &'a StatementLineage, owner_id: &str, column: &str) -> &'a Node {
+    lineage
+        .edges
+        .iter()
+        .filter(|edge| edge.edge_type == EdgeType::Ownership && &*edge.from == owner_id)
+        .filter_map(|edge| lineage.nodes.iter().find(|node| node.id == edge.to))
+        .find(|node| &*node.label == column)
+        .unwrap_or_else(|| panic!("{owner_id} should own column {column}"))
+}
+
+#[test]
+fn unnest_columns_trace_to_the_array_column() {
+    let sql = r#"
+        SELECT t.item, o.id
+        FROM orders o
+        CROSS JOIN UNNEST(o.items) AS t(item);
+    "#;
+
+    let result = run_analysis(sql, Dialect::Postgres, None);
+    let lineage = first_statement(&result);
+    let unnest = find_cte_node(lineage, "t").expect("UNNEST alias node");
+    let orders = find_table_node(lineage, "orders").expect("orders table");
+
+    let items = owned_column(lineage, &orders.id, "items");
+    let item = owned_column(lineage, &unnest.id, "item");
+    assert!(lineage.edges.iter().any(|edge| edge.from == items.id
+        && edge.to == item.id
+        && edge.edge_type == EdgeType::Derivation));
+
+    let output_item = lineage
+        .edges
+        .iter()
+        .find(|edge| edge.from == item.id && edge.edge_type == EdgeType::DataFlow)
+        .expect("unnested column should flow to the output");
+    assert_ne!(output_item.to, item.id);
+    assert!(
+        result.issues.is_empty(),
+        "unexpected issues: {:?}",
+        result.issues
+    );
+}
+
+#[test]
+fn unnest_position_columns_have_no_upstream() {
+    let sql = r#"
+        SELECT tag, pos, user_id
+        FROM users, UNNEST(tags) AS tag WITH OFFSET AS pos;
+    "#;
+    let result = run_analysis(sql, Dialect::Bigquery, None);
+    let lineage = first_statement(&result);
+    let unnest = find_cte_node(lineage, "tag").expect("UNNEST alias node");
+    let users = find_table_node(lineage, "users").expect("users table");
+
+    let tags = owned_column(lineage, &users.id, "tags");
+    let tag = owned_column(lineage, &unnest.id, "tag");
+    let pos = owned_column(lineage, &unnest.id, "pos");
+    assert!(lineage
+        .edges
+        .iter()
+        .any(|edge| edge.from == tags.id && edge.to == tag.id));
+    assert!(!lineage
+        .edges
+        .iter()
+        .any(|edge| edge.to == pos.id && edge.edge_type != EdgeType::Ownership));
+    // Columns the UNNEST does not produce still belong to the table beside it
+    owned_column(lineage, &users.id, "user_id");
+    assert!(result.issues.is_empty(), "{:?}", result.issues);
+
+    let sql = "SELECT x, y, n FROM orders o, UNNEST(o.a, o.b) WITH ORDINALITY AS t(x, y, n)";
+    let result = run_analysis(sql, Dialect::Postgres, None);
+    let lineage = first_statement(&result);
+    let unnest = find_cte_node(lineage, "t").expect("UNNEST alias node");
+    let orders = find_table_node(lineage, "orders").expect("orders table");
+    for (array, column) in [("a", "x"), ("b", "y")] {
+        let array = owned_column(lineage, &orders.id, array);
+        let column = owned_column(lineage, &unnest.id, column);
+        assert!(lineage
+            .edges
+            .iter()
+            .any(|edge| edge.from == array.id && edge.to == column.id));
+    }
+    let ordinality = owned_column(lineage, &unnest.id, "n");
+    assert!(!lineage
+        .edges
+        .iter()
+        .any(|edge| edge.to == ordinality.id && edge.edge_type != EdgeType::Ownership));
+}
+
 #[test]
 fn ansi_pivot_usage_emits_warning() {
     let sql = r#"
@@ -1786,6 +1992,146 @@ fn snowflake_time_travel_query() {
     );
 }
 
+#[test]
+fn snowflake_connect_by_tracks_base_table_and_prior_columns() {
+    let sql = r#"
+        SELECT employee_id, LEVEL AS depth, ROWNUM AS rn
+        FROM hr.employees
+        START WITH manager_id IS NULL
+        CONNECT BY PRIOR employee_id = manager_id;
+    "#;
+
+    let result = run_analysis(sql, Dialect::Snowflake, None);
+    assert!(
+        result.issues.is_empty(),
+        "unexpected issues: {:?}",
+        result.issues
+    );
+    assert!(collect_table_names(&result).contains("HR.EMPLOYEES"));
+
+    // LEVEL/ROWNUM are pseudo-columns and must not be attributed to the table
+    let stmt = first_statement(&result);
+    let table_columns: Vec<&str> = stmt
+        .nodes
+        .iter()
+        .filter_map(|n| n.qualified_name.as_deref())
+        .filter(|name| name.starts_with("HR.EMPLOYEES."))
+        .collect();
+    assert_eq!(table_columns, vec!["HR.EMPLOYEES.employee_id"]);
+
+    // Columns in the PRIOR relationship belong to the same base table
+    let schema = result.resolved_schema.expect("implied schema");
+    let employees = schema
+        .tables
+        .iter()
+        .find(|t| t.name == "EMPLOYEES")
+        .expect("employees table");
+    let columns: HashSet<&str> = employees.columns.iter().map(|c| c.name.as_str()).collect();
+    assert!(columns.contains("employee_id"));
+    assert!(columns.contains("manager_id"));
+    assert!(!columns.contains("LEVEL"));
+}
+
+#[test]
+fn databricks_create_table_using_location_records_storage_metadata() {
+    let sql = r#"
+        CREATE TABLE sales.orders_delta
+        USING delta
+        OPTIONS ('mergeSchema' = 'true')
+        LOCATION 's3://bucket/orders'
+        AS SELECT id, amount FROM raw.orders;
+    "#;
+
+    let result = run_analysis(sql, Dialect::Databricks, None);
+    assert!(
+        result.issues.is_empty(),
+        "unexpected issues: {:?}",
+        result.issues
+    );
+
+    let stmt = first_statement(&result);
+    assert_eq!(stmt.statement_type, "CREATE_TABLE_AS");
+    let tables = collect_table_names(&result);
+    assert!(tables.contains("sales.orders_delta"));
+    assert!(tables.contains("raw.orders"));
+    assert_eq!(edges_by_type(stmt, EdgeType::DataFlow).len(), 3);
+
+    let target = find_table_node(stmt, "sales.orders_delta").expect("target table");
+    let metadata = target.metadata.as_ref().expect("storage metadata");
+    assert_eq!(metadata["format"], "delta");
+    assert_eq!(metadata["location"], "s3://bucket/orders");
+    assert_eq!(metadata["options"]["mergeSchema"], "true");
+}
+
+#[test]
+fn hive_insert_overwrite_partition_records_spec_and_maps_dynamic_columns() {
+    let sql = r#"
+        CREATE TABLE t (a INT, b INT) PARTITIONED BY (dt STRING, region STRING);
+        INSERT OVERWRITE TABLE t PARTITION (dt = '2024-01-01', region)
+        SELECT x, y, r FROM s;
+    "#;
+
+    for dialect in [Dialect::Hive, Dialect::Databricks] {
+        let result = run_analysis(sql, dialect, None);
+        let stmt = &result.statements[1];
+        assert!(has_edge(stmt, "s", "t", EdgeType::DataFlow));
+
+        let target = find_table_node(stmt, "t").expect("target table");
+        let metadata = target.metadata.as_ref().expect("partition metadata");
+        assert_eq!(metadata["overwrite"], true);
+        assert_eq!(
+            metadata["partition"],
+            serde_json::json!([{ "column": "dt", "value": "2024-01-01" }, { "column": "region" }])
+        );
+
+        // The static partition column takes no select item; the dynamic one takes the last
+        let targets: Vec<&str> = edges_by_type(stmt, EdgeType::DataFlow)
+            .iter()
+            .filter_map(|edge| stmt.nodes.iter().find(|node| node.id == edge.to))
+            .filter(|node| node.node_type == NodeType::Column)
+            .map(|node| &*node.label)
+            .collect();
+        assert_eq!(targets, ["a", "b", "region"]);
+    }
+
+    let result = run_analysis(sql, Dialect::Generic, None);
+    let target = find_table_node(&result.statements[1], "t").expect("target table");
+    assert!(target.metadata.is_none());
+}
+
+#[test]
+fn postgres_security_barrier_view_records_view_options() {
+    let sql = r#"
+        CREATE VIEW public.active_users WITH (security_barrier, check_option = 'local') AS
+        SELECT id, email FROM users WHERE active
+        WITH CASCADED CHECK OPTION;
+    "#;
+
+    let result = run_analysis(sql, Dialect::Postgres, None);
+    assert!(
+        result.issues.is_empty(),
+        "unexpected issues: {:?}",
+        result.issues
+    );
+
+    let stmt = first_statement(&result);
+    assert_eq!(stmt.statement_type, "CREATE_VIEW");
+    let tables = collect_table_names(&result);
+    assert!(tables.contains("users"));
+    assert_eq!(edges_by_type(stmt, EdgeType::DataFlow).len(), 3);
+
+    let view = stmt
+        .nodes
+        .iter()
+        .find(|node| node.node_type == NodeType::View)
+        .expect("view node");
+    assert_eq!(view.qualified_name.as_deref(), Some("public.active_users"));
+    let metadata = view.metadata.as_ref().expect("view metadata");
+    assert_eq!(metadata["options"]["security_barrier"], "true");
+    assert_eq!(metadata["options"]["check_option"], "local");
+    assert_eq!(metadata["check_option"], "cascaded");
+}
+
 #[test]
 fn bigquery_struct_and_array_agg() {
     let sql = r#"
@@ -1937,6 +2283,36 @@ fn bigquery_select_except_excludes_columns() {
     );
 }
 
+#[test]
+fn duckdb_select_exclude_drops_columns_from_expansion() {
+    let sql = "SELECT * EXCLUDE (password, ssn) FROM users";
+    let schema = SchemaMetadata {
+        allow_implied: true,
+        default_catalog: None,
+        default_schema: None,
+        search_path: None,
+        case_sensitivity: None,
+        tables: vec![schema_table(
+            None,
+            None,
+            "users",
+            &["id", "email", "password", "ssn"],
+        )],
+    };
+
+    let result = run_analysis(sql, Dialect::Duckdb, Some(schema));
+    let labels = column_labels(first_statement(&result));
+
+    assert!(labels.iter().any(|label| label == "id"), "{labels:?}");
+    assert!(labels.iter().any(|label| label == "email"), "{labels:?}");
+    assert!(
+        !labels
+            .iter()
+            .any(|label| label == "password" || label == "ssn"),
+        "EXCLUDE columns should not be expanded: {labels:?}"
+    );
+}
+
 #[test]
 fn bigquery_select_replace_transforms() {
     let sql = r#"
@@ -3926,6 +4302,65 @@ fn having_filters_attached_correctly() {
     );
 }
 
+#[test]
+fn qualify_filters_attached_correctly() {
+    let sql = r#"
+        SELECT user_id, event_date
+        FROM events
+        QUALIFY ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY event_date DESC) = 1
+            AND event_date > '2024-01-01'
+    "#;
+
+    let result = run_analysis(sql, Dialect::Snowflake, None);
+    let stmt = first_statement(&result);
+
+    let events_node = find_table_node(stmt, "EVENTS").expect("events table not found");
+
+    eprintln!("events filters: {:?}", events_node.filters);
+
+    // QUALIFY is split by AND like WHERE, and each predicate stays on the table it reads
+    let qualify_filters: Vec<_> = events_node
+        .filters
+        .iter()
+        .filter(|f| f.clause_type == FilterClauseType::Qualify)
+        .collect();
+    assert_eq!(
+        qualify_filters.len(),
+        2,
+        "events should have two QUALIFY filters"
+    );
+    assert!(
+        qualify_filters
+            .iter()
+            .any(|f| f.expression == "event_date > '2024-01-01'"),
+        "events QUALIFY filters should include the event_date predicate"
+    );
+    assert!(
+        qualify_filters
+            .iter()
+            .any(|f| f.expression.contains("ROW_NUMBER()")),
+        "events QUALIFY filters should include the window predicate"
+    );
+
+    let source_column = stmt
+        .nodes
+        .iter()
+        .find(|n| n.node_type == NodeType::Column && &*n.label == "event_date")
+        .expect("event_date source column not found");
+    assert!(
+        stmt.edges.iter().any(|e| e.edge_type == EdgeType::Ownership
+            && e.from == events_node.id
+            && e.to == source_column.id),
+        "event_date should be owned by events"
+    );
+    assert!(
+        stmt.edges
+            .iter()
+            .any(|e| e.edge_type == EdgeType::DataFlow && e.from == source_column.id),
+        "event_date should flow from the source column"
+    );
+}
+
 #[test]
 fn nested_or_predicates_not_split() {
     // OR predicates at the top level should NOT be split by AND
@@ -4886,6 +5321,64 @@ fn update_from_with_subquery() {
     }
 }
 
+/// Asserts that `target.column` is fed by `source.source_column` in the first statement.
+fn assert_update_column_flow(
+    result: &AnalyzeResult,
+    target: &str,
+    column: &str,
+    source: &str,
+    source_column: &str,
+) {
+    let stmt = first_statement(result);
+    let owned = |table: &str, col: &str| {
+        let table_id = &find_table_node(stmt, table)
+            .unwrap_or_else(|| panic!("{table} table node"))
+            .id;
+        stmt.edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Ownership && &e.from == table_id)
+            .filter_map(|e| stmt.nodes.iter().find(|n| n.id == e.to))
+            .find(|n| n.label.as_ref() == col)
+            .unwrap_or_else(|| panic!("{table}.{col} column node"))
+            .id
+            .clone()
+    };
+    let (from, to) = (owned(source, source_column), owned(target, column));
+    assert!(
+        stmt.edges.iter().any(|e| e.from == from && e.to == to),
+        "{source}.{source_column} should feed {target}.{column}"
+    );
+}
+
+#[test]
+fn update_from_traces_set_columns_to_source_postgres() {
+    let sql = "UPDATE t SET x = s.y, z = z + 1 FROM source s WHERE t.id = s.id";
+
+    let result = run_analysis(sql, Dialect::Postgres, None);
+    assert!(!result.summary.has_errors);
+    assert_update_column_flow(&result, "t", "x", "source", "y");
+    assert!(
+        !issue_codes_list(&result).contains(&issue_codes::UNRESOLVED_REFERENCE.to_string()),
+        "SET columns resolve against the target and FROM tables: {:?}",
+        result.issues
+    );
+}
+
+#[test]
+fn update_from_traces_set_columns_to_source_mssql() {
+    // SQL Server names the target by an alias declared in FROM
+    let sql = "UPDATE t SET t.x = s.y FROM target t JOIN source s ON t.id = s.id";
+
+    let result = run_analysis(sql, Dialect::Mssql, None);
+    assert!(!result.summary.has_errors);
+    assert_update_column_flow(&result, "target", "x", "source", "y");
+    let stmt = first_statement(&result);
+    assert!(
+        stmt.edges.iter().all(|e| e.from != e.to),
+        "the target should not flow into itself"
+    );
+}
+
 // ============================================================================
 // NEW TABLE FACTOR TESTS (OPENJSON, XMLTABLE, etc.)
 // ============================================================================
@@ -5438,6 +5931,229 @@ fn test_copy_schema_qualified_table() {
     );
 }
 
+// =============================================================================
+// REFRESH MATERIALIZED VIEW
+// =============================================================================
+
+#[test]
+fn test_refresh_materialized_view_records_view() {
+    let sql = "REFRESH MATERIALIZED VIEW CONCURRENTLY analytics.daily_sales WITH DATA";
+    let result = run_analysis(sql, Dialect::Postgres, None);
+
+    assert!(result.issues.iter().all(|i| i.severity != Severity::Error));
+    let stmt = first_statement(&result);
+    assert_eq!(stmt.statement_type, "REFRESH_MATERIALIZED_VIEW");
+    assert_eq!(stmt.nodes.len(), 1);
+    assert_eq!(stmt.nodes[0].node_type, NodeType::View);
+    assert_eq!(
+        stmt.nodes[0].qualified_name.as_deref(),
+        Some("analytics.daily_sales")
+    );
+}
+
+#[test]
+fn test_refresh_materialized_view_replays_known_definition() {
+    let sql = r#"
+        CREATE MATERIALIZED VIEW mv AS SELECT id, amount FROM orders;
+        REFRESH MATERIALIZED VIEW mv;
+    "#;
+    let result = run_analysis(sql, Dialect::Postgres, None);
+
+    assert!(result.issues.iter().all(|i| i.severity != Severity::Error));
+    let refresh = &result.statements[1];
+    assert_eq!(refresh.statement_type, "REFRESH_MATERIALIZED_VIEW");
+    assert!(find_table_node(refresh, "orders").is_some());
+    assert!(
+        refresh
+            .nodes
+            .iter()
+            .any(|n| n.node_type == NodeType::View && n.label.as_ref() == "mv"),
+        "refresh should rebuild mv"
+    );
+}
+
+// =============================================================================
+// DO BLOCKS
+// =============================================================================
+
+#[test]
+fn test_do_block_insert_select_produces_lineage() {
+    let sql = r#"
+        DO $$
+        DECLARE
+            n integer;
+        BEGIN
+            IF NOT EXISTS (SELECT 1 FROM audit_log) THEN
+                INSERT INTO audit_log (id, note) SELECT id, name FROM users;
+            END IF;
+            RAISE NOTICE 'copied % rows', n;
+        END
+        $$;
+        SELECT note FROM audit_log;
+    "#;
+    let result = run_analysis(sql, Dialect::Postgres, None);
+
+    let codes = issue_codes_list(&result);
+    assert!(
+        !codes.contains(&issue_codes::PARSE_ERROR.to_string()),
+        "codes: {codes:?}"
+    );
+    assert_eq!(result.statements.len(), 2);
+
+    let insert = first_statement(&result);
+    assert_eq!(insert.statement_type, "INSERT");
+    let users = find_table_node(insert, "users").expect("users source");
+    let audit_log = find_table_node(insert, "audit_log").expect("audit_log target");
+    assert!(insert
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::DataFlow && e.from == users.id && e.to == audit_log.id));
+
+    let block = insert
+        .nodes
+        .iter()
+        .find(|n| n.label.as_ref() == "DO block")
+        .expect("DO block node");
+    let span = block.span.expect("block span");
+    // run_analysis trims the SQL before analyzing it
+    let block_sql = &sql.trim()[span.start..span.end];
+    assert!(block_sql.starts_with("DO $$") && block_sql.ends_with("$$"));
+}
+
+// =============================================================================
+// IDEMPOTENT DDL
+// =============================================================================
+
+#[test]
+fn test_repeated_idempotent_creates_coalesce_to_one_node() {
+    let sql = r#"
+        CREATE TABLE IF NOT EXISTS orders (id INT);
+        CREATE OR REPLACE TABLE orders (id INT, customer_id INT, amount INT);
+        CREATE TABLE orders (id INT, customer_id INT);
+        INSERT INTO order_totals SELECT customer_id, amount FROM orders;
+    "#;
+    let result = run_analysis(sql, Dialect::Duckdb, None);
+
+    assert!(result.issues.is_empty(), "issues: {:?}", result.issues);
+    let orders_nodes: Vec<_> = result
+        .global_lineage
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Table && n.label.as_ref() == "orders")
+        .collect();
+    assert_eq!(orders_nodes.len(), 1);
+    assert_eq!(orders_nodes[0].statement_refs.len(), 4);
+
+    let schema = result.resolved_schema.expect("resolved schema");
+    let orders = schema
+        .tables
+        .iter()
+        .find(|t| t.name == "orders")
+        .expect("orders schema");
+    let columns: Vec<&str> = orders.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(columns, ["id", "customer_id", "amount"]);
+}
+
+#[test]
+fn test_or_replace_ctas_keeps_more_complete_definition() {
+    let sql = r#"
+        CREATE TABLE IF NOT EXISTS users (id INT, email TEXT);
+        CREATE OR REPLACE TABLE users AS SELECT id FROM raw_users;
+        CREATE VIEW user_emails AS SELECT id, email FROM users;
+    "#;
+    let result = run_analysis(sql, Dialect::Postgres, None);
+
+    let codes = issue_codes_list(&result);
+    assert!(
+        !codes.contains(&issue_codes::UNKNOWN_COLUMN.to_string()),
+        "codes: {codes:?}"
+    );
+    let table_nodes = result
+        .global_lineage
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Table && n.label.as_ref() == "users")
+        .count();
+    assert_eq!(table_nodes, 1);
+}
+
+// =============================================================================
+// CREATE TABLE LIKE / CLONE
+// =============================================================================
+
+#[rstest]
+#[case(Dialect::Mysql, "CREATE TABLE copy LIKE original", "LIKE")]
+#[case(Dialect::Postgres, "CREATE TABLE copy (LIKE original)", "LIKE")]
+#[case(Dialect::Snowflake, "CREATE TABLE copy CLONE original", "CLONE")]
+fn create_table_copy_inherits_columns_from_original(
+    #[case] dialect: Dialect,
+    #[case] copy_sql: &str,
+    #[case] operation: &str,
+) {
+    let sql = format!("CREATE TABLE original (id INT, email VARCHAR(100));\n{copy_sql};");
+    let result = run_analysis(&sql, dialect, None);
+    assert!(!result.summary.has_errors, "issues: {:?}", result.issues);
+
+    let copy_stmt = &result.statements[1];
+    assert_eq!(copy_stmt.statement_type, "CREATE_TABLE");
+    // Snowflake upper-cases unquoted names
+    let table = |name: &str| {
+        copy_stmt
+            .nodes
+            .iter()
+            .find(|n| n.node_type == NodeType::Table && n.label.eq_ignore_ascii_case(name))
+            .unwrap_or_else(|| panic!("{name} table node"))
+    };
+    let (copy, original) = (table("copy"), table("original"));
+    let copy_edge = copy_stmt
+        .edges
+        .iter()
+        .find(|e| e.from == original.id && e.to == copy.id)
+        .expect("copy edge from original");
+    assert_eq!(copy_edge.edge_type, EdgeType::DataFlow);
+    assert_eq!(copy_edge.operation.as_deref(), Some(operation));
+
+    let owned_columns = |table: &Node| -> Vec<(String, std::sync::Arc<str>)> {
+        copy_stmt
+            .edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Ownership && e.from == table.id)
+            .filter_map(|e| copy_stmt.nodes.iter().find(|n| n.id == e.to))
+            .map(|n| (n.label.to_lowercase(), n.id.clone()))
+            .collect()
+    };
+    let copy_columns = owned_columns(copy);
+    let labels: Vec<&str> = copy_columns.iter().map(|(l, _)| l.as_str()).collect();
+    assert_eq!(labels, ["id", "email"]);
+    for (label, original_column) in owned_columns(original) {
+        let (_, copy_column) = copy_columns
+            .iter()
+            .find(|(l, _)| *l == label)
+            .expect("copied column");
+        assert!(
+            copy_stmt
+                .edges
+                .iter()
+                .any(|e| e.from == original_column && e.to == *copy_column),
+            "original.{label} should feed copy.{label}"
+        );
+    }
+}
+
+#[test]
+fn create_table_like_unknown_original_links_tables_only() {
+    let result = run_analysis("CREATE TABLE copy LIKE original", Dialect::Mysql, None);
+
+    let stmt = first_statement(&result);
+    let copy = find_table_node(stmt, "copy").expect("copy table node");
+    let original = find_table_node(stmt, "original").expect("original table node");
+    assert!(stmt
+        .edges
+        .iter()
+        .any(|e| e.from == original.id && e.to == copy.id));
+    assert!(column_labels(stmt).is_empty());
+}
+
 // =============================================================================
 // UNLOAD STATEMENT LINEAGE
 // =============================================================================
@@ -7616,6 +8332,89 @@ fn type_inference_cte_types_propagate_to_outer_query() {
     );
 }
 
+// =============================================================================
+// Nullability inference tests
+// =============================================================================
+
+/// Extract the inferred nullability from a column node's metadata
+fn get_column_nullable(node: &Node) -> Option<bool> {
+    node.metadata
+        .as_ref()
+        .and_then(|m| m.get("nullable"))
+        .and_then(|v| v.as_bool())
+}
+
+const NULLABILITY_DDL: &str = r#"
+    CREATE TABLE users (id INT PRIMARY KEY, email TEXT NOT NULL, nickname TEXT);
+    CREATE TABLE orders (id INT NOT NULL, user_id INT NOT NULL, total INT NOT NULL);
+"#;
+
+#[test]
+fn nullability_outer_joined_column_is_nullable() {
+    let sql = format!(
+        "{NULLABILITY_DDL}
+        SELECT u.email, o.total, u.nickname
+        FROM users u LEFT JOIN orders o ON o.user_id = u.id"
+    );
+
+    let result = run_analysis(&sql, Dialect::Postgres, None);
+    let stmt = &result.statements[2];
+
+    let email = find_column_node(stmt, "email").expect("email column");
+    let total = find_column_node(stmt, "total").expect("total column");
+    assert_eq!(get_column_nullable(email), Some(false), "NOT NULL column");
+    assert_eq!(
+        get_column_nullable(total),
+        Some(true),
+        "NOT NULL column from the optional side of a LEFT JOIN"
+    );
+    let nickname = find_column_node(stmt, "nickname").expect("nickname column");
+    assert_eq!(
+        get_column_nullable(nickname),
+        None,
+        "nullability not declared in DDL stays unknown"
+    );
+}
+
+#[test]
+fn nullability_coalesce_with_literal_is_not_null() {
+    let sql = format!(
+        "{NULLABILITY_DDL}
+        WITH joined AS (
+            SELECT u.id, o.total FROM users u LEFT JOIN orders o ON o.user_id = u.id
+        )
+        SELECT
+            COALESCE(total, 0) AS total_or_zero,
+            CASE WHEN total > 100 THEN 'large' END AS size,
+            total
+        FROM joined"
+    );
+
+    let result = run_analysis(&sql, Dialect::Postgres, None);
+    let stmt = &result.statements[2];
+
+    let columns: Vec<&Node> = stmt
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Column && n.qualified_name.is_none())
+        .collect();
+    let nullable_of = |label: &str| {
+        columns
+            .iter()
+            .rev()
+            .find(|n| &*n.label == label)
+            .map(|n| get_column_nullable(n))
+            .unwrap_or_else(|| panic!("missing column {label}"))
+    };
+    assert_eq!(nullable_of("total_or_zero"), Some(false));
+    assert_eq!(nullable_of("size"), Some(true), "CASE without ELSE");
+    assert_eq!(
+        nullable_of("total"),
+        Some(true),
+        "nullability propagates through the CTE"
+    );
+}
+
 // =============================================================================
 // Schema-aware type lookup tests (Task 3: Schema-Aware Type Lookup)
 // =============================================================================
@@ -7841,3 +8640,317 @@ fn test_schema_type_normalization() {
         "bool should normalize to BOOLEAN"
     );
 }
+
+#[test]
+fn bigquery_struct_field_access_links_nested_path_to_source_table() {
+    let sql = r#"
+        SELECT u.address.city, profile.name AS profile_name
+        FROM `proj.crm.users` AS u;
+    "#;
+
+    let result = run_analysis(sql, Dialect::Bigquery, None);
+    assert!(
+        result.issues.is_empty(),
+        "unexpected issues: {:?}",
+        result.issues
+    );
+    // `u.address` is struct access on the aliased table, not a table reference
+    assert_eq!(collect_table_names(&result).len(), 1);
+
+    let stmt = first_statement(&result);
+    let source_columns: Vec<&str> = stmt
+        .nodes
+        .iter()
+        .filter(|n| n.node_type == NodeType::Column)
+        .filter_map(|n| n.qualified_name.as_deref())
+        .filter(|name| name.contains("users."))
+        .collect();
+    assert_eq!(source_columns.len(), 2, "{source_columns:?}");
+
+    for (path, output) in [("address.city", "city"), ("profile.name", "profile_name")] {
+        let source = stmt
+            .nodes
+            .iter()
+            .find(|n| n.node_type == NodeType::Column && n.label.as_ref() == path)
+            .unwrap_or_else(|| panic!("missing nested column node {path}"));
+        let target = stmt
+            .nodes
+            .iter()
+            .find(|n| n.qualified_name.is_none() && n.label.as_ref() == output)
+            .unwrap_or_else(|| panic!("missing output column {output}"));
+        assert!(
+            stmt.edges
+                .iter()
+                .any(|e| e.from == source.id && e.to == target.id),
+            "{path} should flow into {output}"
+        );
+    }
+}
+
+// =============================================================================
+// MULTI-TABLE INSERT
+// =============================================================================
+
+#[test]
+fn test_insert_all_maps_source_columns_onto_every_target() {
+    let sql = r#"
+        INSERT ALL
+            INTO a (id, total) VALUES (id, amount * 2)
+            INTO b
+        SELECT id, amount FROM src
+    "#;
+    let result = run_analysis(sql, Dialect::Snowflake, None);
+
+    let codes = issue_codes_list(&result);
+    assert!(
+        !codes.contains(&issue_codes::PARSE_ERROR.to_string()),
+        "codes: {codes:?}"
+    );
+    let insert = first_statement(&result);
+    assert_eq!(insert.statement_type, "INSERT");
+
+    let src = find_table_node(insert, "SRC").expect("src source");
+    let amount = insert
+        .nodes
+        .iter()
+        .find(|n| n.qualified_name.as_deref() == Some("SRC.amount"))
+        .expect("src.amount column");
+
+    // Each target owns a column fed by src.amount
+    for (target, column) in [("A", "TOTAL"), ("B", "AMOUNT")] {
+        let table = find_table_node(insert, target).expect("target table");
+        assert!(insert
+            .edges
+            .iter()
+            .any(|e| e.edge_type == EdgeType::DataFlow && e.from == src.id && e.to == table.id));
+        let owned = insert
+            .edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Ownership && e.from == table.id)
+            .filter_map(|e| insert.nodes.iter().find(|n| n.id == e.to))
+            .find(|n| n.label.as_ref() == column)
+            .unwrap_or_else(|| panic!("{target}.{column} column"));
+        assert!(
+            insert
+                .edges
+                .iter()
+                .any(|e| e.from == amount.id && e.to == owned.id),
+            "src.amount should feed {target}.{column}"
+        );
+    }
+}
+
+// =============================================================================
+// MERGING RESULTS
+// =============================================================================
+
+fn analyze_files(files: &[(&str, &str)]) -> AnalyzeResult {
+    analyze(&AnalyzeRequest {
+        sql: String::new(),
+        files: Some(
+            files
+                .iter()
+                .map(|(name, content)| FileSource {
+                    name: name.to_string(),
+                    content: content.to_string(),
+                })
+                .collect(),
+        ),
+        dialect: Dialect::Generic,
+        source_name: None,
+        options: None,
+        schema: None,
+        #[cfg(feature = "templating")]
+        template_config: None,
+    })
+}
+
+/// Global nodes and edges in a comparable, order-independent form.
+fn global_lineage_shape(result: &AnalyzeResult) -> (Vec<String>, Vec<String>) {
+    let mut nodes: Vec<String> = result
+        .global_lineage
+        .nodes
+        .iter()
+        .map(|node| {
+            let refs: Vec<usize> = node
+                .statement_refs
+                .iter()
+                .map(|r| r.statement_index)
+                .collect();
+            format!("{} {:?} {} {refs:?}", node.id, node.node_type, node.label)
+        })
+        .collect();
+    let mut edges: Vec<String> = result
+        .global_lineage
+        .edges
+        .iter()
+        .map(|edge| {
+            format!(
+                "{} {} -> {} {:?} {:?} {:?} {:?} {:?}",
+                edge.id,
+                edge.from,
+                edge.to,
+                edge.edge_type,
+                edge.producer_statement.as_ref().map(|s| s.statement_index),
+                edge.consumer_statement.as_ref().map(|s| s.statement_index),
+                edge.source_statement,
+                edge.source_file
+            )
+        })
+        .collect();
+    nodes.sort();
+    edges.sort();
+    (nodes, edges)
+}
+
+#[test]
+fn global_edges_record_the_statement_and_file_that_created_them() {
+    let result = analyze_files(&[
+        (
+            "staging.sql",
+            "CREATE TABLE staging_orders AS SELECT id, amount FROM raw_orders;",
+        ),
+        (
+            "revenue.sql",
+            "SELECT 1;\nINSERT INTO revenue SELECT id, amount FROM staging_orders;",
+        ),
+    ]);
+    let node_id = |label: &str, node_type: NodeType| {
+        result
+            .global_lineage
+            .nodes
+            .iter()
+            .find(|n| n.label.as_ref() == label && n.node_type == node_type)
+            .unwrap_or_else(|| panic!("missing {label}"))
+            .id
+            .clone()
+    };
+    let staging = node_id("staging_orders", NodeType::Table);
+    let revenue = node_id("revenue", NodeType::Table);
+
+    // Data flowing out of a table created in another file is attributed to the
+    // statement that reads it
+    let read = result
+        .global_lineage
+        .edges
+        .iter()
+        .find(|e| e.edge_type == EdgeType::DataFlow && e.from == staging && e.to == revenue)
+        .expect("staging_orders -> revenue edge");
+    assert_eq!(read.source_statement, Some(2));
+    assert_eq!(read.source_file.as_deref(), Some("revenue.sql"));
+
+    let cross = result
+        .global_lineage
+        .edges
+        .iter()
+        .find(|e| e.edge_type == EdgeType::CrossStatement)
+        .expect("cross-statement edge");
+    assert_eq!(cross.from, staging);
+    assert_eq!(cross.source_statement, Some(2));
+    assert_eq!(cross.source_file.as_deref(), Some("revenue.sql"));
+
+    // Every edge carries its provenance, and it survives a JSON round trip
+    assert!(result
+        .global_lineage
+        .edges
+        .iter()
+        .all(|e| e.source_statement.is_some() && e.source_file.is_some()));
+    let json = serde_json::to_value(&result).unwrap();
+    assert!(json["globalLineage"]["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|e| e["sourceStatement"] == 2 && e["sourceFile"] == "revenue.sql"));
+}
+
+#[rstest]
+#[case::tables(
+    "CREATE TABLE staging_orders AS SELECT id, amount FROM raw_orders;",
+    "INSERT INTO revenue SELECT id, amount * 2 AS amount FROM staging_orders;\n\
+     SELECT id, amount FROM revenue;"
+)]
+#[case::view(
+    "CREATE TABLE staging_orders AS SELECT id, amount FROM raw_orders;\n\
+     CREATE VIEW big_orders AS SELECT id, amount FROM staging_orders WHERE amount > 100;",
+    "SELECT id, amount FROM big_orders;"
+)]
+fn merged_results_match_combined_analysis(#[case] first: &str, #[case] second: &str) {
+    let combined = analyze_files(&[("a.sql", first), ("b.sql", second)]);
+    let merged = analyze_files(&[("a.sql", first)]).merge(analyze_files(&[("b.sql", second)]));
+
+    assert_eq!(
+        global_lineage_shape(&merged),
+        global_lineage_shape(&combined)
+    );
+    assert!(merged
+        .global_lineage
+        .edges
+        .iter()
+        .any(|e| e.edge_type == EdgeType::CrossStatement
+            && e.producer_statement.as_ref().unwrap().statement_index == 0
+            && e.consumer_statement.as_ref().unwrap().statement_index > 0));
+
+    let statement_indices: Vec<usize> = merged
+        .statements
+        .iter()
+        .map(|s| s.statement_index)
+        .collect();
+    assert_eq!(
+        statement_indices,
+        (0..combined.statements.len()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        merged.summary.statement_count,
+        combined.summary.statement_count
+    );
+    assert_eq!(merged.summary.table_count, combined.summary.table_count);
+    assert_eq!(merged.summary.column_count, combined.summary.column_count);
+    assert_eq!(merged.issues.len(), combined.issues.len());
+}
+
+#[test]
+fn merge_is_deterministic_and_shifts_issues() {
+    let first = "SELECT id FROM orders;";
+    let second = "SELECT id FROM orders;\nSELECT a FROM t1 JOIN t2 ON t1.id = t2.id;";
+    let merge = || analyze_files(&[("a.sql", first)]).merge(analyze_files(&[("b.sql", second)]));
+
+    let merged = merge();
+    assert_eq!(
+        serde_json::to_string(&merged.global_lineage).unwrap(),
+        serde_json::to_string(&merge().global_lineage).unwrap()
+    );
+    let ambiguous = merged
+        .issues
+        .iter()
+        .find(|i| i.code == issue_codes::UNRESOLVED_REFERENCE)
+        .expect("ambiguous column from the second result");
+    assert_eq!(ambiguous.statement_index, Some(2));
+    assert_eq!(merged.summary.issue_count.warnings, 1);
+}
+
+#[test]
+fn merge_shifts_statement_timings() {
+    let timed = |sql: &str| {
+        let mut request = AnalyzeRequest::builder().sql(sql).build();
+        request.options = Some(flowscope_core::AnalysisOptions {
+            collect_timings: Some(true),
+            ..Default::default()
+        });
+        analyze(&request)
+    };
+
+    let merged = timed("SELECT 1; SELECT 2").merge(timed("SELECT 3"));
+    let indices: Vec<usize> = merged
+        .summary
+        .timings
+        .expect("timings")
+        .iter()
+        .map(|t| t.statement_index)
+        .collect();
+    assert_eq!(indices, [0, 1, 2]);
+    assert!(analyze_files(&[("a.sql", "SELECT 1")])
+        .merge(analyze_files(&[("b.sql", "SELECT 2")]))
+        .summary
+        .timings
+        .is_none());
+}
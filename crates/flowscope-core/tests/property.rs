@@ -81,7 +81,7 @@ proptest! {
         prop_assert_eq!(result.summary.statement_count, 1);
         prop_assert!(result.summary.table_count >= 1);
 
-        // Check the table name in global lineage reflects case handling
+        // The canonical name reflects case handling, the label keeps the spelling
         // Find the table node (skip output nodes)
         if let Some(node) = result.global_lineage.nodes.iter().find(|n| n.node_type == NodeType::Table) {
             let expected = match dialect {
@@ -89,7 +89,8 @@ proptest! {
                 Dialect::Mysql => table_name.clone(), // preserves exact case
                 _ => table_name.to_lowercase(), // Postgres, Generic, BigQuery
             };
-            prop_assert_eq!(node.label.as_ref(), expected.as_str());
+            prop_assert_eq!(node.canonical_name.name.as_str(), expected.as_str());
+            prop_assert_eq!(node.label.as_ref(), table_name.as_str());
         }
     }
 
@@ -20,6 +20,8 @@ fn run_snapshot_test(name: &str, sql: &str) {
     let clean_result = prepare_for_snapshot(result);
 
     let mut settings = Settings::clone_current();
+    // Node metadata is a HashMap; sort keys so snapshots are stable
+    settings.set_sort_maps(true);
     settings.set_snapshot_suffix(name);
 
     // We bind the snapshot to the settings to ensure the suffix is used
@@ -44,6 +46,8 @@ fn run_postgres_snapshot_test(name: &str, sql: &str) {
     let clean_result = prepare_for_snapshot(result);
 
     let mut settings = Settings::clone_current();
+    // Node metadata is a HashMap; sort keys so snapshots are stable
+    settings.set_sort_maps(true);
     settings.set_snapshot_suffix(name);
 
     settings.bind(|| {
@@ -272,6 +276,8 @@ fn run_snowflake_snapshot_test(name: &str, sql: &str) {
     let clean_result = prepare_for_snapshot(result);
 
     let mut settings = Settings::clone_current();
+    // Node metadata is a HashMap; sort keys so snapshots are stable
+    settings.set_sort_maps(true);
     settings.set_snapshot_suffix(name);
 
     settings.bind(|| {
@@ -385,6 +391,8 @@ fn run_bigquery_snapshot_test(name: &str, sql: &str) {
     let clean_result = prepare_for_snapshot(result);
 
     let mut settings = Settings::clone_current();
+    // Node metadata is a HashMap; sort keys so snapshots are stable
+    settings.set_sort_maps(true);
     settings.set_snapshot_suffix(name);
 
     settings.bind(|| {
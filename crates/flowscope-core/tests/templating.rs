@@ -194,6 +194,40 @@ fn dbt_ref_two_args() {
     );
 }
 
+#[test]
+#[cfg(feature = "templating")]
+fn dbt_rendered_sql_is_returned_on_request() {
+    let sql = "SELECT o.id\nFROM {{ ref('orders') }} o;\nSELECT 1";
+    let mut request = AnalyzeRequest {
+        sql: sql.to_string(),
+        files: None,
+        dialect: Dialect::Generic,
+        source_name: Some("model.sql".to_string()),
+        options: None,
+        schema: None,
+        template_config: Some(TemplateConfig {
+            mode: TemplateMode::Dbt,
+            context: HashMap::new(),
+        }),
+    };
+    assert!(analyze(&request).rendered_sql.is_empty());
+
+    request.options = Some(flowscope_core::AnalysisOptions {
+        return_rendered_sql: Some(true),
+        ..Default::default()
+    });
+    let result = analyze(&request);
+    assert_eq!(result.rendered_sql.len(), 1);
+    let rendered = &result.rendered_sql[0];
+    assert_eq!(rendered.source_name.as_deref(), Some("model.sql"));
+    assert!(rendered.sql.contains("FROM orders o"), "{}", rendered.sql);
+    assert!(!rendered.sql.contains("{{"), "{}", rendered.sql);
+
+    // Statement spans point into the rendered text
+    let span = result.statements[1].span.expect("statement span");
+    assert_eq!(rendered.sql[span.start..span.end].trim(), "SELECT 1");
+}
+
 #[test]
 #[cfg(feature = "templating")]
 fn dbt_source_macro() {
@@ -277,6 +311,30 @@ fn dbt_var_from_context() {
     );
 }
 
+#[test]
+#[cfg(feature = "templating")]
+fn dbt_var_keyword_default_qualifies_table() {
+    let sql = "SELECT u.id FROM {{ var('schema', default='analytics') }}.users u";
+
+    let result = analyze_with_template(sql, TemplateMode::Dbt, HashMap::new());
+
+    assert!(
+        !result.summary.has_errors,
+        "Analysis should succeed: {:?}",
+        result.issues
+    );
+    // The keyword default fills in the schema just like a positional one
+    assert!(
+        has_table(&result, "analytics.users"),
+        "Should detect 'analytics.users' table: {:?}",
+        result.statements.first().map(|s| &s.nodes)
+    );
+    assert!(result.global_lineage.nodes.iter().any(|node| {
+        node.canonical_name.schema.as_deref() == Some("analytics")
+            && node.canonical_name.name == "users"
+    }));
+}
+
 #[test]
 #[cfg(feature = "templating")]
 fn dbt_is_incremental_returns_false() {
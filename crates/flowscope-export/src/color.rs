@@ -0,0 +1,182 @@
+//! Node coloring for graph exports.
+//!
+//! Groups diagram nodes by schema, originating file, or node type and emits Mermaid
+//! `classDef`/`class` statements so each group shares a color.
+
+use std::collections::{BTreeMap, HashMap};
+
+use flowscope_core::{AnalyzeResult, EdgeType, NodeType};
+
+/// Attribute used to group nodes into colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorBy {
+    /// Schema portion of the qualified table name (`analytics.orders` -> `analytics`)
+    Schema,
+    /// Directory of the script that produces (or first references) the node
+    File,
+    /// Node kind: table, view, CTE, column, or script
+    Type,
+}
+
+/// (fill, stroke) pairs assigned to groups in sorted order.
+const PALETTE: &[(&str, &str)] = &[
+    ("#dbeafe", "#2563eb"),
+    ("#dcfce7", "#16a34a"),
+    ("#fef3c7", "#d97706"),
+    ("#fce7f3", "#db2777"),
+    ("#ede9fe", "#7c3aed"),
+    ("#cffafe", "#0891b2"),
+    ("#fee2e2", "#dc2626"),
+    ("#e0e7ff", "#4f46e5"),
+    ("#ecfccb", "#65a30d"),
+    ("#f1f5f9", "#475569"),
+];
+
+const DEFAULT_GROUP: &str = "default";
+
+/// Resolves the color group of diagram nodes for one analysis result.
+pub(crate) struct NodeGroups {
+    color_by: ColorBy,
+    /// Qualified table name -> source name of its originating script
+    table_files: HashMap<String, String>,
+}
+
+impl NodeGroups {
+    pub(crate) fn new(result: &AnalyzeResult, color_by: ColorBy) -> Self {
+        let table_files = if color_by == ColorBy::File {
+            collect_table_files(result)
+        } else {
+            HashMap::new()
+        };
+        Self {
+            color_by,
+            table_files,
+        }
+    }
+
+    /// Group for a table, view, or CTE keyed by its qualified name.
+    pub(crate) fn table(&self, qualified_name: &str, node_type: NodeType) -> Option<String> {
+        match self.color_by {
+            ColorBy::Schema => Some(schema_of(qualified_name)),
+            ColorBy::File => self
+                .table_files
+                .get(qualified_name)
+                .map(|file| directory_of(file)),
+            ColorBy::Type => Some(node_type_group(node_type).to_string()),
+        }
+    }
+
+    /// Group for a column owned by `table` (a qualified table name).
+    pub(crate) fn column(&self, table: &str) -> Option<String> {
+        match self.color_by {
+            ColorBy::Type => Some("column".to_string()),
+            _ => self.table(table, NodeType::Table),
+        }
+    }
+
+    /// Group for a script node.
+    pub(crate) fn script(&self, source_name: &str) -> Option<String> {
+        match self.color_by {
+            ColorBy::Schema => None,
+            ColorBy::File => Some(directory_of(source_name)),
+            ColorBy::Type => Some("script".to_string()),
+        }
+    }
+}
+
+/// Collects node-to-group assignments and renders them as Mermaid class statements.
+#[derive(Default)]
+pub(crate) struct ClassAssignments {
+    groups: BTreeMap<String, Vec<String>>,
+}
+
+impl ClassAssignments {
+    pub(crate) fn assign(&mut self, node_id: &str, group: Option<String>) {
+        if let Some(group) = group {
+            let ids = self.groups.entry(group).or_default();
+            if !ids.iter().any(|id| id == node_id) {
+                ids.push(node_id.to_string());
+            }
+        }
+    }
+
+    /// Append `classDef` and `class` lines; groups are colored in sorted order so the
+    /// same input always yields the same palette.
+    pub(crate) fn render(&self, lines: &mut Vec<String>) {
+        for (index, (group, ids)) in self.groups.iter().enumerate() {
+            let (fill, stroke) = PALETTE[index % PALETTE.len()];
+            let class = class_name(group);
+            lines.push(format!("    classDef {class} fill:{fill},stroke:{stroke}"));
+            lines.push(format!("    class {} {class}", ids.join(",")));
+        }
+    }
+}
+
+/// Mermaid class name for a group.
+pub(crate) fn class_name(group: &str) -> String {
+    let sanitized: String = group
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("group_{sanitized}")
+}
+
+fn schema_of(qualified_name: &str) -> String {
+    match qualified_name.rsplit_once('.') {
+        Some((schema, _)) => schema.to_string(),
+        None => DEFAULT_GROUP.to_string(),
+    }
+}
+
+fn directory_of(source_name: &str) -> String {
+    match source_name.rsplit_once(['/', '\\']) {
+        Some((dir, _)) if !dir.is_empty() => dir.to_string(),
+        _ => source_name.to_string(),
+    }
+}
+
+fn node_type_group(node_type: NodeType) -> &'static str {
+    match node_type {
+        NodeType::Table => "table",
+        NodeType::View => "view",
+        NodeType::Cte => "cte",
+        NodeType::Output => "output",
+        NodeType::Column => "column",
+    }
+}
+
+/// Map each table to the script that writes it, falling back to the first script
+/// that references it.
+fn collect_table_files(result: &AnalyzeResult) -> HashMap<String, String> {
+    let mut producers = HashMap::new();
+    let mut first_seen = HashMap::new();
+
+    for stmt in &result.statements {
+        let source_name = stmt.source_name.as_deref().unwrap_or(DEFAULT_GROUP);
+        for node in &stmt.nodes {
+            if !node.node_type.is_table_like() {
+                continue;
+            }
+            let key = node
+                .qualified_name
+                .as_deref()
+                .unwrap_or(&node.label)
+                .to_string();
+            let is_written = stmt
+                .edges
+                .iter()
+                .any(|edge| edge.to == node.id && edge.edge_type == EdgeType::DataFlow);
+            if is_written {
+                producers
+                    .entry(key.clone())
+                    .or_insert_with(|| source_name.to_string());
+            }
+            first_seen
+                .entry(key)
+                .or_insert_with(|| source_name.to_string());
+        }
+    }
+
+    first_seen.extend(producers);
+    first_seen
+}
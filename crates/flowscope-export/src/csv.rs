@@ -122,6 +122,8 @@ fn export_column_mappings_csv(result: &AnalyzeResult) -> Result<Vec<u8>, ExportE
             "Target Column",
             "Expression",
             "Edge Type",
+            "Statement",
+            "Source File",
         ])
         .map_err(|err| ExportError::Csv(err.to_string()))?;
 
@@ -134,6 +136,8 @@ fn export_column_mappings_csv(result: &AnalyzeResult) -> Result<Vec<u8>, ExportE
                 mapping.target_column,
                 mapping.expression.unwrap_or_default(),
                 mapping.edge_type,
+                mapping.statement_index.to_string(),
+                mapping.source_file.unwrap_or_default(),
             ])
             .map_err(|err| ExportError::Csv(err.to_string()))?;
     }
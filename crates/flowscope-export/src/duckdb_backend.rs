@@ -4,6 +4,7 @@ use crate::schema::{tables_ddl, views_ddl};
 use crate::ExportError;
 use duckdb::{params, Connection};
 use flowscope_core::AnalyzeResult;
+use std::collections::HashSet;
 use std::fs;
 use tempfile::NamedTempFile;
 
@@ -290,8 +291,8 @@ fn write_global_lineage(conn: &Connection, result: &AnalyzeResult) -> Result<(),
     )?;
 
     let mut edge_stmt = conn.prepare(
-        "INSERT INTO global_edges (id, from_node_id, to_node_id, edge_type)
-         VALUES (?, ?, ?, ?)",
+        "INSERT INTO global_edges (id, from_node_id, to_node_id, edge_type, source_statement, source_file)
+         VALUES (?, ?, ?, ?, ?, ?)",
     )?;
 
     let mut ref_id: i64 = 0;
@@ -323,13 +324,21 @@ fn write_global_lineage(conn: &Connection, result: &AnalyzeResult) -> Result<(),
         }
     }
 
+    // Statements sharing an edge repeat it in the global lineage; global_edges.id is
+    // a PRIMARY KEY, so the first statement's occurrence is kept as in the SQL export
+    let mut written_edge_ids: HashSet<&str> = HashSet::new();
     for edge in &result.global_lineage.edges {
+        if !written_edge_ids.insert(edge.id.as_ref()) {
+            continue;
+        }
         let edge_type = format!("{:?}", edge.edge_type).to_lowercase();
         edge_stmt.execute(params![
             edge.id.as_ref(),
             edge.from.as_ref(),
             edge.to.as_ref(),
             edge_type,
+            edge.source_statement.map(|index| index as i64),
+            edge.source_file.as_deref(),
         ])?;
     }
 
@@ -406,4 +415,34 @@ mod tests {
             .unwrap();
         assert!(join_count > 0);
     }
+
+    #[test]
+    fn test_export_records_edge_provenance() {
+        let request = AnalyzeRequest {
+            sql: "CREATE TABLE a AS SELECT id FROM src; INSERT INTO b SELECT id FROM a".to_string(),
+            files: None,
+            dialect: Dialect::Generic,
+            source_name: Some("etl.sql".to_string()),
+            options: None,
+            schema: None,
+            template_config: None,
+        };
+        let result = analyze(&request);
+        let bytes = export(&result).expect("Export should succeed");
+
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), &bytes).unwrap();
+        let conn = Connection::open(temp_file.path()).unwrap();
+
+        let (statement, file): (i64, String) = conn
+            .query_row(
+                "SELECT source_statement, source_file FROM global_edges
+                 WHERE edge_type = 'crossstatement'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(statement, 1);
+        assert_eq!(file, "etl.sql");
+    }
 }
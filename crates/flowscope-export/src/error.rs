@@ -31,6 +31,9 @@ pub enum ExportError {
 
     #[error("HTML export error: {0}")]
     Html(String),
+
+    #[error("PNG export error: {0}")]
+    Png(String),
 }
 
 #[cfg(feature = "duckdb")]
@@ -58,6 +58,11 @@ pub struct ColumnMapping {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expression: Option<String>,
     pub edge_type: String,
+    /// Index of the statement the mapping comes from
+    pub statement_index: usize,
+    /// Source file of that statement (when known)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -240,6 +245,8 @@ pub fn extract_column_mappings(result: &AnalyzeResult) -> Vec<ColumnMapping> {
                         target_column: target.label.to_string(),
                         expression,
                         edge_type: edge_type_label(edge.edge_type).to_string(),
+                        statement_index: stmt.statement_index,
+                        source_file: stmt.source_name.clone(),
                     });
                 }
             }
@@ -299,5 +306,6 @@ fn edge_type_label(edge_type: EdgeType) -> &'static str {
         EdgeType::Derivation => "derivation",
         EdgeType::JoinDependency => "join_dependency",
         EdgeType::CrossStatement => "cross_statement",
+        EdgeType::RecursiveSelfReference => "recursive_self_reference",
     }
 }
@@ -2,18 +2,20 @@ use chrono::{DateTime, Utc};
 
 use flowscope_core::AnalyzeResult;
 
+use crate::color::ColorBy;
 use crate::extract::{extract_column_mappings, extract_script_info, extract_table_info};
-use crate::mermaid::{export_mermaid, MermaidView};
+use crate::mermaid::{export_mermaid_colored, MermaidView};
 
 pub fn export_html(
     result: &AnalyzeResult,
     project_name: &str,
     exported_at: DateTime<Utc>,
+    color_by: Option<ColorBy>,
 ) -> String {
-    let script_view = export_mermaid(result, MermaidView::Script);
-    let hybrid_view = export_mermaid(result, MermaidView::Hybrid);
-    let table_view = export_mermaid(result, MermaidView::Table);
-    let column_view = export_mermaid(result, MermaidView::Column);
+    let script_view = export_mermaid_colored(result, MermaidView::Script, color_by);
+    let hybrid_view = export_mermaid_colored(result, MermaidView::Hybrid, color_by);
+    let table_view = export_mermaid_colored(result, MermaidView::Table, color_by);
+    let column_view = export_mermaid_colored(result, MermaidView::Column, color_by);
 
     let scripts = extract_script_info(result);
     let tables = extract_table_info(result);
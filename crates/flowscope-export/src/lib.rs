@@ -6,24 +6,32 @@
 //! - **Binary export** (`export_duckdb`): Creates a DuckDB database file (native only)
 //! - **SQL export** (`export_sql`): Generates DDL + INSERT statements (WASM-compatible)
 
+mod color;
 mod csv;
 mod error;
 mod extract;
 mod html;
 mod json;
+mod markdown;
 mod mermaid;
 mod naming;
+mod qualify;
 mod schema;
 mod sql_backend;
+mod turtle;
 mod xlsx;
 
 #[cfg(feature = "duckdb")]
 mod duckdb_backend;
+#[cfg(feature = "png")]
+mod png;
 
+pub use color::ColorBy;
 pub use error::ExportError;
 pub use extract::{ColumnMapping, ScriptInfo, TableDependency, TableInfo, TableType};
 pub use mermaid::MermaidView;
 pub use naming::ExportNaming;
+pub use qualify::qualify_table_names;
 
 use flowscope_core::AnalyzeResult;
 
@@ -35,13 +43,59 @@ pub enum ExportFormat {
     Json { compact: bool },
     Mermaid { view: MermaidView },
     Html,
+    Markdown,
+    Turtle,
     CsvBundle,
     Xlsx,
     Png,
 }
 
+impl ExportFormat {
+    /// Short name of the format, as accepted by the CLI's `--format` and the serve
+    /// mode's `/api/export/{format}`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ExportFormat::DuckDb => "duckdb",
+            ExportFormat::Sql { .. } => "sql",
+            ExportFormat::Json { .. } => "json",
+            ExportFormat::Mermaid { .. } => "mermaid",
+            ExportFormat::Html => "html",
+            ExportFormat::Markdown => "markdown",
+            ExportFormat::Turtle => "turtle",
+            ExportFormat::CsvBundle => "csv",
+            ExportFormat::Xlsx => "xlsx",
+            ExportFormat::Png => "png",
+        }
+    }
+}
+
 pub type Format = ExportFormat;
 
+/// Formats `export` can produce in this build, one per format with default options.
+///
+/// `DuckDb` needs the `duckdb` feature and `Png` the `png` feature; without them
+/// they are left out, since `export` rejects them.
+pub fn supported_formats() -> Vec<ExportFormat> {
+    let mut formats = Vec::new();
+    #[cfg(feature = "duckdb")]
+    formats.push(ExportFormat::DuckDb);
+    formats.extend([
+        ExportFormat::Sql { schema: false },
+        ExportFormat::Json { compact: false },
+        ExportFormat::Mermaid {
+            view: MermaidView::Table,
+        },
+        ExportFormat::Html,
+        ExportFormat::Markdown,
+        ExportFormat::Turtle,
+        ExportFormat::CsvBundle,
+        ExportFormat::Xlsx,
+    ]);
+    #[cfg(feature = "png")]
+    formats.push(ExportFormat::Png);
+    formats
+}
+
 /// Export analysis result to a database file.
 ///
 /// Returns raw bytes of the database file.
@@ -56,10 +110,15 @@ pub fn export(result: &AnalyzeResult, format: ExportFormat) -> Result<Vec<u8>, E
         ExportFormat::Json { compact } => Ok(json::export_json(result, compact)?.into_bytes()),
         ExportFormat::Mermaid { view } => Ok(mermaid::export_mermaid(result, view).into_bytes()),
         ExportFormat::Html => {
-            Ok(html::export_html(result, "FlowScope", chrono::Utc::now()).into_bytes())
+            Ok(html::export_html(result, "FlowScope", chrono::Utc::now(), None).into_bytes())
         }
+        ExportFormat::Markdown => Ok(markdown::export_markdown(result, "FlowScope").into_bytes()),
+        ExportFormat::Turtle => Ok(turtle::export_turtle(result).into_bytes()),
         ExportFormat::CsvBundle => csv::export_csv_bundle(result),
         ExportFormat::Xlsx => xlsx::export_xlsx(result),
+        #[cfg(feature = "png")]
+        ExportFormat::Png => png::export_png(result),
+        #[cfg(not(feature = "png"))]
         ExportFormat::Png => Err(ExportError::UnsupportedFormat("PNG export is UI-only")),
     }
 }
@@ -72,6 +131,14 @@ pub fn export_duckdb(result: &AnalyzeResult) -> Result<Vec<u8>, ExportError> {
     duckdb_backend::export(result)
 }
 
+/// Export the table-level lineage graph as a PNG image.
+///
+/// Requires the `png` feature (native only, not WASM-compatible).
+#[cfg(feature = "png")]
+pub fn export_png(result: &AnalyzeResult) -> Result<Vec<u8>, ExportError> {
+    png::export_png(result)
+}
+
 /// Export analysis result as SQL statements.
 ///
 /// Returns DDL (CREATE TABLE/VIEW) + INSERT statements that can be
@@ -81,6 +148,8 @@ pub fn export_duckdb(result: &AnalyzeResult) -> Result<Vec<u8>, ExportError> {
 ///
 /// If `schema` is provided, all tables and views will be prefixed with that schema
 /// (e.g., "myschema.tablename") and a `CREATE SCHEMA IF NOT EXISTS` statement will be added.
+/// Unqualified table and view names in the exported lineage get the same prefix (see
+/// [`qualify_table_names`]).
 pub fn export_sql(result: &AnalyzeResult, schema: Option<&str>) -> Result<String, ExportError> {
     sql_backend::export_sql(result, schema)
 }
@@ -93,6 +162,34 @@ pub fn export_mermaid(result: &AnalyzeResult, view: MermaidView) -> Result<Strin
     Ok(mermaid::export_mermaid(result, view))
 }
 
+/// Export a Mermaid diagram with nodes colored by schema, file, or node type.
+///
+/// Groups are assigned palette colors in sorted order, so output is deterministic.
+pub fn export_mermaid_colored(
+    result: &AnalyzeResult,
+    view: MermaidView,
+    color_by: ColorBy,
+) -> Result<String, ExportError> {
+    Ok(mermaid::export_mermaid_colored(
+        result,
+        view,
+        Some(color_by),
+    ))
+}
+
+/// Export a Markdown report with summary counts, a table listing, per-script
+/// inputs/outputs, and an embedded Mermaid table-level diagram.
+pub fn export_markdown(result: &AnalyzeResult, project_name: &str) -> Result<String, ExportError> {
+    Ok(markdown::export_markdown(result, project_name))
+}
+
+/// Export the lineage graph as RDF/Turtle: nodes typed with FlowScope ontology classes
+/// (`fs:Table`, `fs:Column`, ...) and edges as predicates such as `fs:hasColumn` and
+/// `fs:derivesFrom`.
+pub fn export_turtle(result: &AnalyzeResult) -> Result<String, ExportError> {
+    Ok(turtle::export_turtle(result))
+}
+
 pub fn export_csv_bundle(result: &AnalyzeResult) -> Result<Vec<u8>, ExportError> {
     csv::export_csv_bundle(result)
 }
@@ -106,5 +203,20 @@ pub fn export_html(
     project_name: &str,
     exported_at: chrono::DateTime<chrono::Utc>,
 ) -> Result<String, ExportError> {
-    Ok(html::export_html(result, project_name, exported_at))
+    Ok(html::export_html(result, project_name, exported_at, None))
+}
+
+/// Export an HTML report whose diagrams color nodes by schema, file, or node type.
+pub fn export_html_colored(
+    result: &AnalyzeResult,
+    project_name: &str,
+    exported_at: chrono::DateTime<chrono::Utc>,
+    color_by: ColorBy,
+) -> Result<String, ExportError> {
+    Ok(html::export_html(
+        result,
+        project_name,
+        exported_at,
+        Some(color_by),
+    ))
 }
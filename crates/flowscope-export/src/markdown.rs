@@ -0,0 +1,105 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use flowscope_core::AnalyzeResult;
+
+use crate::extract::{extract_script_info, extract_table_dependencies, extract_table_info};
+use crate::mermaid::{export_mermaid, MermaidView};
+
+pub fn export_markdown(result: &AnalyzeResult, project_name: &str) -> String {
+    let scripts = extract_script_info(result);
+    let tables = extract_table_info(result);
+
+    let mut sources: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for dependency in extract_table_dependencies(result) {
+        sources
+            .entry(dependency.target_table)
+            .or_default()
+            .insert(dependency.source_table);
+    }
+
+    let summary = &result.summary;
+    let mut lines = vec![
+        format!("# {} - Lineage Report", escape_markdown(project_name)),
+        String::new(),
+        "## Summary".to_string(),
+        String::new(),
+        "| Metric | Count |".to_string(),
+        "| --- | --- |".to_string(),
+        format!("| Statements | {} |", summary.statement_count),
+        format!("| Tables | {} |", summary.table_count),
+        format!("| Columns | {} |", summary.column_count),
+        format!("| Joins | {} |", summary.join_count),
+        format!("| Errors | {} |", summary.issue_count.errors),
+        format!("| Warnings | {} |", summary.issue_count.warnings),
+        String::new(),
+        "## Tables".to_string(),
+        String::new(),
+    ];
+
+    if tables.is_empty() {
+        lines.push("_No tables found._".to_string());
+    } else {
+        lines.push("| Table | Type | Sources |".to_string());
+        lines.push("| --- | --- | --- |".to_string());
+        for table in &tables {
+            let table_sources = sources
+                .get(&table.qualified_name)
+                .map(|names| join_names(names.iter()))
+                .unwrap_or_default();
+            lines.push(format!(
+                "| {} | {} | {} |",
+                escape_markdown(&table.qualified_name),
+                table.table_type,
+                table_sources
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push("## Models".to_string());
+
+    for script in &scripts {
+        lines.push(String::new());
+        lines.push(format!("### {}", escape_markdown(&script.source_name)));
+        lines.push(String::new());
+        lines.push(format!(
+            "- Inputs: {}",
+            names_or_none(script.tables_read.iter())
+        ));
+        lines.push(format!(
+            "- Outputs: {}",
+            names_or_none(script.tables_written.iter())
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push("## Lineage".to_string());
+    lines.push(String::new());
+    lines.push("```mermaid".to_string());
+    lines.push(export_mermaid(result, MermaidView::Table));
+    lines.push("```".to_string());
+    lines.push(String::new());
+
+    lines.join("\n")
+}
+
+fn join_names<'a>(names: impl Iterator<Item = &'a String>) -> String {
+    names
+        .map(|name| format!("`{}`", escape_markdown(name)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn names_or_none<'a>(names: impl Iterator<Item = &'a String>) -> String {
+    let joined = join_names(names);
+    if joined.is_empty() {
+        "_none_".to_string()
+    } else {
+        joined
+    }
+}
+
+/// Escape characters that would break a table cell or heading.
+fn escape_markdown(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
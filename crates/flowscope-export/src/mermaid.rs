@@ -3,6 +3,8 @@ use std::sync::Arc;
 
 use flowscope_core::{AnalyzeResult, EdgeType, NodeType};
 
+use crate::color::{ClassAssignments, ColorBy, NodeGroups};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MermaidView {
     All,
@@ -13,37 +15,48 @@ pub enum MermaidView {
 }
 
 pub fn export_mermaid(result: &AnalyzeResult, view: MermaidView) -> String {
+    export_mermaid_colored(result, view, None)
+}
+
+/// Render a Mermaid view, optionally coloring nodes by the given grouping.
+pub fn export_mermaid_colored(
+    result: &AnalyzeResult,
+    view: MermaidView,
+    color_by: Option<ColorBy>,
+) -> String {
+    let groups = color_by.map(|color_by| NodeGroups::new(result, color_by));
+    let groups = groups.as_ref();
     match view {
-        MermaidView::All => generate_all_views(result),
-        MermaidView::Script => generate_script_view(result),
-        MermaidView::Table => generate_table_view(result),
-        MermaidView::Column => generate_column_view(result),
-        MermaidView::Hybrid => generate_hybrid_view(result),
+        MermaidView::All => generate_all_views(result, groups),
+        MermaidView::Script => generate_script_view(result, groups),
+        MermaidView::Table => generate_table_view(result, groups),
+        MermaidView::Column => generate_column_view(result, groups),
+        MermaidView::Hybrid => generate_hybrid_view(result, groups),
     }
 }
 
-fn generate_all_views(result: &AnalyzeResult) -> String {
+fn generate_all_views(result: &AnalyzeResult, groups: Option<&NodeGroups>) -> String {
     let sections = vec![
         "# Lineage Diagrams".to_string(),
         String::new(),
         "## Script View".to_string(),
         "```mermaid".to_string(),
-        generate_script_view(result),
+        generate_script_view(result, groups),
         "```".to_string(),
         String::new(),
         "## Hybrid View (Scripts + Tables)".to_string(),
         "```mermaid".to_string(),
-        generate_hybrid_view(result),
+        generate_hybrid_view(result, groups),
         "```".to_string(),
         String::new(),
         "## Table View".to_string(),
         "```mermaid".to_string(),
-        generate_table_view(result),
+        generate_table_view(result, groups),
         "```".to_string(),
         String::new(),
         "## Column View".to_string(),
         "```mermaid".to_string(),
-        generate_column_view(result),
+        generate_column_view(result, groups),
         "```".to_string(),
     ];
 
@@ -119,14 +132,18 @@ fn extract_script_info(result: &AnalyzeResult) -> Vec<ScriptInfo> {
     script_map.into_values().collect()
 }
 
-fn generate_script_view(result: &AnalyzeResult) -> String {
+fn generate_script_view(result: &AnalyzeResult, groups: Option<&NodeGroups>) -> String {
     let scripts = extract_script_info(result);
     let mut lines = vec!["flowchart LR".to_string()];
+    let mut classes = ClassAssignments::default();
 
     for script in &scripts {
         let id = sanitize_id(&script.source_name);
         let label = escape_label(&script.source_name);
         lines.push(format!("    {id}[\"{label}\"]"));
+        if let Some(groups) = groups {
+            classes.assign(&id, groups.script(&script.source_name));
+        }
     }
 
     for producer in &scripts {
@@ -169,11 +186,13 @@ fn generate_script_view(result: &AnalyzeResult) -> String {
         }
     }
 
+    classes.render(&mut lines);
     lines.join("\n")
 }
 
-fn generate_table_view(result: &AnalyzeResult) -> String {
+fn generate_table_view(result: &AnalyzeResult, groups: Option<&NodeGroups>) -> String {
     let mut lines = vec!["flowchart LR".to_string()];
+    let mut classes = ClassAssignments::default();
     let mut table_ids: HashMap<String, String> = HashMap::new();
     let mut edges = HashSet::new();
 
@@ -200,10 +219,24 @@ fn generate_table_view(result: &AnalyzeResult) -> String {
                     _ => format!("[\"{escaped_label}\"]"),
                 };
                 lines.push(format!("    {id}{shape}"));
+                if let Some(groups) = groups {
+                    classes.assign(&id, groups.table(&key, node.node_type));
+                }
             }
         }
 
         for edge in &stmt.edges {
+            // Recursive CTEs loop back onto themselves with a dashed edge
+            if edge.edge_type == EdgeType::RecursiveSelfReference {
+                if let Some(node) = table_nodes.iter().find(|node| node.id == edge.from) {
+                    let key = node.qualified_name.as_deref().unwrap_or(&node.label);
+                    if edges.insert(format!("{key}->{key}")) {
+                        let id = &table_ids[key];
+                        lines.push(format!("    {id} -.->|recursive| {id}"));
+                    }
+                }
+                continue;
+            }
             if edge.edge_type == EdgeType::DataFlow || edge.edge_type == EdgeType::Derivation {
                 let source_node = table_nodes.iter().find(|node| node.id == edge.from);
                 let target_node = table_nodes.iter().find(|node| node.id == edge.to);
@@ -239,6 +272,7 @@ fn generate_table_view(result: &AnalyzeResult) -> String {
         }
     }
 
+    classes.render(&mut lines);
     lines.join("\n")
 }
 
@@ -309,8 +343,9 @@ fn extract_column_mappings(result: &AnalyzeResult) -> Vec<ColumnMapping> {
     mappings
 }
 
-fn generate_column_view(result: &AnalyzeResult) -> String {
+fn generate_column_view(result: &AnalyzeResult, groups: Option<&NodeGroups>) -> String {
     let mut lines = vec!["flowchart LR".to_string()];
+    let mut classes = ClassAssignments::default();
     let mappings = extract_column_mappings(result);
     let mut nodes = HashSet::new();
     let mut edges = HashSet::new();
@@ -332,12 +367,18 @@ fn generate_column_view(result: &AnalyzeResult) -> String {
                 "    {source_id}[\"{}\"]",
                 escape_label(&source_label)
             ));
+            if let Some(groups) = groups {
+                classes.assign(&source_id, groups.column(&mapping.source_table));
+            }
         }
         if nodes.insert(target_id.clone()) {
             lines.push(format!(
                 "    {target_id}[\"{}\"]",
                 escape_label(&target_label)
             ));
+            if let Some(groups) = groups {
+                classes.assign(&target_id, groups.column(&mapping.target_table));
+            }
         }
 
         let edge_key = format!("{source_id}->{target_id}");
@@ -350,11 +391,13 @@ fn generate_column_view(result: &AnalyzeResult) -> String {
         }
     }
 
+    classes.render(&mut lines);
     lines.join("\n")
 }
 
-fn generate_hybrid_view(result: &AnalyzeResult) -> String {
+fn generate_hybrid_view(result: &AnalyzeResult, groups: Option<&NodeGroups>) -> String {
     let mut lines = vec!["flowchart LR".to_string()];
+    let mut classes = ClassAssignments::default();
     let scripts = extract_script_info(result);
 
     let mut script_ids = HashMap::new();
@@ -365,6 +408,9 @@ fn generate_hybrid_view(result: &AnalyzeResult) -> String {
             "    {id}{{\"{}\"}}",
             escape_label(&script.source_name)
         ));
+        if let Some(groups) = groups {
+            classes.assign(&id, groups.script(&script.source_name));
+        }
     }
 
     let mut table_ids = HashMap::new();
@@ -380,6 +426,9 @@ fn generate_hybrid_view(result: &AnalyzeResult) -> String {
                     let id = sanitize_id(&format!("table_{}", key));
                     table_ids.insert(key.clone(), id.clone());
                     lines.push(format!("    {id}[\"{}\"]", escape_label(&node.label)));
+                    if let Some(groups) = groups {
+                        classes.assign(&id, groups.table(&key, node.node_type));
+                    }
                 }
             }
         }
@@ -407,5 +456,6 @@ fn generate_hybrid_view(result: &AnalyzeResult) -> String {
         }
     }
 
+    classes.render(&mut lines);
     lines.join("\n")
 }
@@ -53,6 +53,8 @@ fn format_filename_parts(format: ExportFormat) -> (&'static str, &'static str) {
             crate::MermaidView::Hybrid => ("mermaid-hybrid", "md"),
         },
         ExportFormat::Html => ("report", "html"),
+        ExportFormat::Markdown => ("report", "md"),
+        ExportFormat::Turtle => ("lineage", "ttl"),
         ExportFormat::Sql { .. } => ("duckdb", "sql"),
         ExportFormat::CsvBundle => ("csv", "zip"),
         ExportFormat::Xlsx => ("xlsx", "xlsx"),
@@ -0,0 +1,206 @@
+//! PNG export of the table-level lineage graph.
+//!
+//! Tables are laid out left to right in layers (each table one layer after its
+//! furthest upstream dependency), written to SVG, and rasterized with `resvg`.
+//! No browser or Mermaid runtime is involved, so this works headless in CI.
+
+use std::collections::{BTreeMap, HashMap};
+
+use flowscope_core::AnalyzeResult;
+use resvg::{tiny_skia, usvg};
+
+use crate::error::ExportError;
+use crate::extract::{extract_table_dependencies, extract_table_info, TableType};
+
+const NODE_WIDTH: f32 = 200.0;
+const NODE_HEIGHT: f32 = 40.0;
+const LAYER_GAP: f32 = 80.0;
+const ROW_GAP: f32 = 24.0;
+const MARGIN: f32 = 24.0;
+const MAX_LABEL_CHARS: usize = 28;
+
+struct Layout {
+    /// Qualified table name -> (x, y) of the node's top-left corner
+    positions: BTreeMap<String, (f32, f32)>,
+    width: f32,
+    height: f32,
+}
+
+pub(crate) fn export_png(result: &AnalyzeResult) -> Result<Vec<u8>, ExportError> {
+    let svg = render_svg(result);
+
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+    let tree = usvg::Tree::from_str(&svg, &options).map_err(|e| ExportError::Png(e.to_string()))?;
+
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| ExportError::Png("diagram has zero size".to_string()))?;
+    pixmap.fill(tiny_skia::Color::WHITE);
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    pixmap
+        .encode_png()
+        .map_err(|e| ExportError::Png(e.to_string()))
+}
+
+/// Render the table-level graph as a standalone SVG document.
+fn render_svg(result: &AnalyzeResult) -> String {
+    let tables = extract_table_info(result);
+    let dependencies = extract_table_dependencies(result);
+
+    let mut names: Vec<String> = tables.iter().map(|t| t.qualified_name.clone()).collect();
+    for dep in &dependencies {
+        for name in [&dep.source_table, &dep.target_table] {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+    }
+    let edges: Vec<(&str, &str)> = dependencies
+        .iter()
+        .map(|d| (d.source_table.as_str(), d.target_table.as_str()))
+        .collect();
+    let layout = layout(&names, &edges);
+    let types: HashMap<&str, TableType> = tables
+        .iter()
+        .map(|t| (t.qualified_name.as_str(), t.table_type))
+        .collect();
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = layout.width,
+        h = layout.height
+    );
+    svg.push_str(
+        "<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+         markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">\
+         <path d=\"M0,0 L10,5 L0,10 z\" fill=\"#64748b\"/></marker></defs>\n",
+    );
+
+    for (from, to) in &edges {
+        let (Some(&(x1, y1)), Some(&(x2, y2))) =
+            (layout.positions.get(*from), layout.positions.get(*to))
+        else {
+            continue;
+        };
+        let (sx, sy) = (x1 + NODE_WIDTH, y1 + NODE_HEIGHT / 2.0);
+        let (tx, ty) = (x2, y2 + NODE_HEIGHT / 2.0);
+        let bend = ((tx - sx).abs() / 2.0).max(LAYER_GAP / 2.0);
+        svg.push_str(&format!(
+            "<path d=\"M{sx},{sy} C{c1},{sy} {c2},{ty} {tx},{ty}\" fill=\"none\" \
+             stroke=\"#64748b\" stroke-width=\"1.5\" marker-end=\"url(#arrow)\"/>\n",
+            c1 = sx + bend,
+            c2 = tx - bend,
+        ));
+    }
+
+    for (name, &(x, y)) in &layout.positions {
+        let (fill, stroke) = match types.get(name.as_str()) {
+            Some(TableType::View) => ("#ede9fe", "#7c3aed"),
+            Some(TableType::Cte) => ("#fef3c7", "#d97706"),
+            _ => ("#dbeafe", "#2563eb"),
+        };
+        svg.push_str(&format!(
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{NODE_WIDTH}\" height=\"{NODE_HEIGHT}\" rx=\"6\" \
+             fill=\"{fill}\" stroke=\"{stroke}\" stroke-width=\"1.5\"/>\n\
+             <text x=\"{tx}\" y=\"{ty}\" font-family=\"sans-serif\" font-size=\"13\" \
+             text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"#0f172a\">{label}</text>\n",
+            tx = x + NODE_WIDTH / 2.0,
+            ty = y + NODE_HEIGHT / 2.0,
+            label = escape_xml(&truncate(name)),
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Assign each node a layer one past its furthest upstream node, then stack nodes
+/// within a layer in name order. Cycles are broken by capping layers at the node count.
+fn layout(names: &[String], edges: &[(&str, &str)]) -> Layout {
+    let mut layers: HashMap<&str, usize> = names.iter().map(|n| (n.as_str(), 0)).collect();
+    for _ in 0..names.len() {
+        let mut changed = false;
+        for (from, to) in edges {
+            let next = layers.get(from).copied().unwrap_or(0) + 1;
+            let current = layers.entry(to).or_insert(0);
+            if next > *current && next < names.len() {
+                *current = next;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut by_layer: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    for (name, layer) in &layers {
+        by_layer.entry(*layer).or_default().push(name);
+    }
+
+    let layer_count = by_layer.keys().next_back().map_or(1, |last| last + 1);
+    let mut positions = BTreeMap::new();
+    let mut rows = 0usize;
+    for (layer, mut members) in by_layer {
+        members.sort_unstable();
+        rows = rows.max(members.len());
+        for (row, name) in members.into_iter().enumerate() {
+            positions.insert(
+                name.to_string(),
+                (
+                    MARGIN + layer as f32 * (NODE_WIDTH + LAYER_GAP),
+                    MARGIN + row as f32 * (NODE_HEIGHT + ROW_GAP),
+                ),
+            );
+        }
+    }
+
+    let rows = rows.max(1);
+    Layout {
+        positions,
+        width: 2.0 * MARGIN
+            + layer_count as f32 * NODE_WIDTH
+            + (layer_count - 1) as f32 * LAYER_GAP,
+        height: 2.0 * MARGIN + rows as f32 * NODE_HEIGHT + (rows - 1) as f32 * ROW_GAP,
+    }
+}
+
+fn truncate(label: &str) -> String {
+    if label.chars().count() <= MAX_LABEL_CHARS {
+        return label.to_string();
+    }
+    let head: String = label.chars().take(MAX_LABEL_CHARS - 1).collect();
+    format!("{head}…")
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_places_downstream_tables_in_later_layers() {
+        let names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let layout = layout(&names, &[("a", "b"), ("b", "c"), ("a", "c")]);
+
+        let x = |name: &str| layout.positions[name].0;
+        assert!(x("a") < x("b"));
+        assert!(x("b") < x("c"));
+    }
+
+    #[test]
+    fn layout_terminates_on_cycles() {
+        let names = vec!["a".to_string(), "b".to_string()];
+        let layout = layout(&names, &[("a", "b"), ("b", "a")]);
+        assert_eq!(layout.positions.len(), 2);
+    }
+}
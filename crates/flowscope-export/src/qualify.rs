@@ -0,0 +1,137 @@
+//! Prefixing table and view names with the export schema.
+//!
+//! When an export targets a schema, unqualified table names in the exported data are
+//! qualified with it so they read the same way as the generated DDL.
+
+use std::sync::Arc;
+
+use flowscope_core::{AnalyzeResult, NodeType};
+
+use crate::sql_backend::validate_schema;
+use crate::ExportError;
+
+/// Returns a copy of `result` whose table and view names carry `schema` as a prefix.
+///
+/// Labels and qualified names of statement nodes and labels and canonical schemas of
+/// global nodes are prefixed. Tables that already have a schema (or catalog) are left
+/// alone, so qualifying an already qualified result changes nothing. An empty
+/// `schema` returns the result unchanged.
+pub fn qualify_table_names(
+    result: &AnalyzeResult,
+    schema: &str,
+) -> Result<AnalyzeResult, ExportError> {
+    validate_schema(schema)?;
+    let mut qualified = result.clone();
+    if schema.is_empty() {
+        return Ok(qualified);
+    }
+
+    for statement in &mut qualified.statements {
+        for node in &mut statement.nodes {
+            let name = node.qualified_name.as_deref().unwrap_or(&node.label);
+            if !is_qualifiable(node.node_type) || name.contains('.') {
+                continue;
+            }
+            let name: Arc<str> = format!("{schema}.{name}").into();
+            if !node.label.contains('.') {
+                node.label = format!("{schema}.{}", node.label).into();
+            }
+            node.qualified_name = Some(name);
+        }
+    }
+
+    for node in &mut qualified.global_lineage.nodes {
+        let canonical = &mut node.canonical_name;
+        if !is_qualifiable(node.node_type)
+            || canonical.catalog.is_some()
+            || canonical.schema.is_some()
+        {
+            continue;
+        }
+        canonical.schema = Some(schema.to_string());
+        if !node.label.contains('.') {
+            node.label = format!("{schema}.{}", node.label).into();
+        }
+    }
+
+    Ok(qualified)
+}
+
+fn is_qualifiable(node_type: NodeType) -> bool {
+    matches!(node_type, NodeType::Table | NodeType::View)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowscope_core::{analyze, AnalyzeRequest, Dialect};
+
+    fn analyze_sql(sql: &str) -> AnalyzeResult {
+        analyze(&AnalyzeRequest {
+            sql: sql.to_string(),
+            files: None,
+            dialect: Dialect::Postgres,
+            source_name: None,
+            options: None,
+            schema: None,
+            template_config: None,
+        })
+    }
+
+    fn table_labels(result: &AnalyzeResult) -> Vec<String> {
+        let mut labels: Vec<String> = result.statements[0]
+            .nodes
+            .iter()
+            .filter(|node| is_qualifiable(node.node_type))
+            .map(|node| node.label.to_string())
+            .collect();
+        labels.sort();
+        labels
+    }
+
+    #[test]
+    fn prefixes_unqualified_tables_only_once() {
+        let result = analyze_sql(
+            "WITH recent AS (SELECT * FROM orders) \
+             SELECT * FROM recent JOIN sales.customers c ON c.id = recent.customer_id",
+        );
+        let qualified = qualify_table_names(&result, "lineage").expect("valid schema");
+
+        assert_eq!(
+            table_labels(&qualified),
+            vec!["customers", "lineage.orders"]
+        );
+        let customers = qualified.statements[0]
+            .nodes
+            .iter()
+            .find(|node| &*node.label == "customers")
+            .expect("customers node");
+        assert_eq!(customers.qualified_name.as_deref(), Some("sales.customers"));
+        // CTEs are not tables of the export schema
+        assert!(qualified.statements[0]
+            .nodes
+            .iter()
+            .any(|node| node.node_type == NodeType::Cte && &*node.label == "recent"));
+
+        let orders = qualified
+            .global_lineage
+            .nodes
+            .iter()
+            .find(|node| &*node.label == "lineage.orders")
+            .expect("global orders node");
+        assert_eq!(orders.canonical_name.schema.as_deref(), Some("lineage"));
+
+        let twice = qualify_table_names(&qualified, "lineage").expect("valid schema");
+        assert_eq!(table_labels(&twice), table_labels(&qualified));
+    }
+
+    #[test]
+    fn rejects_invalid_schema_names() {
+        let result = analyze_sql("SELECT * FROM orders");
+        assert!(qualify_table_names(&result, "bad-name").is_err());
+        assert_eq!(
+            table_labels(&qualify_table_names(&result, "").expect("empty schema")),
+            vec!["orders"]
+        );
+    }
+}
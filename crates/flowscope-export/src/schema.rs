@@ -132,7 +132,9 @@ CREATE TABLE {prefix}global_edges (
     id TEXT PRIMARY KEY,
     from_node_id TEXT NOT NULL,
     to_node_id TEXT NOT NULL,
-    edge_type TEXT NOT NULL
+    edge_type TEXT NOT NULL,
+    source_statement INTEGER,
+    source_file TEXT
 );
 
 -- Statement references for global nodes
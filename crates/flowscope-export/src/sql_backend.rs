@@ -5,6 +5,7 @@
 use std::collections::HashSet;
 use std::sync::LazyLock;
 
+use crate::qualify::qualify_table_names;
 use crate::schema::{tables_ddl, views_ddl};
 use crate::ExportError;
 use flowscope_core::AnalyzeResult;
@@ -35,7 +36,7 @@ static RESERVED_KEYWORDS: LazyLock<HashSet<String>> = LazyLock::new(|| {
 /// - Start with a letter (a-z, A-Z) or underscore
 /// - Contain only letters, numbers, and underscores
 /// - Be at most 63 characters long
-fn validate_schema(schema: &str) -> Result<(), ExportError> {
+pub(crate) fn validate_schema(schema: &str) -> Result<(), ExportError> {
     if schema.is_empty() {
         return Ok(());
     }
@@ -89,7 +90,8 @@ fn validate_schema(schema: &str) -> Result<(), ExportError> {
 ///
 /// This can be executed directly by duckdb-wasm in the browser.
 ///
-/// If `schema` is provided, all tables and views will be created in that schema.
+/// If `schema` is provided, all tables and views will be created in that schema, and
+/// unqualified table names in the exported lineage are prefixed with it.
 pub fn export_sql(result: &AnalyzeResult, schema: Option<&str>) -> Result<String, ExportError> {
     let mut sql = String::with_capacity(64 * 1024); // Pre-allocate 64KB
 
@@ -101,6 +103,15 @@ pub fn export_sql(result: &AnalyzeResult, schema: Option<&str>) -> Result<String
         validate_schema(s)?;
     }
 
+    let qualified;
+    let result = match schema {
+        Some(s) => {
+            qualified = qualify_table_names(result, s)?;
+            &qualified
+        }
+        None => result,
+    };
+
     // Schema prefix (e.g., "myschema." or "" if no schema)
     let prefix = schema.map(|s| format!("{}.", s)).unwrap_or_default();
 
@@ -425,11 +436,13 @@ fn write_global_lineage_sql(sql: &mut String, result: &AnalyzeResult, prefix: &s
 
         let edge_type = format!("{:?}", edge.edge_type).to_lowercase();
         sql.push_str(&format!(
-            "INSERT INTO {prefix}global_edges (id, from_node_id, to_node_id, edge_type) VALUES ({}, {}, {}, {});\n",
+            "INSERT INTO {prefix}global_edges (id, from_node_id, to_node_id, edge_type, source_statement, source_file) VALUES ({}, {}, {}, {}, {}, {});\n",
             sql_str(Some(edge.id.as_ref())),
             sql_str(Some(edge.from.as_ref())),
             sql_str(Some(edge.to.as_ref())),
             sql_str(Some(&edge_type)),
+            sql_int(edge.source_statement.map(|i| i as i64)),
+            sql_str(edge.source_file.as_deref()),
         ));
     }
 }
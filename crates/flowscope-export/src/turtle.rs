@@ -0,0 +1,127 @@
+//! RDF/Turtle export for loading lineage into triple stores.
+//!
+//! Every node becomes a resource typed with a class of the FlowScope ontology
+//! (`fs:Table`, `fs:Column`, ...) and labelled with `rdfs:label`. Edges become
+//! predicates pointing from the downstream node to the upstream one, except
+//! ownership, which reads `table fs:hasColumn column`.
+
+use std::collections::BTreeSet;
+
+use flowscope_core::{AnalyzeResult, EdgeType, NodeType};
+
+const ONTOLOGY_NAMESPACE: &str = "https://flowscope.pondpilot.io/ontology#";
+const NODE_NAMESPACE: &str = "https://flowscope.pondpilot.io/node/";
+
+pub fn export_turtle(result: &AnalyzeResult) -> String {
+    let mut triples: BTreeSet<String> = BTreeSet::new();
+
+    for statement in &result.statements {
+        for node in &statement.nodes {
+            let subject = node_iri(&node.id);
+            triples.insert(format!("{subject} a fs:{} .", node_class(node.node_type)));
+            triples.insert(format!("{subject} rdfs:label {} .", literal(&node.label)));
+            if let Some(name) = &node.qualified_name {
+                triples.insert(format!("{subject} fs:qualifiedName {} .", literal(name)));
+            }
+            if let Some(expression) = &node.expression {
+                triples.insert(format!("{subject} fs:expression {} .", literal(expression)));
+            }
+        }
+        for edge in &statement.edges {
+            triples.insert(edge_triple(edge.edge_type, &edge.from, &edge.to));
+        }
+    }
+    for edge in &result.global_lineage.edges {
+        if edge.edge_type == EdgeType::CrossStatement {
+            triples.insert(edge_triple(edge.edge_type, &edge.from, &edge.to));
+        }
+    }
+
+    let mut output = format!(
+        "@prefix fs: <{ONTOLOGY_NAMESPACE}> .\n\
+         @prefix node: <{NODE_NAMESPACE}> .\n\
+         @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .\n\n"
+    );
+    for triple in triples {
+        output.push_str(&triple);
+        output.push('\n');
+    }
+    output
+}
+
+fn node_class(node_type: NodeType) -> &'static str {
+    match node_type {
+        NodeType::Table => "Table",
+        NodeType::View => "View",
+        NodeType::Cte => "Cte",
+        NodeType::Output => "Output",
+        NodeType::Column => "Column",
+    }
+}
+
+/// The triple for an edge: downstream node, predicate, upstream node (or owner,
+/// `hasColumn`, column).
+fn edge_triple(edge_type: EdgeType, from: &str, to: &str) -> String {
+    let (subject, object) = match edge_type {
+        EdgeType::Ownership => (from, to),
+        _ => (to, from),
+    };
+    let predicate = match edge_type {
+        EdgeType::Ownership => "hasColumn",
+        EdgeType::DataFlow => "flowsFrom",
+        EdgeType::Derivation => "derivesFrom",
+        EdgeType::JoinDependency => "joinDependsOn",
+        EdgeType::CrossStatement => "dependsOn",
+        EdgeType::RecursiveSelfReference => "recursesOn",
+    };
+    format!(
+        "{} fs:{predicate} {} .",
+        node_iri(subject),
+        node_iri(object)
+    )
+}
+
+/// A node ID as a prefixed name, percent-encoding everything but ASCII letters,
+/// digits, `_` and `-`.
+fn node_iri(id: &str) -> String {
+    let mut iri = String::from("node:");
+    for (index, byte) in id.bytes().enumerate() {
+        let plain = byte.is_ascii_alphanumeric() || byte == b'_' || (byte == b'-' && index > 0);
+        if plain {
+            iri.push(byte as char);
+        } else {
+            iri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    iri
+}
+
+/// A string literal with Turtle escapes.
+fn literal(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_node_ids_and_literals() {
+        assert_eq!(node_iri("table_00ff"), "node:table_00ff");
+        assert_eq!(node_iri("-a.b c"), "node:%2Da%2Eb%20c");
+        assert_eq!(literal("say \"hi\"\\\n"), "\"say \\\"hi\\\"\\\\\\n\"");
+    }
+}
@@ -113,6 +113,8 @@ fn write_mappings_sheet(sheet: &mut Worksheet, result: &AnalyzeResult) -> Result
             "Target Column",
             "Expression",
             "Edge Type",
+            "Statement",
+            "Source File",
         ],
     )?;
 
@@ -128,6 +130,8 @@ fn write_mappings_sheet(sheet: &mut Worksheet, result: &AnalyzeResult) -> Result
                 &sanitize_xlsx_value(&mapping.target_column),
                 &sanitize_xlsx_value(mapping.expression.as_deref().unwrap_or("")),
                 &mapping.edge_type,
+                &mapping.statement_index.to_string(),
+                &sanitize_xlsx_value(mapping.source_file.as_deref().unwrap_or("")),
             ],
         )?;
     }
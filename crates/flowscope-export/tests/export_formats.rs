@@ -1,7 +1,8 @@
 use flowscope_core::{analyze, AnalyzeRequest, Dialect};
 use flowscope_export::{
-    export_csv_bundle, export_html, export_json, export_mermaid, export_xlsx, ExportNaming,
-    MermaidView,
+    export, export_csv_bundle, export_html, export_json, export_markdown, export_mermaid,
+    export_mermaid_colored, export_sql, export_turtle, export_xlsx, qualify_table_names,
+    supported_formats, ColorBy, ExportFormat, ExportNaming, MermaidView,
 };
 use std::io::Read;
 
@@ -26,6 +27,49 @@ fn exports_mermaid_views() {
     assert!(mermaid.contains("orders"));
 }
 
+#[test]
+fn colors_mermaid_nodes_by_schema() {
+    let result = analyze(&AnalyzeRequest {
+        sql: "INSERT INTO staging.events SELECT o.id, c.name FROM analytics.orders o JOIN analytics.customers c ON o.customer_id = c.id".to_string(),
+        files: None,
+        dialect: Dialect::Postgres,
+        source_name: None,
+        options: None,
+        schema: None,
+        template_config: None,
+    });
+    let mermaid =
+        export_mermaid_colored(&result, MermaidView::Table, ColorBy::Schema).expect("mermaid");
+
+    assert!(mermaid.contains("classDef group_analytics"));
+    assert!(mermaid.contains("classDef group_staging"));
+    let analytics = mermaid
+        .lines()
+        .find(|line| line.trim_start().starts_with("class ") && line.ends_with(" group_analytics"))
+        .expect("analytics class line");
+    assert_eq!(analytics.matches(',').count(), 1, "{analytics}");
+    assert!(!export_mermaid(&result, MermaidView::Table)
+        .expect("mermaid")
+        .contains("classDef"));
+}
+
+#[test]
+fn mermaid_table_view_draws_recursive_ctes_as_dashed_loops() {
+    let result = analyze(&AnalyzeRequest {
+        sql: "WITH RECURSIVE nums(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM nums WHERE n < 10) SELECT n FROM nums".to_string(),
+        files: None,
+        dialect: Dialect::Postgres,
+        source_name: None,
+        options: None,
+        schema: None,
+        template_config: None,
+    });
+    let mermaid = export_mermaid(&result, MermaidView::Table).expect("mermaid");
+
+    assert!(mermaid.contains("nums -.->|recursive| nums"), "{mermaid}");
+    assert!(!mermaid.contains("nums --> nums"), "{mermaid}");
+}
+
 #[test]
 fn exports_json_pretty() {
     let result = analyze_sample();
@@ -43,6 +87,83 @@ fn exports_html_report() {
     assert!(html.contains("mermaid"));
 }
 
+#[test]
+fn exports_markdown_report() {
+    let result = analyze(&AnalyzeRequest {
+        sql:
+            "INSERT INTO report SELECT u.id, o.total FROM users u JOIN orders o ON u.id = o.user_id"
+                .to_string(),
+        files: None,
+        dialect: Dialect::Postgres,
+        source_name: Some("report.sql".to_string()),
+        options: None,
+        schema: None,
+        template_config: None,
+    });
+    let markdown = export_markdown(&result, "Test Project").expect("markdown export");
+
+    assert!(markdown.starts_with("# Test Project - Lineage Report"));
+    assert!(markdown.contains("| Statements | 1 |"));
+    assert!(markdown.contains("| report | table | `orders`, `users` |"));
+    assert!(markdown.contains("- Outputs: `report`"));
+    assert!(markdown.contains("```mermaid\nflowchart LR"));
+}
+
+#[test]
+fn exports_turtle_triples() {
+    let result = analyze(&AnalyzeRequest {
+        sql:
+            "CREATE TABLE order_totals AS SELECT o.id, o.price * o.quantity AS total FROM orders o"
+                .to_string(),
+        files: None,
+        dialect: Dialect::Postgres,
+        source_name: None,
+        options: None,
+        schema: None,
+        template_config: None,
+    });
+    let turtle = export_turtle(&result).expect("turtle export");
+
+    let mut prefixes = 0;
+    let mut triples = Vec::new();
+    for line in turtle.lines().filter(|line| !line.is_empty()) {
+        assert!(line.ends_with(" ."), "unterminated statement: {line}");
+        if line.starts_with("@prefix ") {
+            prefixes += 1;
+        } else {
+            let parts: Vec<&str> = line.splitn(3, ' ').collect();
+            assert_eq!(parts.len(), 3, "not a triple: {line}");
+            triples.push(parts);
+        }
+    }
+    assert_eq!(prefixes, 3);
+    let node_count: usize = result.statements.iter().map(|s| s.nodes.len()).sum();
+    assert!(triples.len() >= 2 * node_count, "{turtle}");
+
+    let iri_labelled = |label: &str| {
+        triples
+            .iter()
+            .find(|t| t[1] == "rdfs:label" && t[2] == format!("\"{label}\" ."))
+            .map(|t| t[0])
+            .unwrap_or_else(|| panic!("no node labelled {label}: {turtle}"))
+    };
+    let total = iri_labelled("total");
+    let price = triples
+        .iter()
+        .find(|t| t[1] == "fs:qualifiedName" && t[2] == "\"orders.price\" .")
+        .map(|t| t[0])
+        .expect("orders.price column");
+    assert!(
+        turtle.contains(&format!("{total} fs:derivesFrom {price} .")),
+        "{turtle}"
+    );
+    let orders = iri_labelled("orders");
+    assert!(
+        turtle.contains(&format!("{orders} fs:hasColumn {price} .")),
+        "{turtle}"
+    );
+}
+
 #[test]
 fn exports_csv_archive() {
     let result = analyze_sample();
@@ -58,9 +179,75 @@ fn exports_csv_archive() {
     assert!(content.contains("Source Table"));
 }
 
+#[test]
+fn export_schema_prefixes_tables_in_sql_and_csv() {
+    let result = analyze_sample();
+    let sql = export_sql(&result, Some("lineage")).expect("sql export");
+    assert!(sql.contains("CREATE SCHEMA IF NOT EXISTS lineage;"));
+    assert!(sql.contains("'lineage.users'"));
+    assert!(sql.contains("'lineage.orders'"));
+    assert!(!sql.contains("'lineage.lineage."));
+
+    // Qualifying an already qualified result must not double-prefix
+    let without_timestamp = |sql: &str| -> Vec<String> {
+        sql.lines()
+            .filter(|line| !line.contains("'exported_at'"))
+            .map(str::to_string)
+            .collect()
+    };
+    let qualified = qualify_table_names(&result, "lineage").expect("valid schema");
+    assert_eq!(
+        without_timestamp(&export_sql(&qualified, Some("lineage")).expect("sql export")),
+        without_timestamp(&sql)
+    );
+
+    let bytes = export_csv_bundle(&qualified).expect("csv bundle");
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).expect("zip archive");
+    let mut content = String::new();
+    archive
+        .by_name("tables.csv")
+        .expect("tables file")
+        .read_to_string(&mut content)
+        .expect("read csv content");
+    assert!(content.contains("users,lineage.users,table"));
+    assert!(content.contains("orders,lineage.orders,table"));
+}
+
 #[test]
 fn exports_xlsx_bytes() {
     let result = analyze_sample();
     let bytes = export_xlsx(&result).expect("xlsx export");
     assert!(!bytes.is_empty());
 }
+
+#[test]
+fn supported_formats_follow_enabled_features() {
+    let names: Vec<&str> = supported_formats()
+        .into_iter()
+        .map(ExportFormat::name)
+        .collect();
+    assert!(names.contains(&"json"));
+    assert!(names.contains(&"sql"));
+    assert_eq!(names.contains(&"duckdb"), cfg!(feature = "duckdb"));
+    assert_eq!(names.contains(&"png"), cfg!(feature = "png"));
+
+    let result = analyze_sample();
+    for format in supported_formats() {
+        let bytes = export(&result, format)
+            .unwrap_or_else(|e| panic!("{} should export: {e}", format.name()));
+        assert!(!bytes.is_empty(), "{} export is empty", format.name());
+    }
+}
+
+#[cfg(feature = "png")]
+#[test]
+fn exports_png_image() {
+    let result = analyze_sample();
+    let bytes = flowscope_export::export_png(&result).expect("png export");
+    assert!(bytes.len() > 8);
+    assert_eq!(&bytes[..8], b"\x89PNG\r\n\x1a\n");
+
+    let via_format =
+        flowscope_export::export(&result, flowscope_export::ExportFormat::Png).expect("png");
+    assert_eq!(&via_format[..8], b"\x89PNG\r\n\x1a\n");
+}
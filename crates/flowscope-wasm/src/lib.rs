@@ -2,9 +2,9 @@ mod encoding;
 
 use encoding::{convert_spans_to_utf16, utf16_to_utf8_offset, Encoding};
 use flowscope_core::{
-    analyze, completion_context, completion_items, split_statements, AnalyzeRequest, AnalyzeResult,
-    CompletionContext, CompletionItemsResult, CompletionRequest, StatementSplitRequest,
-    StatementSplitResult,
+    analyze, completion_context, completion_items, issue_catalog, split_statements, AnalyzeRequest,
+    AnalyzeResult, CompletionContext, CompletionItemsResult, CompletionRequest, Dialect, EdgeType,
+    Issue, NodeType, StatementSplitRequest, StatementSplitResult,
 };
 use flowscope_export::{
     export_csv_bundle as export_csv_bundle_internal, export_html as export_html_internal,
@@ -12,7 +12,8 @@ use flowscope_export::{
     export_sql as export_sql_internal, export_xlsx as export_xlsx_internal, ExportFormat,
     ExportNaming, MermaidView,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use wasm_bindgen::prelude::*;
 
 /// Request payload for export_to_duckdb_sql.
@@ -79,6 +80,8 @@ enum ExportFormatRequest {
         view: MermaidViewRequest,
     },
     Html,
+    Markdown,
+    Turtle,
     Sql,
     Csv,
     Xlsx,
@@ -109,6 +112,85 @@ impl From<MermaidViewRequest> for MermaidView {
     }
 }
 
+/// Column lineage returned by `analyze_columns`.
+#[derive(Serialize)]
+struct ColumnLineageResult {
+    columns: Vec<ColumnLineageNode>,
+    edges: Vec<ColumnLineageEdge>,
+    /// Analysis issues, including parse errors
+    issues: Vec<Issue>,
+}
+
+#[derive(Serialize)]
+struct ColumnLineageNode {
+    id: String,
+    name: String,
+    /// Label of the table, CTE or output owning the column
+    #[serde(skip_serializing_if = "Option::is_none")]
+    table: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ColumnLineageEdge {
+    from: String,
+    to: String,
+    /// `data_flow` for a column copied as is, `derivation` for one computed from it
+    #[serde(rename = "type")]
+    edge_type: EdgeType,
+}
+
+impl From<AnalyzeResult> for ColumnLineageResult {
+    fn from(result: AnalyzeResult) -> Self {
+        let lineage = result.global_lineage;
+        let labels: HashMap<&str, &str> = lineage
+            .nodes
+            .iter()
+            .map(|node| (&*node.id, &*node.label))
+            .collect();
+        let owners: HashMap<&str, &str> = lineage
+            .edges
+            .iter()
+            .filter(|edge| edge.edge_type == EdgeType::Ownership)
+            .map(|edge| (&*edge.to, &*edge.from))
+            .collect();
+
+        let columns: Vec<ColumnLineageNode> = lineage
+            .nodes
+            .iter()
+            .filter(|node| node.node_type == NodeType::Column)
+            .map(|node| ColumnLineageNode {
+                id: node.id.to_string(),
+                name: node.label.to_string(),
+                table: owners
+                    .get(&*node.id)
+                    .and_then(|owner| labels.get(owner))
+                    .map(|label| label.to_string()),
+            })
+            .collect();
+        let column_ids: HashSet<&str> = columns.iter().map(|column| &*column.id).collect();
+        let edges = lineage
+            .edges
+            .iter()
+            .filter(|edge| {
+                matches!(edge.edge_type, EdgeType::DataFlow | EdgeType::Derivation)
+                    && column_ids.contains(&*edge.from)
+                    && column_ids.contains(&*edge.to)
+            })
+            .map(|edge| ColumnLineageEdge {
+                from: edge.from.to_string(),
+                to: edge.to.to_string(),
+                edge_type: edge.edge_type,
+            })
+            .collect();
+
+        Self {
+            columns,
+            edges,
+            issues: result.issues,
+        }
+    }
+}
+
 fn default_project_name() -> String {
     "lineage".to_string()
 }
@@ -383,12 +465,39 @@ pub fn analyze_sql(sql_input: &str) -> Result<String, JsValue> {
         .map_err(|e| JsValue::from_str(&format!("Serialization error: {e}")))
 }
 
+/// Legacy-style column API - accepts SQL string, returns JSON with column lineage
+///
+/// Runs the full analysis with the generic dialect and keeps only the column nodes
+/// and the column-to-column edges: `{"columns": [{"id", "name", "table"}],
+/// "edges": [{"from", "to", "type"}], "issues": [...]}`. Analysis and parse errors
+/// are reported in `issues` rather than thrown; only a serialization failure is.
+/// This is an addition alongside `analyze_sql`, whose `LineageResult` is unchanged.
+#[wasm_bindgen]
+pub fn analyze_columns(sql_input: &str) -> Result<String, JsValue> {
+    let request = AnalyzeRequest::builder()
+        .sql(sql_input)
+        .dialect(Dialect::Generic)
+        .build();
+    let result = ColumnLineageResult::from(analyze(&request));
+
+    serde_json::to_string(&result)
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {e}")))
+}
+
 /// Get version information
 #[wasm_bindgen]
 pub fn get_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
+/// List every issue code with its default severity, the analysis option that
+/// enables it, and the closest SQLFluff rule, as a JSON array.
+#[wasm_bindgen]
+pub fn get_issue_catalog_json() -> String {
+    serde_json::to_string(issue_catalog())
+        .unwrap_or_else(|_| r#"{"error":"Failed to serialize issue catalog"}"#.to_string())
+}
+
 /// Export analysis result to SQL statements for DuckDB-WASM.
 ///
 /// Takes a JSON object with:
@@ -465,6 +574,8 @@ pub fn export_filename(request_json: &str) -> Result<String, JsValue> {
         ExportFormatRequest::Json { compact } => ExportFormat::Json { compact },
         ExportFormatRequest::Mermaid { view } => ExportFormat::Mermaid { view: view.into() },
         ExportFormatRequest::Html => ExportFormat::Html,
+        ExportFormatRequest::Markdown => ExportFormat::Markdown,
+        ExportFormatRequest::Turtle => ExportFormat::Turtle,
         ExportFormatRequest::Sql => ExportFormat::Sql { schema: false },
         ExportFormatRequest::Csv => ExportFormat::CsvBundle,
         ExportFormatRequest::Xlsx => ExportFormat::Xlsx,
@@ -532,6 +643,46 @@ mod tests {
         assert!(json.contains("users"));
     }
 
+    #[test]
+    fn test_analyze_columns() {
+        let json = analyze_columns("INSERT INTO t SELECT a, b + 1 AS c FROM s").unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let columns = parsed["columns"].as_array().unwrap();
+        let id_of = |table: &str, name: &str| {
+            columns
+                .iter()
+                .find(|column| column["table"] == table && column["name"] == name)
+                .unwrap_or_else(|| panic!("missing column {table}.{name}"))["id"]
+                .clone()
+        };
+        let edges: Vec<_> = parsed["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|edge| {
+                (
+                    edge["from"].clone(),
+                    edge["to"].clone(),
+                    edge["type"].clone(),
+                )
+            })
+            .collect();
+        assert_eq!(edges.len(), 2);
+        assert!(edges.contains(&(id_of("s", "a"), id_of("t", "a"), "data_flow".into())));
+        assert!(edges.contains(&(id_of("s", "b"), id_of("t", "c"), "derivation".into())));
+        assert!(parsed["issues"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_analyze_columns_reports_parse_errors_as_issues() {
+        let json = analyze_columns("SELECT * FROM").unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert!(parsed["columns"].as_array().unwrap().is_empty());
+        assert!(!parsed["issues"].as_array().unwrap().is_empty());
+    }
+
     #[test]
     fn test_analyze_sql_json_simple() {
         let request = r#"{"sql": "SELECT * FROM users", "dialect": "generic"}"#;
@@ -602,6 +753,18 @@ mod tests {
         assert!(!version.is_empty());
     }
 
+    #[test]
+    fn test_get_issue_catalog_json() {
+        let parsed: serde_json::Value =
+            serde_json::from_str(&get_issue_catalog_json()).expect("valid JSON");
+        let entries = parsed.as_array().expect("array");
+        assert_eq!(entries.len(), issue_catalog().len());
+        assert!(entries
+            .iter()
+            .any(|entry| entry["code"] == "MISSING_TRAILING_NEWLINE"
+                && entry["option"] == "checkTrailingNewline"));
+    }
+
     // Note: Tests for export_to_duckdb_sql and analyze_and_export_sql cannot run
     // on native targets because they return Result<_, JsValue> which only works
     // on wasm32. These functions are tested via wasm-pack test.
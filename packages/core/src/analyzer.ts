@@ -501,6 +501,10 @@ function buildExportFormatPayload(
       return { type: 'mermaid', view: view ?? 'table' };
     case 'html':
       return { type: 'html' };
+    case 'markdown':
+      return { type: 'markdown' };
+    case 'turtle':
+      return { type: 'turtle' };
     case 'sql':
       return { type: 'sql' };
     case 'csv':
@@ -67,6 +67,7 @@ export type {
   CanonicalName,
   StatementRef,
   Issue,
+  IssueCodeInfo,
   Severity,
   Span,
   Summary,
@@ -76,6 +77,7 @@ export type {
   ResolvedColumnSchema,
   SchemaOrigin,
   ResolutionSource,
+  RenderedSql,
 } from './types';
 
 // Constants and utilities
@@ -122,20 +122,84 @@ export interface FileSource {
 /** Graph detail level for visualization. */
 export type GraphDetailLevel = 'script' | 'table' | 'column';
 
+/** Where implicit table aliases (written without `AS`) are accepted */
+export type ImplicitAliasContext = 'none' | 'derived' | 'base';
+
 /** Mermaid export view modes. */
 export type MermaidView = 'all' | 'script' | 'table' | 'column' | 'hybrid';
 
 /** Export format identifiers. */
-export type ExportFormat = 'json' | 'mermaid' | 'html' | 'sql' | 'csv' | 'xlsx' | 'duckdb' | 'png';
+export type ExportFormat = 'json' | 'mermaid' | 'html' | 'markdown' | 'turtle' | 'sql' | 'csv' | 'xlsx' | 'duckdb' | 'png';
 
 /** Options controlling the analysis behavior. */
 export interface AnalysisOptions {
-  /** Enable column-level lineage (default: true) */
+  /** Build column-level lineage (default: true); when false, only table-level nodes and edges are produced */
   enableColumnLineage?: boolean;
   /** Preferred graph detail level for visualization (does not affect analysis) */
   graphDetailLevel?: GraphDetailLevel;
   /** Hide CTEs from output, creating bypass edges (A→CTE→B becomes A→B) */
   hideCtes?: boolean;
+  inlineCtes?: boolean;
+  /** Table name patterns to exclude from lineage output (globs, or `re:` regexes) */
+  ignoreTables?: string[];
+  /** Report columns of intermediate models that no downstream statement uses */
+  reportDeadColumns?: boolean;
+  /** Table name patterns treated as terminal models, exempt from dead-column reports */
+  terminalTables?: string[];
+  /** Flag files whose current date/time functions differ from the project's dominant style */
+  checkDatetimeConsistency?: boolean;
+  /** Flag unqualified `SELECT *` over more than one FROM source */
+  checkUnqualifiedWildcards?: boolean;
+  /** Maximum subquery/derived table nesting depth before a warning (off when unset) */
+  maxSubqueryDepth?: number;
+  /** Flag tables, columns and aliases named after a reserved keyword of the dialect */
+  checkReservedKeywords?: boolean;
+  /** Replace string and number literals in echoed SQL (expressions, filters, resolved SQL) with placeholders */
+  redactLiterals?: boolean;
+  /** Flag table and derived-table aliases written without `AS` */
+  checkImplicitAliases?: boolean;
+  /** Alias context in which `checkImplicitAliases` accepts implicit aliases (default 'none') */
+  allowImplicitAliasesFor?: ImplicitAliasContext;
+  /** Flag LIMIT, OFFSET, FETCH and TOP (including `WITH TIES`) on queries without ORDER BY */
+  checkUnorderedLimits?: boolean;
+  /** Flag equi-join conditions whose columns have incompatible types in the schema */
+  checkJoinKeyTypes?: boolean;
+  /** Groups of type names (e.g. `['integer', 'text']`) that `checkJoinKeyTypes` accepts joining with one another */
+  compatibleJoinKeyTypes?: string[][];
+  /** Flag SELECT lists that project the same expression more than once */
+  checkDuplicateProjections?: boolean;
+  /** Also flag repeated projections that carry different aliases (`a AS x, a AS y`) */
+  includeAliasedDuplicateProjections?: boolean;
+  /** Flag WHERE and HAVING conditions that literals alone make always true or always false */
+  checkConstantPredicates?: boolean;
+  /** Return each source's SQL after template rendering in `AnalyzeResult.renderedSql` */
+  returnRenderedSql?: boolean;
+  /** Report tables and columns missing from the provided schema (`UNRESOLVED_REFERENCE`, `UNKNOWN_COLUMN`) as errors instead of warnings */
+  unresolvedAsErrors?: boolean;
+  /** Flag SUM and AVG calls whose argument is a CASE without ELSE (`SUM(CASE WHEN c THEN 1 END)`) */
+  checkAggregateCaseElse?: boolean;
+  /** Compute fan-in, fan-out, and centrality of each table and view in `AnalyzeResult.graphMetrics` */
+  graphMetrics?: boolean;
+  /** Flag tables and views referenced both schema-qualified and unqualified across the request */
+  checkQualificationConsistency?: boolean;
+  /** Flag recursive CTEs whose recursive branch has no WHERE condition on the CTE itself */
+  checkRecursiveTermination?: boolean;
+  /** Report lines longer than this many characters as `LONG_LINE` issues (unset disables the check) */
+  maxLineLength?: number;
+  /** Flag nested casts that convert a value back to a type it already had */
+  checkRedundantCasts?: boolean;
+  /** Record how long each statement took to parse and analyze in `summary.timings` */
+  collectTimings?: boolean;
+  /** Flag tables, columns and aliases (including generated column names) longer than the dialect's identifier limit */
+  checkIdentifierLength?: boolean;
+  /** Identifier length limit for `checkIdentifierLength`, overriding the dialect's (e.g. 63 for Postgres, 255 for Snowflake) */
+  maxIdentifierLength?: number;
+  /** Name prefixes (e.g. `pg_`, `sys_`) reserved by the platform; tables, columns and aliases starting with one are flagged */
+  reservedIdentifierPrefixes?: string[];
+  /** Warn (`SCHEMA_UNKNOWN_TABLE`) when `SELECT *` reads a table missing from the provided schema (default true) */
+  warnUnknownWildcardTables?: boolean;
+  /** Report sources that do not end with a newline as `MISSING_TRAILING_NEWLINE` issues, with a fix adding it */
+  checkTrailingNewline?: boolean;
 }
 
 /**
@@ -178,6 +242,8 @@ export interface ColumnSchema {
   isPrimaryKey?: boolean;
   /** Foreign key reference if this column references another table */
   foreignKey?: ForeignKeyRef;
+  /** False if this column is declared NOT NULL */
+  nullable?: boolean;
 }
 
 /** A foreign key reference to another table's column. */
@@ -380,6 +446,51 @@ export interface AnalyzeResult {
   summary: Summary;
   /** Effective schema used during analysis (imported + implied) */
   resolvedSchema?: ResolvedSchemaMetadata;
+  /** SQL of each input source after template rendering (with `returnRenderedSql`) */
+  renderedSql?: RenderedSql[];
+  /** Fan-in, fan-out, and centrality of each table and view (with `graphMetrics`) */
+  graphMetrics?: GraphMetrics;
+}
+
+/**
+ * The SQL an input source was analyzed as, after template rendering.
+ *
+ * Statement spans of the source point into this text.
+ */
+export interface RenderedSql {
+  /** File name, or the request's source name for inline SQL */
+  sourceName?: string;
+  /** Rendered SQL text (the original text when no templating was applied) */
+  sql: string;
+}
+
+/**
+ * Dependency metrics of the tables and views in the global lineage.
+ *
+ * The metrics treat lineage as a dependency graph: a relation depends on every
+ * relation it reads from, so heavily read hub tables have a high fan-in.
+ */
+export interface GraphMetrics {
+  /** One entry per table or view, by descending fan-in */
+  tables: TableGraphMetrics[];
+}
+
+/** Graph metrics of one table or view. */
+export interface TableGraphMetrics {
+  /** ID of the global lineage node */
+  id: string;
+  /** Qualified name of the relation */
+  name: string;
+  /** Table or view */
+  type: NodeType;
+  /** Number of relations reading from this one */
+  fanIn: number;
+  /** Number of relations this one reads from */
+  fanOut: number;
+  /** Share of the shortest dependency paths between other relations that pass through this one (0 to 1) */
+  betweenness: number;
+  /** PageRank score over the dependency edges; scores of all relations sum to 1 */
+  pageRank: number;
 }
 
 /** Lineage information for a single SQL statement. */
@@ -446,7 +557,7 @@ export function isTableLikeType(type: NodeType): type is TableLikeNodeType {
   return type === 'table' || type === 'view' || type === 'cte';
 }
 
-/** A filter predicate from a WHERE, HAVING, or JOIN ON clause. */
+/** A filter predicate from a WHERE, HAVING, QUALIFY, or JOIN ON clause. */
 export interface FilterPredicate {
   /** The SQL expression text of the predicate */
   expression: string;
@@ -455,7 +566,7 @@ export interface FilterPredicate {
 }
 
 /** The type of SQL clause where a filter predicate appears. */
-export type FilterClauseType = 'WHERE' | 'HAVING' | 'JOIN_ON';
+export type FilterClauseType = 'WHERE' | 'HAVING' | 'JOIN_ON' | 'QUALIFY';
 
 /**
  * Information about aggregation applied to a column.
@@ -502,7 +613,8 @@ export type EdgeType =
   | 'data_flow'
   | 'derivation'
   | 'join_dependency'
-  | 'cross_statement';
+  | 'cross_statement'
+  | 'recursive_self_reference';
 
 /** The type of SQL JOIN operation. */
 export type JoinType =
@@ -569,6 +681,10 @@ export interface GlobalEdge {
   type: EdgeType;
   producerStatement?: StatementRef;
   consumerStatement?: StatementRef;
+  /** Index of the statement that created this edge; for cross-statement edges, the statement reading the table */
+  sourceStatement?: number;
+  /** Source file of the statement that created this edge (when known) */
+  sourceFile?: string;
   metadata?: Record<string, unknown>;
 }
 
@@ -586,6 +702,16 @@ export interface Issue {
   statementIndex?: number;
   /** Optional: source file name where the issue occurred */
   sourceName?: string;
+  /** Optional: an edit to the source SQL that resolves the issue */
+  fix?: IssueFix;
+}
+
+/** A machine-applicable edit resolving an issue: `span` of the source SQL is replaced with `replacement`. */
+export interface IssueFix {
+  /** Byte range of the source SQL to replace */
+  span: Span;
+  /** Text to put in its place */
+  replacement: string;
 }
 
 export type Severity = 'error' | 'warning' | 'info';
@@ -614,6 +740,21 @@ export interface Summary {
   issueCount: IssueCount;
   /** Quick check: true if any errors were encountered */
   hasErrors: boolean;
+  /** Per-statement parse and analysis durations, when `collectTimings` is set */
+  timings?: StatementTiming[];
+}
+
+/** How long one statement took to process. */
+export interface StatementTiming {
+  /** Index of the statement */
+  statementIndex: number;
+  /**
+   * Time spent parsing, in microseconds. A source is parsed as a whole, so its parse
+   * time is shared among its statements in proportion to their length.
+   */
+  parseMicros: number;
+  /** Time spent analyzing, in microseconds */
+  analysisMicros: number;
 }
 
 /** Counts of issues by severity level. */
@@ -640,8 +781,48 @@ export const IssueCodes = {
   CANCELLED: 'CANCELLED',
   PAYLOAD_SIZE_WARNING: 'PAYLOAD_SIZE_WARNING',
   MEMORY_LIMIT_EXCEEDED: 'MEMORY_LIMIT_EXCEEDED',
+  IGNORED_TABLES: 'IGNORED_TABLES',
+  DEAD_COLUMN: 'DEAD_COLUMN',
+  INCONSISTENT_DATETIME_STYLE: 'INCONSISTENT_DATETIME_STYLE',
+  NOT_IN_NULLABLE_SUBQUERY: 'NOT_IN_NULLABLE_SUBQUERY',
+  UNQUALIFIED_WILDCARD: 'UNQUALIFIED_WILDCARD',
+  AMBIGUOUS_ORDER_BY: 'AMBIGUOUS_ORDER_BY',
+  DEEPLY_NESTED_SUBQUERY: 'DEEPLY_NESTED_SUBQUERY',
+  INSERT_COLUMN_ORDER_MISMATCH: 'INSERT_COLUMN_ORDER_MISMATCH',
+  RESERVED_KEYWORD_IDENTIFIER: 'RESERVED_KEYWORD_IDENTIFIER',
+  IMPLICIT_TABLE_ALIAS: 'IMPLICIT_TABLE_ALIAS',
+  UNORDERED_ROW_LIMIT: 'UNORDERED_ROW_LIMIT',
+  RESERVED_KEYWORD_COLUMN_ALIAS: 'RESERVED_KEYWORD_COLUMN_ALIAS',
+  JOIN_KEY_TYPE_MISMATCH: 'JOIN_KEY_TYPE_MISMATCH',
+  DUPLICATE_PROJECTION: 'DUPLICATE_PROJECTION',
+  CONSTANT_PREDICATE: 'CONSTANT_PREDICATE',
+  AGGREGATE_CASE_WITHOUT_ELSE: 'AGGREGATE_CASE_WITHOUT_ELSE',
+  INCONSISTENT_QUALIFICATION: 'INCONSISTENT_QUALIFICATION',
+  UNBOUNDED_RECURSION: 'UNBOUNDED_RECURSION',
+  LONG_LINE: 'LONG_LINE',
+  REDUNDANT_CAST: 'REDUNDANT_CAST',
+  IDENTIFIER_TOO_LONG: 'IDENTIFIER_TOO_LONG',
+  RESERVED_IDENTIFIER_PREFIX: 'RESERVED_IDENTIFIER_PREFIX',
+  SCHEMA_UNKNOWN_TABLE: 'SCHEMA_UNKNOWN_TABLE',
+  MISSING_TRAILING_NEWLINE: 'MISSING_TRAILING_NEWLINE',
 } as const;
 
+/** An entry of the issue code catalog returned by `get_issue_catalog_json`. */
+export interface IssueCodeInfo {
+  /** The code carried by `Issue.code` */
+  code: string;
+  /** Severity the code is usually reported with */
+  defaultSeverity: Severity;
+  /** The `AnalysisOptions` field that enables or tunes the check; absent when always reported */
+  option?: string;
+  /** The closest SQLFluff rule, when there is one */
+  sqlfluffRule?: string;
+  /** True for codes that are no longer emitted */
+  deprecated?: boolean;
+  /** One-line summary of what the code reports */
+  description: string;
+}
+
 // Resolved Schema Types
 
 /** Resolved schema metadata showing the effective schema used during analysis. */
@@ -678,6 +859,8 @@ export interface ResolvedColumnSchema {
   isPrimaryKey?: boolean;
   /** Foreign key reference if this column references another table */
   foreignKey?: ForeignKeyRef;
+  /** False if this column is declared NOT NULL */
+  nullable?: boolean;
 }
 
 /** Information about a table-level constraint (composite PK, FK, etc.). */
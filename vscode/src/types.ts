@@ -87,7 +87,7 @@ export interface Edge {
   approximate?: boolean;
 }
 
-export type EdgeType = 'ownership' | 'data_flow' | 'derivation' | 'cross_statement';
+export type EdgeType = 'ownership' | 'data_flow' | 'derivation' | 'cross_statement' | 'recursive_self_reference';
 
 export type JoinType =
   | 'INNER'